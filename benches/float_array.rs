@@ -0,0 +1,106 @@
+//! Measures how long it takes to parse a large `<float_array>`.
+//!
+//! This compares the fast path used for `<float_array>`, which splits the text on ASCII whitespace
+//! and preallocates the list from the element's `count`, against the same document without a
+//! usable `count` and against the generic `split_whitespace`-and-collect approach.
+//!
+//! Run with `cargo bench --bench float_array`. This uses a plain `main` rather than the unstable
+//! `test` crate so that it builds on stable Rust.
+
+extern crate collaborate;
+
+use ::collaborate::v1_4::*;
+use std::time::{Duration, Instant};
+
+/// The number of floats in the array being parsed.
+const VALUES: usize = 1_000_000;
+
+/// The number of times each case is run. The fastest run is reported.
+const RUNS: usize = 10;
+
+fn main() {
+    let text = (0..VALUES)
+        .map(|index| format!("{}", index as f32 * 0.001 - 500.0))
+        .collect::<Vec<_>>()
+        .join(" ");
+
+    let with_count = document(VALUES, &text);
+    let without_count = document(0, &text);
+
+    println!("Parsing a <float_array> of {} values, fastest of {} runs:", VALUES, RUNS);
+
+    report("fast path, with count", bench(|| {
+        let document = Collada::from_str(&with_count).unwrap();
+        array_len(&document)
+    }));
+
+    report("fast path, without count", bench(|| {
+        let document = Collada::from_str(&without_count).unwrap();
+        array_len(&document)
+    }));
+
+    report("generic split_whitespace (text only)", bench(|| {
+        text.split_whitespace()
+            .map(|word| word.parse::<f32>())
+            .collect::<Result<Vec<_>, _>>()
+            .unwrap()
+            .len()
+    }));
+}
+
+fn document(count: usize, text: &str) -> String {
+    format!(
+        r##"<?xml version="1.0" encoding="utf-8"?>
+        <COLLADA xmlns="http://www.collada.org/2005/11/COLLADASchema" version="1.4.1">
+            <asset>
+                <created>2017-02-07T20:44:30Z</created>
+                <modified>2017-02-07T20:44:30Z</modified>
+            </asset>
+            <library_geometries>
+                <geometry>
+                    <mesh>
+                        <source id="positions">
+                            <float_array id="positions-array" count="{}">{}</float_array>
+                        </source>
+                        <vertices id="vertices">
+                            <input semantic="POSITION" source="#positions"/>
+                        </vertices>
+                    </mesh>
+                </geometry>
+            </library_geometries>
+        </COLLADA>"##,
+        count,
+        text,
+    )
+}
+
+fn array_len(document: &Collada) -> usize {
+    let library = document.libraries[0].as_library_geometries().unwrap();
+    let mesh = library.geometries[0].mesh().unwrap();
+    let array = mesh.sources[0].array.as_ref().unwrap().as_float_array().unwrap();
+    array.data.len()
+}
+
+/// Runs `case` `RUNS` times, returning the fastest time.
+fn bench<F: FnMut() -> usize>(mut case: F) -> Duration {
+    (0..RUNS)
+        .map(|_| {
+            let start = Instant::now();
+            let len = case();
+            let elapsed = start.elapsed();
+            assert_eq!(VALUES, len);
+            elapsed
+        })
+        .min()
+        .unwrap()
+}
+
+fn report(name: &str, elapsed: Duration) {
+    let nanos = elapsed.as_secs() as f64 * 1e9 + elapsed.subsec_nanos() as f64;
+    println!(
+        "{:>40}: {:>8.2} ms ({:.1} ns per value)",
+        name,
+        nanos / 1e6,
+        nanos / VALUES as f64,
+    );
+}
@@ -7,7 +7,7 @@ use proc_macro::TokenStream;
 use quote::{Tokens, ToTokens};
 use syn::*;
 
-#[proc_macro_derive(ColladaElement, attributes(name, attribute, child, text, optional_with_default, required))]
+#[proc_macro_derive(ColladaElement, attributes(name, attribute, child, text, optional_with_default, required, capacity, namespace, validate, position))]
 pub fn derive(input: TokenStream) -> TokenStream {
     // Parse the string representation.
     let ast = syn::parse_derive_input(&input.to_string()).unwrap();
@@ -29,6 +29,8 @@ fn process_derive_input(input: DeriveInput) -> Result<ElementConfiguration, Stri
     let mut children = Vec::new();
     let mut attributes = Vec::new();
     let mut text_contents = None;
+    let mut namespace = None;
+    let mut position = None;
     let mut stub_me_out = false;
 
     let fields = match input.body {
@@ -60,8 +62,10 @@ fn process_derive_input(input: DeriveInput) -> Result<ElementConfiguration, Stri
         }
     };
 
-    // Process the top-level attributes on the type to find the `#[name = "foo"]` attribute.
+    // Process the top-level attributes on the type to find the `#[name = "foo"]` attribute and
+    // the optional `#[validate = "path::to::fn"]` attribute.
     // -------------------------------------------------------------------------------------
+    let mut validate = None;
     let element_name = {
         let mut element_name = None;
 
@@ -70,6 +74,8 @@ fn process_derive_input(input: DeriveInput) -> Result<ElementConfiguration, Stri
                 MetaItem::NameValue(attr_name, Lit::Str(value, _)) => {
                     if attr_name == "name" {
                         element_name = Some(value);
+                    } else if attr_name == "validate" {
+                        validate = Some(Ident::new(value));
                     }
                 }
 
@@ -85,6 +91,8 @@ fn process_derive_input(input: DeriveInput) -> Result<ElementConfiguration, Stri
             Child,
             Attribute,
             Text,
+            Namespace,
+            Position,
         }
 
         // We only support struct-structs, so all fields will have an ident.
@@ -96,6 +104,7 @@ fn process_derive_input(input: DeriveInput) -> Result<ElementConfiguration, Stri
         let mut member_type = None;
         let mut is_required = false;
         let mut optional_with_default = None;
+        let mut capacity = None;
 
         for attribute in field.attrs {
             match attribute.name() {
@@ -114,8 +123,30 @@ fn process_derive_input(input: DeriveInput) -> Result<ElementConfiguration, Stri
                     member_type = Some(MemberType::Text);
                 }
 
+                "namespace" => {
+                    assert!(member_type.is_none(), "Member type may only be specified once");
+                    member_type = Some(MemberType::Namespace);
+                }
+
+                "position" => {
+                    assert!(member_type.is_none(), "Member type may only be specified once");
+                    member_type = Some(MemberType::Position);
+                }
+
                 "required" => { is_required = true; }
 
+                "capacity" => {
+                    match attribute.value {
+                        MetaItem::NameValue(_, Lit::Str(value, _)) => {
+                            capacity = Some(Ident::new(value));
+                        }
+
+                        _ => {
+                            return Err("Capacity attribute must take the form `#[capacity = \"attribute_member\"]`")?;
+                        }
+                    }
+                }
+
                 "optional_with_default" => {
                     match attribute.value {
                         MetaItem::Word(_) => {
@@ -148,7 +179,23 @@ fn process_derive_input(input: DeriveInput) -> Result<ElementConfiguration, Stri
             }
         }
 
-        let member_type = member_type.expect("Missing `#[child]`, `#[attribute]`, or `#[text]` attribute on member {:?}, one is required");
+        let member_type = member_type.expect("Missing `#[child]`, `#[attribute]`, `#[text]`, `#[namespace]`, or `#[position]` attribute on member {:?}, one is required");
+
+        // The namespace member is populated directly from the element's start event, so there's
+        // no need to examine its type.
+        if let MemberType::Namespace = member_type {
+            assert!(namespace.is_none(), "Only one member may have the `#[namespace]` attribute");
+            namespace = Some(member_name);
+            continue;
+        }
+
+        // The position member is likewise populated from the reader's position at the element's
+        // opening tag, and is converted from a `TextPosition` with `Into`.
+        if let MemberType::Position = member_type {
+            assert!(position.is_none(), "Only one member may have the `#[position]` attribute");
+            position = Some(member_name);
+            continue;
+        }
 
         // Determine the data type and occurrences for the member.
         let path = match field.ty.clone() {
@@ -207,7 +254,8 @@ fn process_derive_input(input: DeriveInput) -> Result<ElementConfiguration, Stri
 
         // Determine the data type of the inner type. A specific set of known types are parsed
         // automatically from text data. Any unknown type is assumed to impl `ColladaElement`,
-        // and so parsing defers to the types `ColladaElement` impl.
+        // and so parsing defers to the types `ColladaElement` impl. Booleans must use `Boolean`,
+        // since `bool::from_str` rejects the `1` and `0` allowed by `xs:boolean`.
         let data_type = match inner_type {
             Ty::Path(None, ref path) => {
                 let segment = path.segments.last().expect("Somehow got an empty path ?_?");
@@ -217,7 +265,9 @@ fn process_derive_input(input: DeriveInput) -> Result<ElementConfiguration, Stri
                 || type_ident == "AnyUri"
                 || type_ident == "f32"
                 || type_ident == "f64"
+                || type_ident == "i64"
                 || type_ident == "usize"
+                || type_ident == "Boolean"
                 {
                     DataType::TextData(inner_type.clone())
                 } else {
@@ -260,12 +310,15 @@ fn process_derive_input(input: DeriveInput) -> Result<ElementConfiguration, Stri
                 });
             }
 
+            MemberType::Namespace | MemberType::Position => unreachable!(),
+
             MemberType::Text => {
                 assert!(text_contents.is_none(), "Only one member may have the `#[text]` attribute");
                 text_contents = Some(TextContents {
                     member_name,
                     occurrences,
                     member_type: inner_type,
+                    capacity,
                 });
             }
         }
@@ -277,6 +330,9 @@ fn process_derive_input(input: DeriveInput) -> Result<ElementConfiguration, Stri
         attributes,
         children,
         text_contents,
+        namespace,
+        position,
+        validate,
 
         stub_me_out,
     }))
@@ -294,6 +350,16 @@ struct StructMember {
     children: Vec<Child>,
     text_contents: Option<TextContents>,
 
+    /// The member holding the namespace mappings in scope for the element, if any.
+    namespace: Option<Ident>,
+
+    /// The member recording where in the source document the element started, if any.
+    position: Option<Ident>,
+
+    /// A function to call with the parsed element to check constraints that can't be expressed
+    /// through the other attributes, if any.
+    validate: Option<Ident>,
+
     /// Temporary flag to allow us to stub out elements until the entire spec is covered.
     stub_me_out: bool,
 }
@@ -369,6 +435,9 @@ struct TextContents {
     member_name: Ident,
     occurrences: ChildOccurrences,
     member_type: Ty,
+
+    /// The attribute member holding the expected number of items in a list of text data, if any.
+    capacity: Option<Ident>,
 }
 
 fn generate_impl(derive_input: DeriveInput) -> Result<quote::Tokens, String> {
@@ -387,8 +456,8 @@ fn generate_enum_impl(config: EnumMember) -> Result<quote::Tokens, String> {
         .map(|variant| &variant.inner_type)
         .fold(None, |joined, current| {
             match joined {
-                None => Some(quote! { #current::name_test(name) }),
-                Some(joined) => Some(quote! { #joined || #current::name_test(name) }),
+                None => Some(quote! { <#current as ::utils::ColladaElement>::name_test(name) }),
+                Some(joined) => Some(quote! { #joined || <#current as ::utils::ColladaElement>::name_test(name) }),
             }
         });
 
@@ -397,16 +466,16 @@ fn generate_enum_impl(config: EnumMember) -> Result<quote::Tokens, String> {
             let &EnumMemberVariant { ref name, ref inner_type } = current;
             match joined {
                 None => Some(quote! {
-                    if #inner_type::name_test(&*element_start.name.local_name) {
-                        let element = #inner_type::parse_element(reader, element_start)?;
+                    if <#inner_type as ::utils::ColladaElement>::name_test(&*element_start.name.local_name) {
+                        let element = <#inner_type as ::utils::ColladaElement>::parse_element(reader, element_start)?;
                         Ok(#ident::#name(element))
                     }
                 }),
 
                 Some(joined) => Some(quote! {
                     #joined
-                    else if #inner_type::name_test(&*element_start.name.local_name) {
-                        let element = #inner_type::parse_element(reader, element_start)?;
+                    else if <#inner_type as ::utils::ColladaElement>::name_test(&*element_start.name.local_name) {
+                        let element = <#inner_type as ::utils::ColladaElement>::parse_element(reader, element_start)?;
                         Ok(#ident::#name(element))
                     }
                 }),
@@ -415,7 +484,23 @@ fn generate_enum_impl(config: EnumMember) -> Result<quote::Tokens, String> {
 
     let add_names = variants.iter()
         .map(|variant| &variant.inner_type)
-        .map(|ty| quote! { #ty::add_names(names); });
+        .map(|ty| quote! { <#ty as ::utils::ColladaElement>::add_names(names); });
+
+    let write_variants = variants.iter()
+        .map(|variant| {
+            let name = &variant.name;
+            quote! {
+                #ident::#name(ref element) => ::utils::ColladaElement::write_element(element, writer),
+            }
+        });
+
+    let strip_variants = variants.iter()
+        .map(|variant| {
+            let name = &variant.name;
+            quote! {
+                #ident::#name(ref mut element) => ::utils::ColladaElement::strip_extras(element),
+            }
+        });
 
     Ok(quote! {
         impl ::utils::ColladaElement for #ident {
@@ -424,7 +509,7 @@ fn generate_enum_impl(config: EnumMember) -> Result<quote::Tokens, String> {
             }
 
             fn parse_element<R>(
-                reader: &mut ::xml::reader::EventReader<R>,
+                reader: &mut ::utils::ColladaReader<R>,
                 element_start: ::utils::ElementStart,
             ) -> Result<#ident>
             where
@@ -442,6 +527,21 @@ fn generate_enum_impl(config: EnumMember) -> Result<quote::Tokens, String> {
             fn add_names(names: &mut Vec<&'static str>) {
                 #( #add_names )*
             }
+
+            fn write_element<W: ::std::io::Write>(
+                &self,
+                writer: &mut ::utils::ColladaWriter<W>,
+            ) -> ::std::io::Result<()> {
+                match *self {
+                    #( #write_variants )*
+                }
+            }
+
+            fn strip_extras(&mut self) {
+                match *self {
+                    #( #strip_variants )*
+                }
+            }
         }
     })
 }
@@ -453,6 +553,9 @@ fn generate_struct_impl(config: StructMember) -> Result<quote::Tokens, String> {
         attributes,
         children,
         text_contents,
+        namespace,
+        position,
+        validate,
         stub_me_out
     } = config;
 
@@ -498,10 +601,15 @@ fn generate_struct_impl(config: StructMember) -> Result<quote::Tokens, String> {
             })
             .unwrap_or(Tokens::new());
 
+        let namespace = namespace.as_ref()
+            .map(|member_name| quote! { let #member_name = element_start.namespace; })
+            .unwrap_or(Tokens::new());
+
         quote! {
             #( #attribs )*
             #( #childs )*
             #text
+            #namespace
         }
     };
 
@@ -642,20 +750,20 @@ fn generate_struct_impl(config: StructMember) -> Result<quote::Tokens, String> {
                         }
                     }
 
-                    (&ChildOccurrences::OptionalMany, &DataType::TextData(_)) => {
+                    (&ChildOccurrences::OptionalMany, &DataType::TextData(ref ty)) => {
                         quote! {
                             utils::verify_attributes(reader, #element_name, element_start.attributes)?;
-                            if let Some(result) = utils::optional_text_contents(reader, #element_name)? {
-                                #member_name.push(result.parse()?);
+                            if let Some(result) = utils::optional_text_contents::<_, #ty>(reader, #element_name)? {
+                                #member_name.push(result);
                             }
                         }
                     }
 
-                    (&ChildOccurrences::RequiredMany, &DataType::TextData(_)) => {
+                    (&ChildOccurrences::RequiredMany, &DataType::TextData(ref ty)) => {
                         quote! {
                             utils::verify_attributes(reader, #element_name, element_start.attributes)?;
-                            if let Some(result) = utils::optional_text_contents(reader, #element_name)? {
-                                #member_name.push(result.parse()?);
+                            if let Some(result) = utils::optional_text_contents::<_, #ty>(reader, #element_name)? {
+                                #member_name.push(result);
                             }
                         }
                     }
@@ -717,6 +825,7 @@ fn generate_struct_impl(config: StructMember) -> Result<quote::Tokens, String> {
                     ref member_name,
                     ref occurrences,
                     ref member_type,
+                    ref capacity,
                 } = *text_contents;
 
                 match *occurrences {
@@ -724,23 +833,33 @@ fn generate_struct_impl(config: StructMember) -> Result<quote::Tokens, String> {
                     ChildOccurrences::OptionalWithDefault(_) |
                     ChildOccurrences::Required => {
                         quote! {
-                            Some(&mut |_, text| {
-                                #member_name = Some(text.parse()?);
+                            Some(&mut |reader, text| {
+                                let result = utils::parse_value::<#member_type>(#element_name, &text)
+                                    .map_err(|kind| {
+                                        Error {
+                                            position: reader.position(),
+                                            kind,
+                                        }
+                                    })?;
+                                #member_name = Some(result);
                                 Ok(())
                             })
                         }
                     }
 
                     ChildOccurrences::OptionalMany | ChildOccurrences::RequiredMany => {
+                        let capacity = match *capacity {
+                            Some(ref capacity) => quote! { Some(#capacity) },
+                            None => quote! { None },
+                        };
+
                         quote! {
                             Some(&mut |reader, text| {
-                                #member_name = text.split_whitespace()
-                                    .map(|word| word.parse::<#member_type>())
-                                    .collect::<::std::result::Result<Vec<_>, _>>()
-                                    .map_err(|err| {
+                                #member_name = utils::parse_text_list::<#member_type>(#element_name, &text, #capacity)
+                                    .map_err(|kind| {
                                         Error {
                                             position: reader.position(),
-                                            kind: err.into(),
+                                            kind,
                                         }
                                     })?;
                                 Ok(())
@@ -826,33 +945,229 @@ fn generate_struct_impl(config: StructMember) -> Result<quote::Tokens, String> {
                 quote! { #ident }
             })
             .unwrap_or(Tokens::new());
+        let namespace = namespace.as_ref()
+            .map(|member_name| quote! { #member_name, })
+            .unwrap_or(Tokens::new());
+        let position = position.as_ref()
+            .map(|member_name| quote! { #member_name: element_position.into(), })
+            .unwrap_or(Tokens::new());
+
+        // Validation errors point at the element's opening tag, which is where the reader was
+        // before any of the element's children were parsed.
+        let validate = validate.as_ref()
+            .map(|validate| quote! {
+                #validate(&result).map_err(|kind| Error {
+                    position: element_position,
+                    kind,
+                })?;
+            })
+            .unwrap_or(Tokens::new());
 
         quote! {
-            Ok(#ident {
+            let result = #ident {
                 #( #attribs, )*
                 #( #childs, )*
+                #namespace
+                #position
                 #text
+            };
+            #validate
+            Ok(result)
+        }
+    };
+
+    // Generate code for writing the element.
+    // --------------------------------------
+    let write_impl = {
+        // Attribute values are converted to strings up front, since the start element only
+        // borrows them. `xmlns` is never reported as an attribute when parsing (it's a namespace
+        // declaration), so it isn't written either. The writer declares the namespace instead.
+        let attribs = attributes.iter()
+            .filter(|attrib| attrib.attrib_name != "xmlns")
+            .collect::<Vec<_>>();
+        let attrib_values = attribs.iter()
+            .map(|attrib| {
+                let &&Attribute { ref member_name, ref occurrences, .. } = attrib;
+                let value_name = Ident::new(format!("attrib_{}", member_name));
+                match *occurrences {
+                    AttributeOccurrences::Optional => quote! {
+                        let #value_name = self.#member_name.as_ref().map(::std::string::ToString::to_string);
+                    },
+
+                    AttributeOccurrences::OptionalWithDefault(_) | AttributeOccurrences::Required => quote! {
+                        let #value_name = Some(::std::string::ToString::to_string(&self.#member_name));
+                    },
+                }
+            });
+        let apply_attribs = attribs.iter()
+            .map(|attrib| {
+                let &&Attribute { ref member_name, ref attrib_name, .. } = attrib;
+                let value_name = Ident::new(format!("attrib_{}", member_name));
+                quote! {
+                    if let Some(ref value) = #value_name {
+                        start = start.attr(#attrib_name, value);
+                    }
+                }
+            });
+
+        let childs = children.iter()
+            .map(|child| {
+                let &Child { ref member_name, ref element_name, ref occurrences, ref data_type } = child;
+                let write_child = match *data_type {
+                    DataType::ColladaElement(_) => quote! {
+                        ::utils::ColladaElement::write_element(child, writer)?;
+                    },
+
+                    DataType::TextData(_) => quote! {
+                        writer.text_element(#element_name, &::std::string::ToString::to_string(child))?;
+                    },
+                };
+
+                match *occurrences {
+                    ChildOccurrences::Optional => quote! {
+                        if let Some(ref child) = self.#member_name {
+                            #write_child
+                        }
+                    },
+
+                    ChildOccurrences::OptionalWithDefault(_) | ChildOccurrences::Required => quote! {
+                        {
+                            let child = &self.#member_name;
+                            #write_child
+                        }
+                    },
+
+                    ChildOccurrences::OptionalMany | ChildOccurrences::RequiredMany => quote! {
+                        for child in &self.#member_name {
+                            #write_child
+                        }
+                    },
+                }
+            });
+
+        let text = text_contents.as_ref()
+            .map(|text_contents| {
+                let TextContents { ref member_name, ref occurrences, .. } = *text_contents;
+                match *occurrences {
+                    ChildOccurrences::Optional => quote! {
+                        if let Some(ref text) = self.#member_name {
+                            writer.characters(&::std::string::ToString::to_string(text))?;
+                        }
+                    },
+
+                    ChildOccurrences::OptionalWithDefault(_) | ChildOccurrences::Required => quote! {
+                        writer.characters(&::std::string::ToString::to_string(&self.#member_name))?;
+                    },
+
+                    ChildOccurrences::OptionalMany | ChildOccurrences::RequiredMany => quote! {
+                        writer.characters(&::utils::text_list(&self.#member_name))?;
+                    },
+                }
             })
+            .unwrap_or(Tokens::new());
+
+        quote! {
+            #( #attrib_values )*
+            #[allow(unused_mut)]
+            let mut start = ::xml::writer::XmlEvent::start_element(#element_name);
+            #( #apply_attribs )*
+            writer.start_element(start)?;
+
+            #( #childs )*
+            #text
+
+            writer.end_element()
         }
     };
 
+    // Generate code for stripping 3rd-party extensions.
+    // -------------------------------------------------
+    // `<extra>` children are removed, along with profile-specific `<technique>` children unless
+    // the element requires at least one. Every other child element is stripped recursively.
+    let strip_impl = {
+        let strip_children = children.iter()
+            .filter_map(|child| {
+                let &Child { ref member_name, ref occurrences, ref data_type, .. } = child;
+                let type_name = match *data_type {
+                    DataType::ColladaElement(ref ty) => type_name(ty),
+                    DataType::TextData(_) => return None,
+                };
+                let remove = type_name == Some("Extra")
+                    || (type_name == Some("Technique") && *occurrences != ChildOccurrences::RequiredMany);
+
+                Some(match (remove, occurrences) {
+                    (true, &ChildOccurrences::Optional) => quote! {
+                        self.#member_name = None;
+                    },
+
+                    (true, &ChildOccurrences::OptionalMany) | (true, &ChildOccurrences::RequiredMany) => quote! {
+                        self.#member_name.clear();
+                    },
+
+                    (_, &ChildOccurrences::Optional) => quote! {
+                        if let Some(ref mut child) = self.#member_name {
+                            ::utils::ColladaElement::strip_extras(child);
+                        }
+                    },
+
+                    (_, &ChildOccurrences::OptionalMany) | (_, &ChildOccurrences::RequiredMany) => quote! {
+                        for child in &mut self.#member_name {
+                            ::utils::ColladaElement::strip_extras(child);
+                        }
+                    },
+
+                    (_, &ChildOccurrences::Required) | (_, &ChildOccurrences::OptionalWithDefault(_)) => quote! {
+                        ::utils::ColladaElement::strip_extras(&mut self.#member_name);
+                    },
+                })
+            });
+
+        quote! {
+            #( #strip_children )*
+        }
+    };
+
+    let element_position_decl = if validate.is_some() || position.is_some() {
+        quote! { let element_position = reader.position(); }
+    } else {
+        Tokens::new()
+    };
+
+    // Generate the list of valid child names, in the order they must appear.
+    // ------------------------------------------------------------------------
+    let add_children = children.iter()
+        .map(|child| {
+            let &Child { ref element_name, ref data_type, .. } = child;
+            match *data_type {
+                DataType::TextData(_) => quote! { names.push(#element_name); },
+                DataType::ColladaElement(ref ty) => quote! { <#ty as ::utils::ColladaElement>::add_names(names); },
+            }
+        });
+
     let body = if stub_me_out {
         quote! {
             #[allow(unused_imports)]
             fn parse_element<R: ::std::io::Read>(
-                reader: &mut ::xml::reader::EventReader<R>,
+                reader: &mut ::utils::ColladaReader<R>,
                 _: ::utils::ElementStart,
             ) -> Result<Self> {
-                ::utils::stub_out(reader, #element_name)?;
+                ::utils::stub_out(reader)?;
 
                 Ok(Self {})
             }
+
+            fn write_element<W: ::std::io::Write>(
+                &self,
+                writer: &mut ::utils::ColladaWriter<W>,
+            ) -> ::std::io::Result<()> {
+                writer.empty_element(#element_name)
+            }
         }
     } else {
         quote! {
             #[allow(unused_imports)]
             fn parse_element<R: ::std::io::Read>(
-                reader: &mut ::xml::reader::EventReader<R>,
+                reader: &mut ::utils::ColladaReader<R>,
                 element_start: ::utils::ElementStart,
             ) -> Result<Self> {
                 use std::str::FromStr;
@@ -861,6 +1176,7 @@ fn generate_struct_impl(config: StructMember) -> Result<quote::Tokens, String> {
                 #[allow(unused_imports)]
                 use ::xml::common::Position;
 
+                #element_position_decl
                 #member_decls
 
                 #attributes_impl
@@ -869,6 +1185,17 @@ fn generate_struct_impl(config: StructMember) -> Result<quote::Tokens, String> {
 
                 #result_decl
             }
+
+            fn write_element<W: ::std::io::Write>(
+                &self,
+                writer: &mut ::utils::ColladaWriter<W>,
+            ) -> ::std::io::Result<()> {
+                #write_impl
+            }
+
+            fn strip_extras(&mut self) {
+                #strip_impl
+            }
         }
     };
 
@@ -885,6 +1212,19 @@ fn generate_struct_impl(config: StructMember) -> Result<quote::Tokens, String> {
             fn add_names(names: &mut Vec<&'static str>) {
                 names.push(#element_name);
             }
+
+            #[allow(unused_variables)]
+            fn add_children(names: &mut Vec<&'static str>) {
+                #( #add_children )*
+            }
         }
     })
 }
+
+/// Returns the name of the last segment of a plain type path, e.g. `"Extra"` for `v1_4::Extra`.
+fn type_name(ty: &Ty) -> Option<&str> {
+    match *ty {
+        Ty::Path(None, ref path) => path.segments.last().map(|segment| segment.ident.as_ref()),
+        _ => None,
+    }
+}
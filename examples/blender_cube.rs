@@ -1,3 +1,8 @@
+//! Walks the polygons of a mesh by hand, matching each vertex attribute to its input and source.
+//!
+//! This demonstrates the low-level structure of mesh data. If you just want the vertex data,
+//! `Mesh::resolved_vertices` does all of this for you.
+
 extern crate collaborate;
 
 use ::collaborate::v1_4::*;
@@ -16,7 +21,7 @@ fn main() {
     let document = Collada::from_str(&*source).unwrap();
 
     // Grab the `<library_geometries>` instance.
-    let library = document.libraries[5].as_library_geometries().unwrap();
+    let library = document.libraries_of(Library::as_library_geometries).next().unwrap();
 
     // Get the `<mesh>` instance and put together the mesh data.
     let mesh = library.geometries[0].geometric_element.as_mesh().unwrap();
@@ -69,31 +74,11 @@ fn main() {
                                 .and_then(Array::as_float_array)
                                 .expect("Source wasn't a float array");
 
-                            /// Use the accessor to get the position data for the current vertex.
+                            // Use the accessor to get the position data for the current vertex, then
+                            // use the accessor's params to pick out the X, Y, and Z components.
                             let position_data = accessor.access(array.data.as_ref(), attribute.index);
-
-                            // Use the `params` in the accesor to determine which elements in
-                            // `normal_data` correspond to the normal's X, Y, and Z components.
-                            let mut x = None;
-                            let mut y = None;
-                            let mut z = None;
-
-                            for (param, &position_component) in accessor.params.iter().zip(position_data.iter()) {
-                                match param.name.as_ref().map(String::as_str) {
-                                    Some("X") => { x = Some(position_component); }
-                                    Some("Y") => { y = Some(position_component); }
-                                    Some("Z") => { z = Some(position_component); }
-
-                                    // Ignore any unrecognized or unsupported names.
-                                    _ => {}
-                                }
-                            }
-
-                            position = Some([
-                                x.expect("Normal had no X component"),
-                                y.expect("Normal had no Y component"),
-                                z.expect("Normal had no Z component"),
-                            ])
+                            position = Some(accessor.vec3(position_data)
+                                .expect("Position had no X, Y, or Z component"));
                         }
 
                         "NORMAL" => {
@@ -110,31 +95,11 @@ fn main() {
                                 .and_then(Array::as_float_array)
                                 .expect("Source wasn't a float array");
 
-                            /// Use the accessor to get the normal data for the current vertex.
+                            // Use the accessor to get the normal data for the current vertex, then
+                            // use the accessor's params to pick out the X, Y, and Z components.
                             let normal_data = accessor.access(array.data.as_ref(), attribute.index);
-
-                            // Use the `params` in the accesor to determine which elements in
-                            // `normal_data` correspond to the normal's X, Y, and Z components.
-                            let mut x = None;
-                            let mut y = None;
-                            let mut z = None;
-
-                            for (param, &normal_component) in accessor.params.iter().zip(normal_data.iter()) {
-                                match param.name.as_ref().map(String::as_str) {
-                                    Some("X") => { x = Some(normal_component); }
-                                    Some("Y") => { y = Some(normal_component); }
-                                    Some("Z") => { z = Some(normal_component); }
-
-                                    // Ignore any unrecognized or unsupported names.
-                                    _ => {}
-                                }
-                            }
-
-                            normal = Some([
-                                x.expect("Normal had no X component"),
-                                y.expect("Normal had no Y component"),
-                                z.expect("Normal had no Z component"),
-                            ])
+                            normal = Some(accessor.vec3(normal_data)
+                                .expect("Normal had no X, Y, or Z component"));
                         }
 
                         // Ignore any unknown semantics.
@@ -0,0 +1,148 @@
+//! Support for COLLADA documents packaged in zip archives.
+//!
+//! Applications like Google Earth and SketchUp distribute models as `.kmz` files, which are zip
+//! archives containing a KML file, a COLLADA document, and any textures used by the model.
+//!
+//! This module is only available when the `zip` feature is enabled.
+
+use VersionedDocument;
+use std::fmt::{self, Display, Formatter};
+use std::io::{Read, Seek};
+use utils;
+use xml::reader::XmlEvent;
+use zip::ZipArchive;
+use zip::result::ZipError;
+
+/// An error that occurred while reading a document from an archive.
+#[derive(Debug)]
+pub enum Error {
+    /// The archive couldn't be read.
+    Zip(ZipError),
+
+    /// The archive didn't contain a `.dae` file.
+    MissingDocument,
+
+    /// The COLLADA document in the archive couldn't be parsed.
+    Document(::Error),
+}
+
+impl From<ZipError> for Error {
+    fn from(from: ZipError) -> Error {
+        Error::Zip(from)
+    }
+}
+
+impl From<::std::io::Error> for Error {
+    fn from(from: ::std::io::Error) -> Error {
+        Error::Zip(from.into())
+    }
+}
+
+impl From<::Error> for Error {
+    fn from(from: ::Error) -> Error {
+        Error::Document(from)
+    }
+}
+
+impl Display for Error {
+    fn fmt(&self, formatter: &mut Formatter) -> ::std::result::Result<(), fmt::Error> {
+        match *self {
+            Error::Zip(ref error) => write!(formatter, "Failed to read the archive: {}", error),
+            Error::MissingDocument => write!(formatter, "The archive doesn't contain a .dae file"),
+            Error::Document(ref error) => error.fmt(formatter),
+        }
+    }
+}
+
+impl ::std::error::Error for Error {}
+
+pub type Result<T> = ::std::result::Result<T, Error>;
+
+/// The files in an archive, as `(path, contents)` pairs.
+pub type Files = Vec<(String, Vec<u8>)>;
+
+/// The most memory reserved up front for a file's contents, regardless of the size it declares.
+const MAX_PREALLOCATION: u64 = 1 << 20;
+
+/// Reads a `.kmz` archive, returning the COLLADA document it contains along with the contents of
+/// every other file in the archive.
+///
+/// The other files are returned as `(path, contents)` pairs in the order they appear in the
+/// archive, where `path` is the file's full path within the archive. Directories are skipped.
+///
+/// # Choosing the Document
+///
+/// An archive may contain more than one `.dae` file. If the archive has a KML file (preferring
+/// one named `doc.kml`) then the first `.dae` file referenced by an `<href>` in the KML is used.
+/// Otherwise the first `.dae` file in the archive is used.
+///
+/// # Examples
+///
+/// ```
+/// # #![allow(unused_variables)]
+/// use std::fs::File;
+/// use collaborate::archive;
+///
+/// let file = File::open("resources/blender_cube.kmz").unwrap();
+/// let (document, files) = archive::read_kmz(file).unwrap();
+/// ```
+pub fn read_kmz<R: Read + Seek>(reader: R) -> Result<(VersionedDocument, Files)> {
+    let mut archive = ZipArchive::new(reader)?;
+    let mut files = Vec::with_capacity(archive.len());
+    for index in 0..archive.len() {
+        let mut file = archive.by_index(index)?;
+        if file.is_dir() {
+            continue;
+        }
+
+        // The size comes from the archive's headers and can't be trusted, so only use it to
+        // reserve a bounded amount up front.
+        let capacity = file.size().min(MAX_PREALLOCATION) as usize;
+        let mut contents = Vec::with_capacity(capacity);
+        file.read_to_end(&mut contents)?;
+        files.push((file.name().to_owned(), contents));
+    }
+
+    let index = referenced_document(&files)
+        .or_else(|| files.iter().position(|(path, _)| is_dae(path)))
+        .ok_or(Error::MissingDocument)?;
+    let (_, contents) = files.remove(index);
+    let document = VersionedDocument::from_bytes(&contents)?;
+
+    Ok((document, files))
+}
+
+/// Finds the index of the first `.dae` file referenced by the archive's KML file, if any.
+fn referenced_document(files: &[(String, Vec<u8>)]) -> Option<usize> {
+    let kml = files.iter()
+        .find(|(path, _)| path == "doc.kml")
+        .or_else(|| files.iter().find(|(path, _)| path.to_lowercase().ends_with(".kml")))
+        .map(|(_, contents)| contents)?;
+
+    // The KML isn't a COLLADA document, so this just walks the raw events looking for the text
+    // contents of `<href>` elements. Malformed KML is treated as not referencing anything.
+    let mut in_href = false;
+    for event in utils::event_reader(&kml[..]) {
+        match event {
+            Ok(XmlEvent::StartElement { ref name, .. }) => { in_href = name.local_name == "href"; }
+            Ok(XmlEvent::EndElement { .. }) => { in_href = false; }
+
+            Ok(XmlEvent::Characters(ref href)) if in_href && is_dae(href) => {
+                let href = href.trim_start_matches("./");
+                if let Some(index) = files.iter().position(|(path, _)| path == href) {
+                    return Some(index);
+                }
+            }
+
+            Err(_) => { break; }
+
+            _ => {}
+        }
+    }
+
+    None
+}
+
+fn is_dae(path: &str) -> bool {
+    path.to_lowercase().ends_with(".dae")
+}
@@ -1,12 +1,15 @@
 //! Type definitions common to all supported COLLADA specifications.
 
-use {Error, ErrorKind, Result};
+use {Error, ErrorKind, ParseOptions, Result};
+use extensions::blender::BlenderTechnique;
+use extensions::maya::MayaTechnique;
 use std::io::Read;
 use std::str::FromStr;
 use utils;
 use utils::*;
-use xml::common::Position;
-use xml::reader::{EventReader, XmlEvent};
+use xml::common::{Position, TextPosition};
+use xml::namespace::Namespace;
+use xml::reader::XmlEvent;
 
 /// A URI in the COLLADA document.
 ///
@@ -14,8 +17,179 @@ use xml::reader::{EventReader, XmlEvent};
 ///
 /// [anyURI]: http://www.datypic.com/sc/xsd/t-xsd_anyURI.html
 #[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct AnyUri(String);
 
+impl AnyUri {
+    /// Returns the URI as a string slice.
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+
+    /// Returns the ID targeted by the URI if it refers to an element in the same document.
+    ///
+    /// Only URIs made up of a fragment alone (e.g. `"#Cube"`) are same-document references.
+    /// Returns `None` for URIs pointing into other documents, such as `"lib.dae#Cube"`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use collaborate::common::AnyUri;
+    /// let local: AnyUri = "#Cube".parse().unwrap();
+    /// assert_eq!(Some("Cube"), local.local_id());
+    ///
+    /// let external: AnyUri = "lib.dae#Cube".parse().unwrap();
+    /// assert_eq!(None, external.local_id());
+    /// ```
+    pub fn local_id(&self) -> Option<&str> {
+        self.0.strip_prefix('#')
+    }
+
+    /// Resolves the URI as a reference relative to `base`, following [RFC 3986][RFC 3986].
+    ///
+    /// URIs with a scheme are already absolute and are returned unchanged. Exporters often write
+    /// Windows paths such as `C:/models/tank.s3d`, so a single letter followed by a colon is
+    /// treated as a drive letter rather than a scheme: the path is absolute when it's the
+    /// reference, and is resolved against like any other path when it's the base.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use collaborate::common::AnyUri;
+    /// let base: AnyUri = "file:///home/user/models/scene.dae".parse().unwrap();
+    /// let texture: AnyUri = "../textures/brick.png".parse().unwrap();
+    /// assert_eq!("file:///home/user/textures/brick.png", texture.resolve_against(&base).as_str());
+    ///
+    /// let windows: AnyUri = "C:/models/tank.s3d".parse().unwrap();
+    /// assert_eq!(windows, windows.resolve_against(&base));
+    /// ```
+    ///
+    /// [RFC 3986]: https://tools.ietf.org/html/rfc3986#section-5.2
+    pub fn resolve_against(&self, base: &AnyUri) -> AnyUri {
+        let reference = UriParts::split(&self.0);
+        if reference.scheme.is_some() || is_drive_path(reference.path) {
+            return self.clone();
+        }
+
+        let base = UriParts::split(&base.0);
+        let (authority, path, query) = if reference.authority.is_some() {
+            (reference.authority, remove_dot_segments(reference.path), reference.query)
+        } else if reference.path.is_empty() {
+            (base.authority, base.path.into(), reference.query.or(base.query))
+        } else if reference.path.starts_with('/') {
+            (base.authority, remove_dot_segments(reference.path), reference.query)
+        } else {
+            let merged = if base.authority.is_some() && base.path.is_empty() {
+                format!("/{}", reference.path)
+            } else {
+                let directory = base.path.rfind('/').map(|index| &base.path[..index + 1]).unwrap_or("");
+                format!("{}{}", directory, reference.path)
+            };
+            (base.authority, remove_dot_segments(&merged), reference.query)
+        };
+
+        let mut result = String::new();
+        if let Some(scheme) = base.scheme {
+            result.push_str(scheme);
+            result.push(':');
+        }
+        if let Some(authority) = authority {
+            result.push_str("//");
+            result.push_str(authority);
+        }
+        result.push_str(&path);
+        if let Some(query) = query {
+            result.push('?');
+            result.push_str(query);
+        }
+        if let Some(fragment) = reference.fragment {
+            result.push('#');
+            result.push_str(fragment);
+        }
+
+        AnyUri(result)
+    }
+}
+
+/// The components of a URI, as described in [RFC 3986][RFC 3986].
+///
+/// [RFC 3986]: https://tools.ietf.org/html/rfc3986#section-3
+struct UriParts<'a> {
+    scheme: Option<&'a str>,
+    authority: Option<&'a str>,
+    path: &'a str,
+    query: Option<&'a str>,
+    fragment: Option<&'a str>,
+}
+
+impl<'a> UriParts<'a> {
+    fn split(uri: &'a str) -> UriParts<'a> {
+        let (rest, fragment) = match uri.find('#') {
+            Some(index) => (&uri[..index], Some(&uri[index + 1..])),
+            None => (uri, None),
+        };
+        let (rest, query) = match rest.find('?') {
+            Some(index) => (&rest[..index], Some(&rest[index + 1..])),
+            None => (rest, None),
+        };
+
+        // A scheme is a letter followed by letters, digits, `+`, `-`, or `.`. Single letters are
+        // left out so that Windows drive letters are treated as part of the path.
+        let scheme_end = rest.find(':').filter(|&index| {
+            let scheme = &rest[..index];
+            scheme.len() > 1
+                && scheme.starts_with(|c: char| c.is_ascii_alphabetic())
+                && scheme.chars().all(|c| c.is_ascii_alphanumeric() || c == '+' || c == '-' || c == '.')
+        });
+        let (scheme, rest) = match scheme_end {
+            Some(index) => (Some(&rest[..index]), &rest[index + 1..]),
+            None => (None, rest),
+        };
+
+        let (authority, path) = match rest.strip_prefix("//") {
+            Some(rest) => {
+                let end = rest.find('/').unwrap_or(rest.len());
+                (Some(&rest[..end]), &rest[end..])
+            }
+            None => (None, rest),
+        };
+
+        UriParts { scheme, authority, path, query, fragment }
+    }
+}
+
+/// Checks if `path` begins with a Windows drive letter, e.g. `C:/` or `C:\`.
+fn is_drive_path(path: &str) -> bool {
+    let bytes = path.as_bytes();
+    bytes.len() >= 2 && bytes[0].is_ascii_alphabetic() && bytes[1] == b':'
+}
+
+/// Removes `.` and `..` segments from `path`, as described in [RFC 3986][RFC 3986].
+///
+/// [RFC 3986]: https://tools.ietf.org/html/rfc3986#section-5.2.4
+fn remove_dot_segments(path: &str) -> String {
+    let mut segments: Vec<&str> = Vec::new();
+    let mut ends_in_directory = false;
+    for segment in path.split('/') {
+        ends_in_directory = segment == "." || segment == "..";
+        match segment {
+            "." => {}
+            ".." => {
+                // Never pop the empty segment that represents the leading slash.
+                if segments.len() > 1 || segments.first() != Some(&"") {
+                    segments.pop();
+                }
+            }
+            _ => segments.push(segment),
+        }
+    }
+
+    if ends_in_directory {
+        segments.push("");
+    }
+    segments.join("/")
+}
+
 // TODO: Actually parse the string and verify that it's a valid URI.
 impl ::std::str::FromStr for AnyUri {
     type Err = ::std::string::ParseError;
@@ -25,6 +199,153 @@ impl ::std::str::FromStr for AnyUri {
     }
 }
 
+impl ::std::fmt::Display for AnyUri {
+    fn fmt(&self, formatter: &mut ::std::fmt::Formatter) -> ::std::result::Result<(), ::std::fmt::Error> {
+        write!(formatter, "{}", self.0)
+    }
+}
+
+impl AsRef<str> for AnyUri {
+    fn as_ref(&self) -> &str {
+        &self.0
+    }
+}
+
+/// A boolean value.
+///
+/// Represents the [`xs:boolean`][boolean] XML data type, which accepts `1` and `0` as well as
+/// `true` and `false`. Values are always written as `true` or `false`.
+///
+/// [boolean]: http://www.datypic.com/sc/xsd/t-xsd_boolean.html
+///
+/// # Examples
+///
+/// ```
+/// # use collaborate::common::Boolean;
+/// assert_eq!(Ok(Boolean(true)), "1".parse());
+/// assert_eq!(Ok(Boolean(false)), "false".parse());
+/// assert!("yes".parse::<Boolean>().is_err());
+/// ```
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Boolean(pub bool);
+
+impl FromStr for Boolean {
+    type Err = ::std::str::ParseBoolError;
+
+    fn from_str(source: &str) -> ::std::result::Result<Boolean, ::std::str::ParseBoolError> {
+        match source.trim() {
+            "1" => Ok(Boolean(true)),
+            "0" => Ok(Boolean(false)),
+            other => other.parse().map(Boolean),
+        }
+    }
+}
+
+impl ::std::fmt::Display for Boolean {
+    fn fmt(&self, formatter: &mut ::std::fmt::Formatter) -> ::std::result::Result<(), ::std::fmt::Error> {
+        write!(formatter, "{}", self.0)
+    }
+}
+
+impl From<bool> for Boolean {
+    fn from(from: bool) -> Boolean {
+        Boolean(from)
+    }
+}
+
+impl From<Boolean> for bool {
+    fn from(from: Boolean) -> bool {
+        from.0
+    }
+}
+
+/// A color value, with an optional identifier for targeting it for animation.
+///
+/// Colors are written as either three (RGB) or four (RGBA) floating point components. Colors
+/// that only specify RGB components have an alpha of `1.0`.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Color {
+    /// An identifier for the color that's unique amongst its sibling elements.
+    pub sid: Option<String>,
+
+    /// The red, green, blue, and alpha components of the color.
+    pub rgba: [f32; 4],
+}
+
+impl ColladaElement for Color {
+    fn name_test(name: &str) -> bool {
+        name == "color"
+    }
+
+    fn parse_element<R>(
+        reader: &mut ColladaReader<R>,
+        element_start: ElementStart,
+    ) -> Result<Color>
+    where
+        R: Read,
+    {
+        let mut sid = None;
+        for attribute in element_start.attributes {
+            match &*attribute.name.local_name {
+                "sid" => { sid = Some(attribute.value); }
+
+                attrib_name => {
+                    return Err(Error {
+                        position: reader.position(),
+                        kind: ErrorKind::UnexpectedAttribute {
+                            element: "color",
+                            attribute: attrib_name.into(),
+                            expected: vec!["sid"],
+                        },
+                    });
+                }
+            }
+        }
+
+        let text: String = utils::required_text_contents(reader, "color")?;
+        let components = utils::parse_text_list::<f32>("color", &text, Some(4))
+            .map_err(|kind| Error {
+                position: reader.position(),
+                kind,
+            })?;
+
+        let rgba = match *components {
+            [r, g, b] => [r, g, b, 1.0],
+            [r, g, b, a] => [r, g, b, a],
+            _ => {
+                return Err(Error {
+                    position: reader.position(),
+                    kind: ErrorKind::InvalidValue {
+                        element: "color",
+                        value: text,
+                    },
+                });
+            }
+        };
+
+        Ok(Color { sid, rgba })
+    }
+
+    fn add_names(names: &mut Vec<&'static str>) {
+        names.push("color");
+    }
+
+    fn write_element<W>(&self, writer: &mut ColladaWriter<W>) -> ::std::io::Result<()>
+    where
+        W: ::std::io::Write,
+    {
+        let mut start = ::xml::writer::XmlEvent::start_element("color");
+        if let Some(ref sid) = self.sid {
+            start = start.attr("sid", sid);
+        }
+        writer.start_element(start)?;
+        writer.characters(&utils::text_list(&self.rgba))?;
+        writer.end_element()
+    }
+}
+
 /// A datetime value, with or without a timezone.
 ///
 /// Timestamps in a COLLADA document adhere to [ISO 8601][ISO 8601], which specifies a standard
@@ -46,20 +367,229 @@ pub enum DateTime {
     Naive(::chrono::NaiveDateTime),
 }
 
+/// The format for timestamps with a timezone offset, e.g. `2017-02-07T20:44:30.5+02:00`.
+///
+/// `%.f` accepts the fractional seconds being omitted entirely, and `%:z` accepts offsets both
+/// with and without the colon.
+static OFFSET_FORMAT: &'static str = "%Y-%m-%dT%H:%M:%S%.f%:z";
+
+/// The format for timestamps without a timezone, e.g. `2017-02-07T20:44:30.5`.
+static NAIVE_FORMAT: &'static str = "%Y-%m-%dT%H:%M:%S%.f";
+
 impl FromStr for DateTime {
     type Err = ::chrono::ParseError;
 
     fn from_str(source: &str) -> ::std::result::Result<DateTime, ::chrono::ParseError> {
-        source
-            .parse()
-            .map(|datetime| DateTime::Utc(datetime))
+        // A trailing "Z" is shorthand for UTC, but chrono has no format specifier that accepts it
+        // when parsing. Swap it for the equivalent explicit offset instead.
+        if source.ends_with('Z') || source.ends_with('z') {
+            let utc = format!("{}+00:00", &source[..source.len() - 1]);
+            return ::chrono::DateTime::parse_from_str(&utc, OFFSET_FORMAT).map(DateTime::Utc);
+        }
+
+        ::chrono::DateTime::parse_from_str(source, OFFSET_FORMAT)
+            .map(DateTime::Utc)
             .or_else(|_| {
-                ::chrono::NaiveDateTime::from_str(source)
+                ::chrono::NaiveDateTime::parse_from_str(source, NAIVE_FORMAT)
                     .map(DateTime::Naive)
             })
     }
 }
 
+impl ::std::fmt::Display for DateTime {
+    fn fmt(&self, formatter: &mut ::std::fmt::Formatter) -> ::std::result::Result<(), ::std::fmt::Error> {
+        match *self {
+            DateTime::Utc(ref date_time) => write!(formatter, "{}", date_time.format(OFFSET_FORMAT)),
+            DateTime::Naive(ref date_time) => write!(formatter, "{}", date_time.format(NAIVE_FORMAT)),
+        }
+    }
+}
+
+/// A single floating-point value, with an optional identifier for targeting it for animation.
+#[derive(Debug, Clone, PartialEq, ColladaElement)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[name = "float"]
+pub struct FloatValue {
+    /// An identifier for the value that's unique amongst its sibling elements.
+    #[attribute]
+    pub sid: Option<String>,
+
+    #[text]
+    pub value: f32,
+}
+
+/// Defines an element holding a fixed number of floats, such as `<float3>`.
+///
+/// The elements differ only in their name and the number of values, so they share a single
+/// `ColladaElement` impl built on `parse_float_values` and `write_float_values`.
+macro_rules! float_values_element {
+    ($(#[$attr:meta])* $ty:ident, $name:expr, $len:expr) => {
+        $(#[$attr])*
+        #[derive(Debug, Clone, PartialEq)]
+        #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+        pub struct $ty {
+            /// An identifier for the value that's unique amongst its sibling elements.
+            pub sid: Option<String>,
+
+            /// The values, in the order they're written in the document.
+            pub data: [f32; $len],
+        }
+
+        impl ColladaElement for $ty {
+            fn name_test(name: &str) -> bool {
+                name == $name
+            }
+
+            fn parse_element<R>(
+                reader: &mut ColladaReader<R>,
+                element_start: ElementStart,
+            ) -> Result<$ty>
+            where
+                R: Read,
+            {
+                let mut data = [0.0; $len];
+                let sid = parse_float_values(reader, element_start, $name, &mut data)?;
+                Ok($ty { sid, data })
+            }
+
+            fn add_names(names: &mut Vec<&'static str>) {
+                names.push($name);
+            }
+
+            fn write_element<W>(&self, writer: &mut ColladaWriter<W>) -> ::std::io::Result<()>
+            where
+                W: ::std::io::Write,
+            {
+                write_float_values(writer, $name, &self.sid, &self.data)
+            }
+        }
+    };
+}
+
+float_values_element! {
+    /// A value made up of 2 floating-point values, with an optional identifier for targeting it
+    /// for animation.
+    Float2, "float2", 2
+}
+
+float_values_element! {
+    /// A value made up of 3 floating-point values, with an optional identifier for targeting it
+    /// for animation.
+    Float3, "float3", 3
+}
+
+float_values_element! {
+    /// A value made up of 4 floating-point values, with an optional identifier for targeting it
+    /// for animation.
+    Float4, "float4", 4
+}
+
+float_values_element! {
+    /// A value made up of a 4x4 matrix, with an optional identifier for targeting it for
+    /// animation.
+    ///
+    /// The 16 values are listed in row-major order, the same as a `<matrix>` transform.
+    Float4x4, "float4x4", 16
+}
+
+/// Parses the `sid` attribute and the text of an element holding exactly `values.len()` floats.
+///
+/// The parsed floats are written into `values`. Returns an `ErrorKind::InvalidValue` containing
+/// the text if it holds the wrong number of values.
+fn parse_float_values<R: Read>(
+    reader: &mut ColladaReader<R>,
+    element_start: ElementStart,
+    element: &'static str,
+    values: &mut [f32],
+) -> Result<Option<String>> {
+    let mut sid = None;
+    for attribute in element_start.attributes {
+        match &*attribute.name.local_name {
+            "sid" => { sid = Some(attribute.value); }
+
+            attrib_name => {
+                return Err(Error {
+                    position: reader.position(),
+                    kind: ErrorKind::UnexpectedAttribute {
+                        element,
+                        attribute: attrib_name.into(),
+                        expected: vec!["sid"],
+                    },
+                });
+            }
+        }
+    }
+
+    let text: String = utils::required_text_contents(reader, element)?;
+    let parsed = utils::parse_text_list::<f32>(element, &text, Some(values.len()))
+        .map_err(|kind| Error {
+            position: reader.position(),
+            kind,
+        })?;
+    if parsed.len() != values.len() {
+        return Err(Error {
+            position: reader.position(),
+            kind: ErrorKind::InvalidValue {
+                element,
+                value: text,
+            },
+        });
+    }
+
+    values.copy_from_slice(&parsed);
+    Ok(sid)
+}
+
+/// Writes an element holding the floats in `values`, with an optional `sid` attribute.
+fn write_float_values<W: ::std::io::Write>(
+    writer: &mut ColladaWriter<W>,
+    element: &str,
+    sid: &Option<String>,
+    values: &[f32],
+) -> ::std::io::Result<()> {
+    let mut start = ::xml::writer::XmlEvent::start_element(element);
+    if let Some(ref sid) = *sid {
+        start = start.attr("sid", sid);
+    }
+    writer.start_element(start)?;
+    writer.characters(&utils::text_list(values))?;
+    writer.end_element()
+}
+
+/// The location in the source document where an element started.
+///
+/// Elements that opt in to position tracking record the row and column of their opening tag
+/// when parsed, which is useful for reporting problems found after parsing (e.g. a dangling
+/// reference) back to the user. Elements that weren't parsed from a document, such as those
+/// built in code, have no position.
+///
+/// Positions are ignored when comparing elements, so that an element compares equal to the same
+/// element parsed from a differently formatted document (or built by hand). Positions are also
+/// skipped when serializing with serde.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct SourcePosition(Option<TextPosition>);
+
+impl SourcePosition {
+    /// Returns the position of the element's opening tag, if it was parsed from a document.
+    ///
+    /// Rows and columns are zero-based, matching `TextPosition`.
+    pub fn get(&self) -> Option<TextPosition> {
+        self.0
+    }
+}
+
+impl From<TextPosition> for SourcePosition {
+    fn from(position: TextPosition) -> SourcePosition {
+        SourcePosition(Some(position))
+    }
+}
+
+impl PartialEq for SourcePosition {
+    fn eq(&self, _: &SourcePosition) -> bool {
+        true
+    }
+}
+
 /// Arbitrary additional information represented as XML events.
 ///
 /// > TODO: Provide more information about processing techniques.
@@ -76,6 +606,13 @@ pub struct Technique {
     /// application.
     pub xmlns: Option<AnyUri>,
 
+    /// The namespace mappings in scope for the `<technique>` element, including any prefixes
+    /// declared on the element itself.
+    ///
+    /// Documents that embed vendor extensions often declare additional namespaces (e.g.
+    /// `xmlns:xsi`), which can be looked up here to determine which schema the data belongs to.
+    pub namespace: Namespace,
+
     /// The raw XML events for the data contained within the technique. These events do not contain
     /// the `StartElement` and `EndElement` events for the `<technique>` element itself. As such,
     /// the contents of `data` do not represent a valid XML document, as they may not have a single
@@ -83,13 +620,245 @@ pub struct Technique {
     pub data: Vec<XmlEvent>,
 }
 
+impl Technique {
+    /// Decodes the technique as Blender data, if it has the `"blender"` profile.
+    ///
+    /// See [`BlenderTechnique`] for the data that's available.
+    ///
+    /// [`BlenderTechnique`]: ../extensions/blender/struct.BlenderTechnique.html
+    pub fn as_blender(&self) -> Option<BlenderTechnique> {
+        if self.profile == "blender" {
+            Some(BlenderTechnique::from_technique(self))
+        } else {
+            None
+        }
+    }
+
+    /// Decodes the technique as Maya data, if it has the `"MAYA"` or `"OpenCOLLADAMaya"` profile.
+    ///
+    /// See [`MayaTechnique`] for the data that's available.
+    ///
+    /// [`MayaTechnique`]: ../extensions/maya/struct.MayaTechnique.html
+    pub fn as_maya(&self) -> Option<MayaTechnique> {
+        match &*self.profile {
+            "MAYA" | "OpenCOLLADAMaya" => Some(MayaTechnique::from_technique(self)),
+            _ => None,
+        }
+    }
+
+    /// Returns the text contents of the first direct child element named `name`.
+    ///
+    /// This is useful for pulling a single value out of a technique for a profile that doesn't
+    /// have typed support. Returns `None` if there is no such element. If the element contains
+    /// other elements, only its own text is returned.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use collaborate::v1_4::Collada;
+    /// # static DOCUMENT: &'static str = r#"
+    /// # <COLLADA xmlns="http://www.collada.org/2005/11/COLLADASchema" version="1.4.1">
+    /// #     <asset>
+    /// #         <created>2017-02-07T20:44:30Z</created>
+    /// #         <modified>2017-02-07T20:44:30Z</modified>
+    /// #     </asset>
+    /// #     <extra>
+    /// #         <technique profile="custom"><shadow>soft</shadow></technique>
+    /// #     </extra>
+    /// # </COLLADA>
+    /// # "#;
+    /// let document = Collada::from_str(DOCUMENT).unwrap();
+    /// let technique = &document.extras[0].techniques[0];
+    /// assert_eq!(Some("soft".into()), technique.get("shadow"));
+    /// assert_eq!(None, technique.get("color"));
+    /// ```
+    pub fn get(&self, name: &str) -> Option<String> {
+        self.child_values()
+            .find(|&(child_name, _)| child_name == name)
+            .map(|(_, value)| value)
+    }
+
+    /// Returns the text contents of every direct child element named `name`, in document order.
+    pub fn get_all(&self, name: &str) -> Vec<String> {
+        self.child_values()
+            .filter(|&(child_name, _)| child_name == name)
+            .map(|(_, value)| value)
+            .collect()
+    }
+
+    /// Parses the first direct child of the technique named `name` as a `T`.
+    ///
+    /// This allows structured data to be pulled out of a technique on demand, for vendor
+    /// extensions that embed elements the library already knows how to parse. The stored events
+    /// are written back out and parsed with `T`'s normal parser, so any error positions refer to
+    /// the re-written events rather than the original document.
+    ///
+    /// Returns `None` if the technique has no direct child named `name`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use collaborate::common::Unit;
+    /// # use collaborate::v1_4::Collada;
+    /// # static DOCUMENT: &'static str = r#"
+    /// #     <COLLADA xmlns="http://www.collada.org/2005/11/COLLADASchema" version="1.4.1">
+    /// #         <asset>
+    /// #             <created>2017-02-07T20:44:30Z</created>
+    /// #             <modified>2017-02-07T20:44:30Z</modified>
+    /// #         </asset>
+    /// #         <extra>
+    /// #             <technique profile="VENDOR">
+    /// #                 <unit meter="0.01" name="centimeter" />
+    /// #             </technique>
+    /// #         </extra>
+    /// #     </COLLADA>
+    /// # "#;
+    /// # let document = Collada::from_str(DOCUMENT).unwrap();
+    /// let technique = &document.extras[0].techniques[0];
+    /// let unit = technique.reparse_child::<Unit>("unit").unwrap().unwrap();
+    /// assert_eq!("centimeter", unit.name);
+    /// ```
+    pub fn reparse_child<T: ColladaElement>(&self, name: &str) -> Option<Result<T>> {
+        self.reparse_first(|child_name| child_name == name)
+    }
+
+    /// Parses the first direct child of the technique that matches `T`, if any.
+    ///
+    /// Returns `None` if no child matches or if the matching child fails to parse.
+    pub(crate) fn parse_child<T: ColladaElement>(&self) -> Option<T> {
+        self.reparse_first(T::name_test).and_then(|result| result.ok())
+    }
+
+    /// Parses the first direct child whose name passes `name_test` as a `T`.
+    fn reparse_first<T, F>(&self, name_test: F) -> Option<Result<T>>
+    where
+        T: ColladaElement,
+        F: Fn(&str) -> bool,
+    {
+        let contents = match utils::write_events(&self.data) {
+            Ok(contents) => contents,
+            Err(error) => {
+                return Some(Err(Error {
+                    position: TextPosition::new(),
+                    kind: ErrorKind::InvalidValue {
+                        element: "technique",
+                        value: error.to_string(),
+                    },
+                }));
+            }
+        };
+
+        let source = format!("<technique>{}</technique>", contents);
+        let events = utils::event_reader(source.as_bytes());
+        let mut reader = ColladaReader::new(events, ParseOptions::default());
+
+        // Skip the `StartDocument` event and the start of the wrapper element.
+        if let Err(error) = reader.next().and_then(|_| reader.next()) {
+            return Some(Err(error.into()));
+        }
+
+        loop {
+            let element = match utils::start_element(&mut reader, "technique") {
+                Ok(Some(element)) => element,
+                Ok(None) => return None,
+                Err(error) => return Some(Err(error)),
+            };
+
+            if name_test(&element.name.local_name) {
+                return Some(T::parse_element(&mut reader, element));
+            }
+
+            if let Err(error) = utils::stub_out(&mut reader) {
+                return Some(Err(error));
+            }
+        }
+    }
+
+    /// Checks if two techniques contain the same data, ignoring the order of attributes.
+    ///
+    /// The XML parser preserves attributes in the order they appear in the source, so two
+    /// techniques that describe the same data may not be `==` if they were written by different
+    /// tools. Everything else, including the order of child elements and the namespace mappings
+    /// in scope, must still match.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use collaborate::v1_4::Collada;
+    /// # fn example(cached: &Collada, fresh: &Collada) {
+    /// let unchanged = cached.extras.len() == fresh.extras.len()
+    ///     && cached.extras.iter().zip(&fresh.extras).all(|(cached, fresh)| {
+    ///         cached.techniques.len() == fresh.techniques.len()
+    ///             && cached.techniques.iter().zip(&fresh.techniques).all(|(a, b)| a.semantic_eq(b))
+    ///     });
+    /// # }
+    /// ```
+    pub fn semantic_eq(&self, other: &Technique) -> bool {
+        fn normalized(event: &XmlEvent) -> XmlEvent {
+            let mut event = event.clone();
+            if let XmlEvent::StartElement { ref mut attributes, .. } = event {
+                attributes.sort_by(|left, right| {
+                    (&left.name.namespace, &left.name.local_name)
+                        .cmp(&(&right.name.namespace, &right.name.local_name))
+                });
+            }
+            event
+        }
+
+        self.profile == other.profile
+            && self.xmlns == other.xmlns
+            && self.namespace == other.namespace
+            && self.data.len() == other.data.len()
+            && self.data.iter().zip(&other.data).all(|(left, right)| normalized(left) == normalized(right))
+    }
+
+    /// Returns the name and text contents of each direct child element of the technique.
+    ///
+    /// Any child elements nested more deeply are skipped, and children without text contents
+    /// have an empty value. Values are trimmed, since whitespace is kept in the technique's data
+    /// when [`ParseOptions::keep_whitespace`] is set.
+    ///
+    /// [`ParseOptions::keep_whitespace`]: ../struct.ParseOptions.html#structfield.keep_whitespace
+    pub(crate) fn child_values(&self) -> impl Iterator<Item = (&str, String)> {
+        let mut depth = 0;
+        let mut current = None;
+        self.data.iter().filter_map(move |event| {
+            match *event {
+                XmlEvent::StartElement { ref name, .. } => {
+                    if depth == 0 {
+                        current = Some((&*name.local_name, String::new()));
+                    }
+                    depth += 1;
+                }
+
+                XmlEvent::Characters(ref text) if depth == 1 => {
+                    if let Some((_, ref mut value)) = current {
+                        value.push_str(text);
+                    }
+                }
+
+                XmlEvent::EndElement { .. } => {
+                    depth -= 1;
+                    if depth == 0 {
+                        return current.take().map(|(name, value)| (name, value.trim().into()));
+                    }
+                }
+
+                _ => {}
+            }
+
+            None
+        })
+    }
+}
+
 impl ColladaElement for Technique {
     fn name_test(name: &str) -> bool {
         name == "technique"
     }
 
     fn parse_element<R>(
-        reader: &mut EventReader<R>,
+        reader: &mut ColladaReader<R>,
         element_start: ElementStart,
     ) -> Result<Technique>
     where
@@ -144,7 +913,7 @@ impl ColladaElement for Technique {
 
         let mut depth = 0;
         loop {
-            let event = reader.next()?;
+            let event = reader.next_raw()?;
             match event {
                 XmlEvent::StartElement { ref name, .. } if name.local_name == "technique" => { depth += 1; }
 
@@ -159,12 +928,15 @@ impl ColladaElement for Technique {
                 _ => {}
             }
 
-            data.push(event);
+            if !reader.options.skip_extra_data {
+                data.push(event);
+            }
         }
 
         Ok(Technique {
             profile: profile,
             xmlns: xmlns,
+            namespace: element_start.namespace,
             data: data,
         })
     }
@@ -172,6 +944,24 @@ impl ColladaElement for Technique {
     fn add_names(names: &mut Vec<&'static str>) {
         names.push("technique");
     }
+
+    fn write_element<W>(&self, writer: &mut ColladaWriter<W>) -> ::std::io::Result<()>
+    where
+        W: ::std::io::Write,
+    {
+        let xmlns = self.xmlns.as_ref().map(ToString::to_string);
+        let mut start = ::xml::writer::XmlEvent::start_element("technique").attr("profile", &self.profile);
+        if let Some(ref xmlns) = xmlns {
+            start = start.default_ns(&**xmlns);
+        }
+        writer.start_element(start)?;
+
+        for event in self.data.iter().filter_map(XmlEvent::as_writer_event) {
+            writer.write(event)?;
+        }
+
+        writer.end_element()
+    }
 }
 
 /// Defines the unit of distance for an [`Asset`][Asset].
@@ -182,20 +972,49 @@ impl ColladaElement for Technique {
 ///
 /// [Asset]: struct.Asset.html
 #[derive(Debug, Clone, PartialEq, ColladaElement)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 #[name = "unit"]
 pub struct Unit {
-    /// The name of the distance unit. For example, “meter”, “centimeter”, “inch”, or “parsec”.
-    /// This can be the name of a real measurement, or an imaginary name. Defaults to `1.0`.
+    /// How many real-world meters in one distance unit as a floating-point number. For example,
+    /// 1.0 for the name "meter"; 1000 for the name "kilometer"; 0.3048 for the name
+    /// "foot". Defaults to `1.0`.
     #[attribute]
+    #[optional_with_default = "1.0"]
     pub meter: f64,
 
-    /// How many real-world meters in one distance unit as a floating-point number. For example,
-    /// 1.0 for the name "meter"; 1000 for the name "kilometer"; 0.3048 for the name
-    /// "foot". Defaults to "meter".
+    /// The name of the distance unit. For example, “meter”, “centimeter”, “inch”, or “parsec”.
+    /// This can be the name of a real measurement, or an imaginary name. Defaults to "meter".
     #[attribute]
+    #[optional_with_default = "\"meter\".into()"]
     pub name: String,
 }
 
+impl Unit {
+    /// Returns the factor that converts distances in this unit to distances in `target`.
+    ///
+    /// Multiplying a distance measured in `self` by the returned value gives the same distance
+    /// measured in `target`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use collaborate::common::Unit;
+    ///
+    /// let centimeter = Unit { meter: 0.01, name: "centimeter".into() };
+    /// let meter = Unit::default();
+    /// assert_eq!(0.01, centimeter.scale_to(&meter));
+    /// assert_eq!(100.0, meter.scale_to(&centimeter));
+    /// ```
+    pub fn scale_to(&self, target: &Unit) -> f64 {
+        self.meter / target.meter
+    }
+
+    /// Returns the factor that converts distances in this unit to meters.
+    pub fn to_meters(&self) -> f64 {
+        self.meter
+    }
+}
+
 impl Default for Unit {
     fn default() -> Unit {
         Unit {
@@ -218,6 +1037,7 @@ impl Default for Unit {
 ///
 /// [Asset]: struct.Asset.html
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub enum UpAxis {
     X,
     Y,
@@ -230,7 +1050,7 @@ impl ColladaElement for UpAxis {
     }
 
     fn parse_element<R>(
-        reader: &mut EventReader<R>,
+        reader: &mut ColladaReader<R>,
         element_start: ElementStart,
     ) -> Result<UpAxis>
     where
@@ -238,27 +1058,97 @@ impl ColladaElement for UpAxis {
     {
         utils::verify_attributes(reader, "up_axis", element_start.attributes)?;
         let text: String = utils::optional_text_contents(reader, "up_axis")?.unwrap_or_default();
-        let parsed = match &*text {
-            "X_UP" => { UpAxis::X }
-            "Y_UP" => { UpAxis::Y }
-            "Z_UP" => { UpAxis::Z }
-            _ => {
-                return Err(Error {
-                    position: reader.position(),
-                    kind: ErrorKind::InvalidValue {
-                        element: "up_axis".into(),
-                        value: text,
-                    },
-                });
-            }
-        };
-
-        Ok(parsed)
+        text.parse().map_err(|kind| Error {
+            position: reader.position(),
+            kind,
+        })
     }
 
     fn add_names(names: &mut Vec<&'static str>) {
         names.push("up_axis");
     }
+
+    fn write_element<W>(&self, writer: &mut ColladaWriter<W>) -> ::std::io::Result<()>
+    where
+        W: ::std::io::Write,
+    {
+        writer.text_element("up_axis", &self.to_string())
+    }
+}
+
+impl FromStr for UpAxis {
+    type Err = ErrorKind;
+
+    fn from_str(string: &str) -> ::std::result::Result<UpAxis, ErrorKind> {
+        match string {
+            "X_UP" => Ok(UpAxis::X),
+            "Y_UP" => Ok(UpAxis::Y),
+            "Z_UP" => Ok(UpAxis::Z),
+            _ => Err(ErrorKind::InvalidValue {
+                element: "up_axis",
+                value: string.into(),
+            }),
+        }
+    }
+}
+
+impl ::std::fmt::Display for UpAxis {
+    fn fmt(&self, formatter: &mut ::std::fmt::Formatter) -> ::std::result::Result<(), ::std::fmt::Error> {
+        let value = match *self {
+            UpAxis::X => "X_UP",
+            UpAxis::Y => "Y_UP",
+            UpAxis::Z => "Z_UP",
+        };
+        formatter.write_str(value)
+    }
+}
+
+impl UpAxis {
+    /// Returns the rotation that converts coordinates from the `from` convention to `to`.
+    ///
+    /// The result is a right-handed rotation, as a column-major 4x4 matrix, that maps the right,
+    /// up, and in axes of `from` onto the corresponding axes of `to` (see the table in the
+    /// type-level documentation). If `from` and `to` are the same, the identity matrix is
+    /// returned.
+    ///
+    /// # Examples
+    ///
+    /// Converting a Z-up document (e.g. one exported from Blender) to Y-up:
+    ///
+    /// ```
+    /// use collaborate::common::UpAxis;
+    ///
+    /// let matrix = UpAxis::conversion_matrix(UpAxis::Z, UpAxis::Y);
+    ///
+    /// // The Z axis (up in the source document) becomes the Y axis.
+    /// assert_eq!([0.0, 1.0, 0.0], matrix[8..11]);
+    /// ```
+    pub fn conversion_matrix(from: UpAxis, to: UpAxis) -> [f64; 16] {
+        let from = from.basis();
+        let to = to.basis();
+
+        // Each basis is orthonormal, so `from`'s inverse is its transpose. Converting a vector is
+        // then a matter of projecting it onto `from`'s axes and rebuilding it from `to`'s axes.
+        let mut result = [0.0; 16];
+        for column in 0..3 {
+            for row in 0..3 {
+                result[column * 4 + row] = (0..3)
+                    .map(|axis| to[axis][row] * from[axis][column])
+                    .sum();
+            }
+        }
+        result[15] = 1.0;
+        result
+    }
+
+    /// Returns the right, up, and in axes for the convention, in that order.
+    fn basis(self) -> [[f64; 3]; 3] {
+        match self {
+            UpAxis::X => [[0.0, -1.0, 0.0], [1.0, 0.0, 0.0], [0.0, 0.0, 1.0]],
+            UpAxis::Y => [[1.0, 0.0, 0.0], [0.0, 1.0, 0.0], [0.0, 0.0, 1.0]],
+            UpAxis::Z => [[1.0, 0.0, 0.0], [0.0, 0.0, 1.0], [0.0, -1.0, 0.0]],
+        }
+    }
 }
 
 impl Default for UpAxis {
@@ -273,9 +1163,27 @@ impl Default for UpAxis {
 ///
 /// [`id`]: #method.id
 #[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct UriFragment(String);
 
 impl UriFragment {
+    /// Creates a fragment targeting the element with the ID `id`.
+    ///
+    /// A leading `#` is optional, so `UriFragment::new("Cube")` and `UriFragment::new("#Cube")`
+    /// both target `Cube`. Unlike parsing, this can't fail.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use collaborate::common::UriFragment;
+    /// let fragment = UriFragment::new("Cube-mesh");
+    /// assert_eq!("Cube-mesh", fragment.id());
+    /// assert_eq!("#Cube-mesh", fragment.to_string());
+    /// ```
+    pub fn new(id: &str) -> UriFragment {
+        UriFragment(id.strip_prefix('#').unwrap_or(id).into())
+    }
+
     /// Returns the ID that this fragment targets.
     pub fn id(&self) -> &str { self.0.as_ref() }
 }
@@ -292,6 +1200,12 @@ impl ::std::str::FromStr for UriFragment {
     }
 }
 
+impl ::std::fmt::Display for UriFragment {
+    fn fmt(&self, formatter: &mut ::std::fmt::Formatter) -> ::std::result::Result<(), ::std::fmt::Error> {
+        write!(formatter, "#{}", self.0)
+    }
+}
+
 /// An error when parsing a [`UriFragment`].
 ///
 /// The only way that parsing a [`UriFragment`] from a string can fail is if the string doesn't
@@ -299,6 +1213,7 @@ impl ::std::str::FromStr for UriFragment {
 ///
 /// [`UriFragment`]: ./struct.UriFragment.html
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct UriFragmentParseError;
 
 impl ::std::fmt::Display for UriFragmentParseError {
@@ -306,3 +1221,5 @@ impl ::std::fmt::Display for UriFragmentParseError {
         write!(formatter, "URI fragment did not start with a leading \"#\"")
     }
 }
+
+impl ::std::error::Error for UriFragmentParseError {}
@@ -0,0 +1,558 @@
+//! Export of documents to the [glTF 2.0][glTF] format.
+//!
+//! [glTF]: https://github.com/KhronosGroup/glTF/tree/master/specification/2.0
+
+use std::collections::HashMap;
+use std::fmt::{self, Display, Formatter};
+use v1_4::{Collada, ColorOrTexture, FloatOrParam, Mesh, Shader, Source};
+
+/// A glTF asset produced by [`to_gltf`].
+///
+/// The glTF JSON declares a single buffer that has no `uri`, with its contents in `buffer`. This
+/// is the layout expected for the binary chunk of a `.glb` file. To produce a `.gltf` file
+/// instead, write `buffer` to a separate file and add its location as the buffer's `uri`. If the
+/// document has no meshes to export, `buffer` is empty and no buffer is declared.
+///
+/// [`to_gltf`]: ./fn.to_gltf.html
+#[derive(Debug, Clone, PartialEq)]
+pub struct GltfOutput {
+    /// The glTF JSON document.
+    pub json: String,
+
+    /// The binary data referenced by the JSON document.
+    pub buffer: Vec<u8>,
+}
+
+/// An error that occurred while converting a document to glTF.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Error {
+    /// A mesh didn't have any position data that could be exported.
+    MissingPositions {
+        /// The id of the geometry containing the mesh, if it has one.
+        geometry: Option<String>,
+    },
+
+    /// A primitive had an index that was past the end of the source it refers to.
+    IndexOutOfRange {
+        /// The id of the geometry containing the mesh, if it has one.
+        geometry: Option<String>,
+
+        /// The semantic of the input the index belongs to, e.g. `"POSITION"`.
+        semantic: &'static str,
+
+        /// The out-of-range index.
+        index: usize,
+    },
+
+    /// A mesh had a position that was NaN or infinite, which can't be represented in the bounds
+    /// that glTF requires for positions.
+    NonFinitePosition {
+        /// The id of the geometry containing the mesh, if it has one.
+        geometry: Option<String>,
+    },
+}
+
+impl Display for Error {
+    fn fmt(&self, formatter: &mut Formatter) -> ::std::result::Result<(), fmt::Error> {
+        match *self {
+            Error::MissingPositions { geometry: Some(ref geometry) } => {
+                write!(formatter, "Mesh in geometry \"{}\" has no exportable position data", geometry)
+            }
+
+            Error::MissingPositions { geometry: None } => {
+                write!(formatter, "Mesh has no exportable position data")
+            }
+
+            Error::IndexOutOfRange { ref geometry, semantic, index } => {
+                write!(formatter, "{} index {} is out of range", semantic, index)?;
+                if let Some(ref geometry) = *geometry {
+                    write!(formatter, " in geometry \"{}\"", geometry)?;
+                }
+                Ok(())
+            }
+
+            Error::NonFinitePosition { geometry: Some(ref geometry) } => {
+                write!(formatter, "Mesh in geometry \"{}\" has a NaN or infinite position", geometry)
+            }
+
+            Error::NonFinitePosition { geometry: None } => {
+                write!(formatter, "Mesh has a NaN or infinite position")
+            }
+        }
+    }
+}
+
+impl ::std::error::Error for Error {}
+
+pub type Result<T> = ::std::result::Result<T, Error>;
+
+/// Converts the geometry and materials in `document` to glTF.
+///
+/// Each [`Mesh`] in the document becomes a glTF mesh with one primitive for each of its
+/// primitives, and a node instantiating each mesh is added to a single default scene. Polygons
+/// are triangulated, and the `"POSITION"`, `"NORMAL"`, and first `"TEXCOORD"` inputs become
+/// accessors in the output. Since glTF requires all attributes of a vertex to share one index,
+/// vertices are duplicated wherever a position is used with different normals or texture
+/// coordinates.
+///
+/// Materials are approximated using glTF's metallic-roughness model, based on the shader of the
+/// effect's common profile: the diffuse color becomes the base color, the emission color becomes
+/// the emissive factor, and Phong/Blinn shininess is converted to a roughness value. Textures are
+/// not exported. A primitive's `material` symbol is matched directly against material ids.
+///
+/// > NOTE: Only `<polylist>` and `<triangles>` primitives are currently exported, other
+/// > primitive types are skipped. Scene hierarchy and node transforms are not exported.
+///
+/// # Examples
+///
+/// ```
+/// use std::fs::File;
+/// use collaborate::export::gltf;
+/// use collaborate::v1_4::Collada;
+///
+/// let file = File::open("resources/blender_cube.dae").unwrap();
+/// let document = Collada::read(file).unwrap();
+/// let output = gltf::to_gltf(&document).unwrap();
+/// println!("{}", output.json);
+/// ```
+///
+/// # Errors
+///
+/// Returns an error if a mesh has no position data that can be exported, if a primitive has an
+/// index that's past the end of the source it refers to, or if a position is NaN or infinite.
+///
+/// [`Mesh`]: ../../v1_4/struct.Mesh.html
+pub fn to_gltf(document: &Collada) -> Result<GltfOutput> {
+    let mut builder = Builder::default();
+    let material_indices = builder.add_materials(document);
+
+    for library in document.libraries() {
+        let library = match library.as_library_geometries() {
+            Some(library) => library,
+            None => continue,
+        };
+
+        for geometry in library.geometries() {
+            let mesh = match geometry.geometric_element.as_mesh() {
+                Some(mesh) => mesh,
+                None => continue,
+            };
+
+            builder.add_mesh(geometry.id.as_deref(), mesh, &material_indices)?;
+        }
+    }
+
+    Ok(builder.finish())
+}
+
+#[derive(Debug, Default)]
+struct Builder {
+    buffer: Vec<u8>,
+    buffer_views: Vec<String>,
+    accessors: Vec<String>,
+    materials: Vec<String>,
+    meshes: Vec<String>,
+}
+
+impl Builder {
+    /// Adds all materials in the document, returning the glTF index for each material id.
+    fn add_materials<'a>(&mut self, document: &'a Collada) -> HashMap<&'a str, usize> {
+        let effects = document.libraries()
+            .filter_map(|library| library.as_library_effects())
+            .flat_map(|library| library.effects.iter())
+            .collect::<Vec<_>>();
+
+        let mut indices = HashMap::new();
+        for library in document.libraries() {
+            let library = match library.as_library_materials() {
+                Some(library) => library,
+                None => continue,
+            };
+
+            for material in &library.materials {
+                let shader = effects.iter()
+                    .find(|effect| Some(&*effect.id) == material.instance_effect.url.local_id())
+                    .and_then(|effect| effect.profile_common())
+                    .map(|profile| &profile.technique.shader);
+
+                let mut json = String::from("{");
+                if let Some(name) = material.name.as_ref().or(material.id.as_ref()) {
+                    json.push_str(&format!("\"name\":{},", json_string(name)));
+                }
+                json.push_str(&pbr_material(shader));
+                json.push('}');
+
+                if let Some(ref id) = material.id {
+                    indices.insert(&**id, self.materials.len());
+                }
+                self.materials.push(json);
+            }
+        }
+
+        indices
+    }
+
+    fn add_mesh(&mut self, name: Option<&str>, mesh: &Mesh, materials: &HashMap<&str, usize>) -> Result<()> {
+        let missing_positions = || Error::MissingPositions { geometry: name.map(Into::into) };
+        let out_of_range = |semantic, index| Error::IndexOutOfRange {
+            geometry: name.map(Into::into),
+            semantic,
+            index,
+        };
+        let positions = mesh.vertices.inputs.iter()
+            .find(|input| input.semantic == "POSITION")
+            .and_then(|input| mesh.find_source(input.source.id()))
+            .and_then(|source| read_source(source, &["X", "Y", "Z"]))
+            .ok_or_else(missing_positions)?;
+
+        // Normals and texture coordinates may either be specified per-vertex in `<vertices>`, in
+        // which case they share the position index, or directly on the primitives.
+        let vertex_attribute = |semantic: &str, params: &[&str]| {
+            mesh.vertices.inputs.iter()
+                .find(|input| input.semantic == semantic)
+                .and_then(|input| mesh.find_source(input.source.id()))
+                .and_then(|source| read_source(source, params))
+        };
+        let vertex_normals = vertex_attribute("NORMAL", &["X", "Y", "Z"]);
+        let vertex_texcoords = vertex_attribute("TEXCOORD", &["S", "T"]);
+
+        let mut primitives = Vec::new();
+        for primitive in &mesh.primitives {
+            let triangles = match primitive.triangulate() {
+                Some(triangles) => triangles,
+                None => continue,
+            };
+
+            let input = |semantic: &str, params: &[&str]| {
+                primitive.inputs().iter()
+                    .find(|input| input.semantic == semantic)
+                    .and_then(|input| {
+                        let source = mesh.find_source(input.source.id())?;
+                        Some((input.offset, read_source(source, params)?))
+                    })
+            };
+            let vertex_offset = match primitive.inputs().iter().find(|input| input.semantic == "VERTEX") {
+                Some(input) => input.offset,
+                None => continue,
+            };
+            let normals = input("NORMAL", &["X", "Y", "Z"]);
+            let texcoords = input("TEXCOORD", &["S", "T"]);
+
+            let has_normals = normals.is_some() || vertex_normals.is_some();
+            let has_texcoords = texcoords.is_some() || vertex_texcoords.is_some();
+
+            let mut unique = HashMap::new();
+            let mut vertices = Vertices::default();
+            let mut indices = Vec::new();
+            for triangle in triangles {
+                for vertex in &triangle {
+                    let attributes: Vec<usize> = vertex.iter().map(|attribute| attribute.index).collect();
+                    let position = *attributes.get(vertex_offset).ok_or_else(|| out_of_range("VERTEX", vertex_offset))?;
+                    let normal = match normals {
+                        Some((offset, _)) => attributes.get(offset).cloned(),
+                        None => Some(position),
+                    };
+                    let texcoord = match texcoords {
+                        Some((offset, _)) => attributes.get(offset).cloned(),
+                        None => Some(position),
+                    };
+
+                    let key = (position, normal, texcoord);
+                    if let Some(&index) = unique.get(&key) {
+                        indices.push(index);
+                        continue;
+                    }
+
+                    let index = vertices.positions.len() as u32 / 3;
+                    let position_record = record(&positions, position, 3)
+                        .ok_or_else(|| out_of_range("POSITION", position))?;
+                    vertices.positions.extend_from_slice(position_record);
+                    if has_normals {
+                        let data = normals.as_ref().map(|normals| &normals.1).or(vertex_normals.as_ref());
+                        let normal = normal.unwrap_or(0);
+                        let normal = record(data.unwrap(), normal, 3).ok_or_else(|| out_of_range("NORMAL", normal))?;
+                        vertices.normals.extend_from_slice(normal);
+                    }
+                    if has_texcoords {
+                        let data = texcoords.as_ref().map(|texcoords| &texcoords.1).or(vertex_texcoords.as_ref());
+                        let texcoord = texcoord.unwrap_or(0);
+                        let texcoord = record(data.unwrap(), texcoord, 2).ok_or_else(|| out_of_range("TEXCOORD", texcoord))?;
+
+                        // glTF places the texture coordinate origin in the top-left corner, COLLADA in
+                        // the bottom-left.
+                        vertices.texcoords.extend_from_slice(&[texcoord[0], 1.0 - texcoord[1]]);
+                    }
+
+                    unique.insert(key, index);
+                    indices.push(index);
+                }
+            }
+
+            if indices.is_empty() {
+                continue;
+            }
+
+            // The bounds of the positions are written to the JSON, which can't hold NaN or
+            // infinity.
+            if vertices.positions.iter().any(|value| !value.is_finite()) {
+                return Err(Error::NonFinitePosition { geometry: name.map(Into::into) });
+            }
+
+            let mut attributes = vec![
+                format!("\"POSITION\":{}", self.add_float_accessor(&vertices.positions, "VEC3", true)),
+            ];
+            if has_normals {
+                attributes.push(format!("\"NORMAL\":{}", self.add_float_accessor(&vertices.normals, "VEC3", false)));
+            }
+            if has_texcoords {
+                attributes.push(format!("\"TEXCOORD_0\":{}", self.add_float_accessor(&vertices.texcoords, "VEC2", false)));
+            }
+
+            let mut json = format!(
+                "{{\"attributes\":{{{}}},\"indices\":{},\"mode\":4",
+                attributes.join(","),
+                self.add_index_accessor(&indices),
+            );
+            if let Some(&material) = primitive.material().and_then(|material| materials.get(material)) {
+                json.push_str(&format!(",\"material\":{}", material));
+            }
+            json.push('}');
+            primitives.push(json);
+        }
+
+        // glTF meshes must have at least one primitive.
+        if primitives.is_empty() {
+            return Ok(());
+        }
+
+        let mut json = String::from("{");
+        if let Some(name) = name {
+            json.push_str(&format!("\"name\":{},", json_string(name)));
+        }
+        json.push_str(&format!("\"primitives\":[{}]}}", primitives.join(",")));
+        self.meshes.push(json);
+
+        Ok(())
+    }
+
+    /// Appends `data` to the buffer, returning the index of the new buffer view.
+    fn add_buffer_view(&mut self, data: &[u8], target: u32) -> usize {
+        // Every component type we write is 4 bytes, so keeping each view 4-byte aligned keeps
+        // all accessors aligned.
+        while self.buffer.len() & 3 != 0 {
+            self.buffer.push(0);
+        }
+
+        self.buffer_views.push(format!(
+            "{{\"buffer\":0,\"byteOffset\":{},\"byteLength\":{},\"target\":{}}}",
+            self.buffer.len(),
+            data.len(),
+            target,
+        ));
+        self.buffer.extend_from_slice(data);
+        self.buffer_views.len() - 1
+    }
+
+    fn add_float_accessor(&mut self, data: &[f32], accessor_type: &str, bounds: bool) -> usize {
+        const ARRAY_BUFFER: u32 = 34962;
+        const FLOAT: u32 = 5126;
+
+        let components = if accessor_type == "VEC3" { 3 } else { 2 };
+        let mut bytes = Vec::with_capacity(data.len() * 4);
+        for value in data {
+            bytes.extend_from_slice(&value.to_bits().to_le_bytes());
+        }
+        let view = self.add_buffer_view(&bytes, ARRAY_BUFFER);
+
+        let mut json = format!(
+            "{{\"bufferView\":{},\"componentType\":{},\"count\":{},\"type\":\"{}\"",
+            view,
+            FLOAT,
+            data.len() / components,
+            accessor_type,
+        );
+
+        // glTF requires the bounds of position accessors.
+        if bounds {
+            let mut min = vec![f32::INFINITY; components];
+            let mut max = vec![f32::NEG_INFINITY; components];
+            for chunk in data.chunks(components) {
+                for (index, &value) in chunk.iter().enumerate() {
+                    min[index] = min[index].min(value);
+                    max[index] = max[index].max(value);
+                }
+            }
+            json.push_str(&format!(",\"min\":{},\"max\":{}", json_floats(&min), json_floats(&max)));
+        }
+
+        json.push('}');
+        self.accessors.push(json);
+        self.accessors.len() - 1
+    }
+
+    fn add_index_accessor(&mut self, indices: &[u32]) -> usize {
+        const ELEMENT_ARRAY_BUFFER: u32 = 34963;
+        const UNSIGNED_INT: u32 = 5125;
+
+        let mut bytes = Vec::with_capacity(indices.len() * 4);
+        for index in indices {
+            bytes.extend_from_slice(&index.to_le_bytes());
+        }
+        let view = self.add_buffer_view(&bytes, ELEMENT_ARRAY_BUFFER);
+
+        self.accessors.push(format!(
+            "{{\"bufferView\":{},\"componentType\":{},\"count\":{},\"type\":\"SCALAR\"}}",
+            view,
+            UNSIGNED_INT,
+            indices.len(),
+        ));
+        self.accessors.len() - 1
+    }
+
+    fn finish(self) -> GltfOutput {
+        let nodes = (0..self.meshes.len())
+            .map(|index| format!("{{\"mesh\":{}}}", index))
+            .collect::<Vec<_>>();
+        let scene_nodes = (0..nodes.len())
+            .map(|index| index.to_string())
+            .collect::<Vec<_>>();
+
+        // glTF doesn't allow empty arrays, so any that would be empty are left out entirely.
+        let mut json = String::from("{\"asset\":{\"version\":\"2.0\",\"generator\":\"collaborate\"}");
+        if scene_nodes.is_empty() {
+            json.push_str(",\"scene\":0,\"scenes\":[{}]");
+        } else {
+            json.push_str(&format!(",\"scene\":0,\"scenes\":[{{\"nodes\":[{}]}}]", scene_nodes.join(",")));
+        }
+        push_array(&mut json, "nodes", &nodes);
+        push_array(&mut json, "meshes", &self.meshes);
+        push_array(&mut json, "materials", &self.materials);
+        push_array(&mut json, "accessors", &self.accessors);
+        push_array(&mut json, "bufferViews", &self.buffer_views);
+        if !self.buffer.is_empty() {
+            json.push_str(&format!(",\"buffers\":[{{\"byteLength\":{}}}]", self.buffer.len()));
+        }
+        json.push('}');
+
+        GltfOutput {
+            json,
+            buffer: self.buffer,
+        }
+    }
+}
+
+/// The flattened attribute data for the vertices of a single primitive.
+#[derive(Debug, Default)]
+struct Vertices {
+    positions: Vec<f32>,
+    normals: Vec<f32>,
+    texcoords: Vec<f32>,
+}
+
+/// Reads the values of the accessor params named in `params` for every record in `source`.
+///
+/// Returns `None` if the source doesn't contain float data with all of the requested params.
+fn read_source(source: &Source, params: &[&str]) -> Option<Vec<f32>> {
+    let accessor = source.common_accessor()?;
+    let components = params.iter()
+        .map(|param| accessor.param_index(param))
+        .collect::<Option<Vec<_>>>()?;
+
+    let mut values = Vec::new();
+    for record in source.float_values()? {
+        for &component in &components {
+            values.push(record.get(component).cloned().unwrap_or(0.0));
+        }
+    }
+    Some(values)
+}
+
+/// Returns the record at `index` in data read by `read_source`, or `None` if it's out of bounds.
+fn record(data: &[f32], index: usize, components: usize) -> Option<&[f32]> {
+    let start = index.checked_mul(components)?;
+    data.get(start..start.checked_add(components)?)
+}
+
+/// Builds the metallic-roughness properties approximating a common profile shader.
+fn pbr_material(shader: Option<&Shader>) -> String {
+    // glTF factors must be between 0 and 1, and JSON can't hold NaN or infinity, so colors that
+    // don't fit are replaced with the default.
+    let color = |value: Option<&ColorOrTexture>| {
+        value.and_then(|value| match *value {
+            ColorOrTexture::Color(ref color) if color.rgba.iter().all(|value| value.is_finite()) => {
+                let mut rgba = color.rgba;
+                for value in &mut rgba {
+                    *value = value.clamp(0.0, 1.0);
+                }
+                Some(rgba)
+            }
+            _ => None,
+        })
+    };
+
+    let (base_color, roughness) = match shader {
+        // A constant shader isn't affected by lighting, so all of its color comes from emission.
+        Some(&Shader::Constant(_)) => ([0.0, 0.0, 0.0, 1.0], 1.0),
+
+        Some(shader) => {
+            let base_color = color(shader.diffuse()).unwrap_or([1.0; 4]);
+
+            // Map the Phong exponent to the roughness of a Beckmann distribution with the same
+            // highlight. Negative exponents are treated as 0 to keep the result in the 0 to 1
+            // range that glTF expects.
+            let roughness = shader.shininess()
+                .and_then(|shininess| match *shininess {
+                    FloatOrParam::Float(ref shininess) if !shininess.value.is_nan() => {
+                        Some((2.0 / (shininess.value.max(0.0) + 2.0)).sqrt())
+                    }
+                    _ => None,
+                })
+                .unwrap_or(1.0);
+            (base_color, roughness)
+        }
+
+        None => ([1.0; 4], 1.0),
+    };
+
+    let emissive = shader.and_then(|shader| color(shader.emission())).unwrap_or([0.0; 4]);
+
+    format!(
+        "\"pbrMetallicRoughness\":{{\"baseColorFactor\":{},\"metallicFactor\":0,\"roughnessFactor\":{}}},\"emissiveFactor\":{}",
+        json_floats(&base_color),
+        roughness,
+        json_floats(&emissive[..3]),
+    )
+}
+
+/// Appends `"name":[items]` to an object in `json`, or nothing if `items` is empty.
+fn push_array(json: &mut String, name: &str, items: &[String]) {
+    if !items.is_empty() {
+        json.push_str(&format!(",\"{}\":[{}]", name, items.join(",")));
+    }
+}
+
+/// Writes `values` as a JSON array. The values must all be finite.
+fn json_floats(values: &[f32]) -> String {
+    let values = values.iter().map(|value| value.to_string()).collect::<Vec<_>>();
+    format!("[{}]", values.join(","))
+}
+
+fn json_string(value: &str) -> String {
+    let mut result = String::with_capacity(value.len() + 2);
+    result.push('"');
+    for character in value.chars() {
+        match character {
+            '"' => result.push_str("\\\""),
+            '\\' => result.push_str("\\\\"),
+            '\n' => result.push_str("\\n"),
+            '\r' => result.push_str("\\r"),
+            '\t' => result.push_str("\\t"),
+            character if (character as u32) < 0x20 => {
+                result.push_str(&format!("\\u{:04x}", character as u32));
+            }
+            character => result.push(character),
+        }
+    }
+    result.push('"');
+    result
+}
@@ -0,0 +1,7 @@
+//! Utilities for converting parsed COLLADA data into other file formats.
+//!
+//! These exporters are intended as a quick interchange path (e.g. for inspecting geometry in
+//! other tools), and only cover the commonly used subset of the data in a document.
+
+pub mod gltf;
+pub mod obj;
@@ -0,0 +1,268 @@
+//! Export of mesh data to the [Wavefront OBJ][OBJ] format.
+//!
+//! [OBJ]: https://en.wikipedia.org/wiki/Wavefront_.obj_file
+
+use std::io::{self, Write};
+use v1_4::{Mesh, Primitive, Source};
+
+/// Writes `mesh` to `writer` as a Wavefront OBJ document.
+///
+/// Positions, normals, and texture coordinates are written as `v`, `vn`, and `vt` lines
+/// respectively, followed by one `f` line for each triangle in the mesh. Polygons are
+/// fan-triangulated. Only the `"POSITION"`, `"NORMAL"`, and `"TEXCOORD"` semantics are
+/// exported, all other inputs are ignored. If a mesh has multiple inputs for `"TEXCOORD"`, only
+/// the first one is used.
+///
+/// Face elements only reference the data the mesh actually has, so a mesh with positions but no
+/// normals will produce faces like `f 1 2 3`, and a mesh with positions and normals will produce
+/// faces like `f 1//1 2//2 3//3`.
+///
+/// > NOTE: Only `<polylist>` and `<triangles>` primitives are currently exported, other
+/// > primitive types are skipped.
+///
+/// # Examples
+///
+/// ```
+/// # use std::fs::File;
+/// # use collaborate::v1_4::{Collada, Library};
+/// use collaborate::export::obj;
+///
+/// # let file = File::open("resources/blender_cube.dae").unwrap();
+/// # let document = Collada::read(file).unwrap();
+/// # let library = document.libraries_of(Library::as_library_geometries).next().unwrap();
+/// let mesh = library.geometries[0].geometric_element.as_mesh().unwrap();
+/// let mut output = Vec::new();
+/// obj::write_mesh(mesh, &mut output).unwrap();
+/// ```
+///
+/// # Errors
+///
+/// Returns an error of kind `InvalidData` if the mesh has no position data that can be exported,
+/// or if a primitive's `<p>` has an index that's past the end of the source it refers to. Any
+/// errors from `writer` are forwarded.
+pub fn write_mesh<W: Write>(mesh: &Mesh, mut writer: W) -> io::Result<()> {
+    let position_input = mesh.vertices.inputs.iter()
+        .find(|input| input.semantic == "POSITION")
+        .ok_or_else(|| invalid_data("mesh has no \"POSITION\" input"))?;
+    let positions = mesh.find_source(position_input.source.id())
+        .ok_or_else(|| invalid_data("mesh's \"POSITION\" source is missing"))?;
+    let position_count = match write_records(&mut writer, "v", positions, &["X", "Y", "Z"])? {
+        Some(count) => count,
+        None => return Err(invalid_data("mesh's \"POSITION\" source doesn't contain X, Y, Z float data")),
+    };
+
+    // Normals and texture coordinates may either be specified per-vertex in `<vertices>`, in
+    // which case they share the position index, or directly on the primitives.
+    let mut normals = Attribute::new(mesh, "NORMAL", &["X", "Y", "Z"]);
+    let mut texcoords = Attribute::new(mesh, "TEXCOORD", &["S", "T"]);
+    for primitive in &mesh.primitives {
+        match *primitive {
+            Primitive::Polylist(_) | Primitive::Triangles(_) => {}
+            _ => continue,
+        }
+
+        for input in primitive.inputs() {
+            normals.add_source(mesh, &input.semantic, input.source.id());
+            texcoords.add_source(mesh, &input.semantic, input.source.id());
+        }
+    }
+    normals.write(&mut writer, "vn")?;
+    texcoords.write(&mut writer, "vt")?;
+
+    for primitive in &mesh.primitives {
+        let triangles = match primitive.triangulate() {
+            Some(triangles) => triangles,
+            None => continue,
+        };
+
+        let offset_for = |semantic: &str| {
+            primitive.inputs().iter()
+                .find(|input| input.semantic == semantic)
+                .map(|input| (input.offset, input.source.id()))
+        };
+        let vertex_offset = match offset_for("VERTEX") {
+            Some((offset, _)) => offset,
+            None => continue,
+        };
+        let normal_offset = offset_for("NORMAL");
+        let texcoord_offset = offset_for("TEXCOORD");
+
+        for triangle in triangles {
+            write!(writer, "f")?;
+            for vertex in &triangle {
+                let indices: Vec<usize> = vertex.iter().map(|attribute| attribute.index).collect();
+                let position = *indices.get(vertex_offset)
+                    .ok_or_else(|| invalid_data("vertex is missing its \"VERTEX\" index"))?;
+                if position >= position_count {
+                    return Err(out_of_range("POSITION", position, position_count));
+                }
+                let normal = normals.index(normal_offset, position, &indices)?;
+                let texcoord = texcoords.index(texcoord_offset, position, &indices)?;
+
+                // OBJ indices are 1-based.
+                match (texcoord, normal) {
+                    (None, None) => write!(writer, " {}", position + 1)?,
+                    (Some(texcoord), None) => write!(writer, " {}/{}", position + 1, texcoord + 1)?,
+                    (None, Some(normal)) => write!(writer, " {}//{}", position + 1, normal + 1)?,
+                    (Some(texcoord), Some(normal)) => {
+                        write!(writer, " {}/{}/{}", position + 1, texcoord + 1, normal + 1)?
+                    }
+                }
+            }
+            writeln!(writer)?;
+        }
+    }
+
+    Ok(())
+}
+
+/// Tracks the sources used for a single vertex attribute, and where each source's data starts in
+/// the OBJ output.
+struct Attribute<'a> {
+    semantic: &'static str,
+    params: &'static [&'static str],
+
+    /// The source referenced by `<vertices>`, if any.
+    per_vertex: Option<&'a Source>,
+
+    /// The number of records written for `per_vertex`.
+    per_vertex_count: usize,
+
+    /// The sources referenced by primitive inputs, in the order they're first used.
+    sources: Vec<&'a Source>,
+
+    /// The index of the first record for each entry in `sources`, after they've been written.
+    bases: Vec<usize>,
+
+    /// The number of records written for each entry in `sources`.
+    counts: Vec<usize>,
+}
+
+impl<'a> Attribute<'a> {
+    fn new(mesh: &'a Mesh, semantic: &'static str, params: &'static [&'static str]) -> Attribute<'a> {
+        let per_vertex = mesh.vertices.inputs.iter()
+            .find(|input| input.semantic == semantic)
+            .and_then(|input| mesh.find_source(input.source.id()));
+
+        Attribute {
+            semantic,
+            params,
+            per_vertex,
+            per_vertex_count: 0,
+            sources: Vec::new(),
+            bases: Vec::new(),
+            counts: Vec::new(),
+        }
+    }
+
+    fn add_source(&mut self, mesh: &'a Mesh, semantic: &str, id: &str) {
+        if semantic != self.semantic || self.sources.iter().any(|source| source.id == id) {
+            return;
+        }
+
+        if let Some(source) = mesh.find_source(id) {
+            self.sources.push(source);
+        }
+    }
+
+    /// Writes the data for all sources, dropping any that can't be exported.
+    fn write<W: Write>(&mut self, writer: &mut W, prefix: &str) -> io::Result<()> {
+        let mut total = 0;
+        if let Some(source) = self.per_vertex {
+            match write_records(writer, prefix, source, self.params)? {
+                Some(count) => {
+                    total = count;
+                    self.per_vertex_count = count;
+                }
+                None => { self.per_vertex = None; }
+            }
+        }
+
+        let mut sources = Vec::with_capacity(self.sources.len());
+        for &source in &self.sources {
+            if let Some(count) = write_records(writer, prefix, source, self.params)? {
+                sources.push(source);
+                self.bases.push(total);
+                self.counts.push(count);
+                total += count;
+            }
+        }
+        self.sources = sources;
+
+        Ok(())
+    }
+
+    /// Returns the output index for a vertex, given the offset and source of the primitive input
+    /// for this attribute, the vertex's position index, and all of the vertex's indices.
+    ///
+    /// Returns an error of kind `InvalidData` if the index is past the end of its source.
+    fn index(
+        &self,
+        input: Option<(usize, &str)>,
+        position: usize,
+        indices: &[usize],
+    ) -> io::Result<Option<usize>> {
+        let (base, count, index) = match input {
+            Some((offset, id)) => {
+                let source = self.sources.iter().position(|source| source.id == id);
+                match (source, indices.get(offset)) {
+                    (Some(source), Some(&index)) => (self.bases[source], self.counts[source], index),
+                    _ => return Ok(None),
+                }
+            }
+
+            None if self.per_vertex.is_some() => (0, self.per_vertex_count, position),
+            None => return Ok(None),
+        };
+
+        if index >= count {
+            return Err(out_of_range(self.semantic, index, count));
+        }
+        Ok(Some(base + index))
+    }
+}
+
+/// Writes one line for each record in `source`, using the values of the accessor params named in
+/// `params`.
+///
+/// Returns the number of records written, or `None` (without writing anything) if the source
+/// doesn't contain float data with all of the requested params.
+fn write_records<W: Write>(
+    writer: &mut W,
+    prefix: &str,
+    source: &Source,
+    params: &[&str],
+) -> io::Result<Option<usize>> {
+    let records = match source.float_values() {
+        Some(records) => records,
+        None => return Ok(None),
+    };
+    let components = match source.common_accessor() {
+        Some(accessor) => params.iter().map(|param| accessor.param_index(param)).collect::<Option<Vec<_>>>(),
+        None => None,
+    };
+    let components = match components {
+        Some(components) => components,
+        None => return Ok(None),
+    };
+
+    let mut count = 0;
+    for record in records {
+        write!(writer, "{}", prefix)?;
+        for &component in &components {
+            write!(writer, " {}", record.get(component).cloned().unwrap_or(0.0))?;
+        }
+        writeln!(writer)?;
+        count += 1;
+    }
+
+    Ok(Some(count))
+}
+
+fn invalid_data(message: &str) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, message)
+}
+
+fn out_of_range(semantic: &str, index: usize, count: usize) -> io::Error {
+    invalid_data(&format!("{} index {} is out of range for {} records", semantic, index, count))
+}
@@ -0,0 +1,89 @@
+//! Data from the `"blender"` technique profile, as written by Blender's COLLADA exporter.
+
+use common::Technique;
+use extensions::{self, take_color, take_parsed};
+use std::collections::HashMap;
+
+/// The contents of a `<technique profile="blender">` element.
+///
+/// Blender writes one value per child element of the technique, and which values are present
+/// depends on the element the technique belongs to. For example, cameras have `shift_x` and
+/// `shift_y`, while lights have `energy` and `color`. Any value that isn't present, or that
+/// couldn't be parsed, is `None`.
+///
+/// Values that don't have a dedicated field are kept as raw text in `other`, keyed by the name
+/// of the element that contained them.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct BlenderTechnique {
+    /// The horizontal lens shift of a camera, from `<shiftx>`.
+    pub shift_x: Option<f32>,
+
+    /// The vertical lens shift of a camera, from `<shifty>`.
+    pub shift_y: Option<f32>,
+
+    /// The depth of field focus distance of a camera, from `<YF_dofdist>`.
+    pub dof_distance: Option<f32>,
+
+    /// The strength of a light, from `<energy>`.
+    pub energy: Option<f32>,
+
+    /// The falloff distance of a light, from `<dist>`.
+    pub distance: Option<f32>,
+
+    /// The gamma correction applied to a light, from `<gamma>`.
+    pub gamma: Option<f32>,
+
+    /// The RGB color of a light, from `<red>`, `<green>`, and `<blue>`.
+    ///
+    /// Only present if all three components are.
+    pub color: Option<[f32; 3]>,
+
+    /// The RGB color of the shadows cast by a light, from `<shadow_r>`, `<shadow_g>`, and
+    /// `<shadow_b>`.
+    ///
+    /// Only present if all three components are.
+    pub shadow_color: Option<[f32; 3]>,
+
+    /// The cone angle of a spot light in degrees, from `<spotsize>`.
+    pub spot_size: Option<f32>,
+
+    /// The softness of the edge of a spot light's cone, from `<spotblend>`.
+    pub spot_blend: Option<f32>,
+
+    /// The near clipping distance for a light's shadow buffer, from `<clipsta>`.
+    pub clip_start: Option<f32>,
+
+    /// The far clipping distance for a light's shadow buffer, from `<clipend>`.
+    pub clip_end: Option<f32>,
+
+    /// The name of the layer containing a node, from `<layer>`.
+    pub layer: Option<String>,
+
+    /// All other values in the technique, keyed by element name.
+    pub other: HashMap<String, String>,
+}
+
+impl BlenderTechnique {
+    /// Decodes the contents of `technique`, which must have the `"blender"` profile.
+    pub(crate) fn from_technique(technique: &Technique) -> BlenderTechnique {
+        let mut result = BlenderTechnique::default();
+        let mut values = extensions::child_values(technique);
+
+        result.shift_x = take_parsed(&mut values, "shiftx");
+        result.shift_y = take_parsed(&mut values, "shifty");
+        result.dof_distance = take_parsed(&mut values, "YF_dofdist");
+        result.energy = take_parsed(&mut values, "energy");
+        result.distance = take_parsed(&mut values, "dist");
+        result.gamma = take_parsed(&mut values, "gamma");
+        result.color = take_color(&mut values, ["red", "green", "blue"]);
+        result.shadow_color = take_color(&mut values, ["shadow_r", "shadow_g", "shadow_b"]);
+        result.spot_size = take_parsed(&mut values, "spotsize");
+        result.spot_blend = take_parsed(&mut values, "spotblend");
+        result.clip_start = take_parsed(&mut values, "clipsta");
+        result.clip_end = take_parsed(&mut values, "clipend");
+        result.layer = values.remove("layer");
+        result.other = values;
+
+        result
+    }
+}
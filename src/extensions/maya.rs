@@ -0,0 +1,46 @@
+//! Data from the `"MAYA"` technique profile, as written by Maya's COLLADA exporters.
+
+use common::Technique;
+use extensions::{self, take_bool};
+use std::collections::HashMap;
+
+/// The contents of a `<technique profile="MAYA">` element.
+///
+/// Maya writes one value per child element of the technique. Geometries are tagged with
+/// `double_sided`, while nodes record the name of the Maya node they were exported from and the
+/// display layer they belong to. Any value that isn't present, or that couldn't be parsed, is
+/// `None`.
+///
+/// The OpenCOLLADA exporter writes the same data under the `"OpenCOLLADAMaya"` profile, which is
+/// decoded the same way.
+///
+/// Values that don't have a dedicated field are kept as raw text in `other`, keyed by the name
+/// of the element that contained them.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct MayaTechnique {
+    /// Whether both sides of a geometry's faces should be rendered, from `<double_sided>`.
+    pub double_sided: Option<bool>,
+
+    /// The name of the Maya node that a node was exported from, from `<originalMayaNodeId>`.
+    pub original_node_name: Option<String>,
+
+    /// The name of the display layer containing a node, from `<layer>`.
+    pub layer: Option<String>,
+
+    /// All other values in the technique, keyed by element name.
+    pub other: HashMap<String, String>,
+}
+
+impl MayaTechnique {
+    /// Decodes the contents of `technique`, which must have a Maya profile.
+    pub(crate) fn from_technique(technique: &Technique) -> MayaTechnique {
+        let mut values = extensions::child_values(technique);
+
+        MayaTechnique {
+            double_sided: take_bool(&mut values, "double_sided"),
+            original_node_name: values.remove("originalMayaNodeId"),
+            layer: values.remove("layer"),
+            other: values,
+        }
+    }
+}
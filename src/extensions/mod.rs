@@ -0,0 +1,55 @@
+//! Typed access to data from common 3rd party extensions.
+//!
+//! Applications store their own data in `<extra>` elements, using a [`Technique`] with a profile
+//! naming the application. The types in this module decode the data for the profiles of popular
+//! applications, and are accessed through methods on [`Technique`] such as
+//! [`Technique::as_blender`].
+//!
+//! [`Technique`]: ../common/struct.Technique.html
+//! [`Technique::as_blender`]: ../common/struct.Technique.html#method.as_blender
+
+use common::Technique;
+use std::collections::HashMap;
+use std::str::FromStr;
+
+pub mod blender;
+pub mod maya;
+
+/// Collects the text contents of each direct child of `technique`, keyed by element name.
+pub(crate) fn child_values(technique: &Technique) -> HashMap<String, String> {
+    technique.child_values()
+        .map(|(name, value)| (name.to_owned(), value))
+        .collect()
+}
+
+/// Removes and parses the value for `name`, leaving it in `values` if it can't be parsed.
+pub(crate) fn take_parsed<T: FromStr>(values: &mut HashMap<String, String>, name: &str) -> Option<T> {
+    let parsed = values.get(name)?.trim().parse().ok()?;
+    values.remove(name);
+    Some(parsed)
+}
+
+/// Removes and parses the three components of a color, leaving them in `values` unless all three
+/// can be parsed.
+pub(crate) fn take_color(values: &mut HashMap<String, String>, names: [&str; 3]) -> Option<[f32; 3]> {
+    let mut color = [0.0; 3];
+    for (component, name) in color.iter_mut().zip(names.iter()) {
+        *component = values.get(*name)?.trim().parse().ok()?;
+    }
+
+    for name in &names {
+        values.remove(*name);
+    }
+    Some(color)
+}
+
+/// Removes and parses a boolean value, which may be written as either `0`/`1` or `false`/`true`.
+pub(crate) fn take_bool(values: &mut HashMap<String, String>, name: &str) -> Option<bool> {
+    let parsed = match values.get(name)?.trim() {
+        "1" | "true" => true,
+        "0" | "false" => false,
+        _ => return None,
+    };
+    values.remove(name);
+    Some(parsed)
+}
@@ -54,6 +54,18 @@
 //! let document = v1_4::Collada::read(file).unwrap();
 //! ```
 //!
+//! # Reading From Other Sources
+//!
+//! Parsing isn't tied to the filesystem. [`VersionedDocument::read`] and the `read` methods on
+//! each `Collada` type accept any [`std::io::Read`], so documents can be parsed from memory, from
+//! a network stream, or from a custom reader type. Only the `from_path` methods open files, and
+//! they're thin wrappers around `read`.
+//!
+//! The library depends on `std` and can't be used in `no_std` environments, since the underlying
+//! XML parser reads through `std::io::Read`. Parsing allocates for every element it keeps (e.g. the
+//! `String` values of attributes and the `Vec` contents of arrays), but doesn't otherwise buffer
+//! the whole document in memory.
+//!
 //! # COLLADA Versions
 //!
 //! Currently there are 3 COLLADA versions supported by this library: `1.4.0`, `1.4.1`, and
@@ -78,37 +90,159 @@
 //! directly supported, the underlying XML will be preserved so that the client code can attempt
 //! to still use the data.
 //!
+//! Supported extensions are decoded on demand from a [`Technique`] with the matching profile,
+//! e.g. [`Technique::as_blender`] and [`Technique::as_maya`]. The decoded types live in the
+//! [`extensions`] module.
+//!
+//! # Writing Documents
+//!
+//! Documents can be written back out as XML with [`v1_4::Collada::write`]. New documents can be
+//! built from scratch with [`v1_4::Collada::builder`], which starts out with the minimal content
+//! required for a valid document:
+//!
+//! ```
+//! use collaborate::v1_4::Collada;
+//!
+//! let document = Collada::builder().build();
+//!
+//! let mut output = Vec::new();
+//! document.write(&mut output).unwrap();
+//! assert_eq!(document, Collada::read(&*output).unwrap());
+//! ```
+//!
+//! # Serde Support
+//!
+//! Enabling the optional `serde` feature implements `Serialize` and `Deserialize` for the types in
+//! the [`common`] and [`v1_4`] modules, which allows parsed documents to be cached without
+//! having to parse the XML again. The raw XML events in a [`Technique`] are serialized as a
+//! string of XML. The error types implement `Serialize` only.
+//!
 //! [COLLADA]: https://www.khronos.org/collada/
 //! [FBX]: https://en.wikipedia.org/wiki/FBX
 //! [`VersionedDocument`]: ./enum.VersionedDocument.html
 //! [`VersionedDocument::read`]: ./enum.VersionedDocument.html#method.read
+//! [`std::io::Read`]: https://doc.rust-lang.org/std/io/trait.Read.html
+//! [`common`]: ./common/index.html
+//! [`Technique`]: ./common/struct.Technique.html
+//! [`Technique::as_blender`]: ./common/struct.Technique.html#method.as_blender
+//! [`Technique::as_maya`]: ./common/struct.Technique.html#method.as_maya
+//! [`extensions`]: ./extensions/index.html
 //! [`v1_4`]: ./v1_4/index.html
 //! [`v1_5`]: ./v1_5/index.html
 //! [`v1_4::Collada`]: ./v1_4/struct.Collada.html
 //! [`v1_5::Collada`]: ./v1_5/struct.Collada.html
+//! [`v1_4::Collada::write`]: ./v1_4/struct.Collada.html#method.write
+//! [`v1_4::Collada::builder`]: ./v1_4/struct.Collada.html#method.builder
 
 pub extern crate chrono;
 #[macro_use]
 extern crate collaborate_derive;
-extern crate xml;
+#[cfg(feature = "serde")]
+#[macro_use]
+extern crate serde;
+pub extern crate xml;
+#[cfg(feature = "zip")]
+extern crate zip;
 
 pub use xml::common::TextPosition;
 pub use xml::reader::{Error as XmlError, XmlEvent};
 
 use common::UriFragmentParseError;
 use std::fmt::{self, Display, Formatter};
-use std::io::Read;
+use std::io::{self, Read};
 use std::num::{ParseFloatError, ParseIntError};
-use utils::{ColladaElement, StringListDisplay};
+use std::str::ParseBoolError;
+use std::path::Path;
+use utils::{ColladaElement, ColladaReader, StringListDisplay};
 use xml::common::Position;
 use xml::reader::EventReader;
 
+#[cfg(feature = "zip")]
+pub mod archive;
 pub mod common;
+pub mod export;
+pub mod extensions;
+pub mod stream;
 pub mod v1_4;
 pub mod v1_5;
 
+mod math;
+#[cfg(feature = "serde")]
+mod serde_support;
 mod utils;
 
+/// Reads the version of a COLLADA document without parsing the rest of it.
+///
+/// Only the start of the document up to the opening `<COLLADA>` tag is read, which makes this
+/// much cheaper than a full parse when all you need is to decide how to handle a file. The
+/// version is returned as it appears in the document with any surrounding whitespace removed.
+///
+/// # Examples
+///
+/// ```
+/// use collaborate::detect_version;
+///
+/// let file = std::fs::File::open("resources/blender_cube.dae").unwrap();
+/// assert_eq!("1.4.1", detect_version(file).unwrap());
+/// ```
+///
+/// # Errors
+///
+/// Returns an [`ErrorKind::MissingAttribute`] error if the `<COLLADA>` tag has no `version`
+/// attribute, and an [`ErrorKind::UnexpectedRootElement`] error if the root element isn't
+/// `<COLLADA>`. Unsupported versions are not an error, they're returned like any other version.
+///
+/// [`ErrorKind::MissingAttribute`]: ./enum.ErrorKind.html#variant.MissingAttribute
+/// [`ErrorKind::UnexpectedRootElement`]: ./enum.ErrorKind.html#variant.UnexpectedRootElement
+pub fn detect_version<R: Read>(reader: R) -> Result<String> {
+    let reader = utils::strip_bom(reader)?;
+    let mut reader = ColladaReader::new(utils::event_reader(reader), ParseOptions::default());
+    let element_start = utils::get_document_start(&mut reader)?;
+    document_version(&reader, &element_start)
+}
+
+/// Finds the "version" attribute on the opening `<COLLADA>` tag.
+///
+/// Some exporters pad the version string with whitespace, so it's trimmed before being returned.
+/// This must be called before the reader advances past the opening tag, so that a missing
+/// `version` is reported at the position of the `<COLLADA>` element.
+pub(crate) fn document_version<R: Read>(reader: &ColladaReader<R>, element_start: &utils::ElementStart) -> Result<String> {
+    element_start.attributes.iter()
+        .find(|attrib| attrib.name.local_name == "version")
+        .map(|attrib| attrib.value.trim().to_owned())
+        .ok_or(Error {
+            position: reader.position(),
+            kind: ErrorKind::MissingAttribute {
+                element: "COLLADA",
+                attribute: "version",
+            },
+        })
+}
+
+/// Returns the namespace of the root `<COLLADA>` element, if it has one.
+///
+/// Documents are parsed the same regardless of their namespace, but in lenient mode a warning is
+/// reported if it isn't `expected`, the schema namespace for the document's version.
+pub(crate) fn document_namespace<R: Read>(
+    reader: &mut ColladaReader<R>,
+    element_start: &utils::ElementStart,
+    expected: &'static str,
+) -> Option<String> {
+    let namespace = element_start.name.namespace.clone();
+    if reader.options.lenient && namespace.as_deref() != Some(expected) {
+        let position = reader.position();
+        reader.warnings.push(Warning {
+            position,
+            kind: WarningKind::UnexpectedNamespace {
+                namespace: namespace.clone(),
+                expected,
+            },
+        });
+    }
+
+    namespace
+}
+
 /// A helper type for parsing documents without knowing the version ahead of time.
 ///
 /// If you know the specification used by a COLLADA document ahead of time, you can use
@@ -181,8 +315,19 @@ impl VersionedDocument {
     /// COLLADA versions, 3rd party extensions, and any other details that could influence how
     /// a document is parsed see the [crate-level documentation](./index.html).
     pub fn from_str(source: &str) -> Result<VersionedDocument> {
-        let reader = EventReader::new_with_config(source.as_bytes(), utils::PARSER_CONFIG.clone());
-        Self::parse(reader)
+        Self::from_str_with_options(source, ParseOptions::default())
+    }
+
+    /// Read a COLLADA document from a string, using the specified options.
+    ///
+    /// See [`ParseOptions`] for the available options. Otherwise this behaves exactly like
+    /// [`from_str`](#method.from_str).
+    ///
+    /// [`ParseOptions`]: ./struct.ParseOptions.html
+    pub fn from_str_with_options(source: &str, options: ParseOptions) -> Result<VersionedDocument> {
+        let source = utils::strip_bom_str(source);
+        let mut reader = ColladaReader::new(utils::options_event_reader(source.as_bytes(), &options), options);
+        Self::parse_reader(&mut reader)
     }
 
     /// Attempts to parse the contents of a COLLADA document.
@@ -214,31 +359,84 @@ impl VersionedDocument {
     ///
     /// [crate]: index.html
     pub fn read<R: Read>(reader: R) -> Result<VersionedDocument> {
-        let reader = EventReader::new_with_config(reader, utils::PARSER_CONFIG.clone());
-        Self::parse(reader)
+        Self::read_with_options(reader, ParseOptions::default())
+    }
+
+    /// Attempts to parse the contents of a COLLADA document, using the specified options.
+    ///
+    /// See [`ParseOptions`] for the available options. Otherwise this behaves exactly like
+    /// [`read`](#method.read).
+    ///
+    /// [`ParseOptions`]: ./struct.ParseOptions.html
+    pub fn read_with_options<R: Read>(reader: R, options: ParseOptions) -> Result<VersionedDocument> {
+        Self::read_with_warnings(reader, options).map(|(document, _)| document)
     }
 
-    pub fn parse<R: Read>(mut reader: EventReader<R>) -> Result<VersionedDocument> {
+    /// Attempts to parse the contents of a COLLADA document, also returning any warnings.
+    ///
+    /// Warnings are only produced when [`ParseOptions::lenient`] is set. Otherwise this behaves
+    /// exactly like [`read_with_options`](#method.read_with_options).
+    ///
+    /// [`ParseOptions::lenient`]: ./struct.ParseOptions.html#structfield.lenient
+    pub fn read_with_warnings<R: Read>(reader: R, options: ParseOptions) -> Result<(VersionedDocument, Vec<Warning>)> {
+        let reader = utils::strip_bom(reader)?;
+        let mut reader = ColladaReader::new(utils::options_event_reader(reader, &options), options);
+        let document = Self::parse_reader(&mut reader)?;
+        Ok((document, reader.warnings))
+    }
+
+    /// Read a COLLADA document from a byte slice.
+    ///
+    /// This is a shorthand for [`read`](#method.read), for when the document has already been
+    /// loaded into memory.
+    pub fn from_bytes(bytes: &[u8]) -> Result<VersionedDocument> {
+        Self::read(bytes)
+    }
+
+    /// Read a COLLADA document from the file at `path`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # #![allow(unused_variables)]
+    /// use collaborate::VersionedDocument;
+    ///
+    /// let document = VersionedDocument::from_path("resources/blender_cube.dae").unwrap();
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Returns an [`ErrorKind::Io`] error with the position `0:0` if the file can't be opened.
+    /// Otherwise, returns `Err` under the same conditions as [`read`](#method.read).
+    ///
+    /// [`ErrorKind::Io`]: ./enum.ErrorKind.html#variant.Io
+    pub fn from_path<P: AsRef<Path>>(path: P) -> Result<VersionedDocument> {
+        Self::read(utils::open_file(path)?)
+    }
+
+    pub fn parse<R: Read>(reader: EventReader<R>) -> Result<VersionedDocument> {
+        Self::parse_reader(&mut ColladaReader::new(reader, ParseOptions::default()))
+    }
+
+    fn parse_reader<R: Read>(reader: &mut ColladaReader<R>) -> Result<VersionedDocument> {
         // Get the opening `<COLLADA>` tag and find the "version" attribute.
-        let element_start = utils::get_document_start(&mut reader)?;
-        let version = element_start.attributes.iter()
-            .find(|attrib| attrib.name.local_name == "version")
-            .map(|attrib| attrib.value.clone())
-            .ok_or(Error {
-                position: reader.position(),
-                kind: ErrorKind::MissingAttribute {
-                    element: "COLLADA",
-                    attribute: "version",
-                },
-            })?;
+        let element_start = utils::get_document_start(reader)?;
+        let version = document_version(reader, &element_start)?;
 
         match &*version {
             "1.4.0" | "1.4.1" => {
-                v1_4::Collada::parse_element(&mut reader, element_start).map(Into::into)
+                let xmlns = document_namespace(reader, &element_start, v1_4::NAMESPACE);
+                reader.other_version_elements = Some(&v1_4::FROM_1_5);
+                let mut collada = v1_4::Collada::parse_element(reader, element_start)?;
+                collada.xmlns = xmlns;
+                Ok(collada.into())
             }
 
             "1.5.0" => {
-                v1_5::Collada::parse_element(&mut reader, element_start).map(Into::into)
+                let xmlns = document_namespace(reader, &element_start, v1_5::NAMESPACE);
+                let mut collada = v1_5::Collada::parse_element(reader, element_start)?;
+                collada.xmlns = xmlns;
+                Ok(collada.into())
             }
 
             _ => {
@@ -251,6 +449,72 @@ impl VersionedDocument {
             }
         }
     }
+
+    /// Returns the version string declared by the document, e.g. `"1.4.1"`.
+    ///
+    /// This is the version as it appears in the document with any surrounding whitespace
+    /// removed, so a `1.4.0` document reports `"1.4.0"` even though it's represented by
+    /// [`v1_4::Collada`].
+    ///
+    /// [`v1_4::Collada`]: ./v1_4/struct.Collada.html
+    pub fn version(&self) -> &str {
+        match *self {
+            VersionedDocument::V1_4(ref document) => document.version.trim(),
+            VersionedDocument::V1_5(ref document) => document.version.trim(),
+        }
+    }
+
+    /// Returns the declared version as a `(major, minor, patch)` tuple, for easy comparisons.
+    ///
+    /// Returns `None` if the version string isn't made up of three dot-separated integers,
+    /// which can only happen if the `Collada` object wasn't parsed from a document.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::fs::File;
+    /// use collaborate::VersionedDocument;
+    ///
+    /// let file = File::open("resources/blender_cube.dae").unwrap();
+    /// let document = VersionedDocument::read(file).unwrap();
+    /// assert_eq!(Some((1, 4, 1)), document.version_number());
+    /// ```
+    pub fn version_number(&self) -> Option<(u32, u32, u32)> {
+        let mut parts = self.version().split('.').map(str::parse);
+        match (parts.next(), parts.next(), parts.next(), parts.next()) {
+            (Some(Ok(major)), Some(Ok(minor)), Some(Ok(patch)), None) => Some((major, minor, patch)),
+            _ => None,
+        }
+    }
+
+    /// Returns `true` if the document is a `1.4.0` or `1.4.1` document.
+    pub fn is_1_4(&self) -> bool {
+        match *self {
+            VersionedDocument::V1_4(_) => true,
+            VersionedDocument::V1_5(_) => false,
+        }
+    }
+
+    /// Returns `true` if the document is a `1.5.0` document.
+    pub fn is_1_5(&self) -> bool {
+        match *self {
+            VersionedDocument::V1_4(_) => false,
+            VersionedDocument::V1_5(_) => true,
+        }
+    }
+
+    /// Writes the document as XML to `writer`.
+    ///
+    /// See [`v1_4::Collada::write`] and [`v1_5::Collada::write`] for details.
+    ///
+    /// [`v1_4::Collada::write`]: ./v1_4/struct.Collada.html#method.write
+    /// [`v1_5::Collada::write`]: ./v1_5/struct.Collada.html#method.write
+    pub fn write<W: io::Write>(&self, writer: W) -> io::Result<()> {
+        match *self {
+            VersionedDocument::V1_4(ref document) => document.write(writer),
+            VersionedDocument::V1_5(ref document) => document.write(writer),
+        }
+    }
 }
 
 impl From<v1_4::Collada> for VersionedDocument {
@@ -265,21 +529,409 @@ impl From<v1_5::Collada> for VersionedDocument {
     }
 }
 
+impl ::std::str::FromStr for VersionedDocument {
+    type Err = Error;
+
+    fn from_str(source: &str) -> Result<VersionedDocument> {
+        VersionedDocument::from_str(source)
+    }
+}
+
+/// Options that control how a document is parsed.
+///
+/// The default options parse the entire document, keeping all of its data.
+///
+/// # Examples
+///
+/// ```
+/// # #![allow(unused_variables)]
+/// use std::fs::File;
+/// use collaborate::ParseOptions;
+/// use collaborate::v1_4::Collada;
+///
+/// let options = ParseOptions {
+///     skip_extra_data: true,
+///     ..ParseOptions::default()
+/// };
+///
+/// let file = File::open("resources/blender_cube.dae").unwrap();
+/// let collada = Collada::read_with_options(file, options).unwrap();
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseOptions {
+    /// Discard the contents of every `<technique>` in an `<extra>` element.
+    ///
+    /// Vendor-specific data can make up a large part of some documents, and is buffered as raw
+    /// XML events in [`Technique::data`]. If this is `true` then `data` will always be empty,
+    /// though the `profile` and `xmlns` of each technique are still available.
+    ///
+    /// [`Technique::data`]: ./common/struct.Technique.html#structfield.data
+    pub skip_extra_data: bool,
+
+    /// Skip elements that aren't allowed where they appear instead of failing to parse.
+    ///
+    /// Some exporters emit elements that the spec doesn't allow, most commonly elements from a
+    /// different version of the spec than the one the document declares. If this is `true` then
+    /// such elements (and everything inside them) are skipped and reported as a [`Warning`]
+    /// rather than causing an [`ErrorKind::UnexpectedElement`] error. Use one of the
+    /// `read_with_warnings` methods to get the warnings.
+    ///
+    /// [`Warning`]: ./struct.Warning.html
+    /// [`ErrorKind::UnexpectedElement`]: ./enum.ErrorKind.html#variant.UnexpectedElement
+    pub lenient: bool,
+
+    /// The kinds of libraries to parse.
+    ///
+    /// Libraries of any other kind are skipped without being parsed, and so won't appear in the
+    /// document's `libraries`. Skipped libraries aren't checked for errors, and references into
+    /// them (e.g. from an `<instance_geometry>`) can't be resolved.
+    pub libraries: LibraryFilter,
+
+    /// The maximum depth that elements may be nested within the root `<COLLADA>` element.
+    ///
+    /// Some elements (e.g. `<node>`) can contain themselves, so a maliciously deep document could
+    /// otherwise overflow the stack while being parsed. A document nested deeper than this fails
+    /// to parse with an [`ErrorKind::MaxDepthExceeded`] error.
+    ///
+    /// Defaults to `128`, which is deeper than real documents need while still fitting within the
+    /// 2MB stack given to spawned threads, even in a debug build. Only elements that the library
+    /// parses into its own types count towards the depth. Elements whose contents are buffered as
+    /// raw XML (e.g. a [`Technique`]) or skipped are read without recursing, so nesting within
+    /// them isn't limited.
+    ///
+    /// Each level of nesting can take around 15KB of stack in a debug build, and much less in a
+    /// release build. Only raise the limit when parsing on a thread whose stack is large enough
+    /// for the extra depth.
+    ///
+    /// [`ErrorKind::MaxDepthExceeded`]: ./enum.ErrorKind.html#variant.MaxDepthExceeded
+    /// [`Technique`]: ./common/struct.Technique.html
+    pub max_depth: usize,
+
+    /// Keep XML comments in the data that's buffered as raw XML events.
+    ///
+    /// Comments don't have any meaning in a COLLADA document, so by default they're discarded.
+    /// If this is `true` then comments within elements whose contents are buffered as raw XML
+    /// events, such as the `data` of a [`Technique`], are kept along with the rest of the
+    /// contents and are written back out with the document. Comments anywhere else are still
+    /// discarded, since the parsed types have nowhere to store them.
+    ///
+    /// [`Technique`]: ./common/struct.Technique.html
+    pub keep_comments: bool,
+
+    /// Keep whitespace exactly as it appears in the data that's buffered as raw XML events.
+    ///
+    /// By default whitespace between elements is discarded and text is trimmed. If this is `true`
+    /// then whitespace within elements whose contents are buffered as raw XML events, such as the
+    /// `data` of a [`Technique`], is kept exactly as written, including whitespace-only text
+    /// between elements. Together with `keep_comments`, this lets a tool compare the raw contents
+    /// of two documents. Whitespace is still trimmed from values that are parsed into the
+    /// library's types, so it doesn't affect how the rest of the document is read.
+    ///
+    /// The xml-rs `ParserConfig` used by the library isn't exposed directly, since parsing relies
+    /// on most of its flags: turning off `cdata_to_characters` or `coalesce_characters` would
+    /// split the text of an element into several events, and leaving text untrimmed would make
+    /// values such as `<created>` fail to parse. `keep_comments` and `keep_whitespace` cover the
+    /// flags that are safe to change.
+    ///
+    /// [`Technique`]: ./common/struct.Technique.html
+    pub keep_whitespace: bool,
+}
+
+impl Default for ParseOptions {
+    fn default() -> ParseOptions {
+        ParseOptions {
+            skip_extra_data: false,
+            lenient: false,
+            libraries: LibraryFilter::default(),
+            max_depth: 128,
+            keep_comments: false,
+            keep_whitespace: false,
+        }
+    }
+}
+
+/// Selects which libraries are parsed from a document.
+///
+/// # Examples
+///
+/// ```
+/// # #![allow(unused_variables)]
+/// use std::fs::File;
+/// use collaborate::{LibraryFilter, LibraryKind, ParseOptions};
+/// use collaborate::v1_4::Collada;
+///
+/// let options = ParseOptions {
+///     libraries: LibraryFilter::Only(vec![LibraryKind::Cameras]),
+///     ..ParseOptions::default()
+/// };
+///
+/// let file = File::open("resources/blender_cube.dae").unwrap();
+/// let collada = Collada::read_with_options(file, options).unwrap();
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub enum LibraryFilter {
+    /// Parse every library.
+    #[default]
+    All,
+
+    /// Only parse libraries of the listed kinds.
+    Only(Vec<LibraryKind>),
+}
+
+impl LibraryFilter {
+    /// Returns `true` if libraries of kind `kind` are parsed.
+    pub fn includes(&self, kind: LibraryKind) -> bool {
+        match *self {
+            LibraryFilter::All => true,
+            LibraryFilter::Only(ref kinds) => kinds.contains(&kind),
+        }
+    }
+
+    /// Returns `true` if `name` is the name of a library element that should be skipped.
+    pub(crate) fn skips(&self, name: &str) -> bool {
+        match *self {
+            LibraryFilter::All => false,
+            LibraryFilter::Only(ref kinds) => match LibraryKind::from_element_name(name) {
+                Some(kind) => !kinds.contains(&kind),
+                None => false,
+            },
+        }
+    }
+}
+
+/// The different kinds of library that can appear in a document.
+///
+/// Some kinds, such as `Formulas`, were added in COLLADA 1.5 and never appear in 1.4 documents.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum LibraryKind {
+    /// Libraries of animations, `<library_animations>`.
+    Animations,
+
+    /// Libraries of animation clips, `<library_animation_clips>`.
+    AnimationClips,
+
+    /// Libraries of articulated systems, `<library_articulated_systems>`.
+    ArticulatedSystems,
+
+    /// Libraries of cameras, `<library_cameras>`.
+    Cameras,
+
+    /// Libraries of controllers, `<library_controllers>`.
+    Controllers,
+
+    /// Libraries of effects, `<library_effects>`.
+    Effects,
+
+    /// Libraries of force fields, `<library_force_fields>`.
+    ForceFields,
+
+    /// Libraries of formulas, `<library_formulas>`.
+    Formulas,
+
+    /// Libraries of geometries, `<library_geometries>`.
+    Geometries,
+
+    /// Libraries of images, `<library_images>`.
+    Images,
+
+    /// Libraries of joints, `<library_joints>`.
+    Joints,
+
+    /// Libraries of kinematics models, `<library_kinematics_models>`.
+    KinematicsModels,
+
+    /// Libraries of kinematics scenes, `<library_kinematics_scenes>`.
+    KinematicsScenes,
+
+    /// Libraries of lights, `<library_lights>`.
+    Lights,
+
+    /// Libraries of materials, `<library_materials>`.
+    Materials,
+
+    /// Libraries of nodes, `<library_nodes>`.
+    Nodes,
+
+    /// Libraries of physics materials, `<library_physics_materials>`.
+    PhysicsMaterials,
+
+    /// Libraries of physics models, `<library_physics_models>`.
+    PhysicsModels,
+
+    /// Libraries of physics scenes, `<library_physics_scenes>`.
+    PhysicsScenes,
+
+    /// Libraries of visual scenes, `<library_visual_scenes>`.
+    VisualScenes,
+}
+
+impl LibraryKind {
+    /// Every kind of library, in the order they're listed in the spec.
+    const ALL: [LibraryKind; 20] = [
+        LibraryKind::Animations,
+        LibraryKind::AnimationClips,
+        LibraryKind::ArticulatedSystems,
+        LibraryKind::Cameras,
+        LibraryKind::Controllers,
+        LibraryKind::Effects,
+        LibraryKind::ForceFields,
+        LibraryKind::Formulas,
+        LibraryKind::Geometries,
+        LibraryKind::Images,
+        LibraryKind::Joints,
+        LibraryKind::KinematicsModels,
+        LibraryKind::KinematicsScenes,
+        LibraryKind::Lights,
+        LibraryKind::Materials,
+        LibraryKind::Nodes,
+        LibraryKind::PhysicsMaterials,
+        LibraryKind::PhysicsModels,
+        LibraryKind::PhysicsScenes,
+        LibraryKind::VisualScenes,
+    ];
+
+    /// Returns the name of the element for this kind of library, e.g. `"library_geometries"`.
+    pub fn element_name(self) -> &'static str {
+        match self {
+            LibraryKind::Animations => "library_animations",
+            LibraryKind::AnimationClips => "library_animation_clips",
+            LibraryKind::ArticulatedSystems => "library_articulated_systems",
+            LibraryKind::Cameras => "library_cameras",
+            LibraryKind::Controllers => "library_controllers",
+            LibraryKind::Effects => "library_effects",
+            LibraryKind::ForceFields => "library_force_fields",
+            LibraryKind::Formulas => "library_formulas",
+            LibraryKind::Geometries => "library_geometries",
+            LibraryKind::Images => "library_images",
+            LibraryKind::Joints => "library_joints",
+            LibraryKind::KinematicsModels => "library_kinematics_models",
+            LibraryKind::KinematicsScenes => "library_kinematics_scenes",
+            LibraryKind::Lights => "library_lights",
+            LibraryKind::Materials => "library_materials",
+            LibraryKind::Nodes => "library_nodes",
+            LibraryKind::PhysicsMaterials => "library_physics_materials",
+            LibraryKind::PhysicsModels => "library_physics_models",
+            LibraryKind::PhysicsScenes => "library_physics_scenes",
+            LibraryKind::VisualScenes => "library_visual_scenes",
+        }
+    }
+
+    /// Returns the kind of library with the element name `name`, if there is one.
+    pub fn from_element_name(name: &str) -> Option<LibraryKind> {
+        LibraryKind::ALL.iter()
+            .cloned()
+            .find(|kind| kind.element_name() == name)
+    }
+}
+
+/// A problem with a document that didn't prevent it from being parsed.
+///
+/// Warnings are only produced when parsing with [`ParseOptions::lenient`] set.
+///
+/// [`ParseOptions::lenient`]: ./struct.ParseOptions.html#structfield.lenient
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize))]
+pub struct Warning {
+    #[cfg_attr(feature = "serde", serde(serialize_with = "serde_support::serialize_position"))]
+    pub position: TextPosition,
+    pub kind: WarningKind,
+}
+
+impl Display for Warning {
+    fn fmt(&self, formatter: &mut Formatter) -> ::std::result::Result<(), fmt::Error> {
+        write!(formatter, "Warning at {}: {}", self.position, self.kind)
+    }
+}
+
+/// The specific warning variant.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize))]
+pub enum WarningKind {
+    /// An element that isn't allowed by the spec was skipped.
+    SkippedElement {
+        /// The element containing the skipped element.
+        parent: &'static str,
+
+        /// The element that was skipped.
+        element: String,
+    },
+
+    /// An element that belongs to a different version of the spec than the document was skipped.
+    ///
+    /// This usually indicates a bug in the exporter that produced the document.
+    WrongVersion {
+        /// The element that was skipped.
+        element: String,
+
+        /// The version of the spec that the element belongs to, e.g. `"1.5"`.
+        version: &'static str,
+
+        /// The version of the spec that the document uses, e.g. `"1.4"`.
+        document_version: &'static str,
+    },
+
+    /// The root `<COLLADA>` element isn't in the schema namespace for the document's version.
+    ///
+    /// The document is still parsed as the version it declares.
+    UnexpectedNamespace {
+        /// The namespace of the root element, or `None` if it isn't in a namespace.
+        namespace: Option<String>,
+
+        /// The schema namespace for the document's version.
+        expected: &'static str,
+    },
+}
+
+impl Display for WarningKind {
+    fn fmt(&self, formatter: &mut Formatter) -> ::std::result::Result<(), fmt::Error> {
+        match *self {
+            WarningKind::SkippedElement { ref parent, ref element } => {
+                write!(formatter, "<{}> had a child <{}> which is not allowed, so it was skipped", parent, element)
+            }
+
+            WarningKind::WrongVersion { ref element, ref version, ref document_version } => {
+                write!(formatter, "element <{}> belongs to COLLADA {}, not {}", element, version, document_version)
+            }
+
+            WarningKind::UnexpectedNamespace { namespace: Some(ref namespace), ref expected } => {
+                write!(formatter, "<COLLADA> has the namespace \"{}\", expected \"{}\"", namespace, expected)
+            }
+
+            WarningKind::UnexpectedNamespace { namespace: None, ref expected } => {
+                write!(formatter, "<COLLADA> has no namespace, expected \"{}\"", expected)
+            }
+        }
+    }
+}
+
 /// A COLLADA parsing error.
 ///
 /// Contains where in the document the error occurred (i.e. line number and column), and
 /// details about the nature of the error.
 #[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize))]
 pub struct Error {
+    #[cfg_attr(feature = "serde", serde(serialize_with = "serde_support::serialize_position"))]
     pub position: TextPosition,
     pub kind: ErrorKind,
 }
 
 impl From<xml::reader::Error> for Error {
     fn from(from: xml::reader::Error) -> Error {
+        // xml-rs reports failures of the underlying reader as XML errors. Pull those out so that
+        // they're reported as IO errors instead. xml-rs doesn't give up ownership of the
+        // `io::Error`, so a new one is created with the same kind and message.
+        let kind = match *from.kind() {
+            xml::reader::ErrorKind::Io(ref error) => {
+                ErrorKind::Io(io::Error::new(error.kind(), error.to_string()))
+            }
+
+            _ => ErrorKind::XmlError(from.clone()),
+        };
+
         Error {
             position: from.position(),
-            kind: ErrorKind::XmlError(from),
+            kind,
         }
     }
 }
@@ -290,8 +942,23 @@ impl Display for Error {
     }
 }
 
+impl ::std::error::Error for Error {
+    /// Returns the error that caused the parse failure, if any.
+    ///
+    /// The message for `kind` is already part of the error's `Display` output, so this skips
+    /// `kind` and returns its source directly.
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        self.kind.source()
+    }
+}
+
 /// The specific error variant.
-#[derive(Debug, Clone, PartialEq, Eq)]
+///
+/// `Clone` and `PartialEq` are implemented manually because `std::io::Error` implements neither.
+/// Two `Io` errors are considered equal if they have the same `io::ErrorKind` and message, and
+/// cloning an `Io` error produces a new error with the same kind and message.
+#[derive(Debug)]
+#[cfg_attr(feature = "serde", derive(Serialize))]
 pub enum ErrorKind {
     /// An element was missing a required attribute.
     ///
@@ -335,12 +1002,28 @@ pub enum ErrorKind {
     /// numbers][f64::from_str].
     ///
     /// [f64::from_str]: https://doc.rust-lang.org/std/primitive.f64.html#method.from_str
-    ParseFloatError(ParseFloatError),
+    ParseFloatError(
+        #[cfg_attr(feature = "serde", serde(serialize_with = "serde_support::serialize_display"))]
+        ParseFloatError
+    ),
+
+    /// A boolean value was formatted incorrectly.
+    ///
+    /// Booleans must be either `true` or `false`. When the value is part of an element's text
+    /// data, an [`InvalidValue`](#variant.InvalidValue) error is returned instead so that the
+    /// offending text can be reported.
+    ParseBoolError(
+        #[cfg_attr(feature = "serde", serde(serialize_with = "serde_support::serialize_display"))]
+        ParseBoolError
+    ),
 
     /// A integer value was formatted incorrectly.
     ///
     /// Floating point values are parsed according to Rust's [standard handling for integers](https://doc.rust-lang.org/std/primitive.usize.html#method.from_str).
-    ParseIntError(ParseIntError),
+    ParseIntError(
+        #[cfg_attr(feature = "serde", serde(serialize_with = "serde_support::serialize_display"))]
+        ParseIntError
+    ),
 
     /// A datetime string was formatted incorrectly.
     ///
@@ -348,7 +1031,10 @@ pub enum ErrorKind {
     /// formatted datetime values will cause this error to be returned.
     ///
     /// [ISO 8601]: https://en.wikipedia.org/wiki/ISO_8601
-    TimeError(chrono::ParseError),
+    TimeError(
+        #[cfg_attr(feature = "serde", serde(serialize_with = "serde_support::serialize_display"))]
+        chrono::ParseError
+    ),
 
     /// An element had an attribute that isn't allowed.
     ///
@@ -423,6 +1109,28 @@ pub enum ErrorKind {
         value: String,
     },
 
+    /// An element contained a number that was formatted incorrectly.
+    ///
+    /// This is returned instead of [`ParseFloatError`] or [`ParseIntError`] when the number is
+    /// part of an element's text data, so that the offending text can be reported. Numbers in
+    /// attributes are still reported with the underlying error directly.
+    ///
+    /// [`ParseFloatError`]: #variant.ParseFloatError
+    /// [`ParseIntError`]: #variant.ParseIntError
+    InvalidNumber {
+        /// The element whose text data contained the number.
+        element: &'static str,
+
+        /// The text that couldn't be parsed, e.g. a single value from a `<float_array>`.
+        token: String,
+
+        /// The [`ParseFloatError`] or [`ParseIntError`] describing what was wrong with `token`.
+        ///
+        /// [`ParseFloatError`]: #variant.ParseFloatError
+        /// [`ParseIntError`]: #variant.ParseIntError
+        error: Box<ErrorKind>,
+    },
+
     /// The COLLADA document specified an unsupported version of the specification.
     ///
     /// The root `<COLLADA>` element of every COLLADA document must have a `version` attribute
@@ -436,10 +1144,131 @@ pub enum ErrorKind {
     /// There was an invalid URI fragment in the document.
     UriFragmentParseError(UriFragmentParseError),
 
+    /// Elements were nested deeper than [`ParseOptions::max_depth`] allows.
+    ///
+    /// [`ParseOptions::max_depth`]: ./struct.ParseOptions.html#structfield.max_depth
+    MaxDepthExceeded {
+        /// The depth of the element that exceeded the limit.
+        depth: usize,
+    },
+
+    /// The document couldn't be read.
+    ///
+    /// This is returned when the underlying reader fails partway through the document. Errors
+    /// that occur while opening a file for [`from_path`] are reported with the position `0:0`,
+    /// since nothing has been read yet.
+    ///
+    /// [`from_path`]: ./enum.VersionedDocument.html#method.from_path
+    Io(
+        #[cfg_attr(feature = "serde", serde(serialize_with = "serde_support::serialize_display"))]
+        io::Error
+    ),
+
     /// The XML in the document was malformed in some way.
     ///
     /// Not much more to say about this one ¯\_(ツ)_/¯
-    XmlError(XmlError),
+    XmlError(
+        #[cfg_attr(feature = "serde", serde(serialize_with = "serde_support::serialize_display"))]
+        XmlError
+    ),
+}
+
+impl ErrorKind {
+    /// Returns the broad category of the error.
+    ///
+    /// This is useful for grouping errors for display, or for handling related errors in the same
+    /// way without matching every variant.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use collaborate::{ErrorCategory, ErrorKind};
+    ///
+    /// let error = ErrorKind::InvalidValue { element: "up_axis", value: "W_UP".into() };
+    /// assert_eq!(ErrorCategory::Value, error.category());
+    /// ```
+    pub fn category(&self) -> ErrorCategory {
+        match *self {
+            ErrorKind::MissingAttribute { .. }
+            | ErrorKind::MissingElement { .. }
+            | ErrorKind::MissingValue { .. }
+            | ErrorKind::UnexpectedAttribute { .. }
+            | ErrorKind::UnexpectedCharacterData { .. }
+            | ErrorKind::UnexpectedElement { .. }
+            | ErrorKind::UnexpectedRootElement { .. }
+            | ErrorKind::MaxDepthExceeded { .. } => ErrorCategory::Structure,
+
+            ErrorKind::ParseFloatError(_)
+            | ErrorKind::ParseBoolError(_)
+            | ErrorKind::ParseIntError(_)
+            | ErrorKind::TimeError(_)
+            | ErrorKind::InvalidValue { .. }
+            | ErrorKind::InvalidNumber { .. }
+            | ErrorKind::UriFragmentParseError(_) => ErrorCategory::Value,
+
+            ErrorKind::UnsupportedVersion { .. } => ErrorCategory::Version,
+            ErrorKind::Io(_) => ErrorCategory::Io,
+            ErrorKind::XmlError(_) => ErrorCategory::Xml,
+        }
+    }
+}
+
+/// The broad category of an [`ErrorKind`], as returned by [`ErrorKind::category`].
+///
+/// [`ErrorKind`]: ./enum.ErrorKind.html
+/// [`ErrorKind::category`]: ./enum.ErrorKind.html#method.category
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize))]
+pub enum ErrorCategory {
+    /// An element or attribute is missing, or appears where it isn't allowed.
+    Structure,
+
+    /// An element or attribute has a value that couldn't be parsed.
+    Value,
+
+    /// The document's XML is malformed.
+    Xml,
+
+    /// The document uses an unsupported version of the COLLADA specification.
+    Version,
+
+    /// The document couldn't be read.
+    Io,
+}
+
+/// An error returned by [`v1_4::Collada::merge_with_prefix`].
+///
+/// [`v1_4::Collada::merge_with_prefix`]: ./v1_4/struct.Collada.html#method.merge_with_prefix
+#[derive(Debug, Clone, PartialEq)]
+pub enum MergeError {
+    /// Ids that are used by both documents, in the order they appear in the merged document.
+    Conflicts(Vec<String>),
+
+    /// The merged document couldn't be rewritten with its new ids.
+    Rewrite(Error),
+}
+
+impl Display for MergeError {
+    fn fmt(&self, formatter: &mut Formatter) -> ::std::result::Result<(), fmt::Error> {
+        match *self {
+            MergeError::Conflicts(ref ids) => {
+                write!(formatter, "Conflicting ids: {}", ids.join(", "))
+            }
+
+            MergeError::Rewrite(ref error) => {
+                write!(formatter, "Failed to rename ids: {}", error)
+            }
+        }
+    }
+}
+
+impl ::std::error::Error for MergeError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match *self {
+            MergeError::Conflicts(_) => None,
+            MergeError::Rewrite(ref error) => Some(error),
+        }
+    }
 }
 
 impl From<::chrono::format::ParseError> for ErrorKind {
@@ -454,6 +1283,12 @@ impl From<::std::num::ParseFloatError> for ErrorKind {
     }
 }
 
+impl From<ParseBoolError> for ErrorKind {
+    fn from(from: ParseBoolError) -> ErrorKind {
+        ErrorKind::ParseBoolError(from)
+    }
+}
+
 impl From<::std::num::ParseIntError> for ErrorKind {
     fn from(from: ::std::num::ParseIntError) -> ErrorKind {
         ErrorKind::ParseIntError(from)
@@ -472,6 +1307,151 @@ impl From<UriFragmentParseError> for ErrorKind {
     }
 }
 
+impl From<io::Error> for ErrorKind {
+    fn from(from: io::Error) -> ErrorKind {
+        ErrorKind::Io(from)
+    }
+}
+
+impl Clone for ErrorKind {
+    fn clone(&self) -> ErrorKind {
+        match *self {
+            ErrorKind::MissingAttribute { element, attribute } => {
+                ErrorKind::MissingAttribute { element, attribute }
+            }
+
+            ErrorKind::MissingElement { parent, ref expected } => {
+                ErrorKind::MissingElement { parent, expected: expected.clone() }
+            }
+
+            ErrorKind::MissingValue { element } => ErrorKind::MissingValue { element },
+            ErrorKind::ParseFloatError(ref error) => ErrorKind::ParseFloatError(error.clone()),
+            ErrorKind::ParseBoolError(ref error) => ErrorKind::ParseBoolError(error.clone()),
+            ErrorKind::ParseIntError(ref error) => ErrorKind::ParseIntError(error.clone()),
+            ErrorKind::TimeError(error) => ErrorKind::TimeError(error),
+
+            ErrorKind::UnexpectedAttribute { element, ref attribute, ref expected } => {
+                ErrorKind::UnexpectedAttribute {
+                    element,
+                    attribute: attribute.clone(),
+                    expected: expected.clone(),
+                }
+            }
+
+            ErrorKind::UnexpectedCharacterData { element, ref data } => {
+                ErrorKind::UnexpectedCharacterData { element, data: data.clone() }
+            }
+
+            ErrorKind::UnexpectedElement { parent, ref element, ref expected } => {
+                ErrorKind::UnexpectedElement {
+                    parent,
+                    element: element.clone(),
+                    expected: expected.clone(),
+                }
+            }
+
+            ErrorKind::UnexpectedRootElement { ref element } => {
+                ErrorKind::UnexpectedRootElement { element: element.clone() }
+            }
+
+            ErrorKind::InvalidValue { element, ref value } => {
+                ErrorKind::InvalidValue { element, value: value.clone() }
+            }
+
+            ErrorKind::InvalidNumber { element, ref token, ref error } => {
+                ErrorKind::InvalidNumber { element, token: token.clone(), error: error.clone() }
+            }
+
+            ErrorKind::UnsupportedVersion { ref version } => {
+                ErrorKind::UnsupportedVersion { version: version.clone() }
+            }
+
+            ErrorKind::UriFragmentParseError(error) => ErrorKind::UriFragmentParseError(error),
+            ErrorKind::MaxDepthExceeded { depth } => ErrorKind::MaxDepthExceeded { depth },
+
+            ErrorKind::Io(ref error) => {
+                ErrorKind::Io(io::Error::new(error.kind(), error.to_string()))
+            }
+
+            ErrorKind::XmlError(ref error) => ErrorKind::XmlError(error.clone()),
+        }
+    }
+}
+
+impl PartialEq for ErrorKind {
+    fn eq(&self, other: &ErrorKind) -> bool {
+        use ErrorKind::*;
+
+        match (self, other) {
+            (Io(left), Io(right)) => {
+                left.kind() == right.kind() && left.to_string() == right.to_string()
+            }
+
+            (
+                MissingAttribute { element, attribute },
+                MissingAttribute { element: other_element, attribute: other_attribute },
+            ) => element == other_element && attribute == other_attribute,
+
+            (
+                MissingElement { parent, expected },
+                MissingElement { parent: other_parent, expected: other_expected },
+            ) => parent == other_parent && expected == other_expected,
+
+            (MissingValue { element }, MissingValue { element: other }) => element == other,
+            (ParseFloatError(left), ParseFloatError(right)) => left == right,
+            (ParseBoolError(left), ParseBoolError(right)) => left == right,
+            (ParseIntError(left), ParseIntError(right)) => left == right,
+            (TimeError(left), TimeError(right)) => left == right,
+
+            (
+                UnexpectedAttribute { element, attribute, expected },
+                UnexpectedAttribute {
+                    element: other_element,
+                    attribute: other_attribute,
+                    expected: other_expected,
+                },
+            ) => element == other_element && attribute == other_attribute && expected == other_expected,
+
+            (
+                UnexpectedCharacterData { element, data },
+                UnexpectedCharacterData { element: other_element, data: other_data },
+            ) => element == other_element && data == other_data,
+
+            (
+                UnexpectedElement { parent, element, expected },
+                UnexpectedElement {
+                    parent: other_parent,
+                    element: other_element,
+                    expected: other_expected,
+                },
+            ) => parent == other_parent && element == other_element && expected == other_expected,
+
+            (UnexpectedRootElement { element }, UnexpectedRootElement { element: other }) => {
+                element == other
+            }
+
+            (
+                InvalidValue { element, value },
+                InvalidValue { element: other_element, value: other_value },
+            ) => element == other_element && value == other_value,
+
+            (
+                InvalidNumber { element, token, error },
+                InvalidNumber { element: other_element, token: other_token, error: other_error },
+            ) => element == other_element && token == other_token && error == other_error,
+
+            (UnsupportedVersion { version }, UnsupportedVersion { version: other }) => version == other,
+            (UriFragmentParseError(left), UriFragmentParseError(right)) => left == right,
+            (MaxDepthExceeded { depth }, MaxDepthExceeded { depth: other }) => depth == other,
+            (XmlError(left), XmlError(right)) => left == right,
+
+            _ => false,
+        }
+    }
+}
+
+impl Eq for ErrorKind {}
+
 impl Display for ErrorKind {
     fn fmt(&self, formatter: &mut Formatter) -> ::std::result::Result<(), fmt::Error> {
         match *self {
@@ -499,6 +1479,10 @@ impl Display for ErrorKind {
                 error.fmt(formatter)
             }
 
+            ErrorKind::ParseBoolError(ref error) => {
+                error.fmt(formatter)
+            }
+
             ErrorKind::ParseIntError(ref error) => {
                 error.fmt(formatter)
             }
@@ -539,6 +1523,10 @@ impl Display for ErrorKind {
                 write!(formatter, "<{}> contained an unexpected value {:?}", element, value)
             }
 
+            ErrorKind::InvalidNumber { ref element, ref token, ref error } => {
+                write!(formatter, "<{}> contained an invalid number {:?}: {}", element, token, error)
+            }
+
             ErrorKind::UnsupportedVersion { ref version } => {
                 write!(formatter, "Unsupported COLLADA version {:?}, supported versions are \"1.4.0\", \"1.4.1\", \"1.5.0\"", version)
             }
@@ -547,6 +1535,14 @@ impl Display for ErrorKind {
                 error.fmt(formatter)
             }
 
+            ErrorKind::MaxDepthExceeded { depth } => {
+                write!(formatter, "Elements were nested {} levels deep, which exceeds the maximum depth", depth)
+            }
+
+            ErrorKind::Io(ref error) => {
+                write!(formatter, "Failed to read the document: {}", error)
+            }
+
             ErrorKind::XmlError(ref error) => {
                 write!(formatter, "{}", error.msg())
             }
@@ -554,6 +1550,22 @@ impl Display for ErrorKind {
     }
 }
 
+impl ::std::error::Error for ErrorKind {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match *self {
+            ErrorKind::ParseFloatError(ref error) => Some(error),
+            ErrorKind::ParseBoolError(ref error) => Some(error),
+            ErrorKind::ParseIntError(ref error) => Some(error),
+            ErrorKind::TimeError(ref error) => Some(error),
+            ErrorKind::UriFragmentParseError(ref error) => Some(error),
+            ErrorKind::Io(ref error) => Some(error),
+            ErrorKind::XmlError(ref error) => Some(error),
+            ErrorKind::InvalidNumber { ref error, .. } => error.source(),
+            _ => None,
+        }
+    }
+}
+
 /// A specialized result type for COLLADA parsing.
 ///
 /// Specializes [`std::result::Result`][std::result::Result] to [`Error`][Error] for the purpose
@@ -0,0 +1,234 @@
+//! Minimal 4x4 matrix helpers for working with COLLADA transforms.
+//!
+//! All matrices are `[f64; 16]` stored in column-major order, so the translation component of
+//! an affine transform lives at indices 12, 13, and 14. Points are treated as column vectors,
+//! meaning that `multiply(a, b)` produces a transform that applies `b` first and then `a`.
+
+pub type Matrix = [f64; 16];
+
+/// Returns the 4x4 identity matrix.
+pub fn identity() -> Matrix {
+    [
+        1.0, 0.0, 0.0, 0.0,
+        0.0, 1.0, 0.0, 0.0,
+        0.0, 0.0, 1.0, 0.0,
+        0.0, 0.0, 0.0, 1.0,
+    ]
+}
+
+/// Multiplies `left` by `right`.
+pub fn multiply(left: &Matrix, right: &Matrix) -> Matrix {
+    let mut result = [0.0; 16];
+    for column in 0..4 {
+        for row in 0..4 {
+            result[column * 4 + row] = (0..4)
+                .map(|index| left[index * 4 + row] * right[column * 4 + index])
+                .sum();
+        }
+    }
+    result
+}
+
+/// Converts 16 values listed in row-major order, as they appear in a `<matrix>` element.
+pub fn from_row_major(values: &[f64; 16]) -> Matrix {
+    let mut result = [0.0; 16];
+    for row in 0..4 {
+        for column in 0..4 {
+            result[column * 4 + row] = values[row * 4 + column];
+        }
+    }
+    result
+}
+
+/// Builds a matrix from three basis vectors and a translation.
+fn from_basis(x: [f64; 3], y: [f64; 3], z: [f64; 3], translation: [f64; 3]) -> Matrix {
+    [
+        x[0], x[1], x[2], 0.0,
+        y[0], y[1], y[2], 0.0,
+        z[0], z[1], z[2], 0.0,
+        translation[0], translation[1], translation[2], 1.0,
+    ]
+}
+
+pub fn translation(offset: [f64; 3]) -> Matrix {
+    from_basis([1.0, 0.0, 0.0], [0.0, 1.0, 0.0], [0.0, 0.0, 1.0], offset)
+}
+
+pub fn scale(factors: [f64; 3]) -> Matrix {
+    from_basis(
+        [factors[0], 0.0, 0.0],
+        [0.0, factors[1], 0.0],
+        [0.0, 0.0, factors[2]],
+        [0.0; 3],
+    )
+}
+
+/// Builds a right-handed rotation of `degrees` around `axis`.
+///
+/// Returns the identity matrix if `axis` has zero length.
+pub fn rotation(axis: [f64; 3], degrees: f64) -> Matrix {
+    let [x, y, z] = match normalize(axis) {
+        Some(axis) => axis,
+        None => return identity(),
+    };
+
+    let (sin, cos) = degrees.to_radians().sin_cos();
+    let inv = 1.0 - cos;
+    from_basis(
+        [cos + x * x * inv, y * x * inv + z * sin, z * x * inv - y * sin],
+        [x * y * inv - z * sin, cos + y * y * inv, z * y * inv + x * sin],
+        [x * z * inv + y * sin, y * z * inv - x * sin, cos + z * z * inv],
+        [0.0; 3],
+    )
+}
+
+/// Builds the transform described by a `<lookat>` element.
+///
+/// The result places an object at `eye`, oriented so that its local negative Z axis points
+/// towards `interest` and its local Y axis is as close to `up` as possible. Returns a pure
+/// translation to `eye` if the orientation is degenerate.
+pub fn look_at(eye: [f64; 3], interest: [f64; 3], up: [f64; 3]) -> Matrix {
+    let basis = normalize(sub(eye, interest)).and_then(|z| {
+        let x = normalize(cross(up, z))?;
+        Some((x, cross(z, x), z))
+    });
+
+    match basis {
+        Some((x, y, z)) => from_basis(x, y, z, eye),
+        None => translation(eye),
+    }
+}
+
+/// Builds the transform described by a `<skew>` element, following the RenderMan definition.
+///
+/// Points are sheared along `translation_axis` such that `rotation_axis` ends up rotated by
+/// `degrees` towards `translation_axis`. Returns the identity matrix for degenerate input, i.e.
+/// if either axis has zero length, if the axes are parallel, or if the angle would rotate
+/// `rotation_axis` past `translation_axis`.
+pub fn skew(degrees: f64, rotation_axis: [f64; 3], translation_axis: [f64; 3]) -> Matrix {
+    let (rotation_axis, translation_axis) = match (normalize(rotation_axis), normalize(translation_axis)) {
+        (Some(rotation_axis), Some(translation_axis)) => (rotation_axis, translation_axis),
+        _ => return identity(),
+    };
+
+    // Split the rotation axis into its components parallel and perpendicular to the translation
+    // axis. The perpendicular direction is the one that drives the shear.
+    let parallel = dot(rotation_axis, translation_axis);
+    let perpendicular = match normalize(sub(rotation_axis, mul(translation_axis, parallel))) {
+        Some(perpendicular) => perpendicular,
+        None => return identity(),
+    };
+    let perpendicular_length = dot(rotation_axis, perpendicular);
+
+    let (sin, cos) = degrees.to_radians().sin_cos();
+    let rotated_perpendicular = perpendicular_length * cos - parallel * sin;
+    let rotated_parallel = perpendicular_length * sin + parallel * cos;
+    if rotated_perpendicular <= 0.0 {
+        return identity();
+    }
+
+    let amount = rotated_parallel / rotated_perpendicular - parallel / perpendicular_length;
+    let mut result = identity();
+    for column in 0..3 {
+        for row in 0..3 {
+            result[column * 4 + row] += amount * translation_axis[row] * perpendicular[column];
+        }
+    }
+    result
+}
+
+/// Returns the quaternion for a right-handed rotation of `degrees` around `axis`.
+///
+/// Quaternions are stored as `[x, y, z, w]`. Returns the identity quaternion if `axis` has zero
+/// length.
+pub fn quaternion(axis: [f64; 3], degrees: f64) -> [f64; 4] {
+    match normalize(axis) {
+        Some(axis) => {
+            let (sin, cos) = (degrees.to_radians() / 2.0).sin_cos();
+            [axis[0] * sin, axis[1] * sin, axis[2] * sin, cos]
+        }
+        None => [0.0, 0.0, 0.0, 1.0],
+    }
+}
+
+/// Splits an affine transform into its translation, rotation quaternion, and scale.
+///
+/// The scale along each axis is the length of the corresponding basis vector. If the transform
+/// mirrors, i.e. its determinant is negative, the X scale is made negative to compensate. The
+/// remaining basis is orthonormalized before extracting the rotation, so any shear is discarded.
+///
+/// Returns `None` if the transform isn't affine or if it collapses any axis to zero length.
+pub fn decompose(matrix: &Matrix) -> Option<([f64; 3], [f64; 4], [f64; 3])> {
+    if matrix[3] != 0.0 || matrix[7] != 0.0 || matrix[11] != 0.0 || matrix[15] != 1.0 {
+        return None;
+    }
+
+    let translation = [matrix[12], matrix[13], matrix[14]];
+    let mut x = [matrix[0], matrix[1], matrix[2]];
+    let y = [matrix[4], matrix[5], matrix[6]];
+    let z = [matrix[8], matrix[9], matrix[10]];
+
+    let mut scale = [dot(x, x).sqrt(), dot(y, y).sqrt(), dot(z, z).sqrt()];
+    if dot(cross(x, y), z) < 0.0 {
+        scale[0] = -scale[0];
+        x = mul(x, -1.0);
+    }
+
+    // Gram-Schmidt orthonormalization, deriving Z from X and Y so the basis stays right-handed.
+    let x = normalize(x)?;
+    let y = normalize(sub(y, mul(x, dot(x, y))))?;
+    let z = cross(x, y);
+    if scale[2] == 0.0 {
+        return None;
+    }
+
+    Some((translation, rotation_quaternion(x, y, z), scale))
+}
+
+/// Converts the rotation matrix with the basis vectors `x`, `y`, and `z` to a quaternion.
+fn rotation_quaternion(x: [f64; 3], y: [f64; 3], z: [f64; 3]) -> [f64; 4] {
+    // Branch on the largest diagonal element to keep the square root well away from zero.
+    let trace = x[0] + y[1] + z[2];
+    if trace > 0.0 {
+        let s = (trace + 1.0).sqrt() * 2.0;
+        [(y[2] - z[1]) / s, (z[0] - x[2]) / s, (x[1] - y[0]) / s, 0.25 * s]
+    } else if x[0] > y[1] && x[0] > z[2] {
+        let s = (1.0 + x[0] - y[1] - z[2]).sqrt() * 2.0;
+        [0.25 * s, (y[0] + x[1]) / s, (z[0] + x[2]) / s, (y[2] - z[1]) / s]
+    } else if y[1] > z[2] {
+        let s = (1.0 + y[1] - x[0] - z[2]).sqrt() * 2.0;
+        [(y[0] + x[1]) / s, 0.25 * s, (z[1] + y[2]) / s, (z[0] - x[2]) / s]
+    } else {
+        let s = (1.0 + z[2] - x[0] - y[1]).sqrt() * 2.0;
+        [(z[0] + x[2]) / s, (z[1] + y[2]) / s, 0.25 * s, (x[1] - y[0]) / s]
+    }
+}
+
+fn sub(left: [f64; 3], right: [f64; 3]) -> [f64; 3] {
+    [left[0] - right[0], left[1] - right[1], left[2] - right[2]]
+}
+
+fn mul(vector: [f64; 3], factor: f64) -> [f64; 3] {
+    [vector[0] * factor, vector[1] * factor, vector[2] * factor]
+}
+
+fn dot(left: [f64; 3], right: [f64; 3]) -> f64 {
+    left[0] * right[0] + left[1] * right[1] + left[2] * right[2]
+}
+
+fn cross(left: [f64; 3], right: [f64; 3]) -> [f64; 3] {
+    [
+        left[1] * right[2] - left[2] * right[1],
+        left[2] * right[0] - left[0] * right[2],
+        left[0] * right[1] - left[1] * right[0],
+    ]
+}
+
+fn normalize(vector: [f64; 3]) -> Option<[f64; 3]> {
+    let length = dot(vector, vector).sqrt();
+    if length > 0.0 {
+        Some(mul(vector, 1.0 / length))
+    } else {
+        None
+    }
+}
@@ -0,0 +1,160 @@
+//! Serde implementations for types that can't derive them.
+//!
+//! Only compiled when the `serde` feature is enabled.
+
+use common::{AnyUri, DateTime, Technique};
+use serde::de::{self, Deserialize, Deserializer};
+use serde::ser::{self, Serialize, SerializeStruct, Serializer};
+use std::collections::BTreeMap;
+use std::fmt::Display;
+use utils;
+use xml::common::TextPosition;
+use xml::namespace::Namespace;
+use xml::reader::XmlEvent;
+
+/// Serializes an error position as a `{ row, column }` struct.
+pub fn serialize_position<S>(position: &TextPosition, serializer: S) -> Result<S::Ok, S::Error>
+where
+    S: Serializer,
+{
+    let mut state = serializer.serialize_struct("TextPosition", 2)?;
+    state.serialize_field("row", &position.row)?;
+    state.serialize_field("column", &position.column)?;
+    state.end()
+}
+
+/// Serializes a wrapped third-party error as its display string.
+pub fn serialize_display<T, S>(value: &T, serializer: S) -> Result<S::Ok, S::Error>
+where
+    T: Display,
+    S: Serializer,
+{
+    serializer.collect_str(value)
+}
+
+/// Serializes a `Namespace` as a map from prefix to URI.
+pub mod namespace {
+    use super::*;
+
+    pub fn serialize<S>(namespace: &Namespace, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        namespace.0.serialize(serializer)
+    }
+
+    pub fn deserialize<'de, D>(deserializer: D) -> Result<Namespace, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        BTreeMap::deserialize(deserializer).map(Namespace)
+    }
+}
+
+/// Serializes a list of raw XML events as the XML string they represent.
+pub mod events {
+    use super::*;
+
+    pub fn serialize<S>(events: &Vec<XmlEvent>, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        let data = utils::write_events(events).map_err(ser::Error::custom)?;
+        serializer.serialize_str(&data)
+    }
+
+    pub fn deserialize<'de, D>(deserializer: D) -> Result<Vec<XmlEvent>, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let source = String::deserialize(deserializer)?;
+        parse(&source).map_err(de::Error::custom)
+    }
+
+    /// Parses an XML fragment back into the events it was written from.
+    pub fn parse(source: &str) -> Result<Vec<XmlEvent>, ::xml::reader::Error> {
+        // Wrap the data in a root element so that it forms a valid document, then strip the
+        // events for the wrapper back out.
+        let source = format!("<data>{}</data>", source);
+        let mut data = Vec::new();
+        for event in utils::event_reader(source.as_bytes()) {
+            match event? {
+                XmlEvent::StartDocument { .. } | XmlEvent::EndDocument => {}
+                event => data.push(event),
+            }
+        }
+        data.remove(0);
+        data.pop();
+
+        Ok(data)
+    }
+}
+
+impl Serialize for DateTime {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        match *self {
+            DateTime::Utc(ref date_time) => serializer.serialize_str(&date_time.to_rfc3339()),
+            DateTime::Naive(ref date_time) => {
+                serializer.collect_str(&date_time.format("%Y-%m-%dT%H:%M:%S%.f"))
+            }
+        }
+    }
+}
+
+impl<'de> Deserialize<'de> for DateTime {
+    fn deserialize<D>(deserializer: D) -> Result<DateTime, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let source = String::deserialize(deserializer)?;
+        source.parse().map_err(de::Error::custom)
+    }
+}
+
+/// The serialized form of a `Technique`, with the raw XML events written out as a string.
+#[derive(Serialize, Deserialize)]
+#[serde(rename = "Technique")]
+struct TechniqueRepr {
+    profile: String,
+    xmlns: Option<AnyUri>,
+    #[serde(with = "namespace")]
+    namespace: Namespace,
+    data: String,
+}
+
+impl Serialize for Technique {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        let data = utils::write_events(&self.data).map_err(ser::Error::custom)?;
+
+        TechniqueRepr {
+            profile: self.profile.clone(),
+            xmlns: self.xmlns.clone(),
+            namespace: self.namespace.clone(),
+            data,
+        }.serialize(serializer)
+    }
+}
+
+impl<'de> Deserialize<'de> for Technique {
+    fn deserialize<D>(deserializer: D) -> Result<Technique, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let repr = TechniqueRepr::deserialize(deserializer)?;
+
+        let data = events::parse(&repr.data).map_err(de::Error::custom)?;
+
+        Ok(Technique {
+            profile: repr.profile,
+            xmlns: repr.xmlns,
+            namespace: repr.namespace,
+            data,
+        })
+    }
+}
@@ -0,0 +1,253 @@
+//! Streaming access to the contents of COLLADA `1.4` documents.
+//!
+//! Parsing a document into a [`v1_4::Collada`] holds the whole document in memory at once, which
+//! is wasteful if you only need to look at each piece of data once. [`visit`] instead walks the
+//! document element-by-element, handing each parsed element to a [`Visitor`] and then dropping it
+//! before moving on to the next one. Memory usage is bounded by the size of the largest single
+//! element (e.g. the largest `<geometry>`), rather than by the size of the document.
+//!
+//! # Examples
+//!
+//! Count the geometries in a document without building the full document:
+//!
+//! ```
+//! use std::fs::File;
+//! use collaborate::stream::{self, Visitor};
+//! use collaborate::v1_4::Geometry;
+//!
+//! struct CountGeometries(usize);
+//!
+//! impl Visitor for CountGeometries {
+//!     fn visit_geometry(&mut self, _: &Geometry) {
+//!         self.0 += 1;
+//!     }
+//! }
+//!
+//! let file = File::open("resources/blender_cube.dae").unwrap();
+//! let mut counter = CountGeometries(0);
+//! stream::visit(file, &mut counter).unwrap();
+//! assert_eq!(1, counter.0);
+//! ```
+//!
+//! [`v1_4::Collada`]: ../v1_4/struct.Collada.html
+//! [`visit`]: ./fn.visit.html
+//! [`Visitor`]: ./trait.Visitor.html
+
+use {Error, ErrorKind, ParseOptions, Result};
+use std::io::Read;
+use utils;
+use utils::*;
+use v1_4::*;
+use xml::common::Position;
+
+/// Receives the elements of a document as they're parsed by [`visit`].
+///
+/// All methods have empty default implementations, so implementors only need to override the
+/// ones for the data they're interested in. Elements are visited in document order.
+///
+/// [`visit`]: ./fn.visit.html
+pub trait Visitor {
+    /// Called with the document's `<asset>`.
+    fn visit_asset(&mut self, _asset: &Asset) {}
+
+    /// Called for each `<source>` in a geometry's mesh, before the geometry itself is visited.
+    fn visit_source(&mut self, _source: &Source) {}
+
+    /// Called for each `<geometry>` in every `<library_geometries>`.
+    fn visit_geometry(&mut self, _geometry: &Geometry) {}
+
+    /// Called for each root `<node>` in every `<visual_scene>`.
+    ///
+    /// Child nodes are not visited separately, they're available through `Node::children`.
+    fn visit_node(&mut self, _node: &Node) {}
+
+    /// Called for each library other than `<library_geometries>` and `<library_visual_scenes>`.
+    ///
+    /// These libraries are parsed in their entirety before being visited.
+    fn visit_library(&mut self, _library: &Library) {}
+
+    /// Called for each document-level `<extra>`.
+    fn visit_extra(&mut self, _extra: &Extra) {}
+}
+
+/// Walks a `1.4` COLLADA document, passing each element to `visitor` as it's parsed.
+///
+/// # Errors
+///
+/// Returns `Err` if the document is invalid or malformed in some way, or if it isn't a `1.4.0` or
+/// `1.4.1` document. Any elements visited before the error was encountered will already have been
+/// passed to `visitor`.
+pub fn visit<R: Read, V: Visitor>(reader: R, visitor: &mut V) -> Result<()> {
+    visit_with_options(reader, ParseOptions::default(), visitor)
+}
+
+/// Walks a `1.4` COLLADA document using the specified options.
+///
+/// See [`ParseOptions`] for the available options. Otherwise this behaves exactly like
+/// [`visit`](./fn.visit.html).
+///
+/// [`ParseOptions`]: ../struct.ParseOptions.html
+pub fn visit_with_options<R: Read, V: Visitor>(
+    reader: R,
+    options: ParseOptions,
+    visitor: &mut V,
+) -> Result<()> {
+    let reader = utils::strip_bom(reader)?;
+    let mut reader = ColladaReader::new(utils::options_event_reader(reader, &options), options);
+
+    let element_start = utils::get_document_start(&mut reader)?;
+    let version = ::document_version(&reader, &element_start)?;
+
+    if version != "1.4.0" && version != "1.4.1" {
+        return Err(Error {
+            position: reader.position(),
+            kind: ErrorKind::UnsupportedVersion {
+                version,
+            },
+        });
+    }
+
+    let reader = &mut reader;
+    let mut has_asset = false;
+    while let Some(element) = utils::start_element(reader, "COLLADA")? {
+        match &*element.name.local_name {
+            "asset" if !has_asset => {
+                has_asset = true;
+                visitor.visit_asset(&Asset::parse_element(reader, element)?);
+            }
+
+            _ if !has_asset => {
+                return Err(Error {
+                    position: reader.position(),
+                    kind: ErrorKind::MissingElement {
+                        parent: "COLLADA",
+                        expected: vec!["asset"],
+                    },
+                });
+            }
+
+            name if reader.options.libraries.skips(name) => {
+                utils::stub_out(reader)?;
+            }
+
+            "library_geometries" => {
+                visit_library_geometries(reader, visitor)?;
+            }
+
+            "library_visual_scenes" => {
+                visit_library_visual_scenes(reader, visitor)?;
+            }
+
+            name if Library::name_test(name) => {
+                visitor.visit_library(&Library::parse_element(reader, element)?);
+            }
+
+            "scene" => {
+                Scene::parse_element(reader, element)?;
+            }
+
+            "extra" => {
+                visitor.visit_extra(&Extra::parse_element(reader, element)?);
+            }
+
+            _ => {
+                let mut expected = Vec::new();
+                Library::add_names(&mut expected);
+                expected.push("scene");
+                expected.push("extra");
+
+                return Err(Error {
+                    position: reader.position(),
+                    kind: ErrorKind::UnexpectedElement {
+                        parent: "COLLADA",
+                        element: element.name.local_name,
+                        expected,
+                    },
+                });
+            }
+        }
+    }
+
+    if !has_asset {
+        return Err(Error {
+            position: reader.position(),
+            kind: ErrorKind::MissingElement {
+                parent: "COLLADA",
+                expected: vec!["asset"],
+            },
+        });
+    }
+
+    Ok(())
+}
+
+fn visit_library_geometries<R: Read, V: Visitor>(
+    reader: &mut ColladaReader<R>,
+    visitor: &mut V,
+) -> Result<()> {
+    let expected = ["asset", "geometry", "extra"];
+    visit_children(reader, "library_geometries", &expected, &mut |reader, element| {
+        if element.name.local_name != "geometry" {
+            return utils::stub_out(reader);
+        }
+
+        let geometry = Geometry::parse_element(reader, element)?;
+        if let Some(mesh) = geometry.geometric_element.as_mesh() {
+            for source in &mesh.sources {
+                visitor.visit_source(source);
+            }
+        }
+        visitor.visit_geometry(&geometry);
+        Ok(())
+    })
+}
+
+fn visit_library_visual_scenes<R: Read, V: Visitor>(
+    reader: &mut ColladaReader<R>,
+    visitor: &mut V,
+) -> Result<()> {
+    let expected = ["asset", "visual_scene", "extra"];
+    visit_children(reader, "library_visual_scenes", &expected, &mut |reader, element| {
+        if element.name.local_name != "visual_scene" {
+            return utils::stub_out(reader);
+        }
+
+        let expected = ["asset", "node", "evaluate_scene", "extra"];
+        visit_children(reader, "visual_scene", &expected, &mut |reader, element| {
+            if element.name.local_name != "node" {
+                return utils::stub_out(reader);
+            }
+
+            visitor.visit_node(&Node::parse_element(reader, element)?);
+            Ok(())
+        })
+    })
+}
+
+/// Calls `action` for each child of the current element, which must be one of `expected`.
+///
+/// Attributes of the current element are ignored, and the order of the children isn't validated.
+/// `action` is responsible for consuming the entire child element.
+fn visit_children<R: Read>(
+    reader: &mut ColladaReader<R>,
+    parent: &'static str,
+    expected: &[&'static str],
+    action: &mut dyn FnMut(&mut ColladaReader<R>, ElementStart) -> Result<()>,
+) -> Result<()> {
+    while let Some(element) = utils::start_element(reader, parent)? {
+        if !expected.contains(&&*element.name.local_name) {
+            return Err(Error {
+                position: reader.position(),
+                kind: ErrorKind::UnexpectedElement {
+                    parent,
+                    element: element.name.local_name,
+                    expected: expected.to_vec(),
+                },
+            });
+        }
+
+        action(reader, element)?;
+    }
+
+    Ok(())
+}
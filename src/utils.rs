@@ -1,15 +1,26 @@
-use {Result, Error, ErrorKind};
+use {ParseOptions, Result, Error, ErrorKind, Warning, WarningKind};
 use self::ChildOccurrences::*;
+use std::collections::{HashMap, HashSet};
 use std::fmt::{self, Display, Formatter};
-use std::io::Read;
+use std::fs::File;
+use std::io::{self, BufReader, Chain, Cursor, Read, Write};
+use std::path::Path;
 use std::str::FromStr;
 use xml::attribute::OwnedAttribute;
-use xml::common::Position;
+use xml::common::{Position, TextPosition};
 use xml::name::OwnedName;
-use xml::reader::{EventReader, ParserConfig};
+use xml::namespace::Namespace;
+use xml::reader::{EventReader, ParserConfig, XmlEvent};
 use xml::reader::XmlEvent::*;
-
-pub static PARSER_CONFIG: ParserConfig = ParserConfig {
+use xml::writer::{EmitterConfig, Error as EmitterError, EventWriter};
+use xml::writer::events::StartElementBuilder;
+use xml::writer::XmlEvent as WriterEvent;
+
+/// The configuration used for every `EventReader` created by the library.
+///
+/// This is a `const` rather than a `static` so that each use builds the config in place instead
+/// of cloning a shared copy.
+const PARSER_CONFIG: ParserConfig = ParserConfig {
     trim_whitespace: true,
     whitespace_to_characters: true,
     cdata_to_characters: true,
@@ -17,6 +28,326 @@ pub static PARSER_CONFIG: ParserConfig = ParserConfig {
     coalesce_characters: true,
 };
 
+/// Creates an `EventReader` for `source` using the library's parser configuration.
+pub fn event_reader<R: Read>(source: R) -> EventReader<R> {
+    EventReader::new_with_config(source, PARSER_CONFIG)
+}
+
+/// Creates an `EventReader` for parsing a document from `source` with `options`.
+///
+/// Comments are only reported by the reader if `options.keep_comments` is set, and whitespace is
+/// only reported (and text left untrimmed) if `options.keep_whitespace` is set.
+pub fn options_event_reader<R: Read>(source: R, options: &ParseOptions) -> EventReader<R> {
+    let config = ParserConfig {
+        trim_whitespace: !options.keep_whitespace,
+        whitespace_to_characters: !options.keep_whitespace,
+        ignore_comments: !options.keep_comments,
+        ..PARSER_CONFIG
+    };
+    EventReader::new_with_config(source, config)
+}
+
+/// Writes `events` out as a string of XML, without an XML declaration or any indentation.
+///
+/// Events that can't be written (i.e. `EndDocument`) are skipped.
+pub fn write_events(events: &[XmlEvent]) -> ::xml::writer::Result<String> {
+    let mut writer = EmitterConfig::new()
+        .write_document_declaration(false)
+        .perform_indent(false)
+        .create_writer(Vec::new());
+    for event in events.iter().filter_map(XmlEvent::as_writer_event) {
+        writer.write(event)?;
+    }
+
+    // The writer only ever receives `&str` data, so its output is always valid UTF-8.
+    Ok(String::from_utf8(writer.into_inner()).unwrap())
+}
+
+/// Parses a single value from the text contents of `element`.
+///
+/// Numbers that are formatted incorrectly are reported as an `ErrorKind::InvalidNumber` that
+/// includes the offending text, since the underlying `ParseFloatError` or `ParseIntError` doesn't
+/// say what was wrong. Likewise, booleans are reported as an `ErrorKind::InvalidValue`. Any other
+/// error is returned as-is.
+pub fn parse_value<T>(element: &'static str, text: &str) -> ::std::result::Result<T, ErrorKind>
+    where
+    T: FromStr,
+    ErrorKind: From<<T as FromStr>::Err>,
+{
+    text.parse().map_err(|error: T::Err| {
+        match ErrorKind::from(error) {
+            kind @ ErrorKind::ParseFloatError(_) | kind @ ErrorKind::ParseIntError(_) => {
+                ErrorKind::InvalidNumber {
+                    element,
+                    token: text.into(),
+                    error: Box::new(kind),
+                }
+            }
+
+            ErrorKind::ParseBoolError(_) => {
+                ErrorKind::InvalidValue {
+                    element,
+                    value: text.into(),
+                }
+            }
+
+            kind => kind,
+        }
+    })
+}
+
+/// Parses a whitespace-separated list of values from the text contents of `element`.
+///
+/// `capacity` is the number of values the document claims the list holds (e.g. the `count`
+/// attribute of a `<float_array>`), and is used to allocate the list up front. It's only a hint,
+/// and is capped by the number of values that could actually fit in `text` so that a bogus count
+/// can't trigger a huge allocation.
+///
+/// XML only treats ASCII characters as whitespace, so the text is split on ASCII whitespace bytes
+/// rather than the full set of unicode whitespace that `str::split_whitespace` checks for.
+///
+/// Each value is parsed with `parse_value`, so a badly formatted number reports only the
+/// offending value rather than the whole list.
+pub fn parse_text_list<T>(
+    element: &'static str,
+    text: &str,
+    capacity: Option<usize>,
+) -> ::std::result::Result<Vec<T>, ErrorKind>
+    where
+    T: FromStr,
+    ErrorKind: From<<T as FromStr>::Err>,
+{
+    // Every value takes at least one byte, and all but the last are followed by a separator.
+    let max_values = text.len() / 2 + 1;
+    let mut values = Vec::with_capacity(capacity.map_or(0, |capacity| capacity.min(max_values)));
+
+    let bytes = text.as_bytes();
+    let mut start = None;
+    for (index, byte) in bytes.iter().enumerate() {
+        match (byte.is_ascii_whitespace(), start) {
+            (true, Some(word_start)) => {
+                values.push(parse_value(element, &text[word_start..index])?);
+                start = None;
+            }
+
+            (false, None) => { start = Some(index); }
+
+            _ => {}
+        }
+    }
+
+    if let Some(word_start) = start {
+        values.push(parse_value(element, &text[word_start..])?);
+    }
+
+    Ok(values)
+}
+
+/// An `EventReader` bundled with the options for the current parse.
+///
+/// This is what gets passed down through all of the `ColladaElement` impls, so that any element
+/// can check the options that affect it.
+pub struct ColladaReader<R: Read> {
+    events: EventReader<R>,
+    pub options: ParseOptions,
+
+    /// The warnings produced while parsing in lenient mode.
+    pub warnings: Vec<Warning>,
+
+    /// Elements from other versions of the spec that should get a more specific warning when
+    /// they're skipped in lenient mode.
+    pub other_version_elements: Option<&'static OtherVersionElements>,
+
+    /// How many elements deep the parser currently is, not counting the root element.
+    pub depth: usize,
+}
+
+impl<R: Read> ColladaReader<R> {
+    pub fn new(events: EventReader<R>, options: ParseOptions) -> ColladaReader<R> {
+        ColladaReader {
+            events,
+            options,
+            warnings: Vec::new(),
+            other_version_elements: None,
+            depth: 0,
+        }
+    }
+
+    /// Returns the next event from the document, skipping comments and whitespace.
+    ///
+    /// Comments and whitespace are only reported if [`ParseOptions::keep_comments`] or
+    /// [`ParseOptions::keep_whitespace`] is set, and they have no meaning in a COLLADA document.
+    /// Text is trimmed, as it would have been by the parser if whitespace weren't being kept.
+    ///
+    /// [`ParseOptions::keep_comments`]: ../struct.ParseOptions.html#structfield.keep_comments
+    /// [`ParseOptions::keep_whitespace`]: ../struct.ParseOptions.html#structfield.keep_whitespace
+    pub fn next(&mut self) -> ::xml::reader::Result<::xml::reader::XmlEvent> {
+        loop {
+            match self.events.next()? {
+                Comment(_) | Whitespace(_) => {}
+                Characters(ref data) if self.options.keep_whitespace => {
+                    return Ok(Characters(data.trim_matches(is_xml_whitespace).into()));
+                }
+                event => return Ok(event),
+            }
+        }
+    }
+
+    /// Returns the next event from the document, including comments and whitespace.
+    ///
+    /// This is used when buffering raw XML events, such as the contents of a `<technique>`, so
+    /// that comments and whitespace are kept when [`ParseOptions::keep_comments`] or
+    /// [`ParseOptions::keep_whitespace`] is set.
+    ///
+    /// [`ParseOptions::keep_comments`]: ../struct.ParseOptions.html#structfield.keep_comments
+    /// [`ParseOptions::keep_whitespace`]: ../struct.ParseOptions.html#structfield.keep_whitespace
+    pub fn next_raw(&mut self) -> ::xml::reader::Result<::xml::reader::XmlEvent> {
+        self.events.next()
+    }
+}
+
+/// Returns `true` for the characters that XML treats as whitespace.
+fn is_xml_whitespace(c: char) -> bool {
+    c == ' ' || c == '\t' || c == '\n' || c == '\r'
+}
+
+impl<R: Read> Position for ColladaReader<R> {
+    fn position(&self) -> TextPosition {
+        self.events.position()
+    }
+}
+
+/// Elements that are known to belong to a different version of the spec than the document being
+/// parsed.
+pub struct OtherVersionElements {
+    /// The version of the document being parsed, e.g. `"1.4"`.
+    pub document_version: &'static str,
+
+    /// The version that the elements belong to, e.g. `"1.5"`.
+    pub version: &'static str,
+
+    /// The elements as `(parent, element)` pairs.
+    pub elements: &'static [(&'static str, &'static str)],
+}
+
+/// An `EventWriter` used for writing out a document, the counterpart to `ColladaReader`.
+///
+/// This is what gets passed down through all of the `ColladaElement` impls when writing.
+pub struct ColladaWriter<W: Write> {
+    events: EventWriter<W>,
+
+    /// The default namespace to declare on the next element that's started, if any.
+    namespace: Option<String>,
+}
+
+impl<W: Write> ColladaWriter<W> {
+    /// Creates a writer for a document whose root element declares `namespace` as the default
+    /// namespace.
+    pub fn new(sink: W, namespace: String) -> ColladaWriter<W> {
+        let events = EmitterConfig::new()
+            .perform_indent(true)
+            .create_writer(sink);
+        ColladaWriter { events, namespace: Some(namespace) }
+    }
+
+    pub fn start_element(&mut self, mut start: StartElementBuilder) -> io::Result<()> {
+        if let Some(ref namespace) = self.namespace {
+            start = start.default_ns(&**namespace);
+        }
+        let result = self.write(start);
+        self.namespace = None;
+        result
+    }
+
+    pub fn end_element(&mut self) -> io::Result<()> {
+        self.write(WriterEvent::end_element())
+    }
+
+    pub fn characters(&mut self, text: &str) -> io::Result<()> {
+        self.write(WriterEvent::characters(text))
+    }
+
+    /// Writes an element with no attributes that contains only `text`.
+    pub fn text_element(&mut self, name: &str, text: &str) -> io::Result<()> {
+        self.start_element(WriterEvent::start_element(name))?;
+        self.characters(text)?;
+        self.end_element()
+    }
+
+    /// Writes an element with no attributes or contents.
+    pub fn empty_element(&mut self, name: &str) -> io::Result<()> {
+        self.start_element(WriterEvent::start_element(name))?;
+        self.end_element()
+    }
+
+    pub fn write<'a, E: Into<WriterEvent<'a>>>(&mut self, event: E) -> io::Result<()> {
+        self.events.write(event).map_err(|error| match error {
+            EmitterError::Io(error) => error,
+            error => io::Error::new(io::ErrorKind::InvalidData, error.to_string()),
+        })
+    }
+}
+
+/// The UTF-8 encoding of the unicode byte-order mark.
+static UTF8_BOM: &'static [u8] = b"\xEF\xBB\xBF";
+
+/// Removes a leading UTF-8 byte-order mark from `source`, if present.
+///
+/// Some tools (most notably on Windows) prefix UTF-8 documents with a BOM. xml-rs doesn't
+/// recognize it, so it has to be stripped before the `EventReader` gets to see the document.
+pub fn strip_bom_str(source: &str) -> &str {
+    if source.starts_with('\u{FEFF}') {
+        &source['\u{FEFF}'.len_utf8()..]
+    } else {
+        source
+    }
+}
+
+/// Removes a leading UTF-8 byte-order mark from `reader`, if present.
+///
+/// This has to read the first few bytes of the document to check for the BOM. Any bytes that
+/// aren't part of a BOM are put back in front of the rest of the stream.
+pub fn strip_bom<R: Read>(mut reader: R) -> Result<Chain<Cursor<Vec<u8>>, R>> {
+    let mut start = Vec::with_capacity(UTF8_BOM.len());
+    while start.len() < UTF8_BOM.len() {
+        let mut byte = [0];
+        match reader.read(&mut byte) {
+            Ok(0) => { break; }
+            Ok(_) => { start.push(byte[0]); }
+            Err(ref error) if error.kind() == io::ErrorKind::Interrupted => {}
+            Err(error) => {
+                return Err(Error {
+                    position: TextPosition::new(),
+                    kind: ErrorKind::Io(error),
+                });
+            }
+        }
+
+        // Stop as soon as we know the stream doesn't start with a BOM.
+        if start[..] != UTF8_BOM[..start.len()] {
+            break;
+        }
+    }
+
+    if &*start == UTF8_BOM {
+        start.clear();
+    }
+
+    Ok(Cursor::new(start).chain(reader))
+}
+
+/// Opens the file at `path` for reading a document.
+///
+/// The file is buffered, since xml-rs reads its input in very small chunks.
+pub fn open_file<P: AsRef<Path>>(path: P) -> Result<BufReader<File>> {
+    File::open(path)
+        .map(BufReader::new)
+        .map_err(|error| Error {
+            position: TextPosition::new(),
+            kind: ErrorKind::Io(error),
+        })
+}
+
 /// Helper trait for handling parsing. This can be derived for most types with the
 /// `collaborate-derive` crate.
 pub trait ColladaElement: Sized {
@@ -29,7 +360,7 @@ pub trait ColladaElement: Sized {
     ///
     /// Implementation should panic if `element_start` isn't valid for the current element.
     fn parse_element<R>(
-        reader: &mut EventReader<R>,
+        reader: &mut ColladaReader<R>,
         element_start: ElementStart,
     ) -> Result<Self>
     where
@@ -40,12 +371,71 @@ pub trait ColladaElement: Sized {
     /// This allows both single elements and element groups to add their name(s) to the list of
     /// expected names when returning an error message.
     fn add_names(names: &mut Vec<&'static str>);
+
+    /// Adds the names of all valid children of the element to `names`, in the order they must
+    /// appear.
+    ///
+    /// Element groups and elements that don't have any children add nothing.
+    fn add_children(_names: &mut Vec<&'static str>) {}
+
+    /// Writes the element, including all of its attributes and children, to `writer`.
+    ///
+    /// The output must parse back into an equal value with `parse_element`.
+    fn write_element<W>(&self, writer: &mut ColladaWriter<W>) -> io::Result<()>
+    where
+        W: Write;
+
+    /// Removes all `<extra>` elements and optional `<technique>` elements from this element
+    /// and its descendants.
+    ///
+    /// Elements that don't contain any children, such as stubs, have nothing to strip.
+    fn strip_extras(&mut self) {}
+}
+
+/// Boxed elements parse and write exactly like the element they contain. This lets large members
+/// of an element group be boxed, so that they don't inflate the size of the whole group.
+impl<T: ColladaElement> ColladaElement for Box<T> {
+    fn name_test(name: &str) -> bool {
+        T::name_test(name)
+    }
+
+    fn parse_element<R>(
+        reader: &mut ColladaReader<R>,
+        element_start: ElementStart,
+    ) -> Result<Self>
+    where
+        R: Read,
+    {
+        T::parse_element(reader, element_start).map(Box::new)
+    }
+
+    fn add_names(names: &mut Vec<&'static str>) {
+        T::add_names(names)
+    }
+
+    fn add_children(names: &mut Vec<&'static str>) {
+        T::add_children(names)
+    }
+
+    fn write_element<W>(&self, writer: &mut ColladaWriter<W>) -> io::Result<()>
+    where
+        W: Write,
+    {
+        (**self).write_element(writer)
+    }
+
+    fn strip_extras(&mut self) {
+        (**self).strip_extras()
+    }
 }
 
 #[derive(Debug)]
 pub struct ElementStart {
     pub name: OwnedName,
     pub attributes: Vec<OwnedAttribute>,
+
+    /// The namespace mappings in scope for the element.
+    pub namespace: Namespace,
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -60,11 +450,11 @@ pub enum ChildOccurrences {
 pub struct ElementConfiguration<'a, R: 'a + Read> {
     pub name: &'static str,
     pub children: &'a mut [ChildConfiguration<'a, R>],
-    pub text_contents: Option<&'a mut FnMut(&mut EventReader<R>, String) -> Result<()>>,
+    pub text_contents: Option<&'a mut FnMut(&mut ColladaReader<R>, String) -> Result<()>>,
 }
 
 impl<'a, R: 'a + Read> ElementConfiguration<'a, R> {
-    pub fn parse_children(self, reader: &mut EventReader<R>) -> Result<()> {
+    pub fn parse_children(self, reader: &mut ColladaReader<R>) -> Result<()> {
         // Keep track of the text position for the root element so that it can be used for error
         // messages.
         let root_position = reader.position();
@@ -83,14 +473,38 @@ impl<'a, R: 'a + Read> ElementConfiguration<'a, R> {
         let mut has_encountered_child = false;
 
         'elements: while let Some(element) = start_element(reader, self.name)? {
+            // Remember where we were so that we can pick up from here if the element is skipped.
+            let (previous_child, previous_encountered) = (current_child, has_encountered_child);
+
             while current_child < self.children.len() {
                 let child = &mut self.children[current_child];
 
                 if (child.name)(&*element.name.local_name) {
                     has_encountered_child = true;
 
-                    // We've found a valid child, hooray! Allow it to run its parsing code.
-                    (child.action)(reader, element)?;
+                    // We've found a valid child, hooray! Allow it to run its parsing code, unless
+                    // it's a library that the options exclude. Libraries only appear directly
+                    // within `<COLLADA>`, so other elements don't need to check the filter.
+                    let skipped = self.name == "COLLADA"
+                        && reader.options.libraries.skips(&element.name.local_name);
+                    if skipped {
+                        stub_out(reader)?;
+                    } else {
+                        // Guard against documents nested deep enough to overflow the stack. This is
+                        // the only place depth is counted, since every parsed child passes through
+                        // here, while raw and stubbed elements are read without recursing.
+                        reader.depth += 1;
+                        if reader.depth > reader.options.max_depth {
+                            return Err(Error {
+                                position: reader.position(),
+                                kind: ErrorKind::MaxDepthExceeded { depth: reader.depth },
+                            });
+                        }
+
+                        let result = (child.action)(reader, element);
+                        reader.depth -= 1;
+                        result?;
+                    }
 
                     // Either advance `current_child` or don't, depending on if it's allowed to repeat.
                     match child.occurrences {
@@ -116,18 +530,54 @@ impl<'a, R: 'a + Read> ElementConfiguration<'a, R> {
                 current_child += 1;
             }
 
-            return Err(Error {
-                position: reader.position(),
-                kind: ErrorKind::UnexpectedElement {
-                    parent: self.name,
-                    element: element.name.local_name,
-                    expected: self.collect_expected_children(),
-                },
-            });
+            self.skip_unexpected(reader, element)?;
+            current_child = previous_child;
+            has_encountered_child = previous_encountered;
         }
 
         // No more child elements are present, and none of the children we encountered were invalid.
         // Verify that there are no remaining required children.
+        self.check_required_children(current_child, root_position)
+    }
+
+    // The error handling below is kept out of `parse_children`, since `parse_children` recurses
+    // once for every level of nesting in the document and its locals are on the stack for each.
+
+    /// Handles an element that doesn't match any of the expected children, either skipping it
+    /// with a warning in lenient mode or returning an error.
+    fn skip_unexpected(&self, reader: &mut ColladaReader<R>, element: ElementStart) -> Result<()> {
+        if reader.options.lenient {
+            let position = reader.position();
+            let name = element.name.local_name;
+            stub_out(reader)?;
+
+            let kind = match reader.other_version_elements {
+                Some(other) if other.elements.contains(&(self.name, &*name)) => {
+                    WarningKind::WrongVersion {
+                        element: name,
+                        version: other.version,
+                        document_version: other.document_version,
+                    }
+                }
+
+                _ => WarningKind::SkippedElement { parent: self.name, element: name },
+            };
+            reader.warnings.push(Warning { position, kind });
+            return Ok(());
+        }
+
+        Err(Error {
+            position: reader.position(),
+            kind: ErrorKind::UnexpectedElement {
+                parent: self.name,
+                element: element.name.local_name,
+                expected: self.collect_expected_children(),
+            },
+        })
+    }
+
+    /// Returns an error if any of the children from `current_child` onwards are required.
+    fn check_required_children(&self, current_child: usize, root_position: TextPosition) -> Result<()> {
         for child in &self.children[current_child..] {
             if child.occurrences == ChildOccurrences::Required {
                 let mut expected = Vec::new();
@@ -158,57 +608,69 @@ impl<'a, R: 'a + Read> ElementConfiguration<'a, R> {
 pub struct ChildConfiguration<'a, R: 'a + Read> {
     pub name: &'a Fn(&str) -> bool,
     pub occurrences: ChildOccurrences,
-    pub action: &'a mut FnMut(&mut EventReader<R>, ElementStart) -> Result<()>,
+    pub action: &'a mut FnMut(&mut ColladaReader<R>, ElementStart) -> Result<()>,
     pub add_names: &'a Fn(&mut Vec<&'static str>),
 }
 
-pub fn get_document_start<R: Read>(reader: &mut EventReader<R>) -> Result<ElementStart> {
+pub fn get_document_start<R: Read>(reader: &mut ColladaReader<R>) -> Result<ElementStart> {
+    // Reported when the document ends (or otherwise goes wrong) before the root element. xml-rs
+    // reports most malformed input (e.g. an empty or plain text document) as a syntax error
+    // before we get this far, so this is only a fallback for events we don't expect.
+    let missing_root = |reader: &ColladaReader<R>| Error {
+        position: reader.position(),
+        kind: ErrorKind::MissingElement {
+            parent: "document",
+            expected: vec!["COLLADA"],
+        },
+    };
+
     // Eat the `StartDocument` event. It has no useful information for our purposes, but it
     // will always be the first event emitted, even if there's no XML declaration at the
     // beginning of the document. This is defined as part of the xml-rs API as of v0.3.5,
     // but it's possible this can will change in the future.
     match reader.next()? {
         StartDocument { .. } => {},
-        _ => panic!("First event from EventReader wasn't StartDocument"),
+        _ => return Err(missing_root(reader)),
     }
 
     // The next element will always be the `<COLLADA>` tag. This will specify what version of
     // the COLLADA spec is being used, which is how we'll determine our sub-parser.
-    let element_start = match reader.next()? {
-        StartElement { name, attributes, namespace: _ } => {
-            // If the element isn't the `<COLLADA>` tag then the document is malformed,
-            // return an error.
-            if name.local_name != "COLLADA" {
-                return Err(Error {
-                    position: reader.position(),
-                    kind: ErrorKind::UnexpectedRootElement {
-                        element: name.local_name,
-                    }
-                })
-            }
+    loop {
+        match reader.next()? {
+            StartElement { name, attributes, namespace } => {
+                // If the element isn't the `<COLLADA>` tag then the document is malformed,
+                // return an error. Like every other element it's matched by its local name, so a
+                // document that binds the COLLADA schema to a prefix (e.g. `<c:COLLADA>`) is fine.
+                if name.local_name != "COLLADA" {
+                    return Err(Error {
+                        position: reader.position(),
+                        kind: ErrorKind::UnexpectedRootElement {
+                            element: name.local_name,
+                        }
+                    })
+                }
 
-            ElementStart { name, attributes }
-        }
+                return Ok(ElementStart { name, attributes, namespace });
+            }
 
-        // I'm *almost* 100% certain that the only event that can follow the `StartDocument`
-        // event is a `StartElement` event. As of v0.3.5, xml-rs doesn't support
-        // `<!DOCTYPE>` or processing instructions, and it ignores whitespace and comments
-        // (according to how we configure the parser), and those are the only things allowed
-        // between `StartDocument` and the first `StartElement`. If xml-rs changes its
-        // behavior this will need to be updated.
-        event @ _ => { panic!("Unexpected event: {:?}", event); }
-    };
+            // Processing instructions are allowed before the root element, and whitespace is
+            // reported if the parser was configured not to trim it. Neither has any meaning for
+            // us, so skip them.
+            ProcessingInstruction { .. } | Whitespace(_) => {}
+            Characters(ref data) if data.trim().is_empty() => {}
 
-    Ok(element_start)
+            _ => return Err(missing_root(reader)),
+        }
+    }
 }
 
 pub fn start_element<R: Read>(
-    reader: &mut EventReader<R>,
+    reader: &mut ColladaReader<R>,
     parent: &'static str,
 ) -> Result<Option<ElementStart>> {
     match reader.next()? {
-        StartElement { name, attributes, namespace: _ } => {
-            return Ok(Some(ElementStart { name, attributes }));
+        StartElement { name, attributes, namespace } => {
+            return Ok(Some(ElementStart { name, attributes, namespace }));
         }
 
         EndElement { name } => {
@@ -235,7 +697,7 @@ pub fn start_element<R: Read>(
 }
 
 pub fn required_text_contents<R, T>(
-    reader: &mut EventReader<R>,
+    reader: &mut ColladaReader<R>,
     parent: &'static str,
 ) -> Result<T>
     where
@@ -245,10 +707,10 @@ pub fn required_text_contents<R, T>(
 {
     match reader.next()? {
         Characters(data) => {
-            let result = T::from_str(&*data)
-                .map_err(|error| Error {
+            let result = parse_value(parent, &data)
+                .map_err(|kind| Error {
                     position: reader.position(),
-                    kind: error.into(),
+                    kind,
                 })?;
             end_element(reader, parent)?;
             return Ok(result);
@@ -281,7 +743,7 @@ pub fn required_text_contents<R, T>(
 }
 
 pub fn optional_text_contents<R, T>(
-    reader: &mut EventReader<R>,
+    reader: &mut ColladaReader<R>,
     parent: &'static str,
 ) -> Result<Option<T>>
     where
@@ -291,10 +753,10 @@ pub fn optional_text_contents<R, T>(
 {
     match reader.next()? {
         Characters(data) => {
-            let result = T::from_str(&*data)
-                .map_err(|error| Error {
+            let result = parse_value(parent, &data)
+                .map_err(|kind| Error {
                     position: reader.position(),
-                    kind: error.into(),
+                    kind,
                 })?;
             end_element(reader, parent)?;
             return Ok(Some(result));
@@ -321,7 +783,7 @@ pub fn optional_text_contents<R, T>(
     }
 }
 
-pub fn end_element<R: Read>(reader: &mut EventReader<R>, parent: &'static str) -> Result<()> {
+pub fn end_element<R: Read>(reader: &mut ColladaReader<R>, parent: &'static str) -> Result<()> {
     match reader.next()? {
         EndElement { .. } => {
             return Ok(());
@@ -355,7 +817,7 @@ pub fn end_element<R: Read>(reader: &mut EventReader<R>, parent: &'static str) -
 }
 
 /// Meaning, of course, "verify that there are no attributes".
-pub fn verify_attributes<R: Read>(reader: &EventReader<R>, name: &'static str, attributes: Vec<OwnedAttribute>) -> Result<()> {
+pub fn verify_attributes<R: Read>(reader: &ColladaReader<R>, name: &'static str, attributes: Vec<OwnedAttribute>) -> Result<()> {
     // Make sure the child element has no attributes.
     if attributes.len() != 0 {
         return Err(Error {
@@ -373,16 +835,16 @@ pub fn verify_attributes<R: Read>(reader: &EventReader<R>, name: &'static str, a
 
 // TODO: This is a temporary helper to allow us to ignore COLLADA elements that we don't care
 // about parsing yet. This should be removed once we've implemented the full COLLADA spec.
-pub fn stub_out<R>(reader: &mut EventReader<R>, stubbed_name: &str) -> Result<()> where R: Read {
+pub fn stub_out<R>(reader: &mut ColladaReader<R>) -> Result<()> where R: Read {
+    // The start tag of the stubbed element has already been consumed, so we start one level deep
+    // and count every element regardless of its name until its matching end tag is found.
     let mut depth = 1;
     loop {
         match reader.next()? {
-            StartElement { name, attributes: _, namespace: _ } => {
-                if name.local_name == stubbed_name { depth += 1; }
-            }
+            StartElement { .. } => { depth += 1; }
 
-            EndElement { name } => {
-                if name.local_name == stubbed_name { depth -= 1; }
+            EndElement { .. } => {
+                depth -= 1;
                 if depth == 0 { break; }
             }
 
@@ -393,6 +855,127 @@ pub fn stub_out<R>(reader: &mut EventReader<R>, stubbed_name: &str) -> Result<()
     Ok(())
 }
 
+/// Formats a list of values as the text contents of an element, separated by spaces.
+///
+/// This is the inverse of `parse_text_list`.
+pub fn text_list<T: Display>(values: &[T]) -> String {
+    values.iter()
+        .map(ToString::to_string)
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+/// Finds every `id` attribute in the XML read from `source` that's used by more than one element.
+///
+/// Each duplicated id is returned along with the positions of all the elements that use it, in
+/// the order the ids first appear in the document. Scanning stops at the first malformed event.
+pub fn duplicate_ids<R: Read>(source: R) -> Vec<(String, Vec<TextPosition>)> {
+    let mut ids: Vec<(String, Vec<TextPosition>)> = Vec::new();
+    let mut indices = HashMap::new();
+
+    let mut reader = event_reader(source);
+    loop {
+        match reader.next() {
+            Ok(StartElement { attributes, .. }) => {
+                let position = reader.position();
+                for attribute in attributes {
+                    if attribute.name.local_name != "id" || attribute.name.prefix.is_some() {
+                        continue;
+                    }
+
+                    let index = *indices.entry(attribute.value.clone()).or_insert(ids.len());
+                    if index == ids.len() {
+                        ids.push((attribute.value, Vec::new()));
+                    }
+                    ids[index].1.push(position);
+                }
+            }
+
+            Ok(EndDocument) | Err(_) => { break; }
+
+            Ok(_) => {}
+        }
+    }
+
+    ids.retain(|(_, positions)| positions.len() > 1);
+    ids
+}
+
+/// Finds every `id` attribute in the XML read from `source`.
+///
+/// Each id is returned once, in the order it first appears in the document. Scanning stops at the
+/// first malformed event.
+pub fn element_ids<R: Read>(source: R) -> Vec<String> {
+    let mut ids = Vec::new();
+    let mut seen = HashSet::new();
+
+    let mut reader = event_reader(source);
+    loop {
+        match reader.next() {
+            Ok(StartElement { attributes, .. }) => {
+                for attribute in attributes {
+                    if attribute.name.local_name == "id"
+                        && attribute.name.prefix.is_none()
+                        && seen.insert(attribute.value.clone())
+                    {
+                        ids.push(attribute.value);
+                    }
+                }
+            }
+
+            Ok(EndDocument) | Err(_) => { break; }
+
+            Ok(_) => {}
+        }
+    }
+
+    ids
+}
+
+/// Rewrites the XML read from `source` so that every id starts with `prefix`.
+///
+/// Every `id` attribute is renamed, as is every attribute holding a URI fragment that refers to an
+/// element in the same document (i.e. any value starting with `#`). Other references, such as
+/// SID paths and URIs pointing into other documents, are left unchanged.
+///
+/// Nesting depth isn't limited, so this must only be used on XML that was written by the
+/// library itself.
+pub fn prefix_ids<R: Read>(source: R, prefix: &str) -> Result<String> {
+    let mut events = Vec::new();
+
+    let mut reader = event_reader(source);
+    loop {
+        match reader.next()? {
+            StartDocument { .. } => {}
+
+            EndDocument => { break; }
+
+            StartElement { name, mut attributes, namespace } => {
+                for attribute in &mut attributes {
+                    if attribute.name.prefix.is_some() {
+                        continue;
+                    }
+
+                    if attribute.name.local_name == "id" {
+                        attribute.value = format!("{}{}", prefix, attribute.value);
+                    } else if attribute.value.starts_with('#') {
+                        attribute.value = format!("#{}{}", prefix, &attribute.value[1..]);
+                    }
+                }
+
+                events.push(StartElement { name, attributes, namespace });
+            }
+
+            event => { events.push(event); }
+        }
+    }
+
+    write_events(&events).map_err(|error| Error {
+        position: reader.position(),
+        kind: ErrorKind::Io(io::Error::new(io::ErrorKind::InvalidData, error.to_string())),
+    })
+}
+
 /// Helper struct for pretty-printing lists of strings.
 pub struct StringListDisplay<'a>(pub &'a [&'a str]);
 
@@ -4,16 +4,211 @@
 //! documents are still accurately represented by the types in this module. Users of COLLABORATE
 //! don't need to distinguish between `1.4.0` and `1.4.1` documents.
 
-use {Error, ErrorKind, Result};
+use {Error, ErrorKind, MergeError, ParseOptions, Result, Warning};
 use common::*;
-use std::io::Read;
+use std::collections::{HashMap, HashSet};
+use std::fmt::{self, Display, Formatter};
+use std::io::{self, Read, Write};
+use std::path::Path;
+use std::sync::OnceLock;
 use utils;
 use utils::*;
-use xml::common::Position;
-use xml::reader::EventReader;
+use xml::common::{Position, TextPosition};
+use xml::namespace::Namespace;
+
+/// The XML namespace for COLLADA 1.4 documents.
+pub(crate) static NAMESPACE: &'static str = "http://www.collada.org/2005/11/COLLADASchema";
+
+/// Elements that were added in COLLADA 1.5, which some exporters mistakenly include in 1.4
+/// documents.
+pub(crate) static FROM_1_5: OtherVersionElements = OtherVersionElements {
+    document_version: "1.4",
+    version: "1.5",
+    elements: &[
+        ("asset", "coverage"),
+        ("asset", "extra"),
+        ("contributor", "author_email"),
+        ("contributor", "author_website"),
+    ],
+};
+
+/// Returns the names of the valid children of `element`, in the order they must appear.
+///
+/// This is the same list that's reported by [`ErrorKind::UnexpectedElement`] when a document
+/// contains an invalid child, and can be used to offer completions in a schema-aware editor. Each
+/// child is listed once even if it may repeat. Where a child can be one of several elements, such
+/// as the primitives in a `<mesh>`, all of them are listed in place.
+///
+/// Returns `None` if `element` isn't a known `1.4` element. Elements that the library doesn't
+/// parse yet also return `None`, as do names that are shared by several elements whose children
+/// depend on their parent (e.g. `<technique>`, `<technique_common>` and `<input>`).
+///
+/// # Examples
+///
+/// ```
+/// # use collaborate::v1_4;
+/// assert_eq!(
+///     Some(&["author", "authoring_tool", "comments", "copyright", "source_data"][..]),
+///     v1_4::expected_children("contributor"),
+/// );
+/// assert_eq!(None, v1_4::expected_children("technique_common"));
+/// ```
+///
+/// [`ErrorKind::UnexpectedElement`]: ../enum.ErrorKind.html#variant.UnexpectedElement
+pub fn expected_children(element: &str) -> Option<&'static [&'static str]> {
+    static CHILDREN: OnceLock<HashMap<&str, Vec<&'static str>>> = OnceLock::new();
+
+    let children = CHILDREN.get_or_init(|| {
+        EXPECTED_CHILDREN.iter()
+            .map(|&(name, add_children)| {
+                let mut names = Vec::new();
+                add_children(&mut names);
+                (name, names)
+            })
+            .collect()
+    });
+    children.get(element).map(Vec::as_slice)
+}
+
+/// Adds the names of an element's children, see `ColladaElement::add_children`.
+type AddChildren = fn(&mut Vec<&'static str>);
+
+/// The elements whose children are listed by [`expected_children`], along with the function that
+/// adds their children.
+///
+/// Names that are shared by several elements with different children, such as `<technique>`,
+/// `<technique_common>` and `<input>`, are left out.
+///
+/// [`expected_children`]: ./fn.expected_children.html
+static EXPECTED_CHILDREN: &[(&str, AddChildren)] = &[
+    ("accessor", Accessor::add_children),
+    ("ambient", Ambient::add_children),
+    ("animation_clip", AnimationClip::add_children),
+    ("aspect_ratio", AspectRatio::add_children),
+    ("asset", Asset::add_children),
+    ("bind", Bind::add_children),
+    ("bind_material", BindMaterial::add_children),
+    ("bind_vertex_input", BindVertexInput::add_children),
+    ("blinn", Blinn::add_children),
+    ("border_color", BorderColor::add_children),
+    ("box", BoxShape::add_children),
+    ("camera", Camera::add_children),
+    ("capsule", Capsule::add_children),
+    ("COLLADA", Collada::add_children),
+    ("color", Color::add_children),
+    ("constant", Constant::add_children),
+    ("contributor", Contributor::add_children),
+    ("cylinder", Cylinder::add_children),
+    ("data", ImageData::add_children),
+    ("density", Density::add_children),
+    ("diffuse", Diffuse::add_children),
+    ("dynamic", Dynamic::add_children),
+    ("dynamic_friction", DynamicFriction::add_children),
+    ("effect", Effect::add_children),
+    ("emission", Emission::add_children),
+    ("equation", Equation::add_children),
+    ("evaluate_scene", EvaluateScene::add_children),
+    ("extra", Extra::add_children),
+    ("float", FloatValue::add_children),
+    ("float2", Float2::add_children),
+    ("float3", Float3::add_children),
+    ("float4", Float4::add_children),
+    ("float4x4", Float4x4::add_children),
+    ("float_array", FloatArray::add_children),
+    ("force_field", ForceField::add_children),
+    ("geometry", Geometry::add_children),
+    ("gravity", Gravity::add_children),
+    ("half_extents", HalfExtents::add_children),
+    ("height", Height::add_children),
+    ("hollow", Hollow::add_children),
+    ("image", Image::add_children),
+    ("index_of_refraction", IndexOfRefraction::add_children),
+    ("inertia", Inertia::add_children),
+    ("instance_animation", InstanceAnimation::add_children),
+    ("instance_camera", InstanceCamera::add_children),
+    ("instance_effect", InstanceEffect::add_children),
+    ("instance_force_field", InstanceForceField::add_children),
+    ("instance_geometry", InstanceGeometry::add_children),
+    ("instance_light", InstanceLight::add_children),
+    ("instance_material", InstanceMaterial::add_children),
+    ("instance_node", InstanceNode::add_children),
+    ("instance_physics_material", InstancePhysicsMaterial::add_children),
+    ("instance_physics_model", InstancePhysicsModel::add_children),
+    ("instance_physics_scene", InstancePhysicsScene::add_children),
+    ("instance_visual_scene", InstanceVisualScene::add_children),
+    ("lambert", Lambert::add_children),
+    ("library_animation_clips", LibraryAnimationClips::add_children),
+    ("library_cameras", LibraryCameras::add_children),
+    ("library_effects", LibraryEffects::add_children),
+    ("library_force_fields", LibraryForceFields::add_children),
+    ("library_geometries", LibraryGeometries::add_children),
+    ("library_images", LibraryImages::add_children),
+    ("library_materials", LibraryMaterials::add_children),
+    ("library_nodes", LibraryNodes::add_children),
+    ("library_physics_materials", LibraryPhysicsMaterials::add_children),
+    ("library_physics_models", LibraryPhysicsModels::add_children),
+    ("library_physics_scenes", LibraryPhysicsScenes::add_children),
+    ("library_visual_scenes", LibraryVisualScenes::add_children),
+    ("lookat", LookAt::add_children),
+    ("mass", Mass::add_children),
+    ("mass_frame", MassFrame::add_children),
+    ("material", Material::add_children),
+    ("matrix", Matrix::add_children),
+    ("mesh", Mesh::add_children),
+    ("newparam", NewParam::add_children),
+    ("node", Node::add_children),
+    ("optics", Optics::add_children),
+    ("orthographic", Orthographic::add_children),
+    ("p", Primitives::add_children),
+    ("perspective", Perspective::add_children),
+    ("phong", Phong::add_children),
+    ("physics_material", PhysicsMaterial::add_children),
+    ("physics_model", PhysicsModel::add_children),
+    ("physics_scene", PhysicsScene::add_children),
+    ("plane", Plane::add_children),
+    ("polylist", Polylist::add_children),
+    ("profile_COMMON", ProfileCommon::add_children),
+    ("radius", Radius::add_children),
+    ("reflective", Reflective::add_children),
+    ("reflectivity", Reflectivity::add_children),
+    ("render", Render::add_children),
+    ("restitution", Restitution::add_children),
+    ("rigid_body", RigidBody::add_children),
+    ("rotate", Rotate::add_children),
+    ("sampler2D", Sampler2D::add_children),
+    ("scale", Scale::add_children),
+    ("scene", Scene::add_children),
+    ("setparam", SetParam::add_children),
+    ("shape", Shape::add_children),
+    ("shininess", Shininess::add_children),
+    ("skew", Skew::add_children),
+    ("source", Source::add_children),
+    ("specular", Specular::add_children),
+    ("sphere", Sphere::add_children),
+    ("static_friction", StaticFriction::add_children),
+    ("surface", Surface::add_children),
+    ("texture", Texture::add_children),
+    ("time_step", TimeStep::add_children),
+    ("translate", Translate::add_children),
+    ("transparency", Transparency::add_children),
+    ("transparent", Transparent::add_children),
+    ("triangles", Triangles::add_children),
+    ("unit", Unit::add_children),
+    ("up_axis", UpAxis::add_children),
+    ("vcount", VCount::add_children),
+    ("vertices", Vertices::add_children),
+    ("visual_scene", VisualScene::add_children),
+    ("xfov", Xfov::add_children),
+    ("xmag", Xmag::add_children),
+    ("yfov", Yfov::add_children),
+    ("ymag", Ymag::add_children),
+    ("zfar", Zfar::add_children),
+    ("znear", Znear::add_children),
+];
 
 /// Represents a complete COLLADA document.
 #[derive(Debug, Clone, PartialEq, ColladaElement)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 #[name = "COLLADA"]
 pub struct Collada {
     /// The version string for the COLLADA specification used by the document.
@@ -22,8 +217,12 @@ pub struct Collada {
     #[attribute]
     pub version: String,
 
-    /// Included for completeness in parsing, not actually used.
-    // TODO: Can we remove `xmlns`? Should we remove it?
+    /// The namespace of the root `<COLLADA>` element.
+    ///
+    /// Filled in from the parsed document, and `None` if the root element isn't in a namespace.
+    /// Used as the default namespace when writing the document. See [`schema_namespace`].
+    ///
+    /// [`schema_namespace`]: #method.schema_namespace
     #[attribute]
     pub xmlns: Option<String>,
 
@@ -87,8 +286,19 @@ impl Collada {
     /// COLLADA versions, 3rd party extensions, and any other details that could influence how
     /// a document is parsed see the [crate-level documentation](../index.html)
     pub fn from_str(source: &str) -> Result<Collada> {
-        let reader = EventReader::new_with_config(source.as_bytes(), utils::PARSER_CONFIG.clone());
-        Self::parse(reader)
+        Self::from_str_with_options(source, ParseOptions::default())
+    }
+
+    /// Read a COLLADA document from a string, using the specified options.
+    ///
+    /// See [`ParseOptions`] for the available options. Otherwise this behaves exactly like
+    /// [`from_str`](#method.from_str).
+    ///
+    /// [`ParseOptions`]: ../struct.ParseOptions.html
+    pub fn from_str_with_options(source: &str, options: ParseOptions) -> Result<Collada> {
+        let source = utils::strip_bom_str(source);
+        let mut reader = ColladaReader::new(utils::options_event_reader(source.as_bytes(), &options), options);
+        Self::parse_reader(&mut reader)
     }
 
     /// Attempts to parse the contents of a COLLADA document.
@@ -110,8 +320,319 @@ impl Collada {
     /// COLLADA versions, 3rd party extensions, and any other details that could influence how
     /// a document is parsed see the [crate-level documentation](../index.html).
     pub fn read<R: Read>(reader: R) -> Result<Collada> {
-        let reader = EventReader::new_with_config(reader, utils::PARSER_CONFIG.clone());
-        Self::parse(reader)
+        Self::read_with_options(reader, ParseOptions::default())
+    }
+
+    /// Attempts to parse the contents of a COLLADA document, using the specified options.
+    ///
+    /// See [`ParseOptions`] for the available options. Otherwise this behaves exactly like
+    /// [`read`](#method.read).
+    ///
+    /// [`ParseOptions`]: ../struct.ParseOptions.html
+    pub fn read_with_options<R: Read>(reader: R, options: ParseOptions) -> Result<Collada> {
+        Self::read_with_warnings(reader, options).map(|(collada, _)| collada)
+    }
+
+    /// Attempts to parse the contents of a COLLADA document, also returning any warnings.
+    ///
+    /// Warnings are only produced when [`ParseOptions::lenient`] is set. Otherwise this behaves
+    /// exactly like [`read_with_options`](#method.read_with_options).
+    ///
+    /// [`ParseOptions::lenient`]: ../struct.ParseOptions.html#structfield.lenient
+    pub fn read_with_warnings<R: Read>(reader: R, options: ParseOptions) -> Result<(Collada, Vec<Warning>)> {
+        let reader = utils::strip_bom(reader)?;
+        let mut reader = ColladaReader::new(utils::options_event_reader(reader, &options), options);
+        let collada = Self::parse_reader(&mut reader)?;
+        Ok((collada, reader.warnings))
+    }
+
+    /// Read a COLLADA document from a byte slice.
+    ///
+    /// This is a shorthand for [`read`](#method.read), for when the document has already been
+    /// loaded into memory.
+    pub fn from_bytes(bytes: &[u8]) -> Result<Collada> {
+        Self::read(bytes)
+    }
+
+    /// Read a COLLADA document from the file at `path`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # #![allow(unused_variables)]
+    /// use collaborate::v1_4::Collada;
+    ///
+    /// let document = Collada::from_path("resources/blender_cube.dae").unwrap();
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Returns an [`ErrorKind::Io`] error with the position `0:0` if the file can't be opened.
+    /// Otherwise, returns `Err` under the same conditions as [`read`](#method.read).
+    ///
+    /// [`ErrorKind::Io`]: ../enum.ErrorKind.html#variant.Io
+    pub fn from_path<P: AsRef<Path>>(path: P) -> Result<Collada> {
+        Self::read(utils::open_file(path)?)
+    }
+
+    /// Creates a builder for a new document.
+    ///
+    /// The builder starts out with a minimal valid document: version `1.4.1`, and an `asset`
+    /// whose `created` and `modified` timestamps are set to the current time.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # #![allow(unused_variables)]
+    /// use collaborate::v1_4::Collada;
+    ///
+    /// let collada = Collada::builder().build();
+    /// let mut output = Vec::new();
+    /// collada.write(&mut output).unwrap();
+    /// ```
+    pub fn builder() -> ColladaBuilder {
+        ColladaBuilder::new()
+    }
+
+    /// Writes the document as XML to `writer`.
+    ///
+    /// The document is written with an XML declaration and declares the COLLADA 1.4 schema as
+    /// its default namespace, unless `xmlns` specifies a different one. Reading the output back
+    /// in with [`read`](#method.read) produces a document equal to `self`.
+    ///
+    /// Elements that the library doesn't parse yet (e.g. `<library_cameras>`) are written without
+    /// any of their original contents.
+    ///
+    /// # Errors
+    ///
+    /// Returns `Err` if writing to `writer` fails.
+    pub fn write<W: Write>(&self, writer: W) -> io::Result<()> {
+        let namespace = self.xmlns.clone().unwrap_or_else(|| NAMESPACE.into());
+        let mut writer = ColladaWriter::new(writer, namespace);
+        self.write_element(&mut writer)
+    }
+
+    /// Checks that every `id` attribute in the document is unique.
+    ///
+    /// COLLADA requires ids to be unique within a document, but this isn't checked while parsing.
+    /// Duplicate ids make resolving [`UriFragment`] references ambiguous, so this can be used to
+    /// validate a document up front.
+    ///
+    /// # Errors
+    ///
+    /// Returns every id that's used more than once, along with the positions of all the elements
+    /// using it.
+    ///
+    /// **The positions do not refer to the source the document was parsed from.** Positions
+    /// aren't retained after parsing, so the check is performed against the document as written
+    /// by [`write`](#method.write), and the positions refer to that output. Elements that the
+    /// library doesn't parse yet are written without their contents, so ids within them aren't
+    /// checked. Use [`check_source_unique_ids`](#method.check_source_unique_ids) on the original
+    /// source to get positions within it and to check every element.
+    ///
+    /// [`UriFragment`]: ../common/struct.UriFragment.html
+    pub fn check_unique_ids(&self) -> ::std::result::Result<(), Vec<(String, Vec<TextPosition>)>> {
+        let mut output = Vec::new();
+        self.write(&mut output).expect("Writing to a `Vec` should never fail");
+        Collada::check_source_unique_ids(&*output)
+    }
+
+    /// Checks that every `id` attribute in the XML read from `source` is unique.
+    ///
+    /// Unlike [`check_unique_ids`](#method.check_unique_ids), this scans the XML directly, so ids
+    /// within elements that the library doesn't parse are checked too, and the reported positions
+    /// refer to `source`. The XML isn't otherwise validated, and scanning stops at the first
+    /// malformed event.
+    ///
+    /// # Errors
+    ///
+    /// Returns every id that's used more than once, along with the positions of all the elements
+    /// using it.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use collaborate::v1_4::Collada;
+    /// # use std::fs::File;
+    /// let file = File::open("resources/blender_cube.dae").unwrap();
+    /// assert_eq!(Ok(()), Collada::check_source_unique_ids(file));
+    /// ```
+    pub fn check_source_unique_ids<R: Read>(
+        source: R,
+    ) -> ::std::result::Result<(), Vec<(String, Vec<TextPosition>)>> {
+        let duplicates = utils::duplicate_ids(source);
+        if duplicates.is_empty() {
+            Ok(())
+        } else {
+            Err(duplicates)
+        }
+    }
+
+    /// Moves every library in `other` into the document.
+    ///
+    /// The libraries are added after the document's existing libraries, in the order they appear
+    /// in `other`. The rest of `other`, including its asset information and `<scene>`, is
+    /// discarded.
+    ///
+    /// Ids are checked in the same way as [`check_unique_ids`](#method.check_unique_ids), so ids
+    /// within elements that the library doesn't parse yet aren't checked.
+    ///
+    /// # Errors
+    ///
+    /// Returns every id that's used by both documents, in the order they appear in `other`. The
+    /// document isn't modified if any ids conflict. Use
+    /// [`merge_with_prefix`](#method.merge_with_prefix) to rename the ids in `other` first.
+    pub fn merge(&mut self, other: Collada) -> ::std::result::Result<(), Vec<String>> {
+        let existing = self.ids().into_iter().collect::<::std::collections::HashSet<_>>();
+        let conflicts = other.ids()
+            .into_iter()
+            .filter(|id| existing.contains(id))
+            .collect::<Vec<_>>();
+        if !conflicts.is_empty() {
+            return Err(conflicts);
+        }
+
+        self.libraries.extend(other.libraries);
+        Ok(())
+    }
+
+    /// Moves every library in `other` into the document, adding `prefix` to each of its ids.
+    ///
+    /// Every `id` in `other` is renamed to start with `prefix`, and every reference within `other`
+    /// to one of its own elements (e.g. a [`UriFragment`], or the `url` of an
+    /// `<instance_geometry>`) is updated to match. References to other documents are left
+    /// unchanged. The renamed libraries are then added as with [`merge`](#method.merge).
+    ///
+    /// The ids are rewritten by writing `other` out with [`write`](#method.write) and reading it
+    /// back, so the contents of elements that the library doesn't parse yet are discarded. SID
+    /// paths, such as the `target` of an `<channel>`, aren't renamed.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`MergeError::Conflicts`] with every id that's still used by both documents after
+    /// renaming, as with [`merge`](#method.merge). Returns [`MergeError::Rewrite`] if `other`
+    /// couldn't be written out or read back in. The document isn't modified in either case.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use collaborate::v1_4::Collada;
+    /// let mut document = Collada::from_path("resources/blender_cube.dae").unwrap();
+    /// let other = document.clone();
+    /// assert!(document.merge(other.clone()).is_err());
+    ///
+    /// document.merge_with_prefix(other, "copy-").unwrap();
+    /// assert!(document.geometry_by_id("copy-Cube-mesh").is_some());
+    /// ```
+    ///
+    /// [`UriFragment`]: ../common/struct.UriFragment.html
+    /// [`MergeError::Conflicts`]: ../enum.MergeError.html#variant.Conflicts
+    /// [`MergeError::Rewrite`]: ../enum.MergeError.html#variant.Rewrite
+    pub fn merge_with_prefix(&mut self, other: Collada, prefix: &str) -> ::std::result::Result<(), MergeError> {
+        let mut output = Vec::new();
+        other.write(&mut output).map_err(|error| MergeError::Rewrite(Error {
+            position: TextPosition::new(),
+            kind: ErrorKind::Io(error),
+        }))?;
+
+        let renamed = utils::prefix_ids(&*output, prefix).map_err(MergeError::Rewrite)?;
+
+        // `other` may already be nested deeper than the default limit allows, and it was written
+        // by the library, so there's no need to guard against a malicious document.
+        let options = ParseOptions {
+            max_depth: usize::MAX,
+            ..ParseOptions::default()
+        };
+        let renamed = Collada::from_str_with_options(&renamed, options).map_err(MergeError::Rewrite)?;
+        self.merge(renamed).map_err(MergeError::Conflicts)
+    }
+
+    /// Returns every id in the document, in the order they first appear.
+    fn ids(&self) -> Vec<String> {
+        let mut output = Vec::new();
+        self.write(&mut output).expect("Writing to a `Vec` should never fail");
+        utils::element_ids(&*output)
+    }
+
+    /// Removes all vendor-specific data from the document.
+    ///
+    /// Every `<extra>` element is dropped, as is every `<technique>` that the element containing
+    /// it doesn't require (e.g. the profile-specific techniques of `<optics>`). Elements that
+    /// require at least one technique, such as `<force_field>`, keep theirs so that the document
+    /// remains valid.
+    pub fn strip_extras(&mut self) {
+        ColladaElement::strip_extras(self);
+    }
+
+    /// Compares two documents while ignoring details that don't affect their meaning.
+    ///
+    /// This complements the derived `PartialEq`, which compares every field. The `xmlns`
+    /// attribute and the contents of every `<technique>` in an `<extra>` are ignored, as is any
+    /// difference in how the documents were originally formatted.
+    ///
+    /// Both documents are normalized by writing them with [`write`](#method.write) and reading
+    /// the output back with [`ParseOptions::skip_extra_data`] set, so elements that the library
+    /// doesn't parse yet are also ignored.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use collaborate::v1_4::Collada;
+    /// let document = Collada::from_path("resources/blender_cube.dae").unwrap();
+    /// let mut other = document.clone();
+    /// other.xmlns = Some("http://example.com/schema".into());
+    ///
+    /// assert!(document != other);
+    /// assert!(document.semantic_eq(&other));
+    /// ```
+    ///
+    /// [`ParseOptions::skip_extra_data`]: ../struct.ParseOptions.html#structfield.skip_extra_data
+    pub fn semantic_eq(&self, other: &Collada) -> bool {
+        match (self.normalized(), other.normalized()) {
+            (Some(left), Some(right)) => left == right,
+            _ => false,
+        }
+    }
+
+    /// Round-trips the document through the writer, discarding non-semantic data.
+    fn normalized(&self) -> Option<Collada> {
+        let mut output = Vec::new();
+        self.write_element(&mut ColladaWriter::new(&mut output, NAMESPACE.into()))
+            .expect("Writing to a `Vec` should never fail");
+
+        let options = ParseOptions {
+            skip_extra_data: true,
+            ..ParseOptions::default()
+        };
+        let mut document = Collada::read_with_options(&*output, options).ok()?;
+        document.xmlns = None;
+        Some(document)
+    }
+
+    /// Adds `library` to the end of the document's list of libraries.
+    pub fn push_library(&mut self, library: Library) {
+        self.libraries.push(library);
+    }
+
+    /// Adds `geometry` to the document.
+    ///
+    /// The geometry is added to the first `<library_geometries>` in the document. If the document
+    /// doesn't have one yet, a new library is added to hold the geometry.
+    pub fn push_geometry(&mut self, geometry: Geometry) {
+        for library in &mut self.libraries {
+            if let Library::Geometries(ref mut library) = *library {
+                library.geometries.push(geometry);
+                return;
+            }
+        }
+
+        self.libraries.push(Library::Geometries(LibraryGeometries {
+            id: None,
+            name: None,
+            asset: None,
+            geometries: vec![geometry],
+            extras: Vec::new(),
+        }));
     }
 
     /// Returns an iterator over all the libraries in the document.
@@ -133,505 +654,5315 @@ impl Collada {
         self.libraries.iter()
     }
 
-    /// Helper method that handles the bulk of the parsing work.
+    /// Returns an iterator over the libraries of a single type.
     ///
-    /// `from_str` and `read` just create the `EventReader<R>` instance and then defer to `parse`.
-    fn parse<R: Read>(mut reader: EventReader<R>) -> Result<Collada> {
-        // Get the opening `<COLLADA>` tag and find the "version" attribute.
-        let element_start = utils::get_document_start(&mut reader)?;
-        let version = element_start.attributes.iter()
-            .find(|attrib| attrib.name.local_name == "version")
-            .map(|attrib| attrib.value.clone())
-            .ok_or(Error {
-                position: reader.position(),
-                kind: ErrorKind::MissingAttribute {
-                    element: "COLLADA",
-                    attribute: "version",
-                },
-            })?;
-
-        if version != "1.4.0" && version != "1.4.1" {
-            return Err(Error {
-                position: reader.position(),
-                kind: ErrorKind::UnsupportedVersion {
-                    version: version,
-                },
-            });
-        }
-
-        Collada::parse_element(&mut reader, element_start)
+    /// `select` picks out the libraries to return, and is usually one of the `as_library_*`
+    /// methods on [`Library`]. This avoids having to match on each library, or to know where in
+    /// the document a library appears.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use collaborate::v1_4::{Collada, Library};
+    /// let collada = Collada::from_path("resources/blender_cube.dae").unwrap();
+    /// for library in collada.libraries_of(Library::as_library_geometries) {
+    ///     for geometry in &library.geometries {
+    ///         println!("Geometry: {:?}", geometry.id);
+    ///     }
+    /// }
+    /// ```
+    ///
+    /// [`Library`]: ./enum.Library.html
+    pub fn libraries_of<'a, T, F>(&'a self, select: F) -> impl Iterator<Item = &'a T>
+    where
+        T: 'a,
+        F: FnMut(&'a Library) -> Option<&'a T>,
+    {
+        self.libraries.iter().filter_map(select)
     }
-}
 
-/// Describes a stream of values from an array data source.
-///
-/// An accessor declares an access pattern into an array of source data. The arrays can be
-/// arranged in either an interleaved or noninterleaved manner, depending on the `offset` and
-/// `stride` values.
-#[derive(Debug, Clone, PartialEq, ColladaElement)]
-#[name = "accessor"]
-pub struct Accessor {
-    /// The number of times the array is accessed.
-    #[attribute]
-    pub count: usize,
+    /// Returns the authoring tool of each contributor to the document that specifies one.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use collaborate::v1_4::Collada;
+    /// let collada = Collada::from_path("resources/blender_cube.dae").unwrap();
+    /// for tool in collada.authoring_tools() {
+    ///     println!("Made with {}", tool);
+    /// }
+    /// ```
+    pub fn authoring_tools(&self) -> Vec<&str> {
+        self.asset.contributors.iter()
+            .filter_map(|contributor| contributor.authoring_tool.as_deref())
+            .collect()
+    }
 
-    /// The index of the first value to be read from the array.
-    #[attribute]
-    #[optional_with_default = "0"]
-    pub offset: usize,
+    /// Returns when the document was created.
+    ///
+    /// This is a shorthand for `asset.created`.
+    pub fn created(&self) -> DateTime {
+        self.asset.created
+    }
 
-    /// The location of the array to access.
+    /// Returns when the document was last modified.
     ///
-    /// This may refer to a COLLADA array element or to an array data source outside the scope
-    /// of the instance document; The source does not need to be a COLLADA document.
-    #[attribute]
-    pub source: AnyUri,
+    /// This is a shorthand for `asset.modified`.
+    pub fn modified(&self) -> DateTime {
+        self.asset.modified
+    }
 
-    /// The number of values that are to be considered a unit during each access to the array.
-    #[attribute]
-    #[optional_with_default = "1"]
-    pub stride: usize,
+    /// Returns the namespace declared for the root `<COLLADA>` element, if any.
+    ///
+    /// COLLADA 1.4 documents should use `"http://www.collada.org/2005/11/COLLADASchema"`, but
+    /// documents in other namespaces are still parsed. Parse with [`ParseOptions::lenient`] set
+    /// to get a warning when the namespace doesn't match.
+    ///
+    /// [`ParseOptions::lenient`]: ../struct.ParseOptions.html#structfield.lenient
+    pub fn schema_namespace(&self) -> Option<&str> {
+        self.xmlns.as_deref()
+    }
 
-    #[child]
-    pub params: Vec<Param>,
-}
+    /// Returns the title of the document, if it has one.
+    ///
+    /// This is a shorthand for `asset.title`.
+    pub fn title(&self) -> Option<&str> {
+        self.asset.title.as_deref()
+    }
 
-impl Accessor {
-    /// Access a source array using the accessor.
+    /// Returns the geometry with the ID `id`, searching every `<library_geometries>`.
     ///
-    /// Returns a sub-slice of `array` containing the
-    pub fn access<'a, 'b, T>(&'a self, array: &'b [T], index: usize) -> &'b [T] {
-        let start = self.offset + self.stride * index;
-        let end = start + self.stride;
-        &array[start..end]
+    /// # Examples
+    ///
+    /// ```
+    /// # use collaborate::v1_4::Collada;
+    /// let collada = Collada::from_path("resources/blender_cube.dae").unwrap();
+    /// let cube = collada.geometry_by_id("Cube-mesh").unwrap();
+    /// assert_eq!(Some("Cube"), cube.name.as_deref());
+    /// ```
+    pub fn geometry_by_id<'a>(&'a self, id: &str) -> Option<&'a Geometry> {
+        self.libraries.iter()
+            .filter_map(Library::as_library_geometries)
+            .flat_map(|library| library.geometries.iter())
+            .find(|geometry| geometry.id.as_deref() == Some(id))
     }
-}
 
-#[derive(Debug, Clone, PartialEq, ColladaElement)]
-pub enum Array {
-    Idref(IdrefArray),
-    Name(NameArray),
-    Bool(BoolArray),
-    Float(FloatArray),
-    Int(IntArray),
-}
+    /// Returns the material with the ID `id`, searching every `<library_materials>`.
+    pub fn material_by_id<'a>(&'a self, id: &str) -> Option<&'a Material> {
+        self.libraries.iter()
+            .filter_map(Library::as_library_materials)
+            .flat_map(|library| library.materials.iter())
+            .find(|material| material.id.as_deref() == Some(id))
+    }
 
-impl Array {
-    pub fn as_float_array(&self) -> Option<&FloatArray> {
-        match *self {
-            Array::Float(ref float_array) => Some(float_array),
-            _ => None,
-        }
+    /// Returns the effect with the ID `id`, searching every `<library_effects>`.
+    pub fn effect_by_id<'a>(&'a self, id: &str) -> Option<&'a Effect> {
+        self.libraries.iter()
+            .filter_map(Library::as_library_effects)
+            .flat_map(|library| library.effects.iter())
+            .find(|effect| effect.id == id)
     }
-}
 
-#[derive(Debug, Clone, PartialEq, ColladaElement)]
-#[name = "asset"]
-pub struct Asset {
-    #[child]
-    pub contributors: Vec<Contributor>,
+    /// Returns the image with the ID `id`, searching every `<library_images>`.
+    ///
+    /// Images declared within an effect aren't searched.
+    pub fn image_by_id<'a>(&'a self, id: &str) -> Option<&'a Image> {
+        self.libraries.iter()
+            .filter_map(Library::as_library_images)
+            .flat_map(|library| library.images.iter())
+            .find(|image| image.id.as_deref() == Some(id))
+    }
 
-    #[child]
-    pub created: DateTime,
+    /// Returns the node with the ID `id`.
+    ///
+    /// Nodes can be nested within other nodes, so every node hierarchy is searched. The node
+    /// libraries are searched first, followed by the visual scenes.
+    pub fn node_by_id<'a>(&'a self, id: &str) -> Option<&'a Node> {
+        let library_nodes = self.libraries.iter()
+            .filter_map(Library::as_library_nodes)
+            .flat_map(|library| library.nodes.iter());
+        let scene_nodes = self.libraries.iter()
+            .filter_map(Library::as_library_visual_scenes)
+            .flat_map(|library| library.visual_scenes.iter())
+            .flat_map(|scene| scene.nodes.iter());
+
+        library_nodes.chain(scene_nodes)
+            .filter_map(|node| node.find(id))
+            .next()
+    }
 
-    #[child]
-    pub keywords: Option<String>,
+    /// Returns the node instantiated by `instance`.
+    ///
+    /// Instantiated nodes are usually declared in a `<library_nodes>`, but an instance may also
+    /// refer to a node within a visual scene. The node libraries are searched first, followed by
+    /// the visual scenes. Returns `None` if no node in the document has the targeted ID, or if
+    /// the instance refers to a node in another document.
+    pub fn resolve_node<'a>(&'a self, instance: &InstanceNode) -> Option<&'a Node> {
+        instance.url.local_id().and_then(|id| self.node_by_id(id))
+    }
 
-    #[child]
-    pub modified: DateTime,
+    /// Returns the assets that apply to the element with the ID `id`, outermost first.
+    ///
+    /// Elements can carry their own `<asset>` that overrides the metadata of their ancestors. The
+    /// chain always starts with the document's asset, followed by the asset of each enclosing
+    /// library, visual scene, and node (if they have one), and ends with the element's own asset.
+    /// Top-level library items (geometries, materials, etc.) and nodes at any depth are searched.
+    /// Returns `None` if no such element in the document has the ID `id`.
+    ///
+    /// See [`effective_unit`] and [`effective_up_axis`] for resolving the values that are in
+    /// effect for the element.
+    ///
+    /// [`effective_unit`]: #method.effective_unit
+    /// [`effective_up_axis`]: #method.effective_up_axis
+    pub fn asset_chain<'a>(&'a self, id: &str) -> Option<Vec<&'a Asset>> {
+        let mut chain = vec![&self.asset];
+        for library in &self.libraries {
+            if library.asset_chain(id, &mut chain) {
+                return Some(chain);
+            }
+        }
 
-    #[child]
-    pub revision: Option<String>,
+        None
+    }
 
-    #[child]
-    pub subject: Option<String>,
+    /// Returns the distance unit in effect for the element with the ID `id`.
+    ///
+    /// The innermost asset in the element's [`asset_chain`] that declares a `<unit>` wins. If
+    /// none of them do, the spec's default of one meter is used. Returns `None` if no element in
+    /// the document has the ID `id`.
+    ///
+    /// [`asset_chain`]: #method.asset_chain
+    pub fn effective_unit(&self, id: &str) -> Option<Unit> {
+        self.asset_chain(id).map(|chain| {
+            chain.iter().rev()
+                .filter_map(|asset| asset.unit.clone())
+                .next()
+                .unwrap_or_default()
+        })
+    }
 
-    #[child]
-    pub title: Option<String>,
+    /// Returns the up axis in effect for the element with the ID `id`.
+    ///
+    /// Resolved the same way as [`effective_unit`], falling back to the spec's default of
+    /// `Y_UP`. Returns `None` if no element in the document has the ID `id`.
+    ///
+    /// [`effective_unit`]: #method.effective_unit
+    pub fn effective_up_axis(&self, id: &str) -> Option<UpAxis> {
+        self.asset_chain(id).map(|chain| {
+            chain.iter().rev()
+                .filter_map(|asset| asset.up_axis)
+                .next()
+                .unwrap_or_default()
+        })
+    }
 
-    #[child]
-    #[optional_with_default]
-    pub unit: Unit,
+    /// Returns the force field instantiated by `instance`.
+    ///
+    /// Returns `None` if no force field in the document has the targeted ID, or if the instance
+    /// refers to a force field in another document.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use collaborate::v1_4::{Collada, PhysicsScene};
+    /// # fn example(document: &Collada, physics_scene: &PhysicsScene) {
+    /// for instance in &physics_scene.instance_force_fields {
+    ///     if let Some(force_field) = document.resolve_force_field(instance) {
+    ///         for technique in &force_field.techniques {
+    ///             println!("Force field has a technique for {}", technique.profile);
+    ///         }
+    ///     }
+    /// }
+    /// # }
+    /// ```
+    pub fn resolve_force_field<'a>(&'a self, instance: &InstanceForceField) -> Option<&'a ForceField> {
+        let id = instance.url.local_id()?;
+        self.libraries.iter()
+            .filter_map(Library::as_library_force_fields)
+            .flat_map(|library| library.force_fields.iter())
+            .find(|force_field| force_field.id.as_deref() == Some(id))
+    }
 
-    #[child]
-    #[optional_with_default]
-    pub up_axis: UpAxis,
-}
+    /// Returns the physics scene instantiated by `instance`.
+    ///
+    /// Returns `None` if no physics scene in the document has the targeted ID, or if the instance
+    /// refers to a physics scene in another document.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use collaborate::v1_4::Collada;
+    /// # fn example(document: &Collada) {
+    /// if let Some(ref scene) = document.scene {
+    ///     for instance in &scene.instance_physics_scenes {
+    ///         let physics_scene = document.resolve_physics_scene(instance);
+    ///         println!("Simulating {:?}", physics_scene);
+    ///     }
+    /// }
+    /// # }
+    /// ```
+    pub fn resolve_physics_scene<'a>(&'a self, instance: &InstancePhysicsScene) -> Option<&'a PhysicsScene> {
+        let id = instance.url.local_id()?;
+        self.libraries.iter()
+            .filter_map(Library::as_library_physics_scenes)
+            .flat_map(|library| library.physics_scenes.iter())
+            .find(|scene| scene.id.as_deref() == Some(id))
+    }
 
-#[derive(Debug, Clone, PartialEq, ColladaElement)]
-#[name = "bool_array"]
-pub struct BoolArray;
+    /// Flattens the document's visual scene into a list of positioned instances.
+    ///
+    /// The visual scene instantiated by `scene` is walked depth-first, following any
+    /// `<instance_node>` elements, and every geometry, camera, and light instance is resolved to
+    /// the element it targets. Each instance is paired with the world transform of the node that
+    /// contains it. A document without a visual scene produces an empty scene graph.
+    ///
+    /// Returns an error if an instance targets an element that isn't in the document, or if a
+    /// node instantiates itself. Instances of elements in other documents aren't an error, and are
+    /// listed in the scene graph's `external` instead.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use collaborate::v1_4::{Collada, SceneObject};
+    /// # let document = Collada::from_path("resources/blender_cube.dae").unwrap();
+    /// let scene_graph = document.scene_graph().unwrap();
+    /// for instance in &scene_graph.instances {
+    ///     if let SceneObject::Geometry(geometry) = instance.object {
+    ///         println!("{:?} is at {:?}", geometry.id, &instance.transform[12..15]);
+    ///     }
+    /// }
+    /// ```
+    pub fn scene_graph<'a>(&'a self) -> ::std::result::Result<SceneGraph<'a>, ResolveError> {
+        fn visit<'a>(
+            document: &'a Collada,
+            node: &'a Node,
+            path: &[usize],
+            instanced: bool,
+            parent: &[f64; 16],
+            ancestors: &mut Vec<&'a Node>,
+            graph: &mut SceneGraph<'a>,
+        ) -> ::std::result::Result<(), ResolveError> {
+            let world = ::math::multiply(parent, &node.local_transform());
+            let SceneGraph { ref mut instances, ref mut external } = *graph;
+            let mut push = |object| instances.push(SceneInstance {
+                path: NodePath(path.into()),
+                node,
+                transform: world,
+                object,
+            });
 
-#[derive(Debug, Clone, Default, PartialEq, Eq, ColladaElement)]
-#[name = "contributor"]
-pub struct Contributor {
-    #[child]
-    pub author: Option<String>,
+            for instance in &node.instance_geometries {
+                let id = match instance.url.local_id() {
+                    Some(id) => id,
+                    None => {
+                        external.push(&instance.url);
+                        continue;
+                    }
+                };
+                let geometry = document.geometry_by_id(id)
+                    .ok_or_else(|| ResolveError::MissingTarget { element: "geometry", id: id.into() })?;
+                push(SceneObject::Geometry(geometry));
+            }
 
-    #[child]
-    pub authoring_tool: Option<String>,
+            for instance in &node.instance_cameras {
+                let id = match instance.url.local_id() {
+                    Some(id) => id,
+                    None => {
+                        external.push(&instance.url);
+                        continue;
+                    }
+                };
+                let camera = document.libraries.iter()
+                    .filter_map(Library::as_library_cameras)
+                    .flat_map(|library| library.cameras.iter())
+                    .find(|camera| camera.id.as_deref() == Some(id))
+                    .ok_or_else(|| ResolveError::MissingTarget { element: "camera", id: id.into() })?;
+                push(SceneObject::Camera(camera));
+            }
 
-    #[child]
-    pub comments: Option<String>,
+            for instance in &node.instance_lights {
+                push(SceneObject::Light(instance));
+            }
 
-    #[child]
-    pub copyright: Option<String>,
+            ancestors.push(node);
+            for instance in &node.instance_nodes {
+                let id = match instance.url.local_id() {
+                    Some(id) => id,
+                    None => {
+                        graph.external.push(&instance.url);
+                        continue;
+                    }
+                };
+                let target = document.node_by_id(id)
+                    .ok_or_else(|| ResolveError::MissingTarget { element: "node", id: id.into() })?;
+                if ancestors.iter().any(|&ancestor| ::std::ptr::eq(ancestor, target)) {
+                    return Err(ResolveError::CyclicInstance { id: id.into() });
+                }
 
-    #[child]
-    pub source_data: Option<AnyUri>,
-}
+                visit(document, target, path, true, &world, ancestors, graph)?;
+            }
 
-#[derive(Debug, Clone, PartialEq, ColladaElement)]
-#[name = "convex_mesh"]
-pub struct ConvexMesh;
+            for (index, child) in node.children.iter().enumerate() {
+                // Instantiated nodes aren't part of the visual scene, so their contents are
+                // listed under the path of the node that instantiated them.
+                let mut child_path = path.to_vec();
+                if !instanced {
+                    child_path.push(index);
+                }
 
-/// Provides arbitrary additional information about an element.
-///
-/// COLLADA allows for applications to provide extra information about any given piece of data,
-/// including application-specific information that's not part of the COLLADA specification. This
-/// data can be any syntactically valid XML data, and is not parsed as part of this library, save
-/// for a few specific 3rd party applications that are directly supported.
-///
-/// # Choosing a Technique
-///
-/// There may be more than one [`Technique`][Technique] provided in `techniques`, but generally
-/// only one is used by the consuming application. The application should pick a technique
-/// with a supported profile. If there are multiple techniques with supported profiles the
-/// application is free to pick whichever technique is preferred.
-///
-/// [Technique]: struct.Technique.html
-#[derive(Debug, Clone, Default, PartialEq, ColladaElement)]
-#[name = "extra"]
-pub struct Extra {
-    /// The identifier of the element, if present. Will be unique within the document.
-    #[attribute]
-    pub id: Option<String>,
+                visit(document, child, &child_path, instanced, &world, ancestors, graph)?;
+            }
+            ancestors.pop();
 
-    /// The text string name of the element, if present.
-    #[attribute]
-    pub name: Option<String>,
+            Ok(())
+        }
 
-    /// A hint as to the type of information this element represents, if present. Must be
-    /// must be understood by the consuming application.
-    #[attribute]
-    #[name = "type"]
-    pub type_hint: Option<String>,
+        let mut graph = SceneGraph {
+            instances: Vec::new(),
+            external: Vec::new(),
+        };
+        let instance = match self.scene.as_ref().and_then(|scene| scene.instance_visual_scene.as_ref()) {
+            Some(instance) => instance,
+            None => return Ok(graph),
+        };
 
-    /// Asset-management information for this element, if present.
+        let id = match instance.url.local_id() {
+            Some(id) => id,
+            None => {
+                graph.external.push(&instance.url);
+                return Ok(graph);
+            }
+        };
+        let visual_scene = self.libraries.iter()
+            .filter_map(Library::as_library_visual_scenes)
+            .flat_map(|library| library.visual_scenes.iter())
+            .find(|scene| scene.id.as_deref() == Some(id))
+            .ok_or_else(|| ResolveError::MissingTarget { element: "visual_scene", id: id.into() })?;
+
+        for (index, node) in visual_scene.nodes.iter().enumerate() {
+            visit(self, node, &[index], false, &::math::identity(), &mut Vec::new(), &mut graph)?;
+        }
+
+        Ok(graph)
+    }
+
+    /// Returns the rotation that converts the document's coordinates to use `target` as the up axis.
     ///
-    /// While this is technically allowed in all `<extra>` elements, it is likely only present in
-    /// elements that describe a new "asset" of some kind, rather than in `<extra>` elements that
-    /// provide application-specific information about an existing one.
-    #[child]
-    pub asset: Option<Asset>,
+    /// This is a shorthand for calling [`UpAxis::conversion_matrix`] with the up axis declared in
+    /// the document's `asset`, or `Y_UP` if it doesn't declare one. The result is a column-major
+    /// 4x4 matrix.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # #![allow(unused_variables)]
+    /// # use std::fs::File;
+    /// # use collaborate::common::UpAxis;
+    /// # use collaborate::v1_4::Collada;
+    /// # let file = File::open("resources/blender_cube.dae").unwrap();
+    /// let collada = Collada::read(file).unwrap();
+    /// let to_y_up = collada.up_axis_to(UpAxis::Y);
+    /// ```
+    ///
+    /// [`UpAxis::conversion_matrix`]: ../common/enum.UpAxis.html#method.conversion_matrix
+    pub fn up_axis_to(&self, target: UpAxis) -> [f64; 16] {
+        UpAxis::conversion_matrix(self.asset.up_axis.unwrap_or_default(), target)
+    }
 
-    /// The arbitrary additional information, containing unprocessed XML events. There will always
-    /// be at least one item in `techniques`.
-    #[child]
-    #[required]
-    pub techniques: Vec<Technique>,
+    /// Helper method that handles the bulk of the parsing work.
+    ///
+    /// `from_str` and `read` just create the `ColladaReader<R>` instance and then defer to
+    /// `parse_reader`.
+    fn parse_reader<R: Read>(reader: &mut ColladaReader<R>) -> Result<Collada> {
+        // Get the opening `<COLLADA>` tag and find the "version" attribute.
+        let element_start = utils::get_document_start(reader)?;
+        let version = ::document_version(reader, &element_start)?;
+
+        if version != "1.4.0" && version != "1.4.1" {
+            return Err(Error {
+                position: reader.position(),
+                kind: ErrorKind::UnsupportedVersion {
+                    version: version,
+                },
+            });
+        }
+
+        let xmlns = ::document_namespace(reader, &element_start, NAMESPACE);
+        reader.other_version_elements = Some(&FROM_1_5);
+        let mut collada = Collada::parse_element(reader, element_start)?;
+        collada.xmlns = xmlns;
+        Ok(collada)
+    }
 }
 
+/// Describes a stream of values from an array data source.
+///
+/// An accessor declares an access pattern into an array of source data. The arrays can be
+/// arranged in either an interleaved or noninterleaved manner, depending on the `offset` and
+/// `stride` values.
 #[derive(Debug, Clone, PartialEq, ColladaElement)]
-#[name = "float_array"]
-pub struct FloatArray {
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[name = "accessor"]
+pub struct Accessor {
+    /// The number of times the array is accessed.
     #[attribute]
     pub count: usize,
 
+    /// The index of the first value to be read from the array.
     #[attribute]
-    pub id: Option<String>,
-
-    #[attribute]
-    pub name: Option<String>,
+    #[optional_with_default = "0"]
+    pub offset: usize,
 
+    /// The location of the array to access.
+    ///
+    /// This may refer to a COLLADA array element or to an array data source outside the scope
+    /// of the instance document; The source does not need to be a COLLADA document.
     #[attribute]
-    #[optional_with_default = "6"]
-    pub digits: usize,
+    pub source: AnyUri,
 
+    /// The number of values that are to be considered a unit during each access to the array.
     #[attribute]
-    #[optional_with_default = "38"]
-    pub magnitude: usize,
+    #[optional_with_default = "1"]
+    pub stride: usize,
 
-    #[text]
-    pub data: Vec<f32>,
+    #[child]
+    pub params: Vec<Param>,
 }
 
-/// A geometric element of unknown type.
-///
-/// Each variant wraps a single value containing a given type of geometric data. See the
-/// documentation for each of the possible geometric types for more information.
-#[derive(Debug, Clone, PartialEq, ColladaElement)]
-pub enum GeometricElement {
-    ConvexMesh(ConvexMesh),
-    Mesh(Mesh),
-    Spline(Spline),
-}
+impl Accessor {
+    /// Access a source array using the accessor.
+    ///
+    /// Returns a sub-slice of `array` containing the
+    pub fn access<'a, 'b, T>(&'a self, array: &'b [T], index: usize) -> &'b [T] {
+        let start = self.offset + self.stride * index;
+        let end = start + self.stride;
+        &array[start..end]
+    }
 
-impl GeometricElement {
-    /// Attempts to downcast the geometric element to a [`ConvexMesh`].
+    /// Returns an iterator over each of the accessor's `count` records in `array`.
     ///
-    /// Returns a reference to the inner [`ConvexMesh`] if there is one, returns `None` otherwise.
-    /// This is useful if you have a `GeometricElement` but only want to use it if it represents a
-    /// [`ConvexMesh`].
+    /// Records are read starting at `offset`, advancing by `stride` values for each record.
+    /// Unlike [`access`], this never panics: iteration stops early if `array` is too short to
+    /// contain all of the records.
     ///
     /// # Examples
     ///
     /// ```
-    /// # use ::collaborate::v1_4::*;
-    /// # static TEST_DOCUMENT: &'static [u8] = include_bytes!("../resources/blender_cube.dae");
-    /// # let source = String::from_utf8(TEST_DOCUMENT.into()).unwrap();
-    /// # let document = Collada::from_str(&*source).unwrap();
-    /// # let library_geometries = document.libraries[5].as_library_geometries().unwrap();
-    /// let geometry = &library_geometries.geometries[0];
-    /// if let Some(mesh) = geometry.geometric_element.as_convex_mesh() {
-    ///     // Do something with `mesh`.
+    /// # use collaborate::v1_4::{Collada, Library};
+    /// # let document = Collada::from_path("resources/blender_cube.dae").unwrap();
+    /// # let library = document.libraries_of(Library::as_library_geometries).next().unwrap();
+    /// # let mesh = library.geometries[0].mesh().unwrap();
+    /// let source = mesh.find_source("Cube-mesh-positions").unwrap();
+    /// let accessor = source.common_accessor().unwrap();
+    /// let array = source.array.as_ref().and_then(|array| array.as_float_array()).unwrap();
+    /// for position in accessor.iter(&array.data) {
+    ///     println!("Vertex at {:?}", position);
     /// }
     /// ```
     ///
-    /// [`ConvexMesh`]: ./struct.ConvexMesh.html
-    pub fn as_convex_mesh(&self) -> Option<&ConvexMesh> {
-        match *self {
-            GeometricElement::ConvexMesh(ref mesh) => Some(mesh),
-            _ => None,
+    /// [`access`]: #method.access
+    pub fn iter<'a, T>(&'a self, array: &'a [T]) -> AccessorIter<'a, T> {
+        AccessorIter {
+            accessor: self,
+            array,
+            index: 0,
         }
     }
 
-    /// Attempts to downcast the geometric element to a [`Mesh`].
+    /// Returns the position of the param named `name` within each record, or `None` if no param
+    /// has that name.
     ///
-    /// Returns a reference to the inner [`Mesh`] if there is one, returns `None` otherwise. This
-    /// is useful if you have a `GeometricElement` but only want to use it if it represents a
-    /// [`Mesh`].
+    /// Unnamed params are never matched, but still occupy a position in the record.
+    pub fn param_index(&self, name: &str) -> Option<usize> {
+        self.params.iter()
+            .position(|param| param.name.as_deref() == Some(name))
+    }
+
+    /// Returns the value of the param named `name` within `record`.
+    ///
+    /// `record` should be a record returned by [`access`] or [`iter`]. Returns `None` if no param
+    /// has the name `name`, or if `record` is too short to contain the param's value.
+    ///
+    /// [`access`]: #method.access
+    /// [`iter`]: #method.iter
+    pub fn named_component<'a, T>(&self, record: &'a [T], name: &str) -> Option<&'a T> {
+        record.get(self.param_index(name)?)
+    }
+
+    /// Returns the `"X"`, `"Y"`, and `"Z"` components of `record`, e.g. for position or normal
+    /// data.
+    ///
+    /// The components are found by the names of the params, so they may appear in any order
+    /// within the record and other params are ignored. Returns `None` if any of the three
+    /// components is missing.
     ///
     /// # Examples
     ///
     /// ```
-    /// # use ::collaborate::v1_4::*;
-    /// # static TEST_DOCUMENT: &'static [u8] = include_bytes!("../resources/blender_cube.dae");
-    /// # let source = String::from_utf8(TEST_DOCUMENT.into()).unwrap();
-    /// # let document = Collada::from_str(&*source).unwrap();
-    /// # let library_geometries = document.libraries[5].as_library_geometries().unwrap();
-    /// let geometry = &library_geometries.geometries[0];
-    /// if let Some(mesh) = geometry.geometric_element.as_mesh() {
-    ///     // Do something with `mesh`.
-    /// }
+    /// # use collaborate::v1_4::{Collada, Library};
+    /// # let document = Collada::from_path("resources/blender_cube.dae").unwrap();
+    /// # let library = document.libraries_of(Library::as_library_geometries).next().unwrap();
+    /// # let mesh = library.geometries[0].mesh().unwrap();
+    /// let source = mesh.find_source("Cube-mesh-positions").unwrap();
+    /// let accessor = source.common_accessor().unwrap();
+    /// let record = source.float_values().unwrap().next().unwrap();
+    /// assert_eq!(Some([1.0, 1.0, -1.0]), accessor.vec3(record));
     /// ```
-    ///
-    /// [`Mesh`]: ./struct.Mesh.html
-    pub fn as_mesh(&self) -> Option<&Mesh> {
-        match *self {
-            GeometricElement::Mesh(ref mesh) => Some(mesh),
-            _ => None,
-        }
+    pub fn vec3(&self, record: &[f32]) -> Option<[f32; 3]> {
+        Some([
+            *self.named_component(record, "X")?,
+            *self.named_component(record, "Y")?,
+            *self.named_component(record, "Z")?,
+        ])
     }
 
-    /// Attempts to downcast the geometric element to a [`Spline`].
-    ///
-    /// Returns a reference to the inner [`Spline`] if there is one, returns `None` otherwise. This
-    /// is useful if you have a `GeometricElement` but only want to use it if it represents a
-    /// [`Spline`].
-    ///
-    /// # Examples
-    ///
-    /// ```
-    /// # use ::collaborate::v1_4::*;
-    /// # static TEST_DOCUMENT: &'static [u8] = include_bytes!("../resources/blender_cube.dae");
-    /// # let source = String::from_utf8(TEST_DOCUMENT.into()).unwrap();
-    /// # let document = Collada::from_str(&*source).unwrap();
-    /// # let library_geometries = document.libraries[5].as_library_geometries().unwrap();
-    /// let geometry = &library_geometries.geometries[0];
-    /// if let Some(spline) = geometry.geometric_element.as_spline() {
-    ///     // Do something with `spline`.
-    /// }
-    /// ```
-    ///
-    /// [`Spline`]: ./struct.Spline.html
-    pub fn as_spline(&self) -> Option<&Spline> {
-        match *self {
-            GeometricElement::Spline(ref mesh) => Some(mesh),
-            _ => None,
-        }
+    /// Same as `access`, but returns `None` instead of panicking if the record is out of bounds.
+    fn get<'a, T>(&self, array: &'a [T], index: usize) -> Option<&'a [T]> {
+        let start = self.stride.checked_mul(index)?.checked_add(self.offset)?;
+        let end = start.checked_add(self.stride)?;
+        array.get(start..end)
     }
 }
 
-/// Describes the visual shape and appearance of an object in a scene.
-///
-/// The primary purpose of `Geometry` is to provide access to a [`GeometricElement`], via its
-/// `geographic_element` member. It contains miscellaneous additional data, such as asset
-/// metadata, but otherwise does not directly contain any geometric data.
+/// An iterator over the records of an array, as described by an [`Accessor`].
 ///
-/// # Examples
-///
-/// ```
-/// # use ::collaborate::v1_4::*;
-/// # static TEST_DOCUMENT: &'static [u8] = include_bytes!("../resources/blender_cube.dae");
-/// # let source = String::from_utf8(TEST_DOCUMENT.into()).unwrap();
-/// # let document = Collada::from_str(&*source).unwrap();
-/// # let library_geometries = document.libraries[5].as_library_geometries().unwrap();
-/// let geometry = &library_geometries.geometries[0];
-/// match geometry.geometric_element {
-///     GeometricElement::ConvexMesh(ref mesh) => {
-///         // Do something with `mesh`.
-///     }
+/// Yields one slice of `stride` values for each of the accessor's `count` records. Iteration
+/// stops early if the array is too short to contain all of the records.
 ///
-///     GeometricElement::Mesh(ref mesh) => {
-///         // Do something with `mesh`.
-///     }
+/// [`Accessor`]: ./struct.Accessor.html
+#[derive(Debug, Clone)]
+pub struct AccessorIter<'a, T: 'a> {
+    accessor: &'a Accessor,
+    array: &'a [T],
+    index: usize,
+}
+
+impl<'a, T: 'a> Iterator for AccessorIter<'a, T> {
+    type Item = &'a [T];
+
+    fn next(&mut self) -> Option<&'a [T]> {
+        if self.index >= self.accessor.count {
+            return None;
+        }
+
+        let record = self.accessor.get(self.array, self.index);
+        self.index += 1;
+        record
+    }
+}
+
+/// The data that an [`Accessor`] reads from.
 ///
-///     GeometricElement::Spline(ref spline) => {
-///         // Do something with `spline`.
-///     }
-/// }
-/// ```
+/// Returned by [`Source::accessor_source`].
 ///
-/// [`GeometricElement`]: ./enum.GeometricElement.html
+/// [`Accessor`]: ./struct.Accessor.html
+/// [`Source::accessor_source`]: ./struct.Source.html#method.accessor_source
+#[derive(Debug, Clone, PartialEq)]
+pub enum AccessorSource<'a> {
+    /// The accessor reads from the array in its own source.
+    Array(&'a Array),
+
+    /// The accessor reads from data outside the document, such as an external binary file.
+    ///
+    /// The data isn't loaded, so it's up to the caller to resolve the URI.
+    External(AnyUri),
+}
+
+/// The ambient color of a surface, used by the common profile's shaders.
 #[derive(Debug, Clone, PartialEq, ColladaElement)]
-#[name = "geometry"]
-pub struct Geometry {
-    /// A unique identifier for the geometry instance.
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[name = "ambient"]
+pub struct Ambient {
+    #[child]
+    pub value: ColorOrTexture,
+}
+
+/// A section of the document's animations that's meant to be played as a unit, e.g. a "walk" or
+/// "jump" animation for a character.
+#[derive(Debug, Clone, PartialEq, ColladaElement)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[name = "animation_clip"]
+pub struct AnimationClip {
+    /// A unique identifier for the clip.
     ///
     /// Will be unique within the document.
     #[attribute]
     pub id: Option<String>,
 
-    /// The human-friendly name for this geometry instance.
+    /// The human-friendly name for the clip.
     ///
     /// Has no semantic meaning.
     #[attribute]
     pub name: Option<String>,
 
-    /// Metadata about this geometry instance and the data it contains.
+    /// The time in seconds at which the clip begins.
+    #[attribute]
+    #[optional_with_default = "0.0"]
+    pub start: f64,
+
+    /// The time in seconds at which the clip ends.
+    ///
+    /// If this is `None` the clip runs until the end of the animations it instantiates.
+    #[attribute]
+    pub end: Option<f64>,
+
+    /// Metadata about the clip.
     #[child]
     pub asset: Option<Asset>,
 
-    /// The actual data for the geometry instance.
+    /// The animations played by the clip.
+    ///
+    /// There will always be at least one animation in an `AnimationClip`.
     #[child]
-    pub geometric_element: GeometricElement,
+    #[required]
+    pub instance_animations: Vec<InstanceAnimation>,
 
-    /// Arbitrary additional information about this geometry instance and the data it contains.
+    /// Arbitrary additional information about this clip.
     ///
     /// For more information about 3rd-party extensions, see the
     /// [crate-level documentation](../index.html#3rd-party-extensions).
     #[child]
-    pub extra: Vec<Extra>,
+    pub extras: Vec<Extra>,
 }
 
 #[derive(Debug, Clone, PartialEq, ColladaElement)]
-#[name = "IDREF_array"]
-pub struct IdrefArray;
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[name = "annotate"]
+pub struct Annotate;
 
-#[derive(Debug, Clone)]
-pub struct InputsForOffset<'a> {
-    inputs: ::std::slice::Iter<'a, SharedInput>,
-    offset: usize,
+#[derive(Debug, Clone, PartialEq, ColladaElement)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum Array {
+    Idref(IdrefArray),
+    Name(NameArray),
+    Bool(BoolArray),
+    Float(FloatArray),
+    Int(IntArray),
 }
 
-impl<'a> Iterator for InputsForOffset<'a> {
-    type Item = &'a SharedInput;
+impl Array {
+    pub fn as_float_array(&self) -> Option<&FloatArray> {
+        match *self {
+            Array::Float(ref float_array) => Some(float_array),
+            _ => None,
+        }
+    }
+}
 
-    fn next(&mut self) -> Option<&'a SharedInput> {
-        while let Some(input) = self.inputs.next() {
-            if input.offset == self.offset {
-                return Some(input);
-            }
+/// Builds a new [`Collada`] document.
+///
+/// Created with [`Collada::builder`].
+///
+/// [`Collada`]: ./struct.Collada.html
+/// [`Collada::builder`]: ./struct.Collada.html#method.builder
+#[derive(Debug, Clone, PartialEq)]
+pub struct ColladaBuilder {
+    collada: Collada,
+}
+
+impl ColladaBuilder {
+    fn new() -> ColladaBuilder {
+        ColladaBuilder {
+            collada: Collada {
+                version: "1.4.1".into(),
+                xmlns: Some(NAMESPACE.into()),
+                base_uri: None,
+                asset: Asset::now(),
+                libraries: Vec::new(),
+                scene: None,
+                extras: Vec::new(),
+            },
         }
+    }
 
-        None
+    /// Adds `library` to the document.
+    ///
+    /// See [`Collada::push_library`](./struct.Collada.html#method.push_library).
+    pub fn push_library(mut self, library: Library) -> ColladaBuilder {
+        self.collada.push_library(library);
+        self
+    }
+
+    /// Adds `geometry` to the document.
+    ///
+    /// See [`Collada::push_geometry`](./struct.Collada.html#method.push_geometry).
+    pub fn push_geometry(mut self, geometry: Geometry) -> ColladaBuilder {
+        self.collada.push_geometry(geometry);
+        self
+    }
+
+    /// Returns the finished document.
+    pub fn build(self) -> Collada {
+        self.collada
+    }
+}
+
+/// The aspect ratio of a camera's field of view.
+#[derive(Debug, Clone, PartialEq, ColladaElement)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[name = "aspect_ratio"]
+pub struct AspectRatio {
+    /// An identifier for the value that's unique amongst its sibling elements.
+    #[attribute]
+    pub sid: Option<String>,
+
+    #[text]
+    pub value: f64,
+}
+
+#[derive(Debug, Clone, PartialEq, ColladaElement)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[name = "asset"]
+pub struct Asset {
+    #[child]
+    pub contributors: Vec<Contributor>,
+
+    #[child]
+    pub created: DateTime,
+
+    #[child]
+    pub keywords: Option<String>,
+
+    #[child]
+    pub modified: DateTime,
+
+    #[child]
+    pub revision: Option<String>,
+
+    #[child]
+    pub subject: Option<String>,
+
+    #[child]
+    pub title: Option<String>,
+
+    /// The unit of distance for this asset, or `None` if the `<unit>` element is missing.
+    ///
+    /// The spec defaults the unit to one meter. A nested asset without a unit uses the unit of
+    /// the asset enclosing it, which [`Collada::effective_unit`] resolves.
+    ///
+    /// [`Collada::effective_unit`]: ./struct.Collada.html#method.effective_unit
+    #[child]
+    pub unit: Option<Unit>,
+
+    /// The up axis for this asset, or `None` if the `<up_axis>` element is missing.
+    ///
+    /// The spec defaults the up axis to `Y_UP`. A nested asset without an up axis uses the up
+    /// axis of the asset enclosing it, which [`Collada::effective_up_axis`] resolves.
+    ///
+    /// [`Collada::effective_up_axis`]: ./struct.Collada.html#method.effective_up_axis
+    #[child]
+    pub up_axis: Option<UpAxis>,
+}
+
+impl Asset {
+    /// Creates a minimal asset that was created and last modified at the current time.
+    ///
+    /// Every other field is empty or has its default value. This is the least an `<asset>` needs
+    /// to be valid, which makes it a starting point for generating documents.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use collaborate::v1_4::Asset;
+    ///
+    /// let asset = Asset::now().with_tool("my-exporter 1.0");
+    /// assert_eq!(asset.created, asset.modified);
+    /// assert_eq!(Some("my-exporter 1.0"), asset.contributors[0].authoring_tool.as_deref());
+    /// ```
+    pub fn now() -> Asset {
+        let now = DateTime::Utc(::chrono::UTC::now().with_timezone(&::chrono::FixedOffset::east(0)));
+        Asset {
+            contributors: Vec::new(),
+            created: now,
+            keywords: None,
+            modified: now,
+            revision: None,
+            subject: None,
+            title: None,
+            unit: None,
+            up_axis: None,
+        }
+    }
+
+    /// Adds a contributor whose only information is the authoring tool `name`.
+    pub fn with_tool(mut self, name: &str) -> Asset {
+        self.contributors.push(Contributor {
+            author: None,
+            authoring_tool: Some(name.into()),
+            comments: None,
+            copyright: None,
+            source_data: None,
+        });
+        self
+    }
+
+    /// Returns the asset's keywords as a list.
+    ///
+    /// The `keywords` field holds a whitespace-separated list of search terms, which is split
+    /// into the individual keywords. Returns an empty list if the asset has no keywords.
+    pub fn keyword_list(&self) -> Vec<&str> {
+        self.keywords.iter()
+            .flat_map(|keywords| keywords.split_whitespace())
+            .collect()
+    }
+}
+
+/// Binds a value within an instantiated material to a semantic of the instantiating object.
+#[derive(Debug, Clone, PartialEq, ColladaElement)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[name = "bind"]
+pub struct Bind {
+    /// The semantic of the parameter being bound.
+    #[attribute]
+    pub semantic: String,
+
+    /// The location of the value to bind to the parameter.
+    #[attribute]
+    pub target: String,
+}
+
+/// Binds the material symbols used by an instantiated geometry to concrete materials.
+///
+/// Each [`Primitive`] names the material it's drawn with using a symbol, which is mapped to a
+/// [`Material`] by one of the `instance_materials`.
+///
+/// [`Primitive`]: ./enum.Primitive.html
+/// [`Material`]: ./struct.Material.html
+#[derive(Debug, Clone, PartialEq, ColladaElement)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[name = "bind_material"]
+pub struct BindMaterial {
+    /// Parameters that can be targeted for animation.
+    #[child]
+    pub params: Vec<Param>,
+
+    /// The material bindings, as defined by the common profile.
+    #[child]
+    pub technique_common: BindMaterialTechniqueCommon,
+
+    /// Profile-specific material bindings.
+    #[child]
+    pub techniques: Vec<Technique>,
+
+    /// Arbitrary additional information about the binding.
+    ///
+    /// For more information about 3rd-party extensions, see the
+    /// [crate-level documentation](../index.html#3rd-party-extensions).
+    #[child]
+    pub extras: Vec<Extra>,
+}
+
+/// The material bindings of a [`BindMaterial`] as defined by the common profile.
+///
+/// [`BindMaterial`]: ./struct.BindMaterial.html
+#[derive(Debug, Clone, PartialEq, ColladaElement)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[name = "technique_common"]
+pub struct BindMaterialTechniqueCommon {
+    /// The materials bound to the geometry's symbols. There will always be at least one.
+    #[child]
+    #[required]
+    pub instance_materials: Vec<InstanceMaterial>,
+}
+
+/// Binds a vertex input of a geometry to an effect's varying parameter.
+#[derive(Debug, Clone, PartialEq, ColladaElement)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[name = "bind_vertex_input"]
+pub struct BindVertexInput {
+    /// The semantic of the effect parameter being bound.
+    #[attribute]
+    pub semantic: String,
+
+    /// The semantic of the geometry's input, e.g. `"TEXCOORD"`.
+    #[attribute]
+    pub input_semantic: String,
+
+    /// The set of the geometry's input, if it has several with the same semantic.
+    #[attribute]
+    pub input_set: Option<usize>,
+}
+
+/// A shader that produces a specularly shaded surface using the Blinn BRDF approximation.
+///
+/// The specular highlight is calculated using the half-angle vector between the light and eye
+/// directions.
+#[derive(Debug, Clone, PartialEq, ColladaElement)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[name = "blinn"]
+pub struct Blinn {
+    /// The light emitted by the surface.
+    #[child]
+    pub emission: Option<Emission>,
+
+    /// The light reflected from ambient light sources.
+    #[child]
+    pub ambient: Option<Ambient>,
+
+    /// The light diffusely reflected from the surface.
+    #[child]
+    pub diffuse: Option<Diffuse>,
+
+    /// The light specularly reflected from the surface.
+    #[child]
+    pub specular: Option<Specular>,
+
+    /// The specularity or roughness of the specular reflection lobe.
+    #[child]
+    pub shininess: Option<Shininess>,
+
+    /// The color of a perfect mirror reflection.
+    #[child]
+    pub reflective: Option<Reflective>,
+
+    /// The amount of perfect mirror reflection to be added.
+    #[child]
+    pub reflectivity: Option<Reflectivity>,
+
+    /// The color of perfectly refracted light.
+    #[child]
+    pub transparent: Option<Transparent>,
+
+    /// The amount of perfectly refracted light added.
+    #[child]
+    pub transparency: Option<Transparency>,
+
+    /// The index of refraction for perfectly refracted light.
+    #[child]
+    pub index_of_refraction: Option<IndexOfRefraction>,
+}
+
+/// The color used for texture coordinates outside the texture when a [`Sampler2D`] uses
+/// the `"BORDER"` wrap mode.
+///
+/// `data` contains the red, green, blue, and alpha components of the color.
+///
+/// [`Sampler2D`]: ./struct.Sampler2D.html
+#[derive(Debug, Clone, PartialEq, ColladaElement)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[name = "border_color"]
+pub struct BorderColor {
+    #[text]
+    pub data: Vec<f32>,
+}
+
+#[derive(Debug, Clone, PartialEq, ColladaElement)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[name = "bool_array"]
+pub struct BoolArray;
+
+/// A box-shaped collision shape, centered on the origin.
+///
+/// Named `BoxShape` rather than `Box` to avoid shadowing `std::boxed::Box`.
+#[derive(Debug, Clone, PartialEq, ColladaElement)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[name = "box"]
+pub struct BoxShape {
+    /// Half of the box's size along each of the X, Y, and Z axes.
+    #[child]
+    pub half_extents: HalfExtents,
+
+    /// Arbitrary additional information about this shape.
+    ///
+    /// For more information about 3rd-party extensions, see the
+    /// [crate-level documentation](../index.html#3rd-party-extensions).
+    #[child]
+    pub extras: Vec<Extra>,
+}
+
+/// Declares a view into the scene, which can be instantiated with an [`InstanceCamera`].
+///
+/// [`InstanceCamera`]: ./struct.InstanceCamera.html
+#[derive(Debug, Clone, PartialEq, ColladaElement)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[name = "camera"]
+pub struct Camera {
+    /// A unique identifier for the camera.
+    ///
+    /// Will be unique within the document.
+    #[attribute]
+    pub id: Option<String>,
+
+    /// The human-friendly name for the camera.
+    ///
+    /// Has no semantic meaning.
+    #[attribute]
+    pub name: Option<String>,
+
+    /// Metadata about the camera.
+    #[child]
+    pub asset: Option<Asset>,
+
+    /// Describes the field of view and viewing frustum of the camera.
+    #[child]
+    pub optics: Optics,
+
+    /// Describes the image sensor of the camera.
+    #[child]
+    pub imager: Option<Imager>,
+
+    /// Arbitrary additional information about this camera.
+    ///
+    /// For more information about 3rd-party extensions, see the
+    /// [crate-level documentation](../index.html#3rd-party-extensions).
+    #[child]
+    pub extras: Vec<Extra>,
+}
+
+impl Camera {
+    /// Returns the camera's perspective projection, or `None` if it's an orthographic camera.
+    pub fn perspective(&self) -> Option<&Perspective> {
+        match self.optics.technique_common.projection {
+            Projection::Perspective(ref perspective) => Some(perspective),
+            Projection::Orthographic(_) => None,
+        }
+    }
+
+    /// Computes the horizontal field of view of a perspective camera, in degrees.
+    ///
+    /// A perspective camera may specify only one of `xfov` and `yfov`, in which case the other
+    /// is derived from the camera's `aspect_ratio` (the ratio of `xfov` to `yfov`). If the camera
+    /// doesn't specify an aspect ratio either, `viewport_aspect` (the width of the viewport
+    /// divided by its height) is used instead.
+    ///
+    /// Returns `None` for orthographic cameras, and for perspective cameras that specify neither
+    /// `xfov` nor `yfov`, which isn't valid.
+    pub fn horizontal_fov(&self, viewport_aspect: f64) -> Option<f64> {
+        self.fov(viewport_aspect).map(|(horizontal, _)| horizontal)
+    }
+
+    /// Computes the vertical field of view of a perspective camera, in degrees.
+    ///
+    /// See [`horizontal_fov`](#method.horizontal_fov) for how a missing angle is derived.
+    pub fn vertical_fov(&self, viewport_aspect: f64) -> Option<f64> {
+        self.fov(viewport_aspect).map(|(_, vertical)| vertical)
+    }
+
+    /// Returns the horizontal and vertical fields of view of a perspective camera.
+    fn fov(&self, viewport_aspect: f64) -> Option<(f64, f64)> {
+        let perspective = self.perspective()?;
+        let aspect_ratio = perspective.aspect_ratio.as_ref()
+            .map(|aspect_ratio| aspect_ratio.value)
+            .unwrap_or(viewport_aspect);
+
+        match (perspective.xfov.as_ref(), perspective.yfov.as_ref()) {
+            (Some(xfov), Some(yfov)) => Some((xfov.value, yfov.value)),
+            (Some(xfov), None) => Some((xfov.value, xfov.value / aspect_ratio)),
+            (None, Some(yfov)) => Some((yfov.value * aspect_ratio, yfov.value)),
+            (None, None) => None,
+        }
+    }
+}
+
+/// A capsule-shaped collision shape, i.e. a cylinder capped with half-spheres, aligned with the
+/// Y axis and centered on the origin.
+#[derive(Debug, Clone, PartialEq, ColladaElement)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[name = "capsule"]
+pub struct Capsule {
+    /// The length of the capsule along the Y axis, not including the end caps.
+    #[child]
+    pub height: Height,
+
+    /// The radii of the capsule along the X and Z axes.
+    #[child]
+    pub radius: Radius,
+
+    /// Arbitrary additional information about this shape.
+    ///
+    /// For more information about 3rd-party extensions, see the
+    /// [crate-level documentation](../index.html#3rd-party-extensions).
+    #[child]
+    pub extras: Vec<Extra>,
+}
+
+/// A value that can either be a color or a texture.
+///
+/// The value can also be a reference to a parameter declared elsewhere in the effect.
+#[derive(Debug, Clone, PartialEq, ColladaElement)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum ColorOrTexture {
+    Color(Color),
+    Param(ParamReference),
+    Texture(Texture),
+}
+
+/// A shader that produces a constantly shaded surface that is independent of lighting.
+#[derive(Debug, Clone, PartialEq, ColladaElement)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[name = "constant"]
+pub struct Constant {
+    /// The light emitted by the surface.
+    #[child]
+    pub emission: Option<Emission>,
+
+    /// The color of a perfect mirror reflection.
+    #[child]
+    pub reflective: Option<Reflective>,
+
+    /// The amount of perfect mirror reflection to be added.
+    #[child]
+    pub reflectivity: Option<Reflectivity>,
+
+    /// The color of perfectly refracted light.
+    #[child]
+    pub transparent: Option<Transparent>,
+
+    /// The amount of perfectly refracted light added.
+    #[child]
+    pub transparency: Option<Transparency>,
+
+    /// The index of refraction for perfectly refracted light.
+    #[child]
+    pub index_of_refraction: Option<IndexOfRefraction>,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq, ColladaElement)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[name = "contributor"]
+pub struct Contributor {
+    #[child]
+    pub author: Option<String>,
+
+    #[child]
+    pub authoring_tool: Option<String>,
+
+    #[child]
+    pub comments: Option<String>,
+
+    #[child]
+    pub copyright: Option<String>,
+
+    #[child]
+    pub source_data: Option<AnyUri>,
+}
+
+impl Contributor {
+    /// Returns `source_data` resolved against `base`, usually the document's `base_uri`.
+    ///
+    /// Absolute URIs and Windows paths like `C:/models/tank.s3d` are returned unchanged, and
+    /// `source_data` is returned as-is if there's no `base`. See [`AnyUri::resolve_against`] for
+    /// the details of how relative references are resolved.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use collaborate::v1_4::Collada;
+    /// # fn example(document: &Collada) {
+    /// for contributor in &document.asset.contributors {
+    ///     if let Some(source_data) = contributor.resolved_source_data(document.base_uri.as_ref()) {
+    ///         println!("Created from {}", source_data);
+    ///     }
+    /// }
+    /// # }
+    /// ```
+    ///
+    /// [`AnyUri::resolve_against`]: ../common/struct.AnyUri.html#method.resolve_against
+    pub fn resolved_source_data(&self, base: Option<&AnyUri>) -> Option<AnyUri> {
+        self.source_data.as_ref().map(|source_data| match base {
+            Some(base) => source_data.resolve_against(base),
+            None => source_data.clone(),
+        })
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, ColladaElement)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[name = "convex_mesh"]
+pub struct ConvexMesh;
+
+/// A cylinder-shaped collision shape, aligned with the Y axis and centered on the origin.
+#[derive(Debug, Clone, PartialEq, ColladaElement)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[name = "cylinder"]
+pub struct Cylinder {
+    /// The length of the cylinder along the Y axis.
+    #[child]
+    pub height: Height,
+
+    /// The radii of the cylinder along the X and Z axes.
+    #[child]
+    pub radius: Radius,
+
+    /// Arbitrary additional information about this shape.
+    ///
+    /// For more information about 3rd-party extensions, see the
+    /// [crate-level documentation](../index.html#3rd-party-extensions).
+    #[child]
+    pub extras: Vec<Extra>,
+}
+
+/// The density of a shape, used to compute its mass if the mass isn't specified.
+#[derive(Debug, Clone, PartialEq, ColladaElement)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[name = "density"]
+pub struct Density {
+    /// An identifier for the value that's unique amongst its sibling elements.
+    #[attribute]
+    pub sid: Option<String>,
+
+    #[text]
+    pub value: f64,
+}
+
+/// The diffuse color of a surface, used by the common profile's shaders.
+#[derive(Debug, Clone, PartialEq, ColladaElement)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[name = "diffuse"]
+pub struct Diffuse {
+    #[child]
+    pub value: ColorOrTexture,
+}
+
+/// Whether a rigid body is moved by the simulation (`true`) or is static (`false`).
+#[derive(Debug, Clone, PartialEq, ColladaElement)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[name = "dynamic"]
+pub struct Dynamic {
+    /// An identifier for the value that's unique amongst its sibling elements.
+    #[attribute]
+    pub sid: Option<String>,
+
+    #[text]
+    pub value: Boolean,
+}
+
+/// The coefficient of friction between surfaces that are moving relative to each other.
+#[derive(Debug, Clone, Default, PartialEq, ColladaElement)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[name = "dynamic_friction"]
+pub struct DynamicFriction {
+    /// An identifier for the value that's unique amongst its sibling elements.
+    #[attribute]
+    pub sid: Option<String>,
+
+    #[text]
+    pub value: f64,
+}
+
+/// Defines the visual appearance of a surface.
+///
+/// An effect is a self-contained description of how a surface should be rendered, provided by
+/// one or more profiles. Effects are applied to geometry through a [`Material`].
+///
+/// > NOTE: Currently only the common profile ([`ProfileCommon`]) is parsed, the platform-specific
+/// > profiles are skipped.
+///
+/// [`Material`]: ./struct.Material.html
+/// [`ProfileCommon`]: ./struct.ProfileCommon.html
+#[derive(Debug, Clone, PartialEq, ColladaElement)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[name = "effect"]
+pub struct Effect {
+    /// A unique identifier for the effect.
+    ///
+    /// Will be unique within the document.
+    #[attribute]
+    pub id: String,
+
+    /// The human-friendly name for this effect.
+    ///
+    /// Has no semantic meaning.
+    #[attribute]
+    pub name: Option<String>,
+
+    /// Metadata about this effect.
+    #[child]
+    pub asset: Option<Asset>,
+
+    #[child]
+    pub annotations: Vec<Annotate>,
+
+    #[child]
+    pub images: Vec<Image>,
+
+    #[child]
+    pub new_params: Vec<NewParam>,
+
+    /// The profiles describing the effect. There will always be at least one profile.
+    #[child]
+    #[required]
+    pub profiles: Vec<Profile>,
+
+    /// Arbitrary additional information about this effect.
+    ///
+    /// For more information about 3rd-party extensions, see the
+    /// [crate-level documentation](../index.html#3rd-party-extensions).
+    #[child]
+    pub extras: Vec<Extra>,
+}
+
+impl Effect {
+    /// Returns the effect's common profile, if it has one.
+    pub fn profile_common(&self) -> Option<&ProfileCommon> {
+        self.profiles.iter()
+            .filter_map(|profile| match *profile {
+                Profile::Common(ref profile) => Some(&**profile),
+                _ => None,
+            })
+            .next()
+    }
+}
+
+/// The emitted color of a surface, used by the common profile's shaders.
+#[derive(Debug, Clone, PartialEq, ColladaElement)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[name = "emission"]
+pub struct Emission {
+    #[child]
+    pub value: ColorOrTexture,
+}
+
+/// The coefficients `A`, `B`, `C`, and `D` of a plane's equation `Ax + By + Cz + D = 0`.
+#[derive(Debug, Clone, PartialEq, ColladaElement)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[name = "equation"]
+pub struct Equation {
+    #[text]
+    pub data: Vec<f64>,
+}
+
+/// Describes how a visual scene is rendered, as a sequence of render passes.
+///
+/// A visual scene may have several `EvaluateScene` blocks, e.g. one per output image. Each block
+/// is a self-contained description of how to render the scene.
+#[derive(Debug, Clone, PartialEq, ColladaElement)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[name = "evaluate_scene"]
+pub struct EvaluateScene {
+    /// The human-friendly name for this evaluation.
+    ///
+    /// Has no semantic meaning.
+    #[attribute]
+    pub name: Option<String>,
+
+    /// The render passes, in the order they're performed. There will always be at least one.
+    #[child]
+    #[required]
+    pub renders: Vec<Render>,
+}
+
+/// Provides arbitrary additional information about an element.
+///
+/// COLLADA allows for applications to provide extra information about any given piece of data,
+/// including application-specific information that's not part of the COLLADA specification. This
+/// data can be any syntactically valid XML data, and is not parsed as part of this library, save
+/// for a few specific 3rd party applications that are directly supported.
+///
+/// # Choosing a Technique
+///
+/// There may be more than one [`Technique`][Technique] provided in `techniques`, but generally
+/// only one is used by the consuming application. The application should pick a technique
+/// with a supported profile. If there are multiple techniques with supported profiles the
+/// application is free to pick whichever technique is preferred.
+///
+/// [Technique]: struct.Technique.html
+#[derive(Debug, Clone, PartialEq, ColladaElement)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[name = "extra"]
+pub struct Extra {
+    /// The identifier of the element, if present. Will be unique within the document.
+    #[attribute]
+    pub id: Option<String>,
+
+    /// The text string name of the element, if present.
+    #[attribute]
+    pub name: Option<String>,
+
+    /// A hint as to the type of information this element represents, if present. Must be
+    /// must be understood by the consuming application.
+    #[attribute]
+    #[name = "type"]
+    pub type_hint: Option<String>,
+
+    /// Asset-management information for this element, if present.
+    ///
+    /// While this is technically allowed in all `<extra>` elements, it is likely only present in
+    /// elements that describe a new "asset" of some kind, rather than in `<extra>` elements that
+    /// provide application-specific information about an existing one.
+    #[child]
+    pub asset: Option<Asset>,
+
+    /// The arbitrary additional information, containing unprocessed XML events.
+    ///
+    /// The specification requires at least one technique, but some exporters write `<extra>`
+    /// elements that only contain an `<asset>`, so `techniques` may be empty. Empty `<extra />`
+    /// elements are accepted as well.
+    #[child]
+    pub techniques: Vec<Technique>,
+
+    /// The namespace mappings in scope for the `<extra>` element, including any prefixes
+    /// declared on the element itself.
+    #[namespace]
+    #[cfg_attr(feature = "serde", serde(with = "::serde_support::namespace"))]
+    pub namespace: Namespace,
+}
+
+impl Default for Extra {
+    fn default() -> Extra {
+        Extra {
+            id: None,
+            name: None,
+            type_hint: None,
+            asset: None,
+            techniques: Vec::new(),
+            namespace: Namespace::empty(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, ColladaElement)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[name = "float_array"]
+pub struct FloatArray {
+    #[attribute]
+    pub count: usize,
+
+    #[attribute]
+    pub id: Option<String>,
+
+    #[attribute]
+    pub name: Option<String>,
+
+    #[attribute]
+    #[optional_with_default = "6"]
+    pub digits: usize,
+
+    #[attribute]
+    #[optional_with_default = "38"]
+    pub magnitude: usize,
+
+    #[text]
+    #[capacity = "count"]
+    pub data: Vec<f32>,
+}
+
+/// A value that can either be a float or a reference to a parameter.
+#[derive(Debug, Clone, PartialEq, ColladaElement)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum FloatOrParam {
+    Float(FloatValue),
+    Param(ParamReference),
+}
+
+/// Describes a force that affects the bodies in a physics simulation, e.g. wind or gravity.
+///
+/// COLLADA doesn't define any common properties for force fields, so the behavior of the force is
+/// described entirely by the profile-specific `techniques`.
+#[derive(Debug, Clone, PartialEq, ColladaElement)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[name = "force_field"]
+pub struct ForceField {
+    /// A unique identifier for the force field.
+    ///
+    /// Will be unique within the document.
+    #[attribute]
+    pub id: Option<String>,
+
+    /// The human-friendly name for the force field.
+    ///
+    /// Has no semantic meaning.
+    #[attribute]
+    pub name: Option<String>,
+
+    /// Metadata about the force field.
+    #[child]
+    pub asset: Option<Asset>,
+
+    /// Profile-specific descriptions of the force field.
+    ///
+    /// There will always be at least one technique in a `ForceField`.
+    #[child]
+    #[required]
+    pub techniques: Vec<Technique>,
+
+    /// Arbitrary additional information about this force field.
+    ///
+    /// For more information about 3rd-party extensions, see the
+    /// [crate-level documentation](../index.html#3rd-party-extensions).
+    #[child]
+    pub extras: Vec<Extra>,
+}
+
+#[derive(Debug, Clone, PartialEq, ColladaElement)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[name = "format_hint"]
+pub struct FormatHint;
+
+/// A geometric element of unknown type.
+///
+/// Each variant wraps a single value containing a given type of geometric data. See the
+/// documentation for each of the possible geometric types for more information.
+#[derive(Debug, Clone, PartialEq, ColladaElement)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum GeometricElement {
+    ConvexMesh(ConvexMesh),
+    Mesh(Mesh),
+    Spline(Spline),
+}
+
+impl GeometricElement {
+    /// Attempts to downcast the geometric element to a [`ConvexMesh`].
+    ///
+    /// Returns a reference to the inner [`ConvexMesh`] if there is one, returns `None` otherwise.
+    /// This is useful if you have a `GeometricElement` but only want to use it if it represents a
+    /// [`ConvexMesh`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use ::collaborate::v1_4::*;
+    /// # static TEST_DOCUMENT: &'static [u8] = include_bytes!("../resources/blender_cube.dae");
+    /// # let source = String::from_utf8(TEST_DOCUMENT.into()).unwrap();
+    /// # let document = Collada::from_str(&*source).unwrap();
+    /// # let library_geometries = document.libraries_of(Library::as_library_geometries).next().unwrap();
+    /// let geometry = &library_geometries.geometries[0];
+    /// if let Some(mesh) = geometry.geometric_element.as_convex_mesh() {
+    ///     // Do something with `mesh`.
+    /// }
+    /// ```
+    ///
+    /// [`ConvexMesh`]: ./struct.ConvexMesh.html
+    pub fn as_convex_mesh(&self) -> Option<&ConvexMesh> {
+        match *self {
+            GeometricElement::ConvexMesh(ref mesh) => Some(mesh),
+            _ => None,
+        }
+    }
+
+    /// Attempts to downcast the geometric element to a [`Mesh`].
+    ///
+    /// Returns a reference to the inner [`Mesh`] if there is one, returns `None` otherwise. This
+    /// is useful if you have a `GeometricElement` but only want to use it if it represents a
+    /// [`Mesh`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use ::collaborate::v1_4::*;
+    /// # static TEST_DOCUMENT: &'static [u8] = include_bytes!("../resources/blender_cube.dae");
+    /// # let source = String::from_utf8(TEST_DOCUMENT.into()).unwrap();
+    /// # let document = Collada::from_str(&*source).unwrap();
+    /// # let library_geometries = document.libraries_of(Library::as_library_geometries).next().unwrap();
+    /// let geometry = &library_geometries.geometries[0];
+    /// if let Some(mesh) = geometry.geometric_element.as_mesh() {
+    ///     // Do something with `mesh`.
+    /// }
+    /// ```
+    ///
+    /// [`Mesh`]: ./struct.Mesh.html
+    pub fn as_mesh(&self) -> Option<&Mesh> {
+        match *self {
+            GeometricElement::Mesh(ref mesh) => Some(mesh),
+            _ => None,
+        }
+    }
+
+    /// Attempts to downcast the geometric element to a [`Spline`].
+    ///
+    /// Returns a reference to the inner [`Spline`] if there is one, returns `None` otherwise. This
+    /// is useful if you have a `GeometricElement` but only want to use it if it represents a
+    /// [`Spline`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use ::collaborate::v1_4::*;
+    /// # static TEST_DOCUMENT: &'static [u8] = include_bytes!("../resources/blender_cube.dae");
+    /// # let source = String::from_utf8(TEST_DOCUMENT.into()).unwrap();
+    /// # let document = Collada::from_str(&*source).unwrap();
+    /// # let library_geometries = document.libraries_of(Library::as_library_geometries).next().unwrap();
+    /// let geometry = &library_geometries.geometries[0];
+    /// if let Some(spline) = geometry.geometric_element.as_spline() {
+    ///     // Do something with `spline`.
+    /// }
+    /// ```
+    ///
+    /// [`Spline`]: ./struct.Spline.html
+    pub fn as_spline(&self) -> Option<&Spline> {
+        match *self {
+            GeometricElement::Spline(ref mesh) => Some(mesh),
+            _ => None,
+        }
+    }
+}
+
+/// Describes the visual shape and appearance of an object in a scene.
+///
+/// The primary purpose of `Geometry` is to provide access to a [`GeometricElement`], via its
+/// `geometric_element` member. It contains miscellaneous additional data, such as asset
+/// metadata, but otherwise does not directly contain any geometric data.
+///
+/// # Examples
+///
+/// ```
+/// # use ::collaborate::v1_4::*;
+/// # static TEST_DOCUMENT: &'static [u8] = include_bytes!("../resources/blender_cube.dae");
+/// # let source = String::from_utf8(TEST_DOCUMENT.into()).unwrap();
+/// # let document = Collada::from_str(&*source).unwrap();
+/// # let library_geometries = document.libraries_of(Library::as_library_geometries).next().unwrap();
+/// let geometry = &library_geometries.geometries[0];
+/// match geometry.geometric_element {
+///     GeometricElement::ConvexMesh(ref mesh) => {
+///         // Do something with `mesh`.
+///     }
+///
+///     GeometricElement::Mesh(ref mesh) => {
+///         // Do something with `mesh`.
+///     }
+///
+///     GeometricElement::Spline(ref spline) => {
+///         // Do something with `spline`.
+///     }
+/// }
+/// ```
+///
+/// [`GeometricElement`]: ./enum.GeometricElement.html
+#[derive(Debug, Clone, PartialEq, ColladaElement)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[name = "geometry"]
+pub struct Geometry {
+    /// A unique identifier for the geometry instance.
+    ///
+    /// Will be unique within the document.
+    #[attribute]
+    pub id: Option<String>,
+
+    /// The human-friendly name for this geometry instance.
+    ///
+    /// Has no semantic meaning.
+    #[attribute]
+    pub name: Option<String>,
+
+    /// Metadata about this geometry instance and the data it contains.
+    #[child]
+    pub asset: Option<Asset>,
+
+    /// The actual data for the geometry instance.
+    #[child]
+    pub geometric_element: GeometricElement,
+
+    /// Arbitrary additional information about this geometry instance and the data it contains.
+    ///
+    /// For more information about 3rd-party extensions, see the
+    /// [crate-level documentation](../index.html#3rd-party-extensions).
+    #[child]
+    pub extra: Vec<Extra>,
+
+    /// Where the `<geometry>` element started in the source document.
+    #[position]
+    #[cfg_attr(feature = "serde", serde(skip))]
+    pub position: SourcePosition,
+}
+
+impl Geometry {
+    /// Returns the geometry's [`ConvexMesh`], if it is one.
+    ///
+    /// This is a shorthand for [`GeometricElement::as_convex_mesh`].
+    ///
+    /// [`ConvexMesh`]: ./struct.ConvexMesh.html
+    /// [`GeometricElement::as_convex_mesh`]: ./enum.GeometricElement.html#method.as_convex_mesh
+    pub fn convex_mesh(&self) -> Option<&ConvexMesh> {
+        self.geometric_element.as_convex_mesh()
+    }
+
+    /// Returns the geometry's [`Mesh`], if it is one.
+    ///
+    /// This is a shorthand for [`GeometricElement::as_mesh`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use ::collaborate::v1_4::*;
+    /// # let document = Collada::from_path("resources/blender_cube.dae").unwrap();
+    /// # let library_geometries = document.libraries_of(Library::as_library_geometries).next().unwrap();
+    /// let geometry = &library_geometries.geometries[0];
+    /// if let Some(mesh) = geometry.mesh() {
+    ///     // Do something with `mesh`.
+    /// }
+    /// ```
+    ///
+    /// [`Mesh`]: ./struct.Mesh.html
+    /// [`GeometricElement::as_mesh`]: ./enum.GeometricElement.html#method.as_mesh
+    pub fn mesh(&self) -> Option<&Mesh> {
+        self.geometric_element.as_mesh()
+    }
+
+    /// Returns the geometry's [`Spline`], if it is one.
+    ///
+    /// This is a shorthand for [`GeometricElement::as_spline`].
+    ///
+    /// [`Spline`]: ./struct.Spline.html
+    /// [`GeometricElement::as_spline`]: ./enum.GeometricElement.html#method.as_spline
+    pub fn spline(&self) -> Option<&Spline> {
+        self.geometric_element.as_spline()
+    }
+}
+
+/// Half of the size of a box along each of the X, Y, and Z axes.
+#[derive(Debug, Clone, PartialEq, ColladaElement)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[name = "half_extents"]
+pub struct HalfExtents {
+    #[text]
+    pub data: Vec<f64>,
+}
+
+/// The height of a capsule or cylinder shape.
+#[derive(Debug, Clone, PartialEq, ColladaElement)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[name = "height"]
+pub struct Height {
+    #[text]
+    pub value: f64,
+}
+
+/// Whether a shape is hollow, which affects how its mass is distributed.
+#[derive(Debug, Clone, PartialEq, ColladaElement)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[name = "hollow"]
+pub struct Hollow {
+    /// An identifier for the value that's unique amongst its sibling elements.
+    #[attribute]
+    pub sid: Option<String>,
+
+    #[text]
+    pub value: Boolean,
+}
+
+/// The acceleration due to gravity in a [`PhysicsScene`], as a vector whose direction and
+/// magnitude are those of the acceleration.
+///
+/// [`PhysicsScene`]: ./struct.PhysicsScene.html
+#[derive(Debug, Clone, PartialEq, ColladaElement)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[name = "gravity"]
+pub struct Gravity {
+    /// An identifier for the value that's unique amongst its sibling elements.
+    #[attribute]
+    pub sid: Option<String>,
+
+    #[text]
+    pub data: Vec<f32>,
+}
+
+impl Gravity {
+    /// Returns the gravity as an X, Y, Z vector.
+    ///
+    /// Missing values are treated as `0.0`.
+    pub fn vector(&self) -> [f32; 3] {
+        let component = |index| self.data.get(index).cloned().unwrap_or(0.0);
+        [component(0), component(1), component(2)]
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, ColladaElement)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[name = "IDREF_array"]
+pub struct IdrefArray;
+
+/// Declares the storage for an image, such as a texture used by an effect.
+#[derive(Debug, Clone, PartialEq, ColladaElement)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[name = "image"]
+pub struct Image {
+    /// A unique identifier for the image.
+    ///
+    /// Will be unique within the document.
+    #[attribute]
+    pub id: Option<String>,
+
+    /// The human-friendly name for the image.
+    ///
+    /// Has no semantic meaning.
+    #[attribute]
+    pub name: Option<String>,
+
+    /// The format of the image data, e.g. `"PNG"`.
+    ///
+    /// The format is usually left unspecified, in which case it's implied by the image data.
+    #[attribute]
+    pub format: Option<String>,
+
+    /// The height of the image in pixels.
+    #[attribute]
+    pub height: Option<usize>,
+
+    /// The width of the image in pixels.
+    #[attribute]
+    pub width: Option<usize>,
+
+    /// The depth of the image in pixels, which is `1` for 2D images.
+    #[attribute]
+    #[optional_with_default = "1"]
+    pub depth: usize,
+
+    /// Metadata about the image.
+    #[child]
+    pub asset: Option<Asset>,
+
+    /// Where the image's pixels come from.
+    #[child]
+    pub source: ImageSource,
+
+    /// Arbitrary additional information about the image.
+    ///
+    /// For more information about 3rd-party extensions, see the
+    /// [crate-level documentation](../index.html#3rd-party-extensions).
+    #[child]
+    pub extras: Vec<Extra>,
+}
+
+/// The pixels of an [`Image`], embedded directly in the document.
+///
+/// [`Image`]: ./struct.Image.html
+#[derive(Debug, Clone, PartialEq, ColladaElement)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[name = "data"]
+pub struct ImageData {
+    /// The image data as a string of hexadecimal digits.
+    #[text]
+    pub hex: String,
+}
+
+/// Loads the pixels of an [`Image`] from an external file.
+///
+/// [`Image`]: ./struct.Image.html
+#[derive(Debug, Clone, PartialEq, ColladaElement)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[name = "init_from"]
+pub struct ImageInitFrom {
+    /// The location of the image file, usually relative to the document.
+    #[text]
+    pub uri: AnyUri,
+}
+
+/// The source of the pixels for an [`Image`].
+///
+/// [`Image`]: ./struct.Image.html
+#[derive(Debug, Clone, PartialEq, ColladaElement)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum ImageSource {
+    Data(ImageData),
+    InitFrom(ImageInitFrom),
+}
+
+#[derive(Debug, Clone, PartialEq, ColladaElement)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[name = "imager"]
+pub struct Imager;
+
+/// A mesh converted into a single indexed vertex buffer.
+///
+/// Returned by [`Mesh::to_indexed`]. Each vertex's attributes are stored at the same index in
+/// each of the attribute lists, and every 3 entries in `indices` form a triangle.
+///
+/// [`Mesh::to_indexed`]: ./struct.Mesh.html#method.to_indexed
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct IndexedMesh {
+    /// The position of each vertex.
+    pub positions: Vec<[f32; 3]>,
+
+    /// The normal of each vertex, if the mesh has normals.
+    pub normals: Option<Vec<[f32; 3]>>,
+
+    /// The texture coordinate of each vertex, if the mesh has texture coordinates.
+    pub texcoords: Option<Vec<[f32; 2]>>,
+
+    /// The vertex indices of each triangle.
+    pub indices: Vec<u32>,
+}
+
+/// The index of refraction of a surface, used by the common profile's shaders.
+#[derive(Debug, Clone, PartialEq, ColladaElement)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[name = "index_of_refraction"]
+pub struct IndexOfRefraction {
+    #[child]
+    pub value: FloatOrParam,
+}
+
+/// The principal moments of inertia of a rigid body, along the X, Y, and Z axes of its mass frame.
+#[derive(Debug, Clone, PartialEq, ColladaElement)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[name = "inertia"]
+pub struct Inertia {
+    /// An identifier for the value that's unique amongst its sibling elements.
+    #[attribute]
+    pub sid: Option<String>,
+
+    #[text]
+    pub data: Vec<f64>,
+}
+
+#[derive(Debug, Clone, PartialEq, ColladaElement)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[name = "init_as_null"]
+pub struct InitAsNull;
+
+#[derive(Debug, Clone, PartialEq, ColladaElement)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[name = "init_as_target"]
+pub struct InitAsTarget;
+
+#[derive(Debug, Clone, PartialEq, ColladaElement)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[name = "init_cube"]
+pub struct InitCube;
+
+/// Initializes a [`Surface`] from an image.
+///
+/// [`Surface`]: ./struct.Surface.html
+#[derive(Debug, Clone, PartialEq, ColladaElement)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[name = "init_from"]
+pub struct InitFrom {
+    /// The mip level of the surface that the image initializes.
+    #[attribute]
+    #[optional_with_default = "0"]
+    pub mip: usize,
+
+    /// The slice of a 3D surface that the image initializes.
+    #[attribute]
+    #[optional_with_default = "0"]
+    pub slice: usize,
+
+    /// The face of a cube map surface that the image initializes, e.g. `"POSITIVE_X"`.
+    ///
+    /// The spec defaults this to `"POSITIVE_X"` if it isn't specified.
+    #[attribute]
+    pub face: Option<String>,
+
+    /// The ID of the `<image>` used to initialize the surface.
+    #[text]
+    pub image: String,
+}
+
+#[derive(Debug, Clone, PartialEq, ColladaElement)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[name = "init_planar"]
+pub struct InitPlanar;
+
+#[derive(Debug, Clone, PartialEq, ColladaElement)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[name = "init_volume"]
+pub struct InitVolume;
+
+#[derive(Debug, Clone)]
+pub struct InputsForOffset<'a> {
+    inputs: ::std::slice::Iter<'a, SharedInput>,
+    offset: usize,
+}
+
+impl<'a> Iterator for InputsForOffset<'a> {
+    type Item = &'a SharedInput;
+
+    fn next(&mut self) -> Option<&'a SharedInput> {
+        while let Some(input) = self.inputs.next() {
+            if input.offset == self.offset {
+                return Some(input);
+            }
+        }
+
+        None
+    }
+}
+
+/// Instantiates an animation, e.g. as part of an [`AnimationClip`].
+///
+/// [`AnimationClip`]: ./struct.AnimationClip.html
+#[derive(Debug, Clone, PartialEq, ColladaElement)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[name = "instance_animation"]
+pub struct InstanceAnimation {
+    /// An identifier for the instance that's unique amongst its sibling elements.
+    #[attribute]
+    pub sid: Option<String>,
+
+    /// The human-friendly name for this instance.
+    ///
+    /// Has no semantic meaning.
+    #[attribute]
+    pub name: Option<String>,
+
+    /// The location of the instantiated animation.
+    #[attribute]
+    pub url: AnyUri,
+
+    /// Arbitrary additional information about this instance.
+    ///
+    /// For more information about 3rd-party extensions, see the
+    /// [crate-level documentation](../index.html#3rd-party-extensions).
+    #[child]
+    pub extras: Vec<Extra>,
+}
+
+/// Instantiates a [`Camera`] within a [`Node`].
+///
+/// [`Camera`]: ./struct.Camera.html
+/// [`Node`]: ./struct.Node.html
+#[derive(Debug, Clone, PartialEq, ColladaElement)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[name = "instance_camera"]
+pub struct InstanceCamera {
+    /// An identifier for the instance that's unique amongst its sibling elements.
+    #[attribute]
+    pub sid: Option<String>,
+
+    /// The human-friendly name for this instance.
+    ///
+    /// Has no semantic meaning.
+    #[attribute]
+    pub name: Option<String>,
+
+    /// The location of the instantiated camera.
+    #[attribute]
+    pub url: AnyUri,
+
+    /// Arbitrary additional information about this instance.
+    ///
+    /// For more information about 3rd-party extensions, see the
+    /// [crate-level documentation](../index.html#3rd-party-extensions).
+    #[child]
+    pub extras: Vec<Extra>,
+}
+
+#[derive(Debug, Clone, PartialEq, ColladaElement)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[name = "instance_controller"]
+pub struct InstanceController;
+
+/// Instantiates an [`Effect`] for use by a [`Material`].
+///
+/// [`Effect`]: ./struct.Effect.html
+/// [`Material`]: ./struct.Material.html
+#[derive(Debug, Clone, PartialEq, ColladaElement)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[name = "instance_effect"]
+pub struct InstanceEffect {
+    /// An identifier for the instance that's unique amongst its sibling elements.
+    #[attribute]
+    pub sid: Option<String>,
+
+    /// The human-friendly name for this instance.
+    ///
+    /// Has no semantic meaning.
+    #[attribute]
+    pub name: Option<String>,
+
+    /// The location of the instantiated effect.
+    #[attribute]
+    pub url: AnyUri,
+
+    #[child]
+    pub technique_hints: Vec<TechniqueHint>,
+
+    #[child]
+    pub set_params: Vec<SetParam>,
+
+    /// Arbitrary additional information about this instance.
+    ///
+    /// For more information about 3rd-party extensions, see the
+    /// [crate-level documentation](../index.html#3rd-party-extensions).
+    #[child]
+    pub extras: Vec<Extra>,
+}
+
+/// Instantiates a [`ForceField`].
+///
+/// [`ForceField`]: ./struct.ForceField.html
+#[derive(Debug, Clone, PartialEq, ColladaElement)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[name = "instance_force_field"]
+pub struct InstanceForceField {
+    /// An identifier for the instance that's unique amongst its sibling elements.
+    #[attribute]
+    pub sid: Option<String>,
+
+    /// The human-friendly name for this instance.
+    ///
+    /// Has no semantic meaning.
+    #[attribute]
+    pub name: Option<String>,
+
+    /// The location of the instantiated force field.
+    #[attribute]
+    pub url: AnyUri,
+
+    /// Arbitrary additional information about this instance.
+    ///
+    /// For more information about 3rd-party extensions, see the
+    /// [crate-level documentation](../index.html#3rd-party-extensions).
+    #[child]
+    pub extras: Vec<Extra>,
+}
+
+/// Instantiates a [`Geometry`] within a [`Node`].
+///
+/// [`Geometry`]: ./struct.Geometry.html
+/// [`Node`]: ./struct.Node.html
+#[derive(Debug, Clone, PartialEq, ColladaElement)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[name = "instance_geometry"]
+pub struct InstanceGeometry {
+    /// An identifier for the instance that's unique amongst its sibling elements.
+    #[attribute]
+    pub sid: Option<String>,
+
+    /// The human-friendly name for this instance.
+    ///
+    /// Has no semantic meaning.
+    #[attribute]
+    pub name: Option<String>,
+
+    /// The location of the instantiated geometry.
+    #[attribute]
+    pub url: AnyUri,
+
+    /// Binds the material symbols used by the geometry's primitives to concrete materials.
+    #[child]
+    pub bind_material: Option<BindMaterial>,
+
+    /// Arbitrary additional information about this instance.
+    ///
+    /// For more information about 3rd-party extensions, see the
+    /// [crate-level documentation](../index.html#3rd-party-extensions).
+    #[child]
+    pub extras: Vec<Extra>,
+}
+
+/// Instantiates a light within a [`Node`].
+///
+/// Light libraries aren't parsed yet, so the instantiated light can't be resolved.
+///
+/// [`Node`]: ./struct.Node.html
+#[derive(Debug, Clone, PartialEq, ColladaElement)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[name = "instance_light"]
+pub struct InstanceLight {
+    /// An identifier for the instance that's unique amongst its sibling elements.
+    #[attribute]
+    pub sid: Option<String>,
+
+    /// The human-friendly name for this instance.
+    ///
+    /// Has no semantic meaning.
+    #[attribute]
+    pub name: Option<String>,
+
+    /// The location of the instantiated light.
+    #[attribute]
+    pub url: AnyUri,
+
+    /// Arbitrary additional information about this instance.
+    ///
+    /// For more information about 3rd-party extensions, see the
+    /// [crate-level documentation](../index.html#3rd-party-extensions).
+    #[child]
+    pub extras: Vec<Extra>,
+}
+
+/// Instantiates a [`Material`] for a symbol used by an instantiated geometry.
+///
+/// [`Material`]: ./struct.Material.html
+#[derive(Debug, Clone, PartialEq, ColladaElement)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[name = "instance_material"]
+pub struct InstanceMaterial {
+    /// An identifier for the instance that's unique amongst its sibling elements.
+    #[attribute]
+    pub sid: Option<String>,
+
+    /// The human-friendly name for this instance.
+    ///
+    /// Has no semantic meaning.
+    #[attribute]
+    pub name: Option<String>,
+
+    /// The material symbol used by the geometry's primitives.
+    #[attribute]
+    pub symbol: String,
+
+    /// The location of the instantiated material.
+    #[attribute]
+    pub target: UriFragment,
+
+    /// Binds the material's effect parameters to values in the scene.
+    #[child]
+    pub binds: Vec<Bind>,
+
+    /// Binds the material's varying effect parameters, such as texture coordinates, to the
+    /// geometry's vertex inputs.
+    #[child]
+    pub bind_vertex_inputs: Vec<BindVertexInput>,
+
+    /// Arbitrary additional information about this instance.
+    ///
+    /// For more information about 3rd-party extensions, see the
+    /// [crate-level documentation](../index.html#3rd-party-extensions).
+    #[child]
+    pub extras: Vec<Extra>,
+}
+
+impl InstanceMaterial {
+    /// Returns the vertex input binding for the effect parameter with the semantic `semantic`.
+    ///
+    /// A texture in the material's effect names the texture coordinates it uses with its
+    /// `texcoord` semantic, and the binding determines which of the geometry's texture coordinate
+    /// sets provide them.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use collaborate::v1_4::{InstanceMaterial, Texture};
+    /// # fn example(instance_material: &InstanceMaterial, texture: &Texture) {
+    /// let set = instance_material.bind_vertex_input(&texture.texcoord)
+    ///     .and_then(|binding| binding.input_set)
+    ///     .unwrap_or(0);
+    /// # }
+    /// ```
+    pub fn bind_vertex_input(&self, semantic: &str) -> Option<&BindVertexInput> {
+        self.bind_vertex_inputs.iter().find(|binding| binding.semantic == semantic)
+    }
+}
+
+/// Instantiates a [`Node`] as a child of another node.
+///
+/// This allows a node hierarchy to be declared once and reused throughout a scene. Use
+/// [`Collada::resolve_node`] to find the instantiated node.
+///
+/// [`Node`]: ./struct.Node.html
+/// [`Collada::resolve_node`]: ./struct.Collada.html#method.resolve_node
+#[derive(Debug, Clone, PartialEq, ColladaElement)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[name = "instance_node"]
+pub struct InstanceNode {
+    /// An identifier for the instance that's unique amongst its sibling elements.
+    #[attribute]
+    pub sid: Option<String>,
+
+    /// The human-friendly name for this instance.
+    ///
+    /// Has no semantic meaning.
+    #[attribute]
+    pub name: Option<String>,
+
+    /// The location of the instantiated node.
+    ///
+    /// This is usually a reference to a node in the same document (e.g. `"#wheel"`), but may
+    /// also point into another document.
+    #[attribute]
+    pub url: AnyUri,
+
+    /// The location of a node to use in place of the instantiated node, e.g. a simplified version
+    /// of the node used while the full node is being loaded.
+    #[attribute]
+    pub proxy: Option<AnyUri>,
+
+    /// Arbitrary additional information about this instance.
+    ///
+    /// For more information about 3rd-party extensions, see the
+    /// [crate-level documentation](../index.html#3rd-party-extensions).
+    #[child]
+    pub extras: Vec<Extra>,
+}
+
+/// Instantiates a [`PhysicsMaterial`].
+///
+/// [`PhysicsMaterial`]: ./struct.PhysicsMaterial.html
+#[derive(Debug, Clone, PartialEq, ColladaElement)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[name = "instance_physics_material"]
+pub struct InstancePhysicsMaterial {
+    /// An identifier for the instance that's unique amongst its sibling elements.
+    #[attribute]
+    pub sid: Option<String>,
+
+    /// The human-friendly name for this instance.
+    ///
+    /// Has no semantic meaning.
+    #[attribute]
+    pub name: Option<String>,
+
+    /// The location of the instantiated material.
+    #[attribute]
+    pub url: AnyUri,
+
+    /// Arbitrary additional information about this instance.
+    ///
+    /// For more information about 3rd-party extensions, see the
+    /// [crate-level documentation](../index.html#3rd-party-extensions).
+    #[child]
+    pub extras: Vec<Extra>,
+}
+
+/// Instantiates a [`PhysicsModel`], either within another physics model or in a physics scene.
+///
+/// [`PhysicsModel`]: ./struct.PhysicsModel.html
+#[derive(Debug, Clone, PartialEq, ColladaElement)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[name = "instance_physics_model"]
+pub struct InstancePhysicsModel {
+    /// An identifier for the instance that's unique amongst its sibling elements.
+    #[attribute]
+    pub sid: Option<String>,
+
+    /// The human-friendly name for this instance.
+    ///
+    /// Has no semantic meaning.
+    #[attribute]
+    pub name: Option<String>,
+
+    /// The location of the instantiated physics model.
+    #[attribute]
+    pub url: AnyUri,
+
+    /// The node that the instantiated model's rigid bodies are positioned relative to.
+    #[attribute]
+    pub parent: Option<AnyUri>,
+
+    #[child]
+    pub instance_force_fields: Vec<InstanceForceField>,
+
+    #[child]
+    pub instance_rigid_bodies: Vec<InstanceRigidBody>,
+
+    #[child]
+    pub instance_rigid_constraints: Vec<InstanceRigidConstraint>,
+
+    /// Arbitrary additional information about this instance.
+    ///
+    /// For more information about 3rd-party extensions, see the
+    /// [crate-level documentation](../index.html#3rd-party-extensions).
+    #[child]
+    pub extras: Vec<Extra>,
+}
+
+#[derive(Debug, Clone, PartialEq, ColladaElement)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[name = "instance_rigid_body"]
+pub struct InstanceRigidBody;
+
+#[derive(Debug, Clone, PartialEq, ColladaElement)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[name = "instance_rigid_constraint"]
+pub struct InstanceRigidConstraint;
+
+/// Instantiates a [`PhysicsScene`].
+///
+/// [`PhysicsScene`]: ./struct.PhysicsScene.html
+#[derive(Debug, Clone, PartialEq, ColladaElement)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[name = "instance_physics_scene"]
+pub struct InstancePhysicsScene {
+    /// An identifier for the instance that's unique amongst its sibling elements.
+    #[attribute]
+    pub sid: Option<String>,
+
+    /// The human-friendly name for this instance.
+    ///
+    /// Has no semantic meaning.
+    #[attribute]
+    pub name: Option<String>,
+
+    /// The location of the instantiated physics scene.
+    #[attribute]
+    pub url: AnyUri,
+
+    /// Arbitrary additional information about this instance.
+    ///
+    /// For more information about 3rd-party extensions, see the
+    /// [crate-level documentation](../index.html#3rd-party-extensions).
+    #[child]
+    pub extras: Vec<Extra>,
+}
+
+/// Instantiates a [`VisualScene`].
+///
+/// [`VisualScene`]: ./struct.VisualScene.html
+#[derive(Debug, Clone, PartialEq, ColladaElement)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[name = "instance_visual_scene"]
+pub struct InstanceVisualScene {
+    /// An identifier for the instance that's unique amongst its sibling elements.
+    #[attribute]
+    pub sid: Option<String>,
+
+    /// The human-friendly name for this instance.
+    ///
+    /// Has no semantic meaning.
+    #[attribute]
+    pub name: Option<String>,
+
+    /// The location of the instantiated visual scene.
+    #[attribute]
+    pub url: AnyUri,
+
+    /// Arbitrary additional information about this instance.
+    ///
+    /// For more information about 3rd-party extensions, see the
+    /// [crate-level documentation](../index.html#3rd-party-extensions).
+    #[child]
+    pub extras: Vec<Extra>,
+}
+
+#[derive(Debug, Clone, PartialEq, ColladaElement)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[name = "int_array"]
+pub struct IntArray;
+
+/// A shader that produces a diffuse shaded surface that is independent of lighting.
+///
+/// The result is based on Lambert's law, which states that when light hits a rough surface, the
+/// light is reflected in all directions equally.
+#[derive(Debug, Clone, PartialEq, ColladaElement)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[name = "lambert"]
+pub struct Lambert {
+    /// The light emitted by the surface.
+    #[child]
+    pub emission: Option<Emission>,
+
+    /// The light reflected from ambient light sources.
+    #[child]
+    pub ambient: Option<Ambient>,
+
+    /// The light diffusely reflected from the surface.
+    #[child]
+    pub diffuse: Option<Diffuse>,
+
+    /// The color of a perfect mirror reflection.
+    #[child]
+    pub reflective: Option<Reflective>,
+
+    /// The amount of perfect mirror reflection to be added.
+    #[child]
+    pub reflectivity: Option<Reflectivity>,
+
+    /// The color of perfectly refracted light.
+    #[child]
+    pub transparent: Option<Transparent>,
+
+    /// The amount of perfectly refracted light added.
+    #[child]
+    pub transparency: Option<Transparency>,
+
+    /// The index of refraction for perfectly refracted light.
+    #[child]
+    pub index_of_refraction: Option<IndexOfRefraction>,
+}
+
+/// A single library of unknown type.
+///
+/// Each variant wraps a single value containing the library data. See the documentation for
+/// each of the possible library types for more information on what data each can contain.
+#[derive(Debug, Clone, PartialEq, ColladaElement)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum Library {
+    Animations(LibraryAnimations),
+    AnimationClips(LibraryAnimationClips),
+    Cameras(LibraryCameras),
+    Controllers(LibraryControllers),
+    Effects(LibraryEffects),
+    ForceFields(LibraryForceFields),
+    Geometries(LibraryGeometries),
+    Images(LibraryImages),
+    Lights(LibraryLights),
+    Materials(LibraryMaterials),
+    Nodes(LibraryNodes),
+    PhysicsMaterials(LibraryPhysicsMaterials),
+    PhysicsModels(LibraryPhysicsModels),
+    PhysicsScenes(LibraryPhysicsScenes),
+    VisualScenes(LibraryVisualScenes),
+}
+
+impl Library {
+    /// Extends `chain` with the assets leading to the element with the ID `id` in this library.
+    ///
+    /// Returns `false`, leaving `chain` untouched, if the library has no such element.
+    fn asset_chain<'a>(&'a self, id: &str, chain: &mut Vec<&'a Asset>) -> bool {
+        fn items<'a, I>(
+            library_asset: &'a Option<Asset>,
+            items: I,
+            id: &str,
+            chain: &mut Vec<&'a Asset>,
+        ) -> bool
+        where
+            I: IntoIterator<Item = (Option<&'a str>, &'a Option<Asset>)>,
+        {
+            match items.into_iter().find(|&(item_id, _)| item_id == Some(id)) {
+                Some((_, asset)) => {
+                    chain.extend(library_asset);
+                    chain.extend(asset);
+                    true
+                }
+
+                None => false,
+            }
+        }
+
+        match *self {
+            Library::AnimationClips(ref library) => items(
+                &library.asset,
+                library.animation_clips.iter().map(|item| (item.id.as_deref(), &item.asset)),
+                id,
+                chain,
+            ),
+
+            Library::Cameras(ref library) => items(
+                &library.asset,
+                library.cameras.iter().map(|item| (item.id.as_deref(), &item.asset)),
+                id,
+                chain,
+            ),
+
+            Library::Effects(ref library) => items(
+                &library.asset,
+                library.effects.iter().map(|item| (Some(&*item.id), &item.asset)),
+                id,
+                chain,
+            ),
+
+            Library::ForceFields(ref library) => items(
+                &library.asset,
+                library.force_fields.iter().map(|item| (item.id.as_deref(), &item.asset)),
+                id,
+                chain,
+            ),
+
+            Library::Geometries(ref library) => items(
+                &library.asset,
+                library.geometries.iter().map(|item| (item.id.as_deref(), &item.asset)),
+                id,
+                chain,
+            ),
+
+            Library::Images(ref library) => items(
+                &library.asset,
+                library.images.iter().map(|item| (item.id.as_deref(), &item.asset)),
+                id,
+                chain,
+            ),
+
+            Library::Materials(ref library) => items(
+                &library.asset,
+                library.materials.iter().map(|item| (item.id.as_deref(), &item.asset)),
+                id,
+                chain,
+            ),
+
+            Library::PhysicsMaterials(ref library) => items(
+                &library.asset,
+                library.physics_materials.iter().map(|item| (item.id.as_deref(), &item.asset)),
+                id,
+                chain,
+            ),
+
+            Library::PhysicsModels(ref library) => items(
+                &library.asset,
+                library.physics_models.iter().map(|item| (item.id.as_deref(), &item.asset)),
+                id,
+                chain,
+            ),
+
+            Library::PhysicsScenes(ref library) => items(
+                &library.asset,
+                library.physics_scenes.iter().map(|item| (item.id.as_deref(), &item.asset)),
+                id,
+                chain,
+            ),
+
+            Library::Nodes(ref library) => {
+                let len = chain.len();
+                chain.extend(&library.asset);
+                if library.nodes.iter().any(|node| node.asset_chain(id, chain)) {
+                    return true;
+                }
+
+                chain.truncate(len);
+                false
+            }
+
+            Library::VisualScenes(ref library) => {
+                let len = chain.len();
+                chain.extend(&library.asset);
+                for scene in &library.visual_scenes {
+                    if scene.id.as_deref() == Some(id) {
+                        chain.extend(&scene.asset);
+                        return true;
+                    }
+
+                    let scene_len = chain.len();
+                    chain.extend(&scene.asset);
+                    if scene.nodes.iter().any(|node| node.asset_chain(id, chain)) {
+                        return true;
+                    }
+                    chain.truncate(scene_len);
+                }
+
+                chain.truncate(len);
+                false
+            }
+
+            // These libraries are stubbed out, so there's nothing to search.
+            Library::Animations(_) | Library::Controllers(_) | Library::Lights(_) => false,
+        }
+    }
+
+    pub fn as_library_animation_clips(&self) -> Option<&LibraryAnimationClips> {
+        match *self {
+            Library::AnimationClips(ref library_animation_clips) => Some(library_animation_clips),
+            _ => None,
+        }
+    }
+
+    pub fn as_library_cameras(&self) -> Option<&LibraryCameras> {
+        match *self {
+            Library::Cameras(ref library_cameras) => Some(library_cameras),
+            _ => None,
+        }
+    }
+
+    pub fn as_library_effects(&self) -> Option<&LibraryEffects> {
+        match *self {
+            Library::Effects(ref library_effects) => Some(library_effects),
+            _ => None,
+        }
+    }
+
+    pub fn as_library_force_fields(&self) -> Option<&LibraryForceFields> {
+        match *self {
+            Library::ForceFields(ref library_force_fields) => Some(library_force_fields),
+            _ => None,
+        }
+    }
+
+    pub fn as_library_geometries(&self) -> Option<&LibraryGeometries> {
+        match *self {
+            Library::Geometries(ref library_geometries) => Some(library_geometries),
+            _ => None,
+        }
+    }
+
+    pub fn as_library_images(&self) -> Option<&LibraryImages> {
+        match *self {
+            Library::Images(ref library_images) => Some(library_images),
+            _ => None,
+        }
+    }
+
+    pub fn as_library_materials(&self) -> Option<&LibraryMaterials> {
+        match *self {
+            Library::Materials(ref library_materials) => Some(library_materials),
+            _ => None,
+        }
+    }
+
+    pub fn as_library_nodes(&self) -> Option<&LibraryNodes> {
+        match *self {
+            Library::Nodes(ref library_nodes) => Some(library_nodes),
+            _ => None,
+        }
+    }
+
+    pub fn as_library_physics_materials(&self) -> Option<&LibraryPhysicsMaterials> {
+        match *self {
+            Library::PhysicsMaterials(ref library_physics_materials) => Some(library_physics_materials),
+            _ => None,
+        }
+    }
+
+    pub fn as_library_physics_models(&self) -> Option<&LibraryPhysicsModels> {
+        match *self {
+            Library::PhysicsModels(ref library_physics_models) => Some(library_physics_models),
+            _ => None,
+        }
+    }
+
+    pub fn as_library_physics_scenes(&self) -> Option<&LibraryPhysicsScenes> {
+        match *self {
+            Library::PhysicsScenes(ref library_physics_scenes) => Some(library_physics_scenes),
+            _ => None,
+        }
+    }
+
+    pub fn as_library_visual_scenes(&self) -> Option<&LibraryVisualScenes> {
+        match *self {
+            Library::VisualScenes(ref library_visual_scenes) => Some(library_visual_scenes),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, ColladaElement)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[name = "library_animations"]
+pub struct LibraryAnimations;
+
+/// Contains the animation clips in the document.
+#[derive(Debug, Clone, PartialEq, ColladaElement)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[name = "library_animation_clips"]
+pub struct LibraryAnimationClips {
+    /// A unique identifier for the library.
+    ///
+    /// Will be unique within the document.
+    #[attribute]
+    pub id: Option<String>,
+
+    /// The human-friendly name for this library.
+    ///
+    /// Has no semantic meaning.
+    #[attribute]
+    pub name: Option<String>,
+
+    /// Metada about the library and the data contained within it.
+    #[child]
+    pub asset: Option<Asset>,
+
+    /// The animation clips contained within this library instance.
+    ///
+    /// There will always be at least one clip in a `LibraryAnimationClips`.
+    #[child]
+    #[required]
+    pub animation_clips: Vec<AnimationClip>,
+
+    /// Arbitrary additional information about this library and the data it contains.
+    ///
+    /// For more information about 3rd-party extensions, see the
+    /// [crate-level documentation](../index.html#3rd-party-extensions).
+    #[child]
+    pub extras: Vec<Extra>,
+}
+
+/// Contains the cameras in the document.
+#[derive(Debug, Clone, PartialEq, ColladaElement)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[name = "library_cameras"]
+pub struct LibraryCameras {
+    /// A unique identifier for the library.
+    ///
+    /// Will be unique within the document.
+    #[attribute]
+    pub id: Option<String>,
+
+    /// The human-friendly name for this library.
+    ///
+    /// Has no semantic meaning.
+    #[attribute]
+    pub name: Option<String>,
+
+    /// Metada about the library and the data contained within it.
+    #[child]
+    pub asset: Option<Asset>,
+
+    /// The cameras contained within this library instance.
+    ///
+    /// There will always be at least one camera in a `LibraryCameras`.
+    #[child]
+    #[required]
+    pub cameras: Vec<Camera>,
+
+    /// Arbitrary additional information about this library and the data it contains.
+    ///
+    /// For more information about 3rd-party extensions, see the
+    /// [crate-level documentation](../index.html#3rd-party-extensions).
+    #[child]
+    pub extras: Vec<Extra>,
+}
+
+#[derive(Debug, Clone, PartialEq, ColladaElement)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[name = "library_controllers"]
+pub struct LibraryControllers;
+
+/// Contains the effects for the document.
+///
+/// The effects are contained in `effects` by one or more [`Effect`] instances,
+/// `LibraryEffects` is only a container and does not represent any effect itself.
+///
+/// [`Effect`]: ./struct.Effect.html
+#[derive(Debug, Clone, PartialEq, ColladaElement)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[name = "library_effects"]
+pub struct LibraryEffects {
+    /// A unique identifier for the library.
+    ///
+    /// Will be unique within the document.
+    #[attribute]
+    pub id: Option<String>,
+
+    /// The human-friendly name for this library.
+    ///
+    /// Has no semantic meaning.
+    #[attribute]
+    pub name: Option<String>,
+
+    /// Metada about the library and the data contained within it.
+    #[child]
+    pub asset: Option<Asset>,
+
+    /// The effects contained within this library instance.
+    ///
+    /// There will always be at least one effect in a `LibraryEffects`.
+    #[child]
+    #[required]
+    pub effects: Vec<Effect>,
+
+    /// Arbitrary additional information about this library and the data it contains.
+    ///
+    /// For more information about 3rd-party extensions, see the
+    /// [crate-level documentation](../index.html#3rd-party-extensions).
+    #[child]
+    pub extras: Vec<Extra>,
+}
+
+/// Contains the force fields in the document.
+#[derive(Debug, Clone, PartialEq, ColladaElement)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[name = "library_force_fields"]
+pub struct LibraryForceFields {
+    /// A unique identifier for the library.
+    ///
+    /// Will be unique within the document.
+    #[attribute]
+    pub id: Option<String>,
+
+    /// The human-friendly name for this library.
+    ///
+    /// Has no semantic meaning.
+    #[attribute]
+    pub name: Option<String>,
+
+    /// Metada about the library and the data contained within it.
+    #[child]
+    pub asset: Option<Asset>,
+
+    /// The force fields contained within this library instance.
+    ///
+    /// There will always be at least one force field in a `LibraryForceFields`.
+    #[child]
+    #[required]
+    pub force_fields: Vec<ForceField>,
+
+    /// Arbitrary additional information about this library and the data it contains.
+    ///
+    /// For more information about 3rd-party extensions, see the
+    /// [crate-level documentation](../index.html#3rd-party-extensions).
+    #[child]
+    pub extras: Vec<Extra>,
+}
+
+/// Contains geometric data for the document.
+///
+/// The geometric data is contained in `geometries` by one or more [`Geometry`] instances,
+/// `LibraryGeometries` is only a container and does not represent any geometric data itself.
+///
+/// [`Geometry`]: ./struct.Geometry.html
+#[derive(Debug, Clone, PartialEq, ColladaElement)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[name = "library_geometries"]
+pub struct LibraryGeometries {
+    /// A unique identifier for the library.
+    ///
+    /// Will be unique within the document.
+    #[attribute]
+    pub id: Option<String>,
+
+    /// The human-friendly name for this library.
+    ///
+    /// Has no semantic meaning.
+    #[attribute]
+    pub name: Option<String>,
+
+    /// Metada about the library and the data contained within it.
+    #[child]
+    pub asset: Option<Asset>,
+
+    /// The geometric data contained within this library instance.
+    ///
+    /// There will always be at least one geometric element in a `LibraryGeometries`.
+    #[child]
+    #[required]
+    pub geometries: Vec<Geometry>,
+
+    /// Arbitrary additional information about this library and the data it contains.
+    ///
+    /// For more information about 3rd-party extensions, see the
+    /// [crate-level documentation](../index.html#3rd-party-extensions).
+    #[child]
+    pub extras: Vec<Extra>,
+}
+
+impl LibraryGeometries {
+    /// Returns an iterator over all the [`Geometry`] objects contained in this library.
+    ///
+    /// [`Geometry`]: ./struct.Geometry.html
+    pub fn geometries<'a>(&'a self) -> ::std::slice::Iter<'a, Geometry> {
+        self.geometries.iter()
+    }
+}
+
+/// Contains the images in the document.
+///
+/// The images are contained in `images` by one or more [`Image`] instances, `LibraryImages` is
+/// only a container and does not represent an image itself.
+///
+/// [`Image`]: ./struct.Image.html
+#[derive(Debug, Clone, PartialEq, ColladaElement)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[name = "library_images"]
+pub struct LibraryImages {
+    /// A unique identifier for the library.
+    ///
+    /// Will be unique within the document.
+    #[attribute]
+    pub id: Option<String>,
+
+    /// The human-friendly name for this library.
+    ///
+    /// Has no semantic meaning.
+    #[attribute]
+    pub name: Option<String>,
+
+    /// Metada about the library and the data contained within it.
+    #[child]
+    pub asset: Option<Asset>,
+
+    /// The images contained within this library instance.
+    ///
+    /// There will always be at least one image in a `LibraryImages`.
+    #[child]
+    #[required]
+    pub images: Vec<Image>,
+
+    /// Arbitrary additional information about this library and the data it contains.
+    ///
+    /// For more information about 3rd-party extensions, see the
+    /// [crate-level documentation](../index.html#3rd-party-extensions).
+    #[child]
+    pub extras: Vec<Extra>,
+}
+
+#[derive(Debug, Clone, PartialEq, ColladaElement)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[name = "library_lights"]
+pub struct LibraryLights;
+
+/// Contains the materials for the document.
+///
+/// The materials are contained in `materials` by one or more [`Material`] instances,
+/// `LibraryMaterials` is only a container and does not represent any material itself.
+///
+/// [`Material`]: ./struct.Material.html
+#[derive(Debug, Clone, PartialEq, ColladaElement)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[name = "library_materials"]
+pub struct LibraryMaterials {
+    /// A unique identifier for the library.
+    ///
+    /// Will be unique within the document.
+    #[attribute]
+    pub id: Option<String>,
+
+    /// The human-friendly name for this library.
+    ///
+    /// Has no semantic meaning.
+    #[attribute]
+    pub name: Option<String>,
+
+    /// Metada about the library and the data contained within it.
+    #[child]
+    pub asset: Option<Asset>,
+
+    /// The materials contained within this library instance.
+    ///
+    /// There will always be at least one material in a `LibraryMaterials`.
+    #[child]
+    #[required]
+    pub materials: Vec<Material>,
+
+    /// Arbitrary additional information about this library and the data it contains.
+    ///
+    /// For more information about 3rd-party extensions, see the
+    /// [crate-level documentation](../index.html#3rd-party-extensions).
+    #[child]
+    pub extras: Vec<Extra>,
+}
+
+/// Contains node hierarchies that can be instantiated with an [`InstanceNode`].
+///
+/// The nodes are contained in `nodes` by one or more [`Node`] instances, `LibraryNodes` is only a
+/// container and does not represent a node itself.
+///
+/// [`InstanceNode`]: ./struct.InstanceNode.html
+/// [`Node`]: ./struct.Node.html
+#[derive(Debug, Clone, PartialEq, ColladaElement)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[name = "library_nodes"]
+pub struct LibraryNodes {
+    /// A unique identifier for the library.
+    ///
+    /// Will be unique within the document.
+    #[attribute]
+    pub id: Option<String>,
+
+    /// The human-friendly name for this library.
+    ///
+    /// Has no semantic meaning.
+    #[attribute]
+    pub name: Option<String>,
+
+    /// Metada about the library and the data contained within it.
+    #[child]
+    pub asset: Option<Asset>,
+
+    /// The nodes contained within this library instance.
+    ///
+    /// There will always be at least one node in a `LibraryNodes`.
+    #[child]
+    #[required]
+    pub nodes: Vec<Node>,
+
+    /// Arbitrary additional information about this library and the data it contains.
+    ///
+    /// For more information about 3rd-party extensions, see the
+    /// [crate-level documentation](../index.html#3rd-party-extensions).
+    #[child]
+    pub extras: Vec<Extra>,
+}
+
+/// Contains the physics materials in the document.
+#[derive(Debug, Clone, PartialEq, ColladaElement)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[name = "library_physics_materials"]
+pub struct LibraryPhysicsMaterials {
+    /// A unique identifier for the library.
+    ///
+    /// Will be unique within the document.
+    #[attribute]
+    pub id: Option<String>,
+
+    /// The human-friendly name for this library.
+    ///
+    /// Has no semantic meaning.
+    #[attribute]
+    pub name: Option<String>,
+
+    /// Metada about the library and the data contained within it.
+    #[child]
+    pub asset: Option<Asset>,
+
+    /// The physics materials contained within this library instance.
+    ///
+    /// There will always be at least one material in a `LibraryPhysicsMaterials`.
+    #[child]
+    #[required]
+    pub physics_materials: Vec<PhysicsMaterial>,
+
+    /// Arbitrary additional information about this library and the data it contains.
+    ///
+    /// For more information about 3rd-party extensions, see the
+    /// [crate-level documentation](../index.html#3rd-party-extensions).
+    #[child]
+    pub extras: Vec<Extra>,
+}
+
+/// Contains the physics models in the document.
+#[derive(Debug, Clone, PartialEq, ColladaElement)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[name = "library_physics_models"]
+pub struct LibraryPhysicsModels {
+    /// A unique identifier for the library.
+    ///
+    /// Will be unique within the document.
+    #[attribute]
+    pub id: Option<String>,
+
+    /// The human-friendly name for this library.
+    ///
+    /// Has no semantic meaning.
+    #[attribute]
+    pub name: Option<String>,
+
+    /// Metada about the library and the data contained within it.
+    #[child]
+    pub asset: Option<Asset>,
+
+    /// The physics models contained within this library instance.
+    ///
+    /// There will always be at least one model in a `LibraryPhysicsModels`.
+    #[child]
+    #[required]
+    pub physics_models: Vec<PhysicsModel>,
+
+    /// Arbitrary additional information about this library and the data it contains.
+    ///
+    /// For more information about 3rd-party extensions, see the
+    /// [crate-level documentation](../index.html#3rd-party-extensions).
+    #[child]
+    pub extras: Vec<Extra>,
+}
+
+/// Contains the physics scenes in the document.
+#[derive(Debug, Clone, PartialEq, ColladaElement)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[name = "library_physics_scenes"]
+pub struct LibraryPhysicsScenes {
+    /// A unique identifier for the library.
+    ///
+    /// Will be unique within the document.
+    #[attribute]
+    pub id: Option<String>,
+
+    /// The human-friendly name for this library.
+    ///
+    /// Has no semantic meaning.
+    #[attribute]
+    pub name: Option<String>,
+
+    /// Metada about the library and the data contained within it.
+    #[child]
+    pub asset: Option<Asset>,
+
+    /// The physics scenes contained within this library instance.
+    ///
+    /// There will always be at least one scene in a `LibraryPhysicsScenes`.
+    #[child]
+    #[required]
+    pub physics_scenes: Vec<PhysicsScene>,
+
+    /// Arbitrary additional information about this library and the data it contains.
+    ///
+    /// For more information about 3rd-party extensions, see the
+    /// [crate-level documentation](../index.html#3rd-party-extensions).
+    #[child]
+    pub extras: Vec<Extra>,
+}
+
+/// Contains the visual scenes for the document.
+///
+/// The scenes are contained in `visual_scenes` by one or more [`VisualScene`] instances,
+/// `LibraryVisualScenes` is only a container and does not represent a scene itself.
+///
+/// [`VisualScene`]: ./struct.VisualScene.html
+#[derive(Debug, Clone, PartialEq, ColladaElement)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[name = "library_visual_scenes"]
+pub struct LibraryVisualScenes {
+    /// A unique identifier for the library.
+    ///
+    /// Will be unique within the document.
+    #[attribute]
+    pub id: Option<String>,
+
+    /// The human-friendly name for this library.
+    ///
+    /// Has no semantic meaning.
+    #[attribute]
+    pub name: Option<String>,
+
+    /// Metada about the library and the data contained within it.
+    #[child]
+    pub asset: Option<Asset>,
+
+    /// The visual scenes contained within this library instance.
+    ///
+    /// There will always be at least one visual scene in a `LibraryVisualScenes`.
+    #[child]
+    #[required]
+    pub visual_scenes: Vec<VisualScene>,
+
+    /// Arbitrary additional information about this library and the data it contains.
+    ///
+    /// For more information about 3rd-party extensions, see the
+    /// [crate-level documentation](../index.html#3rd-party-extensions).
+    #[child]
+    pub extras: Vec<Extra>,
+}
+
+#[derive(Debug, Clone, PartialEq, ColladaElement)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[name = "lines"]
+pub struct Lines;
+
+#[derive(Debug, Clone, PartialEq, ColladaElement)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[name = "linestrips"]
+pub struct Linestrips;
+
+/// Positions and orients an object so that it faces a point of interest.
+///
+/// `data` contains 9 values: the position of the object, the position of the point of interest,
+/// and the direction that points up, in that order.
+#[derive(Debug, Clone, PartialEq, ColladaElement)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[name = "lookat"]
+pub struct LookAt {
+    /// An identifier for the transform that's unique amongst its sibling elements.
+    #[attribute]
+    pub sid: Option<String>,
+
+    #[text]
+    pub data: Vec<f64>,
+}
+
+impl LookAt {
+    /// Returns the transform as a column-major 4x4 matrix.
+    ///
+    /// The resulting matrix places the object at the eye position, with its local negative Z axis
+    /// facing the point of interest and its local Y axis as close to the up direction as
+    /// possible. Missing values are treated as `0.0`.
+    pub fn matrix(&self) -> [f64; 16] {
+        ::math::look_at(
+            vector3(&self.data, 0),
+            vector3(&self.data, 3),
+            vector3(&self.data, 6),
+        )
+    }
+}
+
+/// The mass of a rigid body or shape.
+#[derive(Debug, Clone, PartialEq, ColladaElement)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[name = "mass"]
+pub struct Mass {
+    /// An identifier for the value that's unique amongst its sibling elements.
+    #[attribute]
+    pub sid: Option<String>,
+
+    #[text]
+    pub value: f64,
+}
+
+/// The position and orientation of a rigid body's center of mass.
+#[derive(Debug, Clone, PartialEq, ColladaElement)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[name = "mass_frame"]
+pub struct MassFrame {
+    #[child]
+    #[required]
+    pub transforms: Vec<RigidTransform>,
+}
+
+/// Describes the visual appearance of a geometric object.
+///
+/// A material is an instance of an [`Effect`], with the effect being referenced by
+/// `instance_effect`.
+///
+/// [`Effect`]: ./struct.Effect.html
+#[derive(Debug, Clone, PartialEq, ColladaElement)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[name = "material"]
+pub struct Material {
+    /// A unique identifier for the material.
+    ///
+    /// Will be unique within the document.
+    #[attribute]
+    pub id: Option<String>,
+
+    /// The human-friendly name for this material.
+    ///
+    /// Has no semantic meaning.
+    #[attribute]
+    pub name: Option<String>,
+
+    /// Metadata about this material.
+    #[child]
+    pub asset: Option<Asset>,
+
+    /// The effect instantiated by this material.
+    #[child]
+    pub instance_effect: InstanceEffect,
+
+    /// Arbitrary additional information about this material.
+    ///
+    /// For more information about 3rd-party extensions, see the
+    /// [crate-level documentation](../index.html#3rd-party-extensions).
+    #[child]
+    pub extras: Vec<Extra>,
+}
+
+/// An arbitrary transform, given directly as a 4x4 matrix.
+///
+/// `data` contains the 16 values of the matrix in row-major order, which is the order they are
+/// listed in the document.
+#[derive(Debug, Clone, PartialEq, ColladaElement)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[name = "matrix"]
+pub struct Matrix {
+    /// An identifier for the transform that's unique amongst its sibling elements.
+    #[attribute]
+    pub sid: Option<String>,
+
+    #[text]
+    pub data: Vec<f64>,
+}
+
+impl Matrix {
+    /// Returns the transform as a column-major 4x4 matrix.
+    ///
+    /// Missing values are treated as `0.0`.
+    pub fn matrix(&self) -> [f64; 16] {
+        let mut values = [0.0; 16];
+        for (value, &data) in values.iter_mut().zip(self.data.iter()) {
+            *value = data;
+        }
+        ::math::from_row_major(&values)
+    }
+}
+
+/// Describes basic geometric meshes using vertex and primitive information.
+///
+/// Meshes embody a general form of geometric description that primarily includes vertex and
+/// primitive information. Vertex information is the set of attributes associated with a poin on
+/// the surface of the mesh. Each vertex includes data for attributes such as:
+///
+/// * Vertex position
+/// * Vertex color
+/// * Vertex normal
+/// * Vertex texture coordinate
+///
+/// The mesh also includes a description of how the vertices are organized to form the geometric
+/// shape of the mesh. The mesh vertices are collated into geometric primitives such as polygons,
+/// triangles, or lines.
+#[derive(Debug, Clone, PartialEq, ColladaElement)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[name = "mesh"]
+pub struct Mesh {
+    /// One or more [`Source`] instances containing the raw mesh data.
+    ///
+    /// These contain the raw data used to specify the vertex attributes of the vertices in the
+    /// mesh. The primitives in `primitives` will index into these sources to specify the mesh.
+    ///
+    /// [`Source`]: ./struct.Source.html
+    #[child]
+    #[required]
+    pub sources: Vec<Source>,
+
+    /// Describes the mesh's vertex attributes.
+    ///
+    /// `vertices` will have the [`UnsharedInput`] which specifies the "POSITION" attribute for
+    /// the mesh's vertices. It may also specify other mesh attributes.
+    ///
+    /// [`UnsharedInput`]: ./struct.UnsharedInput.html
+    #[child]
+    pub vertices: Vertices,
+
+    /// Geometric primitives that assemble values from the inputs into vertex attribute data.
+    #[child]
+    pub primitives: Vec<Primitive>,
+
+    /// Arbitrary additional information about this geometry instance and the data it contains.
+    ///
+    /// For more information about 3rd-party extensions, see the
+    /// [crate-level documentation](../index.html#3rd-party-extensions).
+    #[child]
+    pub extras: Vec<Extra>,
+}
+
+impl Mesh {
+    /// Returns the source which matches `id`, or `None` if no sources match.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # #![allow(unused_variables)]
+    /// # use std::fs::File;
+    /// # use collaborate::v1_4::{Collada, Library};
+    /// # let file = File::open("resources/blender_cube.dae").unwrap();
+    /// # let document = Collada::read(file).unwrap();
+    /// # let library = document.libraries_of(Library::as_library_geometries).next().unwrap();
+    /// let mesh = library.geometries[0].geometric_element.as_mesh().unwrap();
+    /// let positions_source = mesh.find_source("Cube-mesh-positions");
+    /// assert!(positions_source.is_some());
+    /// ```
+    pub fn find_source<'a>(&'a self, id: &str) -> Option<&'a Source> {
+        self.sources.iter().find(|source| source.id == id)
+    }
+
+    /// Finds the source or vertices with the given id.
+    ///
+    /// An input's `source` can refer to either one of the mesh's sources or, for the `"VERTEX"`
+    /// input of a primitive, to the mesh's `vertices`. This looks up `id` in both places, so any
+    /// input's source can be resolved the same way.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use collaborate::v1_4::{Collada, Library, MeshSourceRef};
+    /// # let document = Collada::from_path("resources/blender_cube.dae").unwrap();
+    /// # let library = document.libraries_of(Library::as_library_geometries).next().unwrap();
+    /// let mesh = library.geometries[0].mesh().unwrap();
+    /// match mesh.resolve_source_or_vertices("Cube-mesh-vertices") {
+    ///     Some(MeshSourceRef::Vertices(vertices)) => assert_eq!(1, vertices.inputs.len()),
+    ///     _ => panic!("Expected the mesh's vertices"),
+    /// }
+    /// ```
+    pub fn resolve_source_or_vertices<'a>(&'a self, id: &str) -> Option<MeshSourceRef<'a>> {
+        if self.vertices.id == id {
+            Some(MeshSourceRef::Vertices(&self.vertices))
+        } else {
+            self.find_source(id).map(MeshSourceRef::Source)
+        }
+    }
+
+    /// Returns an iterator over the primitives in the mesh.
+    pub fn primitives<'a>(&'a self) -> ::std::slice::Iter<'a, Primitive> {
+        self.primitives.iter()
+    }
+
+    /// Returns an iterator over the primitives in the mesh that use the material `symbol`.
+    ///
+    /// `symbol` is matched against the `material` attribute of each primitive, which is bound to
+    /// an actual material when the geometry is instantiated. Primitives without a material are
+    /// never returned.
+    pub fn primitives_for_material<'a>(&'a self, symbol: &str) -> impl Iterator<Item = &'a Primitive> {
+        let symbol = symbol.to_owned();
+        self.primitives.iter().filter(move |primitive| primitive.material() == Some(&*symbol))
+    }
+
+    /// Returns the semantics of every input that provides vertex data for the mesh.
+    ///
+    /// This includes the inputs of the mesh's `<vertices>` and the inputs of each primitive. The
+    /// `"VERTEX"` input of a primitive refers to `<vertices>`, so it's replaced by the semantics
+    /// of the inputs there (e.g. `"POSITION"`) rather than being included itself.
+    ///
+    /// This is a cheap way to check which attributes are available before calling
+    /// [`resolved_vertices`](#method.resolved_vertices).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use collaborate::v1_4::Collada;
+    /// let document = Collada::from_path("resources/blender_cube.dae").unwrap();
+    /// let mesh = document.geometry_by_id("Cube-mesh").unwrap().mesh().unwrap();
+    /// assert!(mesh.semantics().contains("POSITION"));
+    /// ```
+    pub fn semantics(&self) -> HashSet<&str> {
+        let vertex_semantics = self.vertices.inputs.iter().map(|input| &*input.semantic);
+        let primitive_semantics = self.primitives.iter()
+            .flat_map(|primitive| primitive.inputs())
+            .map(|input| &*input.semantic)
+            .filter(|&semantic| semantic != "VERTEX");
+        vertex_semantics.chain(primitive_semantics).collect()
+    }
+
+    /// Checks if the mesh provides normals, i.e. it has a `"NORMAL"` input.
+    pub fn has_normals(&self) -> bool {
+        self.semantics().contains("NORMAL")
+    }
+
+    /// Checks if the mesh provides texture coordinates, i.e. it has a `"TEXCOORD"` input.
+    pub fn has_texcoords(&self) -> bool {
+        self.semantics().contains("TEXCOORD")
+    }
+
+    /// Checks if the mesh provides vertex colors, i.e. it has a `"COLOR"` input.
+    pub fn has_colors(&self) -> bool {
+        self.semantics().contains("COLOR")
+    }
+
+    /// Computes the axis-aligned bounding box of the mesh's vertex positions.
+    ///
+    /// Returns the minimum and maximum corners of the box, in that order. The position data is
+    /// found through the `"POSITION"` input of the mesh's `vertices`, and only the accessor
+    /// params named `"X"`, `"Y"`, and `"Z"` are considered, so any additional components in
+    /// each record are ignored.
+    ///
+    /// Returns `None` if the mesh has no position data, if the position source isn't a float
+    /// array, or if its accessor doesn't have X, Y, and Z params.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::fs::File;
+    /// # use collaborate::v1_4::{Collada, Library};
+    /// # let file = File::open("resources/blender_cube.dae").unwrap();
+    /// # let document = Collada::read(file).unwrap();
+    /// # let library = document.libraries_of(Library::as_library_geometries).next().unwrap();
+    /// let mesh = library.geometries[0].geometric_element.as_mesh().unwrap();
+    /// let (min, max) = mesh.bounding_box().unwrap();
+    /// println!("Cube extends from {:?} to {:?}", min, max);
+    /// ```
+    pub fn bounding_box(&self) -> Option<([f32; 3], [f32; 3])> {
+        let input = self.vertices.inputs.iter()
+            .find(|input| input.semantic == "POSITION")?;
+        let source = self.find_source(input.source.id())?;
+        let accessor = source.common_accessor()?;
+        let components = [
+            accessor.param_index("X")?,
+            accessor.param_index("Y")?,
+            accessor.param_index("Z")?,
+        ];
+
+        let mut bounds: Option<([f32; 3], [f32; 3])> = None;
+        for record in source.float_values()? {
+            let mut position = [0.0; 3];
+            for (value, &component) in position.iter_mut().zip(components.iter()) {
+                *value = *record.get(component)?;
+            }
+
+            let (min, max) = bounds.get_or_insert((position, position));
+            for axis in 0..3 {
+                min[axis] = min[axis].min(position[axis]);
+                max[axis] = max[axis].max(position[axis]);
+            }
+        }
+
+        bounds
+    }
+
+    /// Resolves the attributes of every vertex in the mesh's primitives.
+    ///
+    /// Polygons are fan-triangulated (see [`Polylist::triangulate`]), so every 3 vertices in the
+    /// result form a triangle. Vertices are not deduplicated, so a vertex shared by several
+    /// triangles appears once for each of them.
+    ///
+    /// The position of each vertex comes from the `"POSITION"` input of the mesh's `vertices`.
+    /// The normal, texture coordinate, and color come from the first input with the
+    /// `"NORMAL"`, `"TEXCOORD"`, or `"COLOR"` semantic on the primitive, or on the mesh's
+    /// `vertices` if the primitive doesn't have one. Accessor params are matched by name (e.g.
+    /// `"X"`, `"Y"`, and `"Z"` for positions), falling back to their position in the record if
+    /// no param has the expected name.
+    ///
+    /// Only `<polylist>` and `<triangles>` primitives are currently supported, other primitives
+    /// are skipped.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if an input references a source that isn't in the mesh, if a source has
+    /// no common accessor or no float data, or if a vertex indexes past the end of a source. See
+    /// [`ResolveError`] for details.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::fs::File;
+    /// # use collaborate::v1_4::{Collada, Library};
+    /// # let file = File::open("resources/blender_cube.dae").unwrap();
+    /// # let document = Collada::read(file).unwrap();
+    /// # let library = document.libraries_of(Library::as_library_geometries).next().unwrap();
+    /// let mesh = library.geometries[0].geometric_element.as_mesh().unwrap();
+    /// for triangle in mesh.resolved_vertices().unwrap().chunks(3) {
+    ///     println!("{:?}", triangle);
+    /// }
+    /// ```
+    ///
+    /// [`Polylist::triangulate`]: ./struct.Polylist.html#method.triangulate
+    /// [`ResolveError`]: ./enum.ResolveError.html
+    pub fn resolved_vertices(&self) -> ::std::result::Result<Vec<ResolvedVertex>, ResolveError> {
+        let position_input = self.vertices.inputs.iter()
+            .find(|input| input.semantic == "POSITION")
+            .ok_or(ResolveError::MissingInput { semantic: "POSITION" })?;
+        let positions = ResolvedSource::new(self, position_input.source.id(), &["X", "Y", "Z"])?;
+
+        // Inputs on `<vertices>` are indexed the same as the position.
+        let vertex_input = |semantic: &str, params: &[&str]| {
+            self.vertices.inputs.iter()
+                .find(|input| input.semantic == semantic)
+                .map(|input| ResolvedSource::new(self, input.source.id(), params))
+        };
+        let vertex_normals = vertex_input("NORMAL", &["X", "Y", "Z"]).transpose()?;
+        let vertex_texcoords = vertex_input("TEXCOORD", &["S", "T"]).transpose()?;
+        let vertex_colors = vertex_input("COLOR", &["R", "G", "B", "A"]).transpose()?;
+
+        let mut vertices = Vec::new();
+        for primitive in &self.primitives {
+            // A polylist without a `<vcount>` doesn't describe any polygons.
+            if let Primitive::Polylist(Polylist { vcount: None, .. }) = *primitive {
+                continue;
+            }
+
+            let triangles = match primitive.triangulate() {
+                Some(triangles) => triangles,
+                None => continue,
+            };
+
+            let inputs = primitive.inputs();
+            let input = |semantic: &str, params: &[&str]| {
+                inputs.iter()
+                    .find(|input| input.semantic == semantic)
+                    .map(|input| {
+                        ResolvedSource::new(self, input.source.id(), params)
+                            .map(|source| (input.offset, source))
+                    })
+            };
+            let vertex_offset = inputs.iter()
+                .find(|input| input.semantic == "VERTEX")
+                .ok_or(ResolveError::MissingInput { semantic: "VERTEX" })?
+                .offset;
+            let normals = input("NORMAL", &["X", "Y", "Z"]).transpose()?;
+            let texcoords = input("TEXCOORD", &["S", "T"]).transpose()?;
+            let colors = input("COLOR", &["R", "G", "B", "A"]).transpose()?;
+
+            // Reads an attribute from the primitive's input if it has one, otherwise from the
+            // `<vertices>` input using the position index.
+            let read = |
+                indices: &[usize],
+                input: &Option<(usize, ResolvedSource)>,
+                vertex_input: &Option<ResolvedSource>,
+                out: &mut [f32],
+            | {
+                match (input, vertex_input) {
+                    (Some((offset, source)), _) => source.read(indices[*offset], out).map(|_| true),
+                    (None, Some(source)) => source.read(indices[vertex_offset], out).map(|_| true),
+                    (None, None) => Ok(false),
+                }
+            };
+
+            for triangle in triangles {
+                for vertex in &triangle {
+                    let indices: Vec<usize> = vertex.iter().map(|attribute| attribute.index).collect();
+
+                    let mut position = [0.0; 3];
+                    positions.read(indices[vertex_offset], &mut position)?;
+
+                    let mut normal = [0.0; 3];
+                    let has_normal = read(&indices, &normals, &vertex_normals, &mut normal)?;
+
+                    let mut texcoord = [0.0; 2];
+                    let has_texcoord = read(&indices, &texcoords, &vertex_texcoords, &mut texcoord)?;
+
+                    let mut color = [0.0, 0.0, 0.0, 1.0];
+                    let has_color = read(&indices, &colors, &vertex_colors, &mut color)?;
+
+                    vertices.push(ResolvedVertex {
+                        position,
+                        normal: if has_normal { Some(normal) } else { None },
+                        texcoord: if has_texcoord { Some(texcoord) } else { None },
+                        color: if has_color { Some(color) } else { None },
+                    });
+                }
+            }
+        }
+
+        Ok(vertices)
+    }
+
+    /// Converts the mesh into a single indexed vertex buffer, suitable for uploading to a GPU.
+    ///
+    /// COLLADA indexes each vertex attribute separately, so two corners of a polygon may share a
+    /// position but not a normal. This resolves every corner with
+    /// [`resolved_vertices`](#method.resolved_vertices) and then merges corners with identical
+    /// positions, normals, and texture coordinates into a single vertex. Vertex colors are not
+    /// included. Values are compared exactly, so nearly-equal values are not merged.
+    ///
+    /// # Errors
+    ///
+    /// Returns `Err` under the same conditions as [`resolved_vertices`](#method.resolved_vertices).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use collaborate::v1_4::{Collada, Library};
+    /// # let document = Collada::from_path("resources/blender_cube.dae").unwrap();
+    /// # let library = document.libraries_of(Library::as_library_geometries).next().unwrap();
+    /// let mesh = library.geometries[0].mesh().unwrap();
+    /// let indexed = mesh.to_indexed().unwrap();
+    /// assert_eq!(36, indexed.indices.len());
+    /// assert!(indexed.positions.len() < indexed.indices.len());
+    /// ```
+    pub fn to_indexed(&self) -> ::std::result::Result<IndexedMesh, ResolveError> {
+        Ok(Mesh::index_vertices(self.resolved_vertices()?))
+    }
+
+    /// Converts the mesh into a single indexed vertex buffer with newly generated normals.
+    ///
+    /// This is useful for meshes that were exported without normals. Any normals the mesh already
+    /// has are replaced. Normals are computed from the positions of each triangle after the
+    /// polygons have been triangulated:
+    ///
+    /// * If `smooth` is `false`, every corner of a triangle uses the triangle's face normal,
+    ///   giving the mesh a faceted look.
+    /// * If `smooth` is `true`, every corner at the same position uses the average of the face
+    ///   normals of the triangles that share the position, weighted by the area of each triangle.
+    ///
+    /// Degenerate triangles, whose corners are collinear and so don't have a face normal, are
+    /// left out of the output and don't contribute to smooth normals. The vertices are then
+    /// merged in the same way as [`to_indexed`](#method.to_indexed).
+    ///
+    /// # Errors
+    ///
+    /// Returns `Err` under the same conditions as [`resolved_vertices`](#method.resolved_vertices).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use collaborate::v1_4::Collada;
+    /// let document = Collada::from_path("resources/blender_cube.dae").unwrap();
+    /// let mesh = document.geometry_by_id("Cube-mesh").unwrap().mesh().unwrap();
+    /// let indexed = mesh.with_generated_normals(true).unwrap();
+    /// assert_eq!(Some(indexed.positions.len()), indexed.normals.as_ref().map(Vec::len));
+    /// ```
+    pub fn with_generated_normals(&self, smooth: bool) -> ::std::result::Result<IndexedMesh, ResolveError> {
+        fn subtract(left: [f32; 3], right: [f32; 3]) -> [f32; 3] {
+            [left[0] - right[0], left[1] - right[1], left[2] - right[2]]
+        }
+
+        // The length of the cross product is twice the area of the triangle, which provides the
+        // area weighting for smooth normals.
+        fn face_normal(triangle: &[ResolvedVertex]) -> [f32; 3] {
+            let edge_a = subtract(triangle[1].position, triangle[0].position);
+            let edge_b = subtract(triangle[2].position, triangle[0].position);
+            [
+                edge_a[1] * edge_b[2] - edge_a[2] * edge_b[1],
+                edge_a[2] * edge_b[0] - edge_a[0] * edge_b[2],
+                edge_a[0] * edge_b[1] - edge_a[1] * edge_b[0],
+            ]
+        }
+
+        fn normalize(vector: [f32; 3]) -> Option<[f32; 3]> {
+            let length = (vector[0] * vector[0] + vector[1] * vector[1] + vector[2] * vector[2]).sqrt();
+            if length > 0.0 && length.is_finite() {
+                Some([vector[0] / length, vector[1] / length, vector[2] / length])
+            } else {
+                None
+            }
+        }
+
+        let mut vertices = self.resolved_vertices()?;
+
+        // Drop degenerate triangles, keeping the unnormalized face normal of the rest.
+        let mut triangles = Vec::with_capacity(vertices.len() / 3);
+        for triangle in vertices.chunks(3) {
+            let normal = face_normal(triangle);
+            if normalize(normal).is_some() {
+                triangles.push((triangle.to_vec(), normal));
+            }
+        }
+
+        // `f32` isn't `Hash`, so positions are keyed by their bits instead.
+        let mut smooth_normals = HashMap::new();
+        if smooth {
+            for &(ref triangle, normal) in &triangles {
+                for vertex in triangle {
+                    let sum = smooth_normals.entry(vertex.position.map(f32::to_bits)).or_insert([0.0; 3]);
+                    for (component, value) in sum.iter_mut().zip(normal.iter()) {
+                        *component += value;
+                    }
+                }
+            }
+        }
+
+        vertices.clear();
+        for (triangle, face_normal) in triangles {
+            for mut vertex in triangle {
+                let normal = if smooth {
+                    smooth_normals[&vertex.position.map(f32::to_bits)]
+                } else {
+                    face_normal
+                };
+
+                // A smooth normal can still cancel out, e.g. for a vertex shared by two triangles
+                // facing in opposite directions. Fall back to the face normal in that case.
+                vertex.normal = normalize(normal).or_else(|| normalize(face_normal));
+                vertices.push(vertex);
+            }
+        }
+
+        Ok(Mesh::index_vertices(vertices))
+    }
+
+    /// Merges identical vertices in `vertices`, which are listed in triangle order.
+    fn index_vertices(vertices: Vec<ResolvedVertex>) -> IndexedMesh {
+        let has_normals = vertices.iter().any(|vertex| vertex.normal.is_some());
+        let has_texcoords = vertices.iter().any(|vertex| vertex.texcoord.is_some());
+
+        let mut indexed = IndexedMesh {
+            positions: Vec::new(),
+            normals: if has_normals { Some(Vec::new()) } else { None },
+            texcoords: if has_texcoords { Some(Vec::new()) } else { None },
+            indices: Vec::with_capacity(vertices.len()),
+        };
+
+        // `f32` isn't `Hash`, so vertices are keyed by the bits of their attributes instead.
+        let mut indices = HashMap::new();
+        for vertex in vertices {
+            let normal = vertex.normal.unwrap_or_default();
+            let texcoord = vertex.texcoord.unwrap_or_default();
+            let key = (
+                vertex.position.map(f32::to_bits),
+                normal.map(f32::to_bits),
+                texcoord.map(f32::to_bits),
+            );
+
+            let next_index = indexed.positions.len() as u32;
+            let index = *indices.entry(key).or_insert(next_index);
+            if index == next_index {
+                indexed.positions.push(vertex.position);
+                if let Some(ref mut normals) = indexed.normals {
+                    normals.push(normal);
+                }
+                if let Some(ref mut texcoords) = indexed.texcoords {
+                    texcoords.push(texcoord);
+                }
+            }
+            indexed.indices.push(index);
+        }
+
+        indexed
+    }
+}
+
+/// The target of an input in a [`Mesh`].
+///
+/// Returned by [`Mesh::resolve_source_or_vertices`].
+///
+/// [`Mesh`]: ./struct.Mesh.html
+/// [`Mesh::resolve_source_or_vertices`]: ./struct.Mesh.html#method.resolve_source_or_vertices
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum MeshSourceRef<'a> {
+    /// One of the mesh's sources.
+    Source(&'a Source),
+
+    /// The mesh's vertices, which is the target of a primitive's `"VERTEX"` input.
+    Vertices(&'a Vertices),
+}
+
+/// The float data of a source in a mesh, prepared for reading specific params from each record.
+struct ResolvedSource<'a> {
+    id: &'a str,
+    accessor: &'a Accessor,
+    data: &'a [f32],
+
+    /// The position within each record of each of the requested params.
+    components: Vec<usize>,
+}
+
+impl<'a> ResolvedSource<'a> {
+    fn new(
+        mesh: &'a Mesh,
+        id: &str,
+        params: &[&str],
+    ) -> ::std::result::Result<ResolvedSource<'a>, ResolveError> {
+        let source = mesh.find_source(id)
+            .ok_or_else(|| ResolveError::MissingSource { id: id.into() })?;
+        let accessor = source.common_accessor()
+            .ok_or_else(|| ResolveError::MissingAccessor { source: id.into() })?;
+        let array = match source.accessor_source() {
+            Some(AccessorSource::External(uri)) => {
+                return Err(ResolveError::ExternalSource { source: id.into(), uri });
+            }
+            Some(AccessorSource::Array(array)) => array.as_float_array(),
+            None => None,
+        };
+        let array = array.ok_or_else(|| ResolveError::UnsupportedArray { source: id.into() })?;
+        let components = params.iter()
+            .enumerate()
+            .map(|(position, param)| accessor.param_index(param).unwrap_or(position))
+            .collect();
+
+        Ok(ResolvedSource {
+            id: &source.id,
+            accessor,
+            data: &array.data,
+            components,
+        })
+    }
+
+    /// Reads the requested params of the record at `index` into `out`.
+    ///
+    /// Components that are past the end of the record are left unchanged.
+    fn read(&self, index: usize, out: &mut [f32]) -> ::std::result::Result<(), ResolveError> {
+        let record = self.accessor.get(self.data, index)
+            .filter(|_| index < self.accessor.count)
+            .ok_or_else(|| ResolveError::IndexOutOfBounds { source: self.id.into(), index })?;
+        for (value, &component) in out.iter_mut().zip(self.components.iter()) {
+            if let Some(&component) = record.get(component) {
+                *value = component;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// Declares a new parameter for an effect.
+///
+/// Parameters are referenced by their `sid` from elsewhere in the effect. For example, a
+/// [`Texture`] refers to a `sampler2D` parameter, which in turn refers to a `surface` parameter
+/// that specifies the image to sample.
+///
+/// [`Texture`]: ./struct.Texture.html
+#[derive(Debug, Clone, PartialEq, ColladaElement)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[name = "newparam"]
+pub struct NewParam {
+    /// An identifier for the parameter that's unique amongst its sibling elements.
+    #[attribute]
+    pub sid: String,
+
+    #[child]
+    pub annotations: Vec<Annotate>,
+
+    /// Metadata describing the meaning of the parameter's value.
+    #[child]
+    pub semantic: Option<String>,
+
+    /// A storage qualifier for the parameter, e.g. `"CONST"` or `"VOLATILE"`.
+    #[child]
+    pub modifier: Option<String>,
+
+    /// The parameter's value.
+    #[child]
+    pub value: ParamValue,
+}
+
+/// A single point of interest in a scene hierarchy.
+///
+/// Nodes form a tree: each node may contain any number of child nodes, and the transforms of a
+/// node apply to all of its children as well. A node's own transform is the product of each of
+/// the entries in `transforms`, in the order they appear in the document.
+#[derive(Debug, Clone, PartialEq, ColladaElement)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[name = "node"]
+pub struct Node {
+    /// A unique identifier for the node.
+    ///
+    /// Will be unique within the document.
+    #[attribute]
+    pub id: Option<String>,
+
+    /// The human-friendly name for this node.
+    ///
+    /// Has no semantic meaning.
+    #[attribute]
+    pub name: Option<String>,
+
+    /// An identifier for the node that's unique amongst its sibling elements.
+    #[attribute]
+    pub sid: Option<String>,
+
+    /// Whether the node is a regular node or a joint in a skeleton. Defaults to `NodeType::Node`.
+    #[attribute]
+    #[name = "type"]
+    #[optional_with_default]
+    pub node_type: NodeType,
+
+    /// The names of the layers the node belongs to, as a whitespace-separated list.
+    #[attribute]
+    pub layer: Option<String>,
+
+    /// Metadata about this node.
+    #[child]
+    pub asset: Option<Asset>,
+
+    /// The transforms that position the node relative to its parent, in document order.
+    #[child]
+    pub transforms: Vec<Transform>,
+
+    #[child]
+    pub instance_cameras: Vec<InstanceCamera>,
+
+    #[child]
+    pub instance_controllers: Vec<InstanceController>,
+
+    #[child]
+    pub instance_geometries: Vec<InstanceGeometry>,
+
+    #[child]
+    pub instance_lights: Vec<InstanceLight>,
+
+    #[child]
+    pub instance_nodes: Vec<InstanceNode>,
+
+    /// The child nodes of this node.
+    #[child]
+    pub children: Vec<Node>,
+
+    /// Arbitrary additional information about this node.
+    ///
+    /// For more information about 3rd-party extensions, see the
+    /// [crate-level documentation](../index.html#3rd-party-extensions).
+    #[child]
+    pub extras: Vec<Extra>,
+}
+
+impl Node {
+    /// Returns the transform of the node relative to its parent, as a column-major 4x4 matrix.
+    ///
+    /// Each entry in `transforms` is post-multiplied in document order, so the last transform
+    /// listed is the first one applied to the node's contents. A node without any transforms
+    /// has the identity transform.
+    pub fn local_transform(&self) -> [f64; 16] {
+        self.transforms.iter()
+            .fold(::math::identity(), |local, transform| {
+                ::math::multiply(&local, &transform.matrix())
+            })
+    }
+
+    /// Returns the node with the ID `id`, searching this node and all of its descendants.
+    pub fn find(&self, id: &str) -> Option<&Node> {
+        if self.id.as_deref() == Some(id) {
+            return Some(self);
+        }
+
+        self.children.iter().filter_map(|child| child.find(id)).next()
+    }
+
+    /// Extends `chain` with the assets from this node down to its descendant with the ID `id`.
+    ///
+    /// Returns `false`, leaving `chain` untouched, if neither this node nor any descendant has
+    /// the ID `id`.
+    fn asset_chain<'a>(&'a self, id: &str, chain: &mut Vec<&'a Asset>) -> bool {
+        let len = chain.len();
+        chain.extend(&self.asset);
+        if self.id.as_deref() == Some(id)
+            || self.children.iter().any(|child| child.asset_chain(id, chain))
+        {
+            return true;
+        }
+
+        chain.truncate(len);
+        false
+    }
+}
+
+/// The location of a [`Node`] within a [`VisualScene`].
+///
+/// A path is the list of indices leading from the scene to the node: the first index selects
+/// one of the scene's `nodes`, and each subsequent index selects one of the previous node's
+/// `children`.
+///
+/// [`Node`]: ./struct.Node.html
+/// [`VisualScene`]: ./struct.VisualScene.html
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct NodePath(Vec<usize>);
+
+impl NodePath {
+    /// Returns the indices that make up the path.
+    pub fn indices(&self) -> &[usize] { &self.0 }
+}
+
+/// The type of a [`Node`].
+///
+/// [`Node`]: ./struct.Node.html
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum NodeType {
+    Joint,
+    #[default]
+    Node,
+}
+
+impl ::std::str::FromStr for NodeType {
+    type Err = ErrorKind;
+
+    fn from_str(string: &str) -> ::std::result::Result<NodeType, ErrorKind> {
+        match string {
+            "JOINT" => Ok(NodeType::Joint),
+            "NODE" => Ok(NodeType::Node),
+            _ => Err(ErrorKind::InvalidValue {
+                element: "node".into(),
+                value: string.into(),
+            }),
+        }
+    }
+}
+
+impl Display for NodeType {
+    fn fmt(&self, formatter: &mut Formatter) -> ::std::result::Result<(), fmt::Error> {
+        match *self {
+            NodeType::Joint => write!(formatter, "JOINT"),
+            NodeType::Node => write!(formatter, "NODE"),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, ColladaElement)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[name = "Name_array"]
+pub struct NameArray;
+
+/// How the transparency of a [`Transparent`] color is computed.
+///
+/// [`Transparent`]: ./struct.Transparent.html
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum Opaque {
+    /// Transparency is taken from the alpha channel, where `1.0` is opaque.
+    #[default]
+    AOne,
+
+    /// Transparency is taken from the luminance of the RGB channels, where `0.0` is opaque.
+    RgbZero,
+}
+
+impl ::std::str::FromStr for Opaque {
+    type Err = ErrorKind;
+
+    fn from_str(string: &str) -> ::std::result::Result<Opaque, ErrorKind> {
+        match string {
+            "A_ONE" => Ok(Opaque::AOne),
+            "RGB_ZERO" => Ok(Opaque::RgbZero),
+            _ => Err(ErrorKind::InvalidValue {
+                element: "transparent",
+                value: string.into(),
+            }),
+        }
+    }
+}
+
+impl Display for Opaque {
+    fn fmt(&self, formatter: &mut Formatter) -> ::std::result::Result<(), fmt::Error> {
+        match *self {
+            Opaque::AOne => write!(formatter, "A_ONE"),
+            Opaque::RgbZero => write!(formatter, "RGB_ZERO"),
+        }
+    }
+}
+
+/// Describes the field of view and viewing frustum of a [`Camera`].
+///
+/// [`Camera`]: ./struct.Camera.html
+#[derive(Debug, Clone, PartialEq, ColladaElement)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[name = "optics"]
+pub struct Optics {
+    /// The projection used by the camera, as defined by the common profile.
+    #[child]
+    pub technique_common: OpticsTechniqueCommon,
+
+    /// Profile-specific descriptions of the camera's optics.
+    #[child]
+    pub techniques: Vec<Technique>,
+
+    /// Arbitrary additional information about the optics.
+    ///
+    /// For more information about 3rd-party extensions, see the
+    /// [crate-level documentation](../index.html#3rd-party-extensions).
+    #[child]
+    pub extras: Vec<Extra>,
+}
+
+#[derive(Debug, Clone, PartialEq, ColladaElement)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[name = "technique_common"]
+pub struct OpticsTechniqueCommon {
+    #[child]
+    pub projection: Projection,
+}
+
+/// An orthographic projection, which has no perspective distortion.
+///
+/// At least one of `xmag` and `ymag` is expected to be present.
+#[derive(Debug, Clone, PartialEq, ColladaElement)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[name = "orthographic"]
+pub struct Orthographic {
+    /// The horizontal magnification of the view.
+    #[child]
+    pub xmag: Option<Xmag>,
+
+    /// The vertical magnification of the view.
+    #[child]
+    pub ymag: Option<Ymag>,
+
+    /// The ratio of `xmag` to `ymag`.
+    #[child]
+    pub aspect_ratio: Option<AspectRatio>,
+
+    /// The distance to the near clipping plane.
+    #[child]
+    pub znear: Znear,
+
+    /// The distance to the far clipping plane.
+    #[child]
+    pub zfar: Zfar,
+}
+
+/// The names of the parameter value types that are kept as an [`OtherParamValue`].
+///
+/// [`OtherParamValue`]: ./struct.OtherParamValue.html
+static OTHER_PARAM_VALUE_NAMES: &[&str] = &[
+    "bool", "bool2", "bool3", "bool4",
+    "int", "int2", "int3", "int4",
+    "float1x1", "float1x2", "float1x3", "float1x4",
+    "float2x1", "float2x2", "float2x3", "float2x4",
+    "float3x1", "float3x2", "float3x3", "float3x4",
+    "float4x1", "float4x2", "float4x3",
+    "sampler1D", "sampler3D", "samplerCUBE", "samplerRECT", "samplerDEPTH",
+    "enum",
+];
+
+/// A [`ParamValue`] of a type that isn't parsed yet, e.g. `<int>` or `<samplerCUBE>`.
+///
+/// The value is kept as raw XML so that documents using these types can still be read and
+/// written back out unchanged.
+///
+/// [`ParamValue`]: ./enum.ParamValue.html
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct OtherParamValue {
+    /// The name of the element holding the value, e.g. `"int"`.
+    pub name: String,
+
+    /// The raw XML events for the contents of the element. These events do not contain the
+    /// `StartElement` and `EndElement` events for the element itself.
+    #[cfg_attr(feature = "serde", serde(with = "::serde_support::events"))]
+    pub data: Vec<::xml::reader::XmlEvent>,
+}
+
+impl ColladaElement for OtherParamValue {
+    fn name_test(name: &str) -> bool {
+        OTHER_PARAM_VALUE_NAMES.contains(&name)
+    }
+
+    fn parse_element<R>(
+        reader: &mut ColladaReader<R>,
+        element_start: ElementStart,
+    ) -> Result<Self>
+    where
+        R: Read,
+    {
+        let name = element_start.name.local_name;
+        let element = OTHER_PARAM_VALUE_NAMES.iter()
+            .cloned()
+            .find(|&element| element == name)
+            .expect("`name_test` only accepts known names");
+        utils::verify_attributes(reader, element, element_start.attributes)?;
+
+        let mut data = Vec::new();
+        let mut depth = 0;
+        loop {
+            let event = reader.next_raw()?;
+            match event {
+                ::xml::reader::XmlEvent::StartElement { .. } => { depth += 1; }
+
+                ::xml::reader::XmlEvent::EndElement { .. } => {
+                    if depth == 0 {
+                        break;
+                    }
+
+                    depth -= 1;
+                }
+
+                _ => {}
+            }
+
+            data.push(event);
+        }
+
+        Ok(OtherParamValue { name, data })
+    }
+
+    fn add_names(names: &mut Vec<&'static str>) {
+        names.extend(OTHER_PARAM_VALUE_NAMES);
+    }
+
+    fn write_element<W>(&self, writer: &mut ColladaWriter<W>) -> ::std::io::Result<()>
+    where
+        W: ::std::io::Write,
+    {
+        writer.start_element(::xml::writer::XmlEvent::start_element(&*self.name))?;
+
+        for event in self.data.iter().filter_map(::xml::reader::XmlEvent::as_writer_event) {
+            writer.write(event)?;
+        }
+
+        writer.end_element()
+    }
+}
+
+/// Declares parametric information for its parent element.
+///
+/// A functional or programmatical format requires a means for users to specify parametric
+/// information. This information represents function parameter (argument) data.
+///
+/// Material shader programs may contain code representing vertex or pixel programs. These
+/// programs require parameters as part of their state information.
+///
+/// The basic declaration of a parameter describes the name, data type, and value data of the
+/// parameter. That parameter name identifies it to the function or program. The parameter type
+/// indicates the encoding of its value.
+#[derive(Debug, Clone, PartialEq, ColladaElement)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[name = "param"]
+pub struct Param {
+    /// The name of the parameter.
+    #[attribute]
+    pub name: Option<String>,
+
+    /// The subidentifier of this parameter.
+    ///
+    /// This value is unique within the scope of the parent element.
+    #[attribute]
+    pub sid: Option<String>,
+
+    /// The type of the value data.
+    ///
+    /// Must be understood by the application consuming the COLLADA document.
+    #[attribute]
+    #[name = "type"]
+    pub data_type: Option<String>,
+
+    /// The user-defined meaning of the parameter.
+    #[attribute]
+    pub semantic: Option<String>,
+}
+
+/// A reference to a parameter declared elsewhere, e.g. by a `<newparam>` element.
+#[derive(Debug, Clone, PartialEq, ColladaElement)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[name = "param"]
+pub struct ParamReference {
+    /// The identifier of the referenced parameter.
+    #[attribute]
+    #[name = "ref"]
+    pub reference: String,
+}
+
+/// The value of a [`NewParam`] or [`SetParam`].
+///
+/// [`NewParam`]: ./struct.NewParam.html
+/// [`SetParam`]: ./struct.SetParam.html
+#[derive(Debug, Clone, PartialEq, ColladaElement)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum ParamValue {
+    Float(FloatValue),
+    Float2(Float2),
+    Float3(Float3),
+    Float4(Float4),
+    Float4x4(Float4x4),
+    Surface(Surface),
+    Sampler2D(Sampler2D),
+
+    /// Any other value type allowed by the schema, kept as raw XML.
+    Other(OtherParamValue),
+}
+
+impl ParamValue {
+    /// Returns the value as a [`Surface`], if it is one.
+    ///
+    /// [`Surface`]: ./struct.Surface.html
+    pub fn as_surface(&self) -> Option<&Surface> {
+        match *self {
+            ParamValue::Surface(ref surface) => Some(surface),
+            _ => None,
+        }
+    }
+
+    /// Returns the value as a [`Sampler2D`], if it is one.
+    ///
+    /// [`Sampler2D`]: ./struct.Sampler2D.html
+    pub fn as_sampler_2d(&self) -> Option<&Sampler2D> {
+        match *self {
+            ParamValue::Sampler2D(ref sampler) => Some(sampler),
+            _ => None,
+        }
+    }
+}
+
+/// An iterator over a single component of each record in a source.
+///
+/// Created by [`Source::float_param_values`].
+///
+/// [`Source::float_param_values`]: ./struct.Source.html#method.float_param_values
+#[derive(Debug, Clone)]
+pub struct ParamValues<'a, T: 'a> {
+    records: AccessorIter<'a, T>,
+    index: usize,
+}
+
+impl<'a, T: 'a> Iterator for ParamValues<'a, T> {
+    type Item = &'a T;
+
+    fn next(&mut self) -> Option<&'a T> {
+        let index = self.index;
+        self.records.next().and_then(|record| record.get(index))
+    }
+}
+
+/// A perspective projection, which makes objects further from the camera appear smaller.
+///
+/// At least one of `xfov` and `yfov` is expected to be present. Use [`Camera::horizontal_fov`]
+/// and [`Camera::vertical_fov`] to get both angles.
+///
+/// [`Camera::horizontal_fov`]: ./struct.Camera.html#method.horizontal_fov
+/// [`Camera::vertical_fov`]: ./struct.Camera.html#method.vertical_fov
+#[derive(Debug, Clone, PartialEq, ColladaElement)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[name = "perspective"]
+pub struct Perspective {
+    /// The horizontal field of view, in degrees.
+    #[child]
+    pub xfov: Option<Xfov>,
+
+    /// The vertical field of view, in degrees.
+    #[child]
+    pub yfov: Option<Yfov>,
+
+    /// The ratio of `xfov` to `yfov`.
+    #[child]
+    pub aspect_ratio: Option<AspectRatio>,
+
+    /// The distance to the near clipping plane.
+    #[child]
+    pub znear: Znear,
+
+    /// The distance to the far clipping plane.
+    #[child]
+    pub zfar: Zfar,
+}
+
+/// Describes the physical properties of a surface, e.g. for use in a rigid body simulation.
+#[derive(Debug, Clone, PartialEq, ColladaElement)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[name = "physics_material"]
+pub struct PhysicsMaterial {
+    /// A unique identifier for the material.
+    ///
+    /// Will be unique within the document.
+    #[attribute]
+    pub id: Option<String>,
+
+    /// The human-friendly name for the material.
+    ///
+    /// Has no semantic meaning.
+    #[attribute]
+    pub name: Option<String>,
+
+    /// Metadata about the material.
+    #[child]
+    pub asset: Option<Asset>,
+
+    /// The properties of the material, as defined by the common profile.
+    #[child]
+    pub technique_common: PhysicsMaterialTechniqueCommon,
+
+    /// Profile-specific properties of the material.
+    #[child]
+    pub techniques: Vec<Technique>,
+
+    /// Arbitrary additional information about this material.
+    ///
+    /// For more information about 3rd-party extensions, see the
+    /// [crate-level documentation](../index.html#3rd-party-extensions).
+    #[child]
+    pub extras: Vec<Extra>,
+}
+
+/// The properties of a [`PhysicsMaterial`] as defined by the common profile.
+///
+/// Every property defaults to `0.0` if it isn't specified in the document.
+///
+/// [`PhysicsMaterial`]: ./struct.PhysicsMaterial.html
+#[derive(Debug, Clone, PartialEq, ColladaElement)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[name = "technique_common"]
+pub struct PhysicsMaterialTechniqueCommon {
+    /// The friction between surfaces that are moving relative to each other.
+    #[child]
+    #[optional_with_default]
+    pub dynamic_friction: DynamicFriction,
+
+    /// How much energy is retained when colliding with another object, from `0.0` (none) to
+    /// `1.0` (all of it).
+    #[child]
+    #[optional_with_default]
+    pub restitution: Restitution,
+
+    /// The friction between surfaces that aren't moving relative to each other.
+    #[child]
+    #[optional_with_default]
+    pub static_friction: StaticFriction,
+}
+
+/// A physics material used by a rigid body or shape, either instantiated or declared inline.
+#[derive(Debug, Clone, PartialEq, ColladaElement)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum PhysicsMaterialSource {
+    Instance(InstancePhysicsMaterial),
+    Inline(Box<PhysicsMaterial>),
+}
+
+/// A collection of rigid bodies and the constraints between them, e.g. a ragdoll.
+///
+/// Physics models are instantiated in a physics scene with an [`InstancePhysicsModel`].
+///
+/// [`InstancePhysicsModel`]: ./struct.InstancePhysicsModel.html
+#[derive(Debug, Clone, PartialEq, ColladaElement)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[name = "physics_model"]
+pub struct PhysicsModel {
+    /// A unique identifier for the model.
+    ///
+    /// Will be unique within the document.
+    #[attribute]
+    pub id: Option<String>,
+
+    /// The human-friendly name for the model.
+    ///
+    /// Has no semantic meaning.
+    #[attribute]
+    pub name: Option<String>,
+
+    /// Metadata about the model.
+    #[child]
+    pub asset: Option<Asset>,
+
+    /// The rigid bodies that make up the model.
+    #[child]
+    pub rigid_bodies: Vec<RigidBody>,
+
+    /// The constraints between the model's rigid bodies.
+    #[child]
+    pub rigid_constraints: Vec<RigidConstraint>,
+
+    /// Other physics models that are part of this one.
+    #[child]
+    pub instance_physics_models: Vec<InstancePhysicsModel>,
+
+    /// Arbitrary additional information about this model.
+    ///
+    /// For more information about 3rd-party extensions, see the
+    /// [crate-level documentation](../index.html#3rd-party-extensions).
+    #[child]
+    pub extras: Vec<Extra>,
+}
+
+/// A physics simulation, made up of the physics models and force fields it instantiates.
+///
+/// The document's physics scenes are instantiated by its [`Scene`], and can be found with
+/// [`Collada::resolve_physics_scene`].
+///
+/// [`Scene`]: ./struct.Scene.html
+/// [`Collada::resolve_physics_scene`]: ./struct.Collada.html#method.resolve_physics_scene
+#[derive(Debug, Clone, PartialEq, ColladaElement)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[name = "physics_scene"]
+pub struct PhysicsScene {
+    /// A unique identifier for the scene.
+    ///
+    /// Will be unique within the document.
+    #[attribute]
+    pub id: Option<String>,
+
+    /// The human-friendly name for the scene.
+    ///
+    /// Has no semantic meaning.
+    #[attribute]
+    pub name: Option<String>,
+
+    /// Metadata about the scene.
+    #[child]
+    pub asset: Option<Asset>,
+
+    /// The force fields that affect the scene.
+    #[child]
+    pub instance_force_fields: Vec<InstanceForceField>,
+
+    /// The physics models that are simulated in the scene.
+    #[child]
+    pub instance_physics_models: Vec<InstancePhysicsModel>,
+
+    /// The properties of the scene, as defined by the common profile.
+    #[child]
+    pub technique_common: PhysicsSceneTechniqueCommon,
+
+    /// Profile-specific properties of the scene.
+    #[child]
+    pub techniques: Vec<Technique>,
+
+    /// Arbitrary additional information about this scene.
+    ///
+    /// For more information about 3rd-party extensions, see the
+    /// [crate-level documentation](../index.html#3rd-party-extensions).
+    #[child]
+    pub extras: Vec<Extra>,
+}
+
+/// The properties of a [`PhysicsScene`] as defined by the common profile.
+///
+/// [`PhysicsScene`]: ./struct.PhysicsScene.html
+#[derive(Debug, Clone, PartialEq, ColladaElement)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[name = "technique_common"]
+pub struct PhysicsSceneTechniqueCommon {
+    /// The acceleration due to gravity in the scene.
+    #[child]
+    pub gravity: Option<Gravity>,
+
+    /// The amount of time that passes in each step of the simulation, in seconds.
+    #[child]
+    pub time_step: Option<TimeStep>,
+}
+
+/// An infinite plane collision shape.
+#[derive(Debug, Clone, PartialEq, ColladaElement)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[name = "plane"]
+pub struct Plane {
+    /// The equation of the plane.
+    #[child]
+    pub equation: Equation,
+
+    /// Arbitrary additional information about this shape.
+    ///
+    /// For more information about 3rd-party extensions, see the
+    /// [crate-level documentation](../index.html#3rd-party-extensions).
+    #[child]
+    pub extras: Vec<Extra>,
+}
+
+/// A shader that produces a specularly shaded surface using the Phong BRDF approximation.
+///
+/// The surface reflects ambient, diffuse, and specular light, with the specular highlight based
+/// on the angle between the reflected light and the eye direction.
+#[derive(Debug, Clone, PartialEq, ColladaElement)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[name = "phong"]
+pub struct Phong {
+    /// The light emitted by the surface.
+    #[child]
+    pub emission: Option<Emission>,
+
+    /// The light reflected from ambient light sources.
+    #[child]
+    pub ambient: Option<Ambient>,
+
+    /// The light diffusely reflected from the surface.
+    #[child]
+    pub diffuse: Option<Diffuse>,
+
+    /// The light specularly reflected from the surface.
+    #[child]
+    pub specular: Option<Specular>,
+
+    /// The specularity or roughness of the specular reflection lobe.
+    #[child]
+    pub shininess: Option<Shininess>,
+
+    /// The color of a perfect mirror reflection.
+    #[child]
+    pub reflective: Option<Reflective>,
+
+    /// The amount of perfect mirror reflection to be added.
+    #[child]
+    pub reflectivity: Option<Reflectivity>,
+
+    /// The color of perfectly refracted light.
+    #[child]
+    pub transparent: Option<Transparent>,
+
+    /// The amount of perfectly refracted light added.
+    #[child]
+    pub transparency: Option<Transparency>,
+
+    /// The index of refraction for perfectly refracted light.
+    #[child]
+    pub index_of_refraction: Option<IndexOfRefraction>,
+}
+
+#[derive(Debug, Clone)]
+pub struct Polygon<'a> {
+    len: usize,
+    chunks: ::std::slice::Chunks<'a, usize>,
+}
+
+impl<'a> Polygon<'a> {
+    pub fn iter(&self) -> PolygonIter<'a> {
+        PolygonIter { chunks: self.chunks.clone() }
+    }
+
+    /// Returns the number of vertices in this polygon.
+    pub fn len(&self) -> usize {
+        self.len
+    }
+}
+
+impl<'a> ::std::iter::IntoIterator for Polygon<'a> {
+    type Item = Vertex<'a>;
+    type IntoIter = PolygonIter<'a>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        PolygonIter { chunks: self.chunks }
+    }
+}
+
+impl<'a> ::std::iter::IntoIterator for &'a Polygon<'a> {
+    type Item = Vertex<'a>;
+    type IntoIter = PolygonIter<'a>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        PolygonIter { chunks: self.chunks.clone() }
+    }
+}
+
+pub struct PolygonIter<'a> {
+    chunks: ::std::slice::Chunks<'a, usize>,
+}
+
+impl<'a> ::std::iter::Iterator for PolygonIter<'a> {
+    type Item = Vertex<'a>;
+
+    fn next(&mut self) -> Option<Vertex<'a>> {
+        self.chunks.next().map(|attributes| Vertex { attributes })
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, ColladaElement)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[name = "polygons"]
+pub struct Polygons;
+
+/// A list of polygons that are not necessarily triangles.
+///
+/// Provides the information needed for a mesh to bind vertex attributes together and then
+/// organize those vertices into individual polygons. `Polylist` provides functionality for
+/// iterating over the polygons it represents.
+///
+/// # Examples
+///
+/// Iterate over all of the polygons in a polylist, then iterate over each vertex in each polygon:
+///
+/// ```
+/// # #![allow(unused_variables)]
+/// # use std::fs::File;
+/// # use collaborate::v1_4::{Collada, Library};
+/// # let file = File::open("resources/blender_cube.dae").unwrap();
+/// # let document = Collada::read(file).unwrap();
+/// # let library = document.libraries_of(Library::as_library_geometries).next().unwrap();
+/// # let mesh = library.geometries[0].geometric_element.as_mesh().unwrap();
+/// let polylist = mesh.primitives[0].as_polylist().unwrap();
+/// for polygon in polylist {
+///     println!("Vertices in polygon: {}", polygon.len());
+///     for vertex in polygon {
+///         println!("{:?}", vertex);
+///         for attribute in vertex {
+///             for input in polylist.inputs_for_offset(attribute.offset) {
+///                 println!(
+///                     "Attribute {:?} indexes into {:?}",
+///                     attribute,
+///                     input,
+///                 );
+///             }
+///         }
+///     }
+/// }
+/// ```
+#[derive(Debug, Clone, PartialEq, ColladaElement)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[name = "polylist"]
+#[validate = "Polylist::validate"]
+pub struct Polylist {
+    /// A human-friendly name for this polylist.
+    ///
+    /// Has no semantic meaning.
+    #[attribute]
+    pub name: Option<String>,
+
+    /// The number of polygon primitives in the polylist.
+    #[attribute]
+    pub count: usize,
+
+    /// The name of the material associated with this polylist.
+    ///
+    /// This name is bound to a material at the time of instantiaion. See [`InstanceGeometry`]
+    /// and [`BindMaterial`].
+    ///
+    /// If `None`, then the lighting and shading results are appplication-defined.
+    ///
+    /// [`InstanceGeometry`]: ./struct.InstanceGeometry.html
+    /// [`BindMaterial`]: ./struct.BindMaterial.html
+    #[attribute]
+    pub material: Option<String>,
+
+    /// The input data for the polylist.
+    #[child]
+    pub inputs: Vec<SharedInput>,
+
+    /// A list of integers, each specifying the number of vertices for one polygon in the polylist.
+    #[child]
+    pub vcount: Option<VCount>,
+
+    /// A list of integers that specify the vertex attributes as indexes into the inputs.
+    #[child]
+    pub primitives: Option<Primitives>,
+
+    /// Arbitrary additional information about this polylist and the data it contains.
+    ///
+    /// For more information about 3rd-party extensions, see the
+    /// [crate-level documentation](../index.html#3rd-party-extensions).
+    #[child]
+    pub extras: Vec<Extra>,
+}
+
+impl Polylist {
+    /// Returns the total number of vertices across all of the polygons in the polylist.
+    ///
+    /// This is the sum of the `<vcount>` values, which is useful for preallocating buffers
+    /// before iterating. Returns `0` if the polylist has no `<vcount>`.
+    ///
+    /// Parsed polylists are always checked that the sum fits in a `usize`. For one that was
+    /// modified or built by hand, the sum saturates at `usize::MAX` rather than overflowing.
+    pub fn total_vertices(&self) -> usize {
+        self.vcount.as_ref().map_or(0, |vcount| {
+            vcount.iter().fold(0usize, |total, &count| total.saturating_add(count))
+        })
+    }
+
+    /// Returns an iterator over the polygons in the polylist.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the polylist is malformed. Parsed polylists are always valid, but one that was
+    /// modified or built by hand may not be. Use [`try_iter`](#method.try_iter) to check for
+    /// this instead.
+    pub fn iter<'a>(&'a self) -> PolylistIter<'a> {
+        self.try_iter().expect("Polylist is malformed, use `try_iter` to handle the error")
+    }
+
+    /// Returns an iterator over the polygons in the polylist, checking that the polylist is valid.
+    ///
+    /// A polylist without a `<vcount>`, a `<p>`, or any inputs doesn't describe any polygons, so
+    /// the iterator is empty.
+    ///
+    /// # Errors
+    ///
+    /// Returns `ErrorKind::InvalidValue` if `vcount` doesn't have an entry for each polygon, if
+    /// `p` doesn't have an index for each attribute of each vertex counted by `vcount`, or if the
+    /// number of indices is too large to fit in a `usize`. These are the same checks performed
+    /// when parsing.
+    pub fn try_iter<'a>(&'a self) -> ::std::result::Result<PolylistIter<'a>, ErrorKind> {
+        Polylist::validate(self)?;
+
+        let num_indices_per_vertex = indices_per_vertex(&self.inputs)?;
+        let vcount = match (num_indices_per_vertex, self.vcount.as_ref()) {
+            (Some(_), Some(vcount)) => &**vcount,
+            _ => &[],
+        };
+
+        Ok(PolylistIter {
+            polylist: self,
+            num_indices_per_vertex: num_indices_per_vertex.unwrap_or(0),
+            vcount_iter: vcount.iter(),
+            verts_so_far: 0,
+        })
+    }
+
+    /// Returns the number of polygons in the polylist.
+    pub fn len(&self) -> usize {
+        self.count
+    }
+
+    /// Checks that `vcount` has an entry for each polygon, and that `p` has an index for each
+    /// attribute of each vertex counted by `vcount`.
+    ///
+    /// Without this, a truncated `<p>` would only be noticed when iterating over the polygons.
+    fn validate(polylist: &Polylist) -> ::std::result::Result<(), ErrorKind> {
+        let vcount = match polylist.vcount {
+            Some(ref vcount) => vcount,
+            None => return Ok(()),
+        };
+
+        if vcount.len() != polylist.count {
+            return Err(ErrorKind::InvalidValue {
+                element: "vcount",
+                value: format!("{} vertex counts for {} polygons", vcount.len(), polylist.count),
+            });
+        }
+
+        let total_vertices = Polylist::sum_vcount(vcount)?;
+        let num_indices_per_vertex = indices_per_vertex(&polylist.inputs)?;
+        if let (Some(primitives), Some(num_indices_per_vertex)) =
+            (polylist.primitives.as_ref(), num_indices_per_vertex)
+        {
+            let expected = total_vertices.checked_mul(num_indices_per_vertex).ok_or_else(|| {
+                ErrorKind::InvalidValue {
+                    element: "p",
+                    value: format!(
+                        "{} vertices with {} indices each, which overflows",
+                        total_vertices,
+                        num_indices_per_vertex,
+                    ),
+                }
+            })?;
+            if primitives.len() != expected {
+                return Err(ErrorKind::InvalidValue {
+                    element: "p",
+                    value: format!("{} indices where {} were expected", primitives.len(), expected),
+                });
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Sums the vertex counts in `vcount`, checking that the total fits in a `usize`.
+    fn sum_vcount(vcount: &[usize]) -> ::std::result::Result<usize, ErrorKind> {
+        vcount.iter()
+            .try_fold(0usize, |total, &count| total.checked_add(count))
+            .ok_or_else(|| ErrorKind::InvalidValue {
+                element: "vcount",
+                value: format!("{} vertex counts whose sum overflows", vcount.len()),
+            })
+    }
+
+    /// Returns an iterator that splits the polygons in the polylist into triangles.
+    ///
+    /// Each polygon is fan-triangulated around its first vertex, so a polygon with `n` vertices
+    /// yields `n - 2` triangles. Degenerate polygons with fewer than 3 vertices are skipped. The
+    /// vertices of each triangle are the same [`Vertex`] values yielded when iterating over
+    /// the polygon directly, so the attribute offsets can still be matched against the inputs.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::fs::File;
+    /// # use collaborate::v1_4::{Collada, Library};
+    /// # let file = File::open("resources/blender_cube.dae").unwrap();
+    /// # let document = Collada::read(file).unwrap();
+    /// # let library = document.libraries_of(Library::as_library_geometries).next().unwrap();
+    /// # let mesh = library.geometries[0].geometric_element.as_mesh().unwrap();
+    /// let polylist = mesh.primitives[0].as_polylist().unwrap();
+    /// for triangle in polylist.triangulate() {
+    ///     for vertex in &triangle {
+    ///         println!("{:?}", vertex);
+    ///     }
+    /// }
+    /// ```
+    ///
+    /// [`Vertex`]: ./struct.Vertex.html
+    pub fn triangulate<'a>(&'a self) -> Triangulated<'a> {
+        Triangulated {
+            polygons: self.iter(),
+            fan: None,
+        }
+    }
+
+    /// Returns an iterator yielding all inputs that match `offset`.
+    ///
+    /// When matching a vertex attribute to an input, the attribute's offset is matched against
+    /// the input's offset. It's possible for multiple inputs to share the same offset, so this
+    /// method provides an easy way to iterate over all inputs with a given offset.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # #![allow(unused_variables)]
+    /// # use std::fs::File;
+    /// # use collaborate::v1_4::{Collada, Library};
+    /// # let file = File::open("resources/blender_cube.dae").unwrap();
+    /// # let document = Collada::read(file).unwrap();
+    /// # let library = document.libraries_of(Library::as_library_geometries).next().unwrap();
+    /// # let mesh = library.geometries[0].geometric_element.as_mesh().unwrap();
+    /// let polylist = mesh.primitives[0].as_polylist().unwrap();
+    /// for polygon in polylist {
+    ///     println!("Vertices in polygon: {}", polygon.len());
+    ///     for vertex in polygon {
+    ///         println!("{:?}", vertex);
+    ///         for attribute in vertex {
+    ///             for input in polylist.inputs_for_offset(attribute.offset) {
+    ///                 println!(
+    ///                     "Attribute {:?} indexes into {:?}",
+    ///                     attribute,
+    ///                     input,
+    ///                 );
+    ///             }
+    ///         }
+    ///     }
+    /// }
+    /// ```
+    pub fn inputs_for_offset<'a>(&'a self, offset: usize) -> InputsForOffset<'a> {
+        InputsForOffset {
+            inputs: self.inputs.iter(),
+            offset,
+        }
+    }
+
+    /// Returns the first input with the given `semantic` and `set`.
+    ///
+    /// Meshes with several inputs of the same semantic, e.g. one `"TEXCOORD"` input for the base
+    /// texture and another for a lightmap, distinguish them by [`SharedInput::set`]. If `set` is
+    /// `None`, the first input with a matching semantic is returned regardless of its set.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::fs::File;
+    /// # use collaborate::v1_4::{Collada, Library};
+    /// # let file = File::open("resources/blender_cube.dae").unwrap();
+    /// # let document = Collada::read(file).unwrap();
+    /// # let library = document.libraries_of(Library::as_library_geometries).next().unwrap();
+    /// # let mesh = library.geometries[0].geometric_element.as_mesh().unwrap();
+    /// let polylist = mesh.primitives[0].as_polylist().unwrap();
+    /// let normals = polylist.input("NORMAL", None).unwrap();
+    /// assert_eq!(1, normals.offset);
+    /// assert!(polylist.input("TEXCOORD", Some(1)).is_none());
+    /// ```
+    ///
+    /// [`SharedInput::set`]: ./struct.SharedInput.html#structfield.set
+    pub fn input(&self, semantic: &str, set: Option<usize>) -> Option<&SharedInput> {
+        self.inputs.iter().find(|input| {
+            input.semantic == semantic && (set.is_none() || input.set == set)
+        })
+    }
+}
+
+impl<'a> ::std::iter::IntoIterator for &'a Polylist {
+    type Item = Polygon<'a>;
+    type IntoIter = PolylistIter<'a>;
+
+    fn into_iter(self) -> PolylistIter<'a> {
+        self.iter()
+    }
+}
+
+pub struct PolylistIter<'a> {
+    polylist: &'a Polylist,
+    num_indices_per_vertex: usize,
+    vcount_iter: ::std::slice::Iter<'a, usize>,
+    verts_so_far: usize,
+}
+
+impl<'a> ::std::iter::Iterator for PolylistIter<'a> {
+    type Item = Polygon<'a>;
+
+    fn next(&mut self) -> Option<Polygon<'a>> {
+        let primitives = match self.polylist.primitives {
+            Some(ref primitives) => primitives,
+            None => return None,
+        };
+
+        self.vcount_iter.next()
+            .map(|&num_verts| {
+                let indices = &primitives[self.verts_so_far * self.num_indices_per_vertex .. (self.verts_so_far + num_verts) * self.num_indices_per_vertex];
+                self.verts_so_far += num_verts;
+                Polygon {
+                    len: num_verts,
+                    chunks: indices.chunks(self.num_indices_per_vertex),
+                }
+            })
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, ColladaElement)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum Primitive {
+    Lines(Lines),
+    Linestrips(Linestrips),
+    Polygons(Polygons),
+    Polylist(Polylist),
+    Triangles(Triangles),
+    Trifans(Trifans),
+    Tristrips(Tristrips),
+}
+
+impl Primitive {
+    pub fn as_lines(&self) -> Option<&Lines> {
+        match *self {
+            Primitive::Lines(ref lines) => Some(lines),
+            _ => None,
+        }
+    }
+
+    pub fn as_linestrips(&self) -> Option<&Linestrips> {
+        match *self {
+            Primitive::Linestrips(ref linestrips) => Some(linestrips),
+            _ => None,
+        }
+    }
+
+    pub fn as_polygons(&self) -> Option<&Polygons> {
+        match *self {
+            Primitive::Polygons(ref polygons) => Some(polygons),
+            _ => None,
+        }
+    }
+
+    pub fn as_polylist(&self) -> Option<&Polylist> {
+        match *self {
+            Primitive::Polylist(ref polylist) => Some(polylist),
+            _ => None,
+        }
+    }
+
+    pub fn as_triangles(&self) -> Option<&Triangles> {
+        match *self {
+            Primitive::Triangles(ref triangles) => Some(triangles),
+            _ => None,
+        }
+    }
+
+    pub fn as_trifans(&self) -> Option<&Trifans> {
+        match *self {
+            Primitive::Trifans(ref trifans) => Some(trifans),
+            _ => None,
+        }
+    }
+
+    pub fn as_tristrips(&self) -> Option<&Tristrips> {
+        match *self {
+            Primitive::Tristrips(ref tristrips) => Some(tristrips),
+            _ => None,
+        }
+    }
+
+    /// Returns the inputs that describe the primitive's vertex attributes.
+    ///
+    /// Only [`Polylist`] and [`Triangles`] are parsed at the moment, so this is always empty for
+    /// the other kinds of primitives.
+    ///
+    /// [`Polylist`]: ./struct.Polylist.html
+    /// [`Triangles`]: ./struct.Triangles.html
+    pub fn inputs(&self) -> &[SharedInput] {
+        match *self {
+            Primitive::Polylist(ref polylist) => &polylist.inputs,
+            Primitive::Triangles(ref triangles) => &triangles.inputs,
+            _ => &[],
+        }
+    }
+
+    /// Returns the number of primitives (e.g. polygons or lines) declared by the element.
+    ///
+    /// Only [`Polylist`] and [`Triangles`] are parsed at the moment, so this is always `0` for
+    /// the other kinds of primitives.
+    ///
+    /// [`Polylist`]: ./struct.Polylist.html
+    /// [`Triangles`]: ./struct.Triangles.html
+    pub fn count(&self) -> usize {
+        match *self {
+            Primitive::Polylist(ref polylist) => polylist.count,
+            Primitive::Triangles(ref triangles) => triangles.count,
+            _ => 0,
+        }
+    }
+
+    /// Returns the total number of vertices the primitive yields across all of its primitives.
+    ///
+    /// See [`Polylist::total_vertices`] and [`Triangles::total_vertices`]. Only [`Polylist`] and
+    /// [`Triangles`] are parsed at the moment, so this is always `0` for the other kinds of
+    /// primitives.
+    ///
+    /// [`Polylist::total_vertices`]: ./struct.Polylist.html#method.total_vertices
+    /// [`Triangles::total_vertices`]: ./struct.Triangles.html#method.total_vertices
+    /// [`Polylist`]: ./struct.Polylist.html
+    /// [`Triangles`]: ./struct.Triangles.html
+    pub fn total_vertices(&self) -> usize {
+        match *self {
+            Primitive::Polylist(ref polylist) => polylist.total_vertices(),
+            Primitive::Triangles(ref triangles) => triangles.total_vertices(),
+            _ => 0,
+        }
     }
-}
-
-#[derive(Debug, Clone, PartialEq, ColladaElement)]
-#[name = "int_array"]
-pub struct IntArray;
 
-/// A single library of unknown type.
-///
-/// Each variant wraps a single value containing the library data. See the documentation for
-/// each of the possible library types for more information on what data each can contain.
-#[derive(Debug, Clone, PartialEq, ColladaElement)]
-pub enum Library {
-    Animations(LibraryAnimations),
-    AnimationClips(LibraryAnimationClips),
-    Cameras(LibraryCameras),
-    Controllers(LibraryControllers),
-    Effects(LibraryEffects),
-    ForceFields(LibraryForceFields),
-    Geometries(LibraryGeometries),
-    Images(LibraryImages),
-    Lights(LibraryLights),
-    Materials(LibraryMaterials),
-    Nodes(LibraryNodes),
-    PhysicsMaterials(LibraryPhysicsMaterials),
-    PhysicsModels(LibraryPhysicsModels),
-    PhysicsScenes(LibraryPhysicsScenes),
-    VisualScenes(LibraryVisualScenes),
-}
+    /// Returns the material symbol used by the primitive, if any.
+    ///
+    /// Only [`Polylist`] and [`Triangles`] are parsed at the moment, so this is always `None` for
+    /// the other kinds of primitives.
+    ///
+    /// [`Polylist`]: ./struct.Polylist.html
+    /// [`Triangles`]: ./struct.Triangles.html
+    pub fn material(&self) -> Option<&str> {
+        match *self {
+            Primitive::Polylist(ref polylist) => polylist.material.as_deref(),
+            Primitive::Triangles(ref triangles) => triangles.material.as_deref(),
+            _ => None,
+        }
+    }
 
-impl Library {
-    pub fn as_library_geometries(&self) -> Option<&LibraryGeometries> {
+    /// Returns an iterator over the triangles that make up the primitive.
+    ///
+    /// The polygons of a [`Polylist`] are fan-triangulated as with [`Polylist::triangulate`], and
+    /// [`Triangles`] are yielded as they are. Returns `None` for the other kinds of primitives,
+    /// which aren't parsed at the moment.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the primitive is malformed, in the same way as [`Polylist::iter`] and
+    /// [`Triangles::iter`].
+    ///
+    /// [`Polylist`]: ./struct.Polylist.html
+    /// [`Polylist::triangulate`]: ./struct.Polylist.html#method.triangulate
+    /// [`Polylist::iter`]: ./struct.Polylist.html#method.iter
+    /// [`Triangles`]: ./struct.Triangles.html
+    /// [`Triangles::iter`]: ./struct.Triangles.html#method.iter
+    pub fn triangulate<'a>(&'a self) -> Option<Box<dyn Iterator<Item = [Vertex<'a>; 3]> + 'a>> {
         match *self {
-            Library::Geometries(ref library_geometries) => Some(library_geometries),
+            Primitive::Polylist(ref polylist) => Some(Box::new(polylist.triangulate())),
+            Primitive::Triangles(ref triangles) => Some(Box::new(triangles.iter())),
             _ => None,
         }
     }
 }
 
+/// The indices of a primitive, as listed in a `<p>` element.
+///
+/// COLLADA indices are always non-negative integers. Some formats use `-1` as a sentinel value,
+/// but a `<p>` containing one (or any other token that isn't a non-negative integer) fails to
+/// parse with an [`ErrorKind::InvalidNumber`] that names the offending token.
+///
+/// [`ErrorKind::InvalidNumber`]: ../enum.ErrorKind.html#variant.InvalidNumber
 #[derive(Debug, Clone, PartialEq, ColladaElement)]
-#[name = "library_animations"]
-pub struct LibraryAnimations;
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[name = "p"]
+pub struct Primitives {
+    #[text]
+    data: Vec<usize>,
+}
 
-#[derive(Debug, Clone, PartialEq, ColladaElement)]
-#[name = "library_animation_clips"]
-pub struct LibraryAnimationClips;
+impl From<Vec<usize>> for Primitives {
+    fn from(data: Vec<usize>) -> Primitives { Primitives { data } }
+}
 
-#[derive(Debug, Clone, PartialEq, ColladaElement)]
-#[name = "library_cameras"]
-pub struct LibraryCameras;
+impl ::std::ops::Deref for Primitives {
+    type Target = [usize];
 
-#[derive(Debug, Clone, PartialEq, ColladaElement)]
-#[name = "library_controllers"]
-pub struct LibraryControllers;
+    fn deref(&self) -> &[usize] { &*self.data }
+}
 
+/// A single profile of an [`Effect`].
+///
+/// Each variant wraps a single value containing the profile data. See the documentation for
+/// each of the possible profiles for more information.
+///
+/// [`Effect`]: ./struct.Effect.html
 #[derive(Debug, Clone, PartialEq, ColladaElement)]
-#[name = "library_effects"]
-pub struct LibraryEffects;
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum Profile {
+    Cg(ProfileCg),
+    Common(Box<ProfileCommon>),
+    Gles(ProfileGles),
+    Glsl(ProfileGlsl),
+}
 
 #[derive(Debug, Clone, PartialEq, ColladaElement)]
-#[name = "library_force_fields"]
-pub struct LibraryForceFields;
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[name = "profile_CG"]
+pub struct ProfileCg;
 
-/// Contains geometric data for the document.
+/// The platform-independent fixed-function profile for an [`Effect`].
 ///
-/// The geometric data is contained in `geometries` by one or more [`Geometry`] instances,
-/// `LibraryGeometries` is only a container and does not represent any geometric data itself.
-///
-/// [`Geometry`]: ./struct.Geometry.html
+/// [`Effect`]: ./struct.Effect.html
 #[derive(Debug, Clone, PartialEq, ColladaElement)]
-#[name = "library_geometries"]
-pub struct LibraryGeometries {
-    /// A unique identifier for the library.
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[name = "profile_COMMON"]
+pub struct ProfileCommon {
+    /// A unique identifier for the profile.
     ///
     /// Will be unique within the document.
     #[attribute]
     pub id: Option<String>,
 
-    /// The human-friendly name for this library.
-    ///
-    /// Has no semantic meaning.
-    #[attribute]
-    pub name: Option<String>,
-
-    /// Metada about the library and the data contained within it.
+    /// Metadata about this profile.
     #[child]
     pub asset: Option<Asset>,
 
-    /// The geometric data contained within this library instance.
-    ///
-    /// There will always be at least one geometric element in a `LibraryGeometries`.
     #[child]
-    #[required]
-    pub geometries: Vec<Geometry>,
+    pub images: Vec<Image>,
 
-    /// Arbitrary additional information about this library and the data it contains.
+    #[child]
+    pub new_params: Vec<NewParam>,
+
+    /// The shading technique used by the profile.
+    #[child]
+    pub technique: ProfileCommonTechnique,
+
+    /// Arbitrary additional information about this profile.
     ///
     /// For more information about 3rd-party extensions, see the
     /// [crate-level documentation](../index.html#3rd-party-extensions).
@@ -639,293 +5970,458 @@ pub struct LibraryGeometries {
     pub extras: Vec<Extra>,
 }
 
-impl LibraryGeometries {
-    /// Returns an iterator over all the [`Geometry`] objects contained in this library.
+impl ProfileCommon {
+    /// Returns the parameter declared with `sid`, if there is one.
     ///
-    /// [`Geometry`]: ./struct.Geometry.html
-    pub fn geometries<'a>(&'a self) -> ::std::slice::Iter<'a, Geometry> {
-        self.geometries.iter()
+    /// Parameters declared by the profile's technique are searched first, followed by those
+    /// declared by the profile itself.
+    pub fn find_param(&self, sid: &str) -> Option<&NewParam> {
+        self.technique.new_params.iter()
+            .chain(self.new_params.iter())
+            .find(|param| param.sid == sid)
+    }
+
+    /// Returns the ID of the `<image>` sampled by `texture`.
+    ///
+    /// A texture refers to a `sampler2D` parameter by its `sid`, which in turn refers to a
+    /// `surface` parameter that's initialized from the image. Returns `None` if any of those
+    /// references can't be resolved.
+    pub fn image_for_texture(&self, texture: &Texture) -> Option<&str> {
+        let sampler = self.find_param(&texture.texture)?.value.as_sampler_2d()?;
+        let surface = self.find_param(&sampler.source)?.value.as_surface()?;
+        surface.init_from.first().map(|init_from| &*init_from.image)
     }
 }
 
+/// The shading technique for a [`ProfileCommon`].
+///
+/// [`ProfileCommon`]: ./struct.ProfileCommon.html
 #[derive(Debug, Clone, PartialEq, ColladaElement)]
-#[name = "library_images"]
-pub struct LibraryImages;
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[name = "technique"]
+pub struct ProfileCommonTechnique {
+    /// A unique identifier for the technique.
+    ///
+    /// Will be unique within the document.
+    #[attribute]
+    pub id: Option<String>,
 
-#[derive(Debug, Clone, PartialEq, ColladaElement)]
-#[name = "library_lights"]
-pub struct LibraryLights;
+    /// An identifier for the technique that's unique amongst its sibling elements.
+    #[attribute]
+    pub sid: String,
 
-#[derive(Debug, Clone, PartialEq, ColladaElement)]
-#[name = "library_materials"]
-pub struct LibraryMaterials;
+    /// Metadata about this technique.
+    #[child]
+    pub asset: Option<Asset>,
 
-#[derive(Debug, Clone, PartialEq, ColladaElement)]
-#[name = "library_nodes"]
-pub struct LibraryNodes;
+    #[child]
+    pub images: Vec<Image>,
+
+    #[child]
+    pub new_params: Vec<NewParam>,
+
+    /// The shader describing how the surface is lit.
+    #[child]
+    pub shader: Shader,
+
+    /// Arbitrary additional information about this technique.
+    ///
+    /// For more information about 3rd-party extensions, see the
+    /// [crate-level documentation](../index.html#3rd-party-extensions).
+    #[child]
+    pub extras: Vec<Extra>,
+}
 
 #[derive(Debug, Clone, PartialEq, ColladaElement)]
-#[name = "library_physics_materials"]
-pub struct LibraryPhysicsMaterials;
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[name = "profile_GLES"]
+pub struct ProfileGles;
 
 #[derive(Debug, Clone, PartialEq, ColladaElement)]
-#[name = "library_physics_models"]
-pub struct LibraryPhysicsModels;
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[name = "profile_GLSL"]
+pub struct ProfileGlsl;
 
+/// The projection used by a [`Camera`].
+///
+/// [`Camera`]: ./struct.Camera.html
 #[derive(Debug, Clone, PartialEq, ColladaElement)]
-#[name = "library_physics_scenes"]
-pub struct LibraryPhysicsScenes;
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum Projection {
+    Orthographic(Orthographic),
+    Perspective(Perspective),
+}
 
+/// The radius of a sphere, or the radii along the X and Z axes of a capsule or cylinder.
 #[derive(Debug, Clone, PartialEq, ColladaElement)]
-#[name = "library_visual_scenes"]
-pub struct LibraryVisualScenes;
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[name = "radius"]
+pub struct Radius {
+    #[text]
+    pub data: Vec<f64>,
+}
 
+/// The mirror reflection color of a surface, used by the common profile's shaders.
 #[derive(Debug, Clone, PartialEq, ColladaElement)]
-#[name = "lines"]
-pub struct Lines;
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[name = "reflective"]
+pub struct Reflective {
+    #[child]
+    pub value: ColorOrTexture,
+}
 
+/// The mirror reflection amount of a surface, used by the common profile's shaders.
 #[derive(Debug, Clone, PartialEq, ColladaElement)]
-#[name = "linestrips"]
-pub struct Linestrips;
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[name = "reflectivity"]
+pub struct Reflectivity {
+    #[child]
+    pub value: FloatOrParam,
+}
 
-/// Describes basic geometric meshes using vertex and primitive information.
+/// An error that occurred while resolving the vertices of a [`Mesh`] or the instances in a scene.
 ///
-/// Meshes embody a general form of geometric description that primarily includes vertex and
-/// primitive information. Vertex information is the set of attributes associated with a poin on
-/// the surface of the mesh. Each vertex includes data for attributes such as:
+/// Returned by [`Mesh::resolved_vertices`] and [`Collada::scene_graph`].
 ///
-/// * Vertex position
-/// * Vertex color
-/// * Vertex normal
-/// * Vertex texture coordinate
+/// [`Mesh`]: ./struct.Mesh.html
+/// [`Mesh::resolved_vertices`]: ./struct.Mesh.html#method.resolved_vertices
+/// [`Collada::scene_graph`]: ./struct.Collada.html#method.scene_graph
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ResolveError {
+    /// The mesh's `vertices` had no `"POSITION"` input, or a primitive had no `"VERTEX"` input.
+    MissingInput {
+        /// The semantic of the missing input.
+        semantic: &'static str,
+    },
+
+    /// An input referenced a source that isn't in the mesh.
+    MissingSource {
+        /// The id of the missing source.
+        id: String,
+    },
+
+    /// A source had no common accessor.
+    MissingAccessor {
+        /// The id of the source.
+        source: String,
+    },
+
+    /// A source didn't contain float data.
+    UnsupportedArray {
+        /// The id of the source.
+        source: String,
+    },
+
+    /// A source's accessor read from data outside the document, which isn't loaded.
+    ExternalSource {
+        /// The id of the source.
+        source: String,
+
+        /// The location of the external data.
+        uri: AnyUri,
+    },
+
+    /// A vertex referenced a record past the end of a source.
+    IndexOutOfBounds {
+        /// The id of the source.
+        source: String,
+
+        /// The index of the record.
+        index: usize,
+    },
+
+    /// An instance referenced an element that isn't in the document.
+    MissingTarget {
+        /// The name of the element that was referenced, e.g. `"geometry"`.
+        element: &'static str,
+
+        /// The id of the missing element.
+        id: String,
+    },
+
+    /// A node instantiated itself, either directly or through one of its descendants.
+    CyclicInstance {
+        /// The id of the node.
+        id: String,
+    },
+}
+
+impl Display for ResolveError {
+    fn fmt(&self, formatter: &mut Formatter) -> ::std::result::Result<(), fmt::Error> {
+        match *self {
+            ResolveError::MissingInput { semantic } => {
+                write!(formatter, "Missing input with the \"{}\" semantic", semantic)
+            }
+
+            ResolveError::MissingSource { ref id } => {
+                write!(formatter, "Mesh has no source with the id \"{}\"", id)
+            }
+
+            ResolveError::MissingAccessor { ref source } => {
+                write!(formatter, "Source \"{}\" has no common accessor", source)
+            }
+
+            ResolveError::UnsupportedArray { ref source } => {
+                write!(formatter, "Source \"{}\" doesn't contain float data", source)
+            }
+
+            ResolveError::ExternalSource { ref source, ref uri } => {
+                write!(formatter, "Source \"{}\" reads from external data at \"{}\"", source, uri)
+            }
+
+            ResolveError::IndexOutOfBounds { ref source, index } => {
+                write!(formatter, "Index {} is out of bounds for source \"{}\"", index, source)
+            }
+
+            ResolveError::MissingTarget { element, ref id } => {
+                write!(formatter, "Document has no <{}> with the id \"{}\"", element, id)
+            }
+
+            ResolveError::CyclicInstance { ref id } => {
+                write!(formatter, "Node \"{}\" instantiates itself", id)
+            }
+        }
+    }
+}
+
+impl ::std::error::Error for ResolveError {}
+
+/// A vertex of a mesh with all of its attributes resolved from the mesh's sources.
 ///
-/// The mesh also includes a description of how the vertices are organized to form the geometric
-/// shape of the mesh. The mesh vertices are collated into geometric primitives such as polygons,
-/// triangles, or lines.
-#[derive(Debug, Clone, PartialEq, ColladaElement)]
-#[name = "mesh"]
-pub struct Mesh {
-    /// One or more [`Source`] instances containing the raw mesh data.
-    ///
-    /// These contain the raw data used to specify the vertex attributes of the vertices in the
-    /// mesh. The primitives in `primitives` will index into these sources to specify the mesh.
-    ///
-    /// [`Source`]: ./struct.Source.html
-    #[child]
-    #[required]
-    pub sources: Vec<Source>,
+/// Returned by [`Mesh::resolved_vertices`].
+///
+/// [`Mesh::resolved_vertices`]: ./struct.Mesh.html#method.resolved_vertices
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct ResolvedVertex {
+    /// The position of the vertex.
+    pub position: [f32; 3],
 
-    /// Describes the mesh's vertex attributes.
-    ///
-    /// `vertices` will have the [`UnsharedInput`] which specifies the "POSITION" attribute for
-    /// the mesh's vertices. It may also specify other mesh attributes.
-    ///
-    /// [`UnsharedInput`]: ./struct.UnsharedInput.html
-    #[child]
-    pub vertices: Vertices,
+    /// The normal of the vertex, if the mesh has normals.
+    pub normal: Option<[f32; 3]>,
 
-    /// Geometric primitives that assemble values from the inputs into vertex attribute data.
-    #[child]
-    pub primitives: Vec<Primitive>,
+    /// The texture coordinate of the vertex, if the mesh has texture coordinates.
+    pub texcoord: Option<[f32; 2]>,
 
-    /// Arbitrary additional information about this geometry instance and the data it contains.
+    /// The RGBA color of the vertex, if the mesh has vertex colors.
     ///
-    /// For more information about 3rd-party extensions, see the
-    /// [crate-level documentation](../index.html#3rd-party-extensions).
-    #[child]
-    pub extras: Vec<Extra>,
+    /// If the color source only has RGB components, alpha is `1.0`.
+    pub color: Option<[f32; 4]>,
 }
 
-impl Mesh {
-    /// Returns the source which matches `id`, or `None` if no sources match.
-    ///
-    /// # Examples
+/// A single render pass within an [`EvaluateScene`].
+///
+/// [`EvaluateScene`]: ./struct.EvaluateScene.html
+#[derive(Debug, Clone, PartialEq, ColladaElement)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[name = "render"]
+pub struct Render {
+    /// The location of the node containing the camera that the pass is rendered from.
+    #[attribute]
+    pub camera_node: UriFragment,
+
+    /// The layers that are rendered in this pass. If empty, the entire scene is rendered.
     ///
-    /// ```
-    /// # #![allow(unused_variables)]
-    /// # use std::fs::File;
-    /// # use collaborate::v1_4::Collada;
-    /// # let file = File::open("resources/blender_cube.dae").unwrap();
-    /// # let document = Collada::read(file).unwrap();
-    /// # let library = document.libraries[5].as_library_geometries().unwrap();
-    /// let mesh = library.geometries[0].geometric_element.as_mesh().unwrap();
-    /// let positions_source = mesh.find_source("Cube-mesh-positions");
-    /// assert!(positions_source.is_some());
-    /// ```
-    pub fn find_source<'a>(&'a self, id: &str) -> Option<&'a Source> {
-        self.sources.iter().find(|source| source.id == id)
-    }
+    /// Nodes are assigned to layers by their `layer` attribute.
+    #[child]
+    #[name = "layer"]
+    pub layers: Vec<String>,
 
-    /// Returns an iterator over the primitives in the mesh.
-    pub fn primitives<'a>(&'a self) -> ::std::slice::Iter<'a, Primitive> {
-        self.primitives.iter()
-    }
+    /// The effect used to render this pass, if it overrides the materials in the scene.
+    #[child]
+    pub instance_effect: Option<InstanceEffect>,
 }
 
-#[derive(Debug, Clone, PartialEq, ColladaElement)]
-#[name = "Name_array"]
-pub struct NameArray;
+/// The fraction of energy that's retained when an object collides with another.
+#[derive(Debug, Clone, Default, PartialEq, ColladaElement)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[name = "restitution"]
+pub struct Restitution {
+    /// An identifier for the value that's unique amongst its sibling elements.
+    #[attribute]
+    pub sid: Option<String>,
 
-/// Declares parametric information for its parent element.
-///
-/// A functional or programmatical format requires a means for users to specify parametric
-/// information. This information represents function parameter (argument) data.
-///
-/// Material shader programs may contain code representing vertex or pixel programs. These
-/// programs require parameters as part of their state information.
+    #[text]
+    pub value: f64,
+}
+
+/// A rigid body in a [`PhysicsModel`].
 ///
-/// The basic declaration of a parameter describes the name, data type, and value data of the
-/// parameter. That parameter name identifies it to the function or program. The parameter type
-/// indicates the encoding of its value.
+/// [`PhysicsModel`]: ./struct.PhysicsModel.html
 #[derive(Debug, Clone, PartialEq, ColladaElement)]
-#[name = "param"]
-pub struct Param {
-    /// The name of the parameter.
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[name = "rigid_body"]
+pub struct RigidBody {
+    /// An identifier for the rigid body that's unique amongst its sibling elements.
     #[attribute]
-    pub name: Option<String>,
+    pub sid: String,
 
-    /// The subidentifier of this parameter.
+    /// The human-friendly name for the rigid body.
     ///
-    /// This value is unique within the scope of the parent element.
+    /// Has no semantic meaning.
     #[attribute]
-    pub sid: Option<String>,
+    pub name: Option<String>,
 
-    /// The type of the value data.
-    ///
-    /// Must be understood by the application consuming the COLLADA document.
-    #[attribute]
-    #[name = "type"]
-    pub data_type: Option<String>,
+    /// The properties of the rigid body, as defined by the common profile.
+    #[child]
+    pub technique_common: RigidBodyTechniqueCommon,
 
-    /// The user-defined meaning of the parameter.
-    #[attribute]
-    pub semantic: Option<String>,
-}
+    /// Profile-specific properties of the rigid body.
+    #[child]
+    pub techniques: Vec<Technique>,
 
-#[derive(Debug, Clone)]
-pub struct Polygon<'a> {
-    len: usize,
-    chunks: ::std::slice::Chunks<'a, usize>,
+    /// Arbitrary additional information about this rigid body.
+    ///
+    /// For more information about 3rd-party extensions, see the
+    /// [crate-level documentation](../index.html#3rd-party-extensions).
+    #[child]
+    pub extras: Vec<Extra>,
 }
 
-impl<'a> Polygon<'a> {
-    pub fn iter(&self) -> PolygonIter<'a> {
-        PolygonIter { chunks: self.chunks.clone() }
-    }
+/// The properties of a [`RigidBody`] as defined by the common profile.
+///
+/// [`RigidBody`]: ./struct.RigidBody.html
+#[derive(Debug, Clone, PartialEq, ColladaElement)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[name = "technique_common"]
+pub struct RigidBodyTechniqueCommon {
+    /// Whether the rigid body is moved by the simulation.
+    #[child]
+    pub dynamic: Option<Dynamic>,
 
-    /// Returns the number of vertices in this polygon.
-    pub fn len(&self) -> usize {
-        self.len
-    }
-}
+    /// The total mass of the rigid body.
+    #[child]
+    pub mass: Option<Mass>,
 
-impl<'a> ::std::iter::IntoIterator for Polygon<'a> {
-    type Item = Vertex<'a>;
-    type IntoIter = PolygonIter<'a>;
+    /// The position and orientation of the rigid body's center of mass.
+    #[child]
+    pub mass_frame: Option<MassFrame>,
 
-    fn into_iter(self) -> Self::IntoIter {
-        PolygonIter { chunks: self.chunks }
-    }
-}
+    /// The principal moments of inertia of the rigid body.
+    #[child]
+    pub inertia: Option<Inertia>,
 
-impl<'a> ::std::iter::IntoIterator for &'a Polygon<'a> {
-    type Item = Vertex<'a>;
-    type IntoIter = PolygonIter<'a>;
+    /// The physics material used by the rigid body's shapes.
+    #[child]
+    pub physics_material: Option<PhysicsMaterialSource>,
 
-    fn into_iter(self) -> Self::IntoIter {
-        PolygonIter { chunks: self.chunks.clone() }
-    }
+    /// The shapes that make up the rigid body.
+    ///
+    /// There will always be at least one shape in a rigid body.
+    #[child]
+    #[required]
+    pub shapes: Vec<Shape>,
 }
 
-pub struct PolygonIter<'a> {
-    chunks: ::std::slice::Chunks<'a, usize>,
+#[derive(Debug, Clone, PartialEq, ColladaElement)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[name = "rigid_constraint"]
+pub struct RigidConstraint;
+
+/// A transform used to position the center of mass or a shape of a rigid body.
+#[derive(Debug, Clone, PartialEq, ColladaElement)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum RigidTransform {
+    Rotate(Rotate),
+    Translate(Translate),
 }
 
-impl<'a> ::std::iter::Iterator for PolygonIter<'a> {
-    type Item = Vertex<'a>;
+/// A rotation around an arbitrary axis.
+///
+/// `data` contains 4 values: the axis of rotation followed by the angle in degrees.
+#[derive(Debug, Clone, PartialEq, ColladaElement)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[name = "rotate"]
+pub struct Rotate {
+    /// An identifier for the transform that's unique amongst its sibling elements.
+    #[attribute]
+    pub sid: Option<String>,
 
-    fn next(&mut self) -> Option<Vertex<'a>> {
-        self.chunks.next().map(|attributes| Vertex { attributes })
-    }
+    #[text]
+    pub data: Vec<f64>,
 }
 
-#[derive(Debug, Clone, PartialEq, ColladaElement)]
-#[name = "polygons"]
-pub struct Polygons;
+impl Rotate {
+    /// Returns the transform as a column-major 4x4 matrix.
+    ///
+    /// The rotation is right-handed. Missing values are treated as `0.0`.
+    pub fn matrix(&self) -> [f64; 16] {
+        ::math::rotation(vector3(&self.data, 0), component(&self.data, 3))
+    }
+}
 
-/// A list of polygons that are not necessarily triangles.
-///
-/// Provides the information needed for a mesh to bind vertex attributes together and then
-/// organize those vertices into individual polygons. `Polylist` provides functionality for
-/// iterating over the polygons it represents.
-///
-/// # Examples
-///
-/// Iterate over all of the polygons in a polylist, then iterate over each vertex in each polygon:
+/// A non-uniform scale along each axis.
 ///
-/// ```
-/// # #![allow(unused_variables)]
-/// # use std::fs::File;
-/// # use collaborate::v1_4::Collada;
-/// # let file = File::open("resources/blender_cube.dae").unwrap();
-/// # let document = Collada::read(file).unwrap();
-/// # let library = document.libraries[5].as_library_geometries().unwrap();
-/// # let mesh = library.geometries[0].geometric_element.as_mesh().unwrap();
-/// let polylist = mesh.primitives[0].as_polylist().unwrap();
-/// for polygon in polylist {
-///     println!("Vertices in polygon: {}", polygon.len());
-///     for vertex in polygon {
-///         println!("{:?}", vertex);
-///         for attribute in vertex {
-///             for input in polylist.inputs_for_offset(attribute.offset) {
-///                 println!(
-///                     "Attribute {:?} indexes into {:?}",
-///                     attribute,
-///                     input,
-///                 );
-///             }
-///         }
-///     }
-/// }
-/// ```
+/// `data` contains the 3 scale factors for the X, Y, and Z axes.
 #[derive(Debug, Clone, PartialEq, ColladaElement)]
-#[name = "polylist"]
-pub struct Polylist {
-    /// A human-friendly name for this polylist.
-    ///
-    /// Has no semantic meaning.
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[name = "scale"]
+pub struct Scale {
+    /// An identifier for the transform that's unique amongst its sibling elements.
     #[attribute]
-    pub name: Option<String>,
+    pub sid: Option<String>,
 
-    /// The number of polygon primitives in the polylist.
-    #[attribute]
-    pub count: usize,
+    #[text]
+    pub data: Vec<f64>,
+}
 
-    /// The name of the material associated with this polylist.
-    ///
-    /// This name is bound to a material at the time of instantiaion. See [`InstanceGeometry`]
-    /// and [`BindMaterial`].
+impl Scale {
+    /// Returns the transform as a column-major 4x4 matrix.
     ///
-    /// If `None`, then the lighting and shading results are appplication-defined.
+    /// Missing values are treated as `0.0`.
+    pub fn matrix(&self) -> [f64; 16] {
+        ::math::scale(vector3(&self.data, 0))
+    }
+}
+
+/// Declares how a 2D texture is sampled.
+///
+/// The wrap modes are one of `"WRAP"`, `"MIRROR"`, `"CLAMP"`, `"BORDER"`, or `"NONE"`, and the
+/// filters are one of `"NONE"`, `"NEAREST"`, `"LINEAR"`, `"NEAREST_MIPMAP_NEAREST"`,
+/// `"LINEAR_MIPMAP_NEAREST"`, `"NEAREST_MIPMAP_LINEAR"`, or `"LINEAR_MIPMAP_LINEAR"`.
+#[derive(Debug, Clone, PartialEq, ColladaElement)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[name = "sampler2D"]
+pub struct Sampler2D {
+    /// The `sid` of the [`NewParam`] containing the [`Surface`] to sample.
     ///
-    /// [`InstanceGeometry`]: ./struct.InstanceGeometry.html
-    /// [`BindMaterial`]: ./struct.BindMaterial.html
-    #[attribute]
-    pub material: Option<String>,
+    /// [`NewParam`]: ./struct.NewParam.html
+    /// [`Surface`]: ./struct.Surface.html
+    #[child]
+    pub source: String,
 
-    /// The input data for the polylist.
+    /// How the texture is wrapped in the S direction.
     #[child]
-    pub inputs: Vec<SharedInput>,
+    pub wrap_s: Option<String>,
 
-    /// A list of integers, each specifying the number of vertices for one polygon in the polylist.
+    /// How the texture is wrapped in the T direction.
     #[child]
-    pub vcount: Option<VCount>,
+    pub wrap_t: Option<String>,
 
-    /// A list of integers that specify the vertex attributes as indexes into the inputs.
+    /// The filter used when the texture is minified.
     #[child]
-    pub primitives: Option<Primitives>,
+    pub minfilter: Option<String>,
 
-    /// Arbitrary additional information about this polylist and the data it contains.
+    /// The filter used when the texture is magnified.
+    #[child]
+    pub magfilter: Option<String>,
+
+    /// The filter used between mip levels.
+    #[child]
+    pub mipfilter: Option<String>,
+
+    #[child]
+    pub border_color: Option<BorderColor>,
+
+    /// The highest mip level that will be sampled.
+    #[child]
+    pub mipmap_maxlevel: Option<usize>,
+
+    /// The bias applied when choosing a mip level.
+    #[child]
+    pub mipmap_bias: Option<f32>,
+
+    /// Arbitrary additional information about this sampler.
     ///
     /// For more information about 3rd-party extensions, see the
     /// [crate-level documentation](../index.html#3rd-party-extensions).
@@ -933,148 +6429,155 @@ pub struct Polylist {
     pub extras: Vec<Extra>,
 }
 
-impl Polylist {
-    /// Returns an iterator over the polygons in the polylist.
-    pub fn iter<'a>(&'a self) -> PolylistIter<'a> {
-        // Determine the number of indices that are used for each vertex. Generally, we expect this to
-        // be the same as the number of inputs (e.g. if there's an input for position and an input
-        // for normal, then we'd expect there to be 2 indices for each vertex), but the COLLADA spec
-        // allows multiple inputs to share an offset, effectively reducing the number of indices
-        // needed for each vertex. To account for this, we look for the largest offset used by the
-        // inputs, which should tell us consistently how many unique offsets there are.
-        // TODO: How do we handle a polylist with no inputs? Probably return no polygons.
-        let largest_offset = self.inputs.iter()
-            .map(|input| input.offset)
-            .max()
-            .unwrap();
-
-        PolylistIter {
-            polylist: self,
-            num_indices_per_vertex: largest_offset + 1,
-            vcount_iter: self.vcount.as_ref().unwrap().iter(),
-            verts_so_far: 0,
-        }
-    }
+/// The scene that the document describes.
+///
+/// A document may contain several visual and physics scenes in its libraries, but only those
+/// instantiated here are part of the scene that the document describes.
+#[derive(Debug, Clone, PartialEq, ColladaElement)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[name = "scene"]
+pub struct Scene {
+    /// The physics scenes that are simulated in the scene.
+    #[child]
+    pub instance_physics_scenes: Vec<InstancePhysicsScene>,
 
-    /// Returns the number of polygons in the polylist.
-    pub fn len(&self) -> usize {
-        self.count
-    }
+    /// The visual scene that's rendered for the scene.
+    #[child]
+    pub instance_visual_scene: Option<InstanceVisualScene>,
 
-    /// Returns an iterator yielding all inputs that match `offset`.
-    ///
-    /// When matching a vertex attribute to an input, the attribute's offset is matched against
-    /// the input's offset. It's possible for multiple inputs to share the same offset, so this
-    /// method provides an easy way to iterate over all inputs with a given offset.
-    ///
-    /// # Examples
+    /// Arbitrary additional information about this scene.
     ///
-    /// ```
-    /// # #![allow(unused_variables)]
-    /// # use std::fs::File;
-    /// # use collaborate::v1_4::Collada;
-    /// # let file = File::open("resources/blender_cube.dae").unwrap();
-    /// # let document = Collada::read(file).unwrap();
-    /// # let library = document.libraries[5].as_library_geometries().unwrap();
-    /// # let mesh = library.geometries[0].geometric_element.as_mesh().unwrap();
-    /// let polylist = mesh.primitives[0].as_polylist().unwrap();
-    /// for polygon in polylist {
-    ///     println!("Vertices in polygon: {}", polygon.len());
-    ///     for vertex in polygon {
-    ///         println!("{:?}", vertex);
-    ///         for attribute in vertex {
-    ///             for input in polylist.inputs_for_offset(attribute.offset) {
-    ///                 println!(
-    ///                     "Attribute {:?} indexes into {:?}",
-    ///                     attribute,
-    ///                     input,
-    ///                 );
-    ///             }
-    ///         }
-    ///     }
-    /// }
-    /// ```
-    pub fn inputs_for_offset<'a>(&'a self, offset: usize) -> InputsForOffset<'a> {
-        InputsForOffset {
-            inputs: self.inputs.iter(),
-            offset,
-        }
-    }
+    /// For more information about 3rd-party extensions, see the
+    /// [crate-level documentation](../index.html#3rd-party-extensions).
+    #[child]
+    pub extras: Vec<Extra>,
 }
 
-impl<'a> ::std::iter::IntoIterator for &'a Polylist {
-    type Item = Polygon<'a>;
-    type IntoIter = PolylistIter<'a>;
+/// A flattened view of the document's visual scene.
+///
+/// Returned by [`Collada::scene_graph`].
+///
+/// [`Collada::scene_graph`]: ./struct.Collada.html#method.scene_graph
+#[derive(Debug, Clone, PartialEq)]
+pub struct SceneGraph<'a> {
+    /// Every instance in the scene, in depth-first order.
+    pub instances: Vec<SceneInstance<'a>>,
 
-    fn into_iter(self) -> PolylistIter<'a> {
-        self.iter()
-    }
+    /// The URLs of instances that refer to elements in other documents, in depth-first order.
+    ///
+    /// Other documents aren't loaded, so these instances can't be resolved and aren't included
+    /// in `instances`.
+    pub external: Vec<&'a AnyUri>,
 }
 
-pub struct PolylistIter<'a> {
-    polylist: &'a Polylist,
-    num_indices_per_vertex: usize,
-    vcount_iter: ::std::slice::Iter<'a, usize>,
-    verts_so_far: usize,
+impl<'a> SceneGraph<'a> {
+    /// Returns the instances contained in the node at `path`.
+    ///
+    /// Instances that come from an `<instance_node>` are listed under the path of the node that
+    /// contains the `<instance_node>` element.
+    pub fn instances_at<'b>(&'b self, path: &'b NodePath) -> impl Iterator<Item = &'b SceneInstance<'a>> {
+        self.instances.iter().filter(move |instance| instance.path == *path)
+    }
 }
 
-impl<'a> ::std::iter::Iterator for PolylistIter<'a> {
-    type Item = Polygon<'a>;
+/// A geometry, camera, or light placed within a [`SceneGraph`].
+///
+/// [`SceneGraph`]: ./struct.SceneGraph.html
+#[derive(Debug, Clone, PartialEq)]
+pub struct SceneInstance<'a> {
+    /// The location of the node containing the instance, within the visual scene.
+    pub path: NodePath,
 
-    fn next(&mut self) -> Option<Polygon<'a>> {
-        let primitives = match self.polylist.primitives {
-            Some(ref primitives) => primitives,
-            None => return None,
-        };
+    /// The node that directly contains the instance.
+    ///
+    /// This is a library node if the instance was reached through an `<instance_node>`.
+    pub node: &'a Node,
 
-        self.vcount_iter.next()
-            .map(|&num_verts| {
-                let indices = &primitives[self.verts_so_far * self.num_indices_per_vertex .. (self.verts_so_far + num_verts) * self.num_indices_per_vertex];
-                self.verts_so_far += num_verts;
-                Polygon {
-                    len: num_verts,
-                    chunks: indices.chunks(self.num_indices_per_vertex),
-                }
-            })
-    }
+    /// The world transform of the instance, as a column-major 4x4 matrix.
+    pub transform: [f64; 16],
+
+    /// The instantiated element.
+    pub object: SceneObject<'a>,
+}
+
+/// The element instantiated by a [`SceneInstance`].
+///
+/// [`SceneInstance`]: ./struct.SceneInstance.html
+#[derive(Debug, Clone, PartialEq)]
+pub enum SceneObject<'a> {
+    Camera(&'a Camera),
+    Geometry(&'a Geometry),
+
+    /// Light libraries aren't parsed yet, so lights are represented by their instance.
+    Light(&'a InstanceLight),
 }
 
+/// Overrides the value of a parameter when instantiating an effect.
 #[derive(Debug, Clone, PartialEq, ColladaElement)]
-pub enum Primitive {
-    Lines(Lines),
-    Linestrips(Linestrips),
-    Polygons(Polygons),
-    Polylist(Polylist),
-    Triangles(Triangles),
-    Trifans(Trifans),
-    Tristrips(Tristrips),
-}
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[name = "setparam"]
+pub struct SetParam {
+    /// The `sid` of the parameter being overridden.
+    #[attribute]
+    #[name = "ref"]
+    pub reference: String,
 
-impl Primitive {
-    pub fn as_polylist(&self) -> Option<&Polylist> {
-        match *self {
-            Primitive::Polylist(ref polylist) => Some(polylist),
-            _ => None,
-        }
-    }
+    /// The new value for the parameter.
+    #[child]
+    pub value: ParamValue,
 }
 
+/// A shader of unknown type, used by the common profile.
+///
+/// Each variant wraps a single value containing the shader's parameters. All shaders share a
+/// subset of parameters, e.g. `emission` and `reflective`, which can be retrieved regardless of
+/// the shader type using the methods on `Shader`.
 #[derive(Debug, Clone, PartialEq, ColladaElement)]
-#[name = "p"]
-pub struct Primitives {
-    #[text]
-    data: Vec<usize>,
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum Shader {
+    Blinn(Blinn),
+    Constant(Constant),
+    Lambert(Lambert),
+    Phong(Phong),
 }
 
-impl ::std::ops::Deref for Primitives {
-    type Target = [usize];
+impl Shader {
+    /// Returns the emitted color of the surface, if specified.
+    pub fn emission(&self) -> Option<&ColorOrTexture> {
+        let emission = match *self {
+            Shader::Blinn(ref blinn) => &blinn.emission,
+            Shader::Constant(ref constant) => &constant.emission,
+            Shader::Lambert(ref lambert) => &lambert.emission,
+            Shader::Phong(ref phong) => &phong.emission,
+        };
+        emission.as_ref().map(|emission| &emission.value)
+    }
 
-    fn deref(&self) -> &[usize] { &*self.data }
-}
+    /// Returns the diffuse color of the surface, if specified.
+    ///
+    /// Always returns `None` for a `Constant` shader.
+    pub fn diffuse(&self) -> Option<&ColorOrTexture> {
+        let diffuse = match *self {
+            Shader::Blinn(ref blinn) => &blinn.diffuse,
+            Shader::Constant(_) => &None,
+            Shader::Lambert(ref lambert) => &lambert.diffuse,
+            Shader::Phong(ref phong) => &phong.diffuse,
+        };
+        diffuse.as_ref().map(|diffuse| &diffuse.value)
+    }
 
-#[derive(Debug, Clone, PartialEq, ColladaElement)]
-#[name = "scene"]
-pub struct Scene;
+    /// Returns the shininess of the specular highlight, if specified.
+    ///
+    /// Always returns `None` for `Constant` and `Lambert` shaders.
+    pub fn shininess(&self) -> Option<&FloatOrParam> {
+        let shininess = match *self {
+            Shader::Blinn(ref blinn) => &blinn.shininess,
+            Shader::Phong(ref phong) => &phong.shininess,
+            Shader::Constant(_) | Shader::Lambert(_) => &None,
+        };
+        shininess.as_ref().map(|shininess| &shininess.value)
+    }
+}
 
 /// Declares the input semantic of a data source and connects a consumer of that source.
 ///
@@ -1118,6 +6621,7 @@ pub struct Scene;
 /// | `"VERTEX"`          | Mesh vertex.                                               |
 /// | `"WEIGHT"`          | Skin influence weighting value.                            |
 #[derive(Debug, Clone, PartialEq, ColladaElement)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 #[name = "input"]
 pub struct SharedInput {
     /// The offset into the list of indices provided by the parent object.
@@ -1126,76 +6630,784 @@ pub struct SharedInput {
     /// This is a simple form of compression for the list of indices and also defines the order
     /// in which inputs are used.
     #[attribute]
-    pub offset: usize,
+    pub offset: usize,
+
+    /// The user-defined meaning of the input connnection.
+    ///
+    /// See the type-level documentation for a [list of common semantic values](#common-semantics).
+    #[attribute]
+    pub semantic: String,
+
+    /// The location of the data source.
+    #[attribute]
+    pub source: UriFragment,
+
+    /// Which inputs to group as a single set.
+    ///
+    /// This is helpful when multiple inputs share the same semantic.
+    #[attribute]
+    pub set: Option<usize>,
+}
+
+#[derive(Debug, Clone, PartialEq, ColladaElement)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[name = "source"]
+pub struct Source {
+    #[attribute]
+    pub id: String,
+
+    #[attribute]
+    pub name: Option<String>,
+
+    #[child]
+    pub asset: Option<Asset>,
+
+    #[child]
+    pub array: Option<Array>,
+
+    #[child]
+    pub technique_common: Option<SourceTechniqueCommon>,
+
+    #[child]
+    pub techniques: Vec<Technique>,
+
+    /// Where the `<source>` element started in the source document.
+    #[position]
+    #[cfg_attr(feature = "serde", serde(skip))]
+    pub position: SourcePosition,
+}
+
+impl Source {
+    // Returns the [`Accessor`] in the source's `technique_common` member.
+    pub fn common_accessor(&self) -> Option<&Accessor> {
+        self.technique_common
+            .as_ref()
+            .map(|technique| &technique.accessor)
+    }
+
+    /// Returns the [`Accessor`] in the first of the source's techniques that has the given
+    /// profile and contains one.
+    ///
+    /// Some exporters describe additional layouts of the source data in profile-specific
+    /// techniques, which aren't used by [`common_accessor`]. The accessor must be a direct child
+    /// of the `<technique>`. Returns `None` if no technique with the profile has a valid accessor.
+    ///
+    /// [`Accessor`]: ./struct.Accessor.html
+    /// [`common_accessor`]: #method.common_accessor
+    pub fn accessor_for_profile(&self, profile: &str) -> Option<Accessor> {
+        self.techniques.iter()
+            .filter(|technique| technique.profile == profile)
+            .filter_map(Technique::parse_child)
+            .next()
+    }
+
+    /// Finds the data read by the source's common accessor.
+    ///
+    /// An accessor whose `source` is a fragment (e.g. `"#positions-array"`) reads from the array
+    /// in this source. Any other URI refers to data outside the document, which is returned as
+    /// [`AccessorSource::External`] rather than being matched against the local array. Returns
+    /// `None` if the source has no common accessor, or if the accessor reads from a local array
+    /// but the source doesn't have one.
+    ///
+    /// [`AccessorSource::External`]: ./enum.AccessorSource.html#variant.External
+    pub fn accessor_source<'a>(&'a self) -> Option<AccessorSource<'a>> {
+        let accessor = self.common_accessor()?;
+        if accessor.source.as_str().starts_with('#') {
+            self.array.as_ref().map(AccessorSource::Array)
+        } else {
+            Some(AccessorSource::External(accessor.source.clone()))
+        }
+    }
+
+    /// Checks that the source's common accessor fits within the source's array.
+    ///
+    /// The accessor must not have more params than its `stride`, since each param names one value
+    /// of a record. The spec allows fewer params than `stride`, in which case the remaining values
+    /// of each record are skipped. The last of the accessor's `count` records must also end within
+    /// the array, so that every record can be read with [`Accessor::access`].
+    ///
+    /// Only float arrays are checked, since the other array types don't store their values yet.
+    /// Sources without a common accessor, and accessors reading from external data, always pass.
+    ///
+    /// # Errors
+    ///
+    /// Returns an [`ErrorKind::InvalidValue`] for the `accessor` describing the mismatch, at the
+    /// [`position`](#structfield.position) of the `<source>`. Sources that weren't parsed from a
+    /// document report the start of the document instead.
+    ///
+    /// [`Accessor::access`]: ./struct.Accessor.html#method.access
+    /// [`ErrorKind::InvalidValue`]: ../enum.ErrorKind.html#variant.InvalidValue
+    pub fn validate(&self) -> Result<()> {
+        let invalid = |value| Error {
+            position: self.position.get().unwrap_or_else(TextPosition::new),
+            kind: ErrorKind::InvalidValue { element: "accessor", value },
+        };
+
+        let accessor = match self.common_accessor() {
+            Some(accessor) => accessor,
+            None => return Ok(()),
+        };
+
+        if accessor.params.len() > accessor.stride {
+            return Err(invalid(format!(
+                "{} params with a stride of {}",
+                accessor.params.len(),
+                accessor.stride,
+            )));
+        }
+
+        let array = match self.accessor_source() {
+            Some(AccessorSource::Array(Array::Float(array))) => array,
+            _ => return Ok(()),
+        };
+
+        let required = accessor.stride
+            .checked_mul(accessor.count)
+            .and_then(|values| values.checked_add(accessor.offset))
+            .ok_or_else(|| invalid(format!(
+                "{} records of {} values from offset {}, which overflows",
+                accessor.count,
+                accessor.stride,
+                accessor.offset,
+            )))?;
+        if required > array.data.len() {
+            return Err(invalid(format!(
+                "{} records of {} values from offset {} need {} values, but the array has {}",
+                accessor.count,
+                accessor.stride,
+                accessor.offset,
+                required,
+                array.data.len(),
+            )));
+        }
+
+        Ok(())
+    }
+
+    /// Returns an iterator over the records of the source's float data.
+    ///
+    /// Each record is read from the source's `<float_array>` using its common accessor, so this
+    /// yields one slice of `stride` values for each of the accessor's `count` records. Returns
+    /// `None` if the source has no common accessor or its array isn't a float array.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::fs::File;
+    /// # use collaborate::v1_4::{Collada, Library};
+    /// # let file = File::open("resources/blender_cube.dae").unwrap();
+    /// # let document = Collada::read(file).unwrap();
+    /// # let library = document.libraries_of(Library::as_library_geometries).next().unwrap();
+    /// # let mesh = library.geometries[0].geometric_element.as_mesh().unwrap();
+    /// let source = mesh.find_source("Cube-mesh-positions").unwrap();
+    /// let first = source.float_values().unwrap().next().unwrap();
+    /// assert_eq!(&[1.0, 1.0, -1.0], first);
+    /// ```
+    pub fn float_values<'a>(&'a self) -> Option<AccessorIter<'a, f32>> {
+        let accessor = self.common_accessor()?;
+        let array = match self.accessor_source()? {
+            AccessorSource::Array(array) => array.as_float_array()?,
+            AccessorSource::External(_) => return None,
+        };
+        Some(accessor.iter(&array.data))
+    }
+
+    /// Returns an iterator over a single named component of the source's float data.
+    ///
+    /// `name` is matched against the names of the common accessor's params, e.g. `"X"`, `"Y"`,
+    /// and `"Z"` for position data or `"S"` and `"T"` for texture coordinates. Returns `None` if
+    /// `float_values` would return `None`, or if no param has the given name.
+    pub fn float_param_values<'a>(&'a self, name: &str) -> Option<ParamValues<'a, f32>> {
+        let index = self.common_accessor()?.param_index(name)?;
+        Some(ParamValues {
+            records: self.float_values()?,
+            index,
+        })
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, ColladaElement)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[name = "technique_common"]
+pub struct SourceTechniqueCommon {
+    #[child]
+    pub accessor: Accessor,
+}
+
+/// One of the shapes that make up a [`RigidBody`].
+///
+/// [`RigidBody`]: ./struct.RigidBody.html
+#[derive(Debug, Clone, PartialEq, ColladaElement)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[name = "shape"]
+pub struct Shape {
+    /// Whether the shape is hollow.
+    #[child]
+    pub hollow: Option<Hollow>,
+
+    /// The mass of the shape.
+    #[child]
+    pub mass: Option<Mass>,
+
+    /// The density of the shape.
+    #[child]
+    pub density: Option<Density>,
+
+    /// The physics material used by the shape.
+    #[child]
+    pub physics_material: Option<PhysicsMaterialSource>,
+
+    /// The geometry of the shape.
+    #[child]
+    pub geometry: Option<ShapeGeometry>,
+
+    /// The transforms that position the shape within the rigid body.
+    #[child]
+    pub transforms: Vec<RigidTransform>,
+
+    /// Arbitrary additional information about this shape.
+    ///
+    /// For more information about 3rd-party extensions, see the
+    /// [crate-level documentation](../index.html#3rd-party-extensions).
+    #[child]
+    pub extras: Vec<Extra>,
+}
+
+/// The geometry of a [`Shape`].
+///
+/// [`Shape`]: ./struct.Shape.html
+#[derive(Debug, Clone, PartialEq, ColladaElement)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum ShapeGeometry {
+    Box(BoxShape),
+    Capsule(Capsule),
+    Cylinder(Cylinder),
+    InstanceGeometry(InstanceGeometry),
+    Plane(Plane),
+    Sphere(Sphere),
+    TaperedCapsule(TaperedCapsule),
+    TaperedCylinder(TaperedCylinder),
+}
+
+/// The specular shininess of a surface, used by the common profile's shaders.
+#[derive(Debug, Clone, PartialEq, ColladaElement)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[name = "shininess"]
+pub struct Shininess {
+    #[child]
+    pub value: FloatOrParam,
+}
+
+/// A shear transform, as defined by the RenderMan specification.
+///
+/// `data` contains 7 values: the skew angle in degrees, the axis of rotation, and the axis of
+/// translation, in that order.
+#[derive(Debug, Clone, PartialEq, ColladaElement)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[name = "skew"]
+pub struct Skew {
+    /// An identifier for the transform that's unique amongst its sibling elements.
+    #[attribute]
+    pub sid: Option<String>,
+
+    #[text]
+    pub data: Vec<f64>,
+}
+
+impl Skew {
+    /// Returns the transform as a column-major 4x4 matrix.
+    ///
+    /// Points are shifted along the translation axis such that the rotation axis ends up rotated
+    /// by the skew angle towards the translation axis. Degenerate skews (e.g. parallel axes)
+    /// produce the identity matrix. Missing values are treated as `0.0`.
+    pub fn matrix(&self) -> [f64; 16] {
+        ::math::skew(
+            component(&self.data, 0),
+            vector3(&self.data, 1),
+            vector3(&self.data, 4),
+        )
+    }
+}
+
+/// The specular color of a surface, used by the common profile's shaders.
+#[derive(Debug, Clone, PartialEq, ColladaElement)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[name = "specular"]
+pub struct Specular {
+    #[child]
+    pub value: ColorOrTexture,
+}
+
+/// The coefficient of friction between surfaces that aren't moving relative to each other.
+#[derive(Debug, Clone, Default, PartialEq, ColladaElement)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[name = "static_friction"]
+pub struct StaticFriction {
+    /// An identifier for the value that's unique amongst its sibling elements.
+    #[attribute]
+    pub sid: Option<String>,
+
+    #[text]
+    pub value: f64,
+}
+
+/// A sphere-shaped collision shape, centered on the origin.
+#[derive(Debug, Clone, PartialEq, ColladaElement)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[name = "sphere"]
+pub struct Sphere {
+    /// The radius of the sphere.
+    #[child]
+    pub radius: Radius,
+
+    /// Arbitrary additional information about this shape.
+    ///
+    /// For more information about 3rd-party extensions, see the
+    /// [crate-level documentation](../index.html#3rd-party-extensions).
+    #[child]
+    pub extras: Vec<Extra>,
+}
+
+#[derive(Debug, Clone, PartialEq, ColladaElement)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[name = "spline"]
+pub struct Spline;
+
+/// Declares an image resource that can be sampled by a [`Sampler2D`].
+///
+/// A surface is initialized in one of several ways, at most one of which is present. Images
+/// referenced with `<init_from>` are fully supported, which is how exporters declare textures in
+/// practice. The other ways of initializing a surface are parsed, but their contents are skipped.
+///
+/// [`Sampler2D`]: ./struct.Sampler2D.html
+#[derive(Debug, Clone, PartialEq, ColladaElement)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[name = "surface"]
+pub struct Surface {
+    /// The type of the surface, e.g. `"2D"` or `"CUBE"`.
+    #[attribute]
+    #[name = "type"]
+    pub surface_type: String,
+
+    /// Present if the surface is explicitly left uninitialized.
+    #[child]
+    pub init_as_null: Option<InitAsNull>,
+
+    /// Present if the surface is initialized as a render target.
+    #[child]
+    pub init_as_target: Option<InitAsTarget>,
+
+    /// Present if the surface is initialized as a cube map.
+    #[child]
+    pub init_cube: Option<InitCube>,
+
+    /// Present if the surface is initialized as a 3D texture.
+    #[child]
+    pub init_volume: Option<InitVolume>,
+
+    /// Present if the surface is initialized as a 1D or 2D texture with explicit mip levels.
+    #[child]
+    pub init_planar: Option<InitPlanar>,
+
+    /// The images that initialize the surface.
+    #[child]
+    pub init_from: Vec<InitFrom>,
+
+    /// The texel format of the surface, e.g. `"A8R8G8B8"`.
+    #[child]
+    pub format: Option<String>,
+
+    #[child]
+    pub format_hint: Option<FormatHint>,
+
+    /// The dimensions of the surface in texels.
+    #[child]
+    pub size: Option<SurfaceSize>,
+
+    /// The dimensions of the surface relative to the viewport.
+    #[child]
+    pub viewport_ratio: Option<ViewportRatio>,
+
+    /// The number of mip levels in the surface, where `0` means the full mip chain.
+    #[child]
+    pub mip_levels: Option<usize>,
+
+    /// Whether the mip levels should be generated from the first level, either `"true"` or
+    /// `"false"`.
+    #[child]
+    pub mipmap_generate: Option<String>,
+
+    /// Arbitrary additional information about this surface.
+    ///
+    /// For more information about 3rd-party extensions, see the
+    /// [crate-level documentation](../index.html#3rd-party-extensions).
+    #[child]
+    pub extras: Vec<Extra>,
+}
+
+#[derive(Debug, Clone, PartialEq, ColladaElement)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[name = "size"]
+pub struct SurfaceSize;
+
+#[derive(Debug, Clone, PartialEq, ColladaElement)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[name = "tapered_capsule"]
+pub struct TaperedCapsule;
+
+#[derive(Debug, Clone, PartialEq, ColladaElement)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[name = "tapered_cylinder"]
+pub struct TaperedCylinder;
+
+#[derive(Debug, Clone, PartialEq, ColladaElement)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[name = "technique_hint"]
+pub struct TechniqueHint;
+
+/// A reference to a texture sampler, along with the texture coordinates to use for sampling it.
+#[derive(Debug, Clone, PartialEq, ColladaElement)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[name = "texture"]
+pub struct Texture {
+    /// The identifier of the sampler parameter for the texture.
+    #[attribute]
+    pub texture: String,
+
+    /// The semantic of the texture coordinates used to sample the texture.
+    ///
+    /// This is bound to an actual mesh input when the material is instantiated.
+    #[attribute]
+    pub texcoord: String,
 
-    /// The user-defined meaning of the input connnection.
+    /// Arbitrary additional information about this texture.
     ///
-    /// See the type-level documentation for a [list of common semantic values](#common-semantics).
+    /// For more information about 3rd-party extensions, see the
+    /// [crate-level documentation](../index.html#3rd-party-extensions).
+    #[child]
+    pub extras: Vec<Extra>,
+}
+
+/// A single transform of unknown type.
+///
+/// Each variant wraps a single value containing the transform data. Use [`matrix`] to get any
+/// transform as a matrix regardless of its type.
+///
+/// [`matrix`]: #method.matrix
+#[derive(Debug, Clone, PartialEq, ColladaElement)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum Transform {
+    LookAt(LookAt),
+    Matrix(Matrix),
+    Rotate(Rotate),
+    Scale(Scale),
+    Skew(Skew),
+    Translate(Translate),
+}
+
+impl Transform {
+    /// Returns the transform as a column-major 4x4 matrix.
+    pub fn matrix(&self) -> [f64; 16] {
+        match *self {
+            Transform::LookAt(ref look_at) => look_at.matrix(),
+            Transform::Matrix(ref matrix) => matrix.matrix(),
+            Transform::Rotate(ref rotate) => rotate.matrix(),
+            Transform::Scale(ref scale) => scale.matrix(),
+            Transform::Skew(ref skew) => skew.matrix(),
+            Transform::Translate(ref translate) => translate.matrix(),
+        }
+    }
+
+    /// Splits the transform into its translation, rotation, and scale.
+    ///
+    /// The rotation is returned as a unit quaternion in `[x, y, z, w]` order. A transform that
+    /// mirrors geometry is reported with a negative X scale, and any shear in a `<matrix>` is
+    /// discarded.
+    ///
+    /// Returns `None` for `<skew>` transforms, and for matrices that aren't affine or that
+    /// collapse an axis to zero length.
+    pub fn decompose(&self) -> Option<([f64; 3], [f64; 4], [f64; 3])> {
+        const IDENTITY: [f64; 4] = [0.0, 0.0, 0.0, 1.0];
+
+        match *self {
+            Transform::LookAt(ref look_at) => ::math::decompose(&look_at.matrix()),
+            Transform::Matrix(ref matrix) => ::math::decompose(&matrix.matrix()),
+            Transform::Rotate(ref rotate) => {
+                let rotation = ::math::quaternion(vector3(&rotate.data, 0), component(&rotate.data, 3));
+                Some(([0.0; 3], rotation, [1.0; 3]))
+            }
+            Transform::Scale(ref scale) => Some(([0.0; 3], IDENTITY, vector3(&scale.data, 0))),
+            Transform::Skew(_) => None,
+            Transform::Translate(ref translate) => Some((vector3(&translate.data, 0), IDENTITY, [1.0; 3])),
+        }
+    }
+}
+
+/// The amount of time that passes in each step of a physics simulation, in seconds.
+#[derive(Debug, Clone, PartialEq, ColladaElement)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[name = "time_step"]
+pub struct TimeStep {
+    /// An identifier for the value that's unique amongst its sibling elements.
     #[attribute]
-    pub semantic: String,
+    pub sid: Option<String>,
 
-    /// The location of the data source.
+    #[text]
+    pub value: f32,
+}
+
+/// A translation along each axis.
+///
+/// `data` contains the 3 offsets along the X, Y, and Z axes.
+#[derive(Debug, Clone, PartialEq, ColladaElement)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[name = "translate"]
+pub struct Translate {
+    /// An identifier for the transform that's unique amongst its sibling elements.
     #[attribute]
-    pub source: UriFragment,
+    pub sid: Option<String>,
 
-    /// Which inputs to group as a single set.
+    #[text]
+    pub data: Vec<f64>,
+}
+
+impl Translate {
+    /// Returns the transform as a column-major 4x4 matrix.
     ///
-    /// This is helpful when multiple inputs share the same semantic.
-    #[attribute]
-    pub set: Option<usize>,
+    /// Missing values are treated as `0.0`.
+    pub fn matrix(&self) -> [f64; 16] {
+        ::math::translation(vector3(&self.data, 0))
+    }
 }
 
+/// The transparency amount of a surface, used by the common profile's shaders.
 #[derive(Debug, Clone, PartialEq, ColladaElement)]
-#[name = "source"]
-pub struct Source {
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[name = "transparency"]
+pub struct Transparency {
+    #[child]
+    pub value: FloatOrParam,
+}
+
+/// The transparent color of a surface, used by the common profile's shaders.
+#[derive(Debug, Clone, PartialEq, ColladaElement)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[name = "transparent"]
+pub struct Transparent {
+    /// How the transparency is derived from the color. Defaults to `Opaque::AOne`.
     #[attribute]
-    pub id: String,
+    #[optional_with_default]
+    pub opaque: Opaque,
 
+    #[child]
+    pub value: ColorOrTexture,
+}
+
+/// Geometric primitives made up of individual triangles.
+///
+/// Each triangle has exactly three vertices, so unlike [`Polylist`] there's no `<vcount>`, and
+/// the primitives list holds `count * 3` vertices.
+///
+/// [`Polylist`]: ./struct.Polylist.html
+#[derive(Debug, Clone, PartialEq, ColladaElement)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[name = "triangles"]
+#[validate = "Triangles::validate"]
+pub struct Triangles {
+    /// A human-friendly name for these triangles.
+    ///
+    /// Has no semantic meaning.
     #[attribute]
     pub name: Option<String>,
 
-    #[child]
-    pub asset: Option<Asset>,
+    /// The number of triangles.
+    #[attribute]
+    pub count: usize,
+
+    /// The name of the material associated with these triangles.
+    ///
+    /// This name is bound to a material at the time of instantiaion. See [`InstanceGeometry`]
+    /// and [`BindMaterial`].
+    ///
+    /// If `None`, then the lighting and shading results are appplication-defined.
+    ///
+    /// [`InstanceGeometry`]: ./struct.InstanceGeometry.html
+    /// [`BindMaterial`]: ./struct.BindMaterial.html
+    #[attribute]
+    pub material: Option<String>,
 
+    /// The input data for the triangles.
     #[child]
-    pub array: Option<Array>,
+    pub inputs: Vec<SharedInput>,
 
+    /// A list of integers that specify the vertex attributes as indexes into the inputs.
     #[child]
-    pub technique_common: Option<SourceTechniqueCommon>,
+    pub primitives: Option<Primitives>,
 
+    /// Arbitrary additional information about these triangles and the data they contain.
+    ///
+    /// For more information about 3rd-party extensions, see the
+    /// [crate-level documentation](../index.html#3rd-party-extensions).
     #[child]
-    pub techniques: Vec<Technique>,
+    pub extras: Vec<Extra>,
 }
 
-impl Source {
-    // Returns the [`Accessor`] in the source's `technique_common` member.
-    pub fn common_accessor(&self) -> Option<&Accessor> {
-        self.technique_common
-            .as_ref()
-            .map(|technique| &technique.accessor)
+impl Triangles {
+    /// Returns the total number of vertices across all of the triangles, i.e. `count * 3`.
+    ///
+    /// Parsed triangles are always checked that this fits in a `usize`. For ones that were
+    /// modified or built by hand, the total saturates at `usize::MAX` rather than overflowing.
+    pub fn total_vertices(&self) -> usize {
+        self.count.saturating_mul(3)
+    }
+
+    /// Returns an iterator over the triangles.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the triangles are malformed. Parsed triangles are always valid, but ones that
+    /// were modified or built by hand may not be. Use [`try_iter`](#method.try_iter) to check for
+    /// this instead.
+    pub fn iter<'a>(&'a self) -> TrianglesIter<'a> {
+        self.try_iter().expect("Triangles are malformed, use `try_iter` to handle the error")
+    }
+
+    /// Returns an iterator over the triangles, checking that they're valid.
+    ///
+    /// Triangles without a `<p>` or any inputs don't describe any vertices, so the iterator is
+    /// empty.
+    ///
+    /// # Errors
+    ///
+    /// Returns `ErrorKind::InvalidValue` if `p` doesn't have an index for each attribute of each
+    /// of the `count * 3` vertices, or if the number of indices is too large to fit in a `usize`.
+    /// These are the same checks performed when parsing.
+    pub fn try_iter<'a>(&'a self) -> ::std::result::Result<TrianglesIter<'a>, ErrorKind> {
+        Triangles::validate(self)?;
+
+        let num_indices_per_vertex = indices_per_vertex(&self.inputs)?;
+        let indices = match (num_indices_per_vertex, self.primitives.as_ref()) {
+            (Some(_), Some(primitives)) => &**primitives,
+            _ => &[],
+        };
+
+        Ok(TrianglesIter {
+            chunks: indices.chunks(num_indices_per_vertex.unwrap_or(1)),
+        })
+    }
+
+    /// Checks that `p` has an index for each attribute of each of the `count * 3` vertices.
+    ///
+    /// Without this, a truncated `<p>` would only be noticed when iterating over the triangles.
+    fn validate(triangles: &Triangles) -> ::std::result::Result<(), ErrorKind> {
+        let total_vertices = triangles.count.checked_mul(3).ok_or_else(|| ErrorKind::InvalidValue {
+            element: "triangles",
+            value: format!("{} triangles, which overflows the vertex count", triangles.count),
+        })?;
+        let num_indices_per_vertex = indices_per_vertex(&triangles.inputs)?;
+        if let (Some(primitives), Some(num_indices_per_vertex)) =
+            (triangles.primitives.as_ref(), num_indices_per_vertex)
+        {
+            let expected = total_vertices.checked_mul(num_indices_per_vertex).ok_or_else(|| {
+                ErrorKind::InvalidValue {
+                    element: "p",
+                    value: format!(
+                        "{} vertices with {} indices each, which overflows",
+                        total_vertices,
+                        num_indices_per_vertex,
+                    ),
+                }
+            })?;
+            if primitives.len() != expected {
+                return Err(ErrorKind::InvalidValue {
+                    element: "p",
+                    value: format!("{} indices where {} were expected", primitives.len(), expected),
+                });
+            }
+        }
+
+        Ok(())
     }
 }
 
-#[derive(Debug, Clone, PartialEq, ColladaElement)]
-#[name = "technique_common"]
-pub struct SourceTechniqueCommon {
-    #[child]
-    pub accessor: Accessor,
+/// An iterator over the triangles in a [`Triangles`].
+///
+/// Created by [`Triangles::iter`]. Each item holds the three vertices of a triangle, in the same
+/// form as the triangles yielded by [`Polylist::triangulate`].
+///
+/// [`Triangles`]: ./struct.Triangles.html
+/// [`Triangles::iter`]: ./struct.Triangles.html#method.iter
+/// [`Polylist::triangulate`]: ./struct.Polylist.html#method.triangulate
+#[derive(Debug, Clone)]
+pub struct TrianglesIter<'a> {
+    chunks: ::std::slice::Chunks<'a, usize>,
 }
 
-#[derive(Debug, Clone, PartialEq, ColladaElement)]
-#[name = "spline"]
-pub struct Spline;
+impl<'a> ::std::iter::Iterator for TrianglesIter<'a> {
+    type Item = [Vertex<'a>; 3];
+
+    fn next(&mut self) -> Option<[Vertex<'a>; 3]> {
+        let first = self.chunks.next()?;
+        let second = self.chunks.next()?;
+        let third = self.chunks.next()?;
+        Some([
+            Vertex { attributes: first },
+            Vertex { attributes: second },
+            Vertex { attributes: third },
+        ])
+    }
+}
 
-#[derive(Debug, Clone, PartialEq, ColladaElement)]
-#[name = "triangles"]
-pub struct Triangles;
+/// An iterator that fan-triangulates the polygons of a [`Polylist`].
+///
+/// Created by [`Polylist::triangulate`].
+///
+/// [`Polylist`]: ./struct.Polylist.html
+/// [`Polylist::triangulate`]: ./struct.Polylist.html#method.triangulate
+pub struct Triangulated<'a> {
+    polygons: PolylistIter<'a>,
+
+    /// The state of the fan for the current polygon: The first vertex of the polygon, the most
+    /// recently used vertex, and the remaining vertices.
+    fan: Option<(Vertex<'a>, Vertex<'a>, PolygonIter<'a>)>,
+}
+
+impl<'a> ::std::iter::Iterator for Triangulated<'a> {
+    type Item = [Vertex<'a>; 3];
+
+    fn next(&mut self) -> Option<[Vertex<'a>; 3]> {
+        loop {
+            if let Some((ref first, ref mut previous, ref mut remaining)) = self.fan {
+                if let Some(vertex) = remaining.next() {
+                    let triangle = [first.clone(), previous.clone(), vertex.clone()];
+                    *previous = vertex;
+                    return Some(triangle);
+                }
+            }
+
+            // The current polygon has been used up, so start a fan for the next one. Polygons
+            // with fewer than 3 vertices can't form a triangle and are skipped.
+            let polygon = self.polygons.next()?;
+            self.fan = None;
+            if polygon.len() < 3 {
+                continue;
+            }
+
+            let mut vertices = polygon.into_iter();
+            let first = vertices.next().unwrap();
+            let second = vertices.next().unwrap();
+            self.fan = Some((first, second, vertices));
+        }
+    }
+}
 
 #[derive(Debug, Clone, PartialEq, ColladaElement)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 #[name = "trifans"]
 pub struct Trifans;
 
 #[derive(Debug, Clone, PartialEq, ColladaElement)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 #[name = "tristrips"]
 pub struct Tristrips;
 
@@ -1213,6 +7425,7 @@ pub struct Tristrips;
 /// must store. These inputs are described in this section as shared inputs but otherwise
 /// operate in the same manner as unshared inputs.
 #[derive(Debug, Clone, PartialEq, ColladaElement)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 #[name = "input"]
 pub struct UnsharedInput {
     /// The user-defined meaning of the input connnection.
@@ -1228,13 +7441,24 @@ pub struct UnsharedInput {
     pub source: UriFragment,
 }
 
+/// The number of vertices in each polygon of a [`Polylist`].
+///
+/// Like the indices in [`Primitives`], the counts must be non-negative integers.
+///
+/// [`Polylist`]: ./struct.Polylist.html
+/// [`Primitives`]: ./struct.Primitives.html
 #[derive(Debug, Clone, PartialEq, ColladaElement)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 #[name = "vcount"]
 pub struct VCount {
     #[text]
     data: Vec<usize>,
 }
 
+impl From<Vec<usize>> for VCount {
+    fn from(data: Vec<usize>) -> VCount { VCount { data } }
+}
+
 impl ::std::ops::Deref for VCount {
     type Target = [usize];
 
@@ -1251,10 +7475,10 @@ impl ::std::ops::Deref for VCount {
 /// ```
 /// # #![allow(unused_variables)]
 /// # use std::fs::File;
-/// # use collaborate::v1_4::Collada;
+/// # use collaborate::v1_4::{Collada, Library};
 /// # let file = File::open("resources/blender_cube.dae").unwrap();
 /// # let document = Collada::read(file).unwrap();
-/// # let library = document.libraries[5].as_library_geometries().unwrap();
+/// # let library = document.libraries_of(Library::as_library_geometries).next().unwrap();
 /// # let mesh = library.geometries[0].geometric_element.as_mesh().unwrap();
 /// let polylist = mesh.primitives[0].as_polylist().unwrap();
 /// for polygon in polylist {
@@ -1286,7 +7510,7 @@ impl<'a> Vertex<'a> {
     /// # use collaborate::v1_4::*;
     /// # let file = File::open("resources/blender_cube.dae").unwrap();
     /// # let document = Collada::read(file).unwrap();
-    /// # let library = document.libraries[5].as_library_geometries().unwrap();
+    /// # let library = document.libraries_of(Library::as_library_geometries).next().unwrap();
     /// # let mesh = library.geometries[0].geometric_element.as_mesh().unwrap();
     /// # let polylist = mesh.primitives[0].as_polylist().unwrap();
     /// # let polygon = polylist.iter().next().unwrap();
@@ -1325,6 +7549,7 @@ impl<'a> ::std::iter::IntoIterator for &'a Vertex<'a> {
 /// * An offset, used to determine which input(s) this attribute references.
 /// * An index, which is used to index into the data specified by the referenced input.
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct VertexAttribute {
     /// The index within the relevant source array which has this attribute's value.
     pub index: usize,
@@ -1361,7 +7586,9 @@ impl<'a> ::std::iter::Iterator for VertexIter<'a> {
 /// Mesh-vertices represent the position (identity) of the vertices comprising the mesh and other
 /// vertex attributes that are invariant to tessellation.
 #[derive(Debug, Clone, PartialEq, ColladaElement)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 #[name = "vertices"]
+#[validate = "Vertices::validate"]
 pub struct Vertices {
     /// A unique identifier of the vertices instance.
     ///
@@ -1385,3 +7612,225 @@ pub struct Vertices {
     #[child]
     pub extras: Vec<Extra>,
 }
+
+impl Vertices {
+    /// Checks that the vertices have the `"POSITION"` input required by the spec.
+    fn validate(vertices: &Vertices) -> ::std::result::Result<(), ErrorKind> {
+        if vertices.inputs.iter().any(|input| input.semantic == "POSITION") {
+            Ok(())
+        } else {
+            Err(ErrorKind::MissingElement {
+                parent: "vertices",
+                expected: vec!["input (POSITION)"],
+            })
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, ColladaElement)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[name = "viewport_ratio"]
+pub struct ViewportRatio;
+
+/// The scene graph for a single visual scene.
+///
+/// The scene is made up of a hierarchy of [`Node`] instances, with the top-level nodes of the
+/// hierarchy contained in `nodes`.
+///
+/// [`Node`]: ./struct.Node.html
+#[derive(Debug, Clone, PartialEq, ColladaElement)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[name = "visual_scene"]
+pub struct VisualScene {
+    /// A unique identifier for the scene.
+    ///
+    /// Will be unique within the document.
+    #[attribute]
+    pub id: Option<String>,
+
+    /// The human-friendly name for this scene.
+    ///
+    /// Has no semantic meaning.
+    #[attribute]
+    pub name: Option<String>,
+
+    /// Metadata about this scene.
+    #[child]
+    pub asset: Option<Asset>,
+
+    /// The top-level nodes in the scene. There will always be at least one node.
+    #[child]
+    #[required]
+    pub nodes: Vec<Node>,
+
+    /// Descriptions of how the scene is rendered, if any are provided.
+    #[child]
+    pub evaluate_scenes: Vec<EvaluateScene>,
+
+    /// Arbitrary additional information about this scene.
+    ///
+    /// For more information about 3rd-party extensions, see the
+    /// [crate-level documentation](../index.html#3rd-party-extensions).
+    #[child]
+    pub extras: Vec<Extra>,
+}
+
+impl VisualScene {
+    /// Returns the node at `path`, if there is one.
+    pub fn node(&self, path: &NodePath) -> Option<&Node> {
+        let (first, rest) = path.indices().split_first()?;
+        rest.iter().fold(self.nodes.get(*first), |node, &index| {
+            node.and_then(|node| node.children.get(index))
+        })
+    }
+
+    /// Computes the world transform of every node in the scene.
+    ///
+    /// Each node's world transform is its parent's world transform multiplied by the node's
+    /// [local transform]. The nodes are returned in depth-first order, with each parent listed
+    /// before its children, and all matrices are column-major.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::fs::File;
+    /// # use collaborate::v1_4::{Collada, Library};
+    /// # let file = File::open("resources/blender_cube.dae").unwrap();
+    /// # let document = Collada::read(file).unwrap();
+    /// # let library = document.libraries_of(Library::as_library_visual_scenes).next().unwrap();
+    /// let scene = &library.visual_scenes[0];
+    /// for (path, transform) in scene.world_transforms() {
+    ///     let node = scene.node(&path).unwrap();
+    ///     println!("{:?} is at {:?}", node.id, &transform[12..15]);
+    /// }
+    /// ```
+    ///
+    /// [local transform]: ./struct.Node.html#method.local_transform
+    pub fn world_transforms(&self) -> Vec<(NodePath, [f64; 16])> {
+        fn visit(
+            nodes: &[Node],
+            path: &mut Vec<usize>,
+            parent: &[f64; 16],
+            results: &mut Vec<(NodePath, [f64; 16])>,
+        ) {
+            for (index, node) in nodes.iter().enumerate() {
+                let world = ::math::multiply(parent, &node.local_transform());
+
+                path.push(index);
+                results.push((NodePath(path.clone()), world));
+                visit(&node.children, path, &world, results);
+                path.pop();
+            }
+        }
+
+        let mut results = Vec::new();
+        visit(&self.nodes, &mut Vec::new(), &::math::identity(), &mut results);
+        results
+    }
+}
+
+/// The horizontal field of view of a perspective camera, in degrees.
+#[derive(Debug, Clone, PartialEq, ColladaElement)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[name = "xfov"]
+pub struct Xfov {
+    /// An identifier for the value that's unique amongst its sibling elements.
+    #[attribute]
+    pub sid: Option<String>,
+
+    #[text]
+    pub value: f64,
+}
+
+/// The horizontal magnification of an orthographic camera.
+#[derive(Debug, Clone, PartialEq, ColladaElement)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[name = "xmag"]
+pub struct Xmag {
+    /// An identifier for the value that's unique amongst its sibling elements.
+    #[attribute]
+    pub sid: Option<String>,
+
+    #[text]
+    pub value: f64,
+}
+
+/// The vertical field of view of a perspective camera, in degrees.
+#[derive(Debug, Clone, PartialEq, ColladaElement)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[name = "yfov"]
+pub struct Yfov {
+    /// An identifier for the value that's unique amongst its sibling elements.
+    #[attribute]
+    pub sid: Option<String>,
+
+    #[text]
+    pub value: f64,
+}
+
+/// The vertical magnification of an orthographic camera.
+#[derive(Debug, Clone, PartialEq, ColladaElement)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[name = "ymag"]
+pub struct Ymag {
+    /// An identifier for the value that's unique amongst its sibling elements.
+    #[attribute]
+    pub sid: Option<String>,
+
+    #[text]
+    pub value: f64,
+}
+
+/// The distance from a camera to its far clipping plane.
+#[derive(Debug, Clone, PartialEq, ColladaElement)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[name = "zfar"]
+pub struct Zfar {
+    /// An identifier for the value that's unique amongst its sibling elements.
+    #[attribute]
+    pub sid: Option<String>,
+
+    #[text]
+    pub value: f64,
+}
+
+/// The distance from a camera to its near clipping plane.
+#[derive(Debug, Clone, PartialEq, ColladaElement)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[name = "znear"]
+pub struct Znear {
+    /// An identifier for the value that's unique amongst its sibling elements.
+    #[attribute]
+    pub sid: Option<String>,
+
+    #[text]
+    pub value: f64,
+}
+
+/// Returns the value at `index` in a transform's data, or `0.0` if the data is too short.
+fn component(data: &[f64], index: usize) -> f64 {
+    data.get(index).cloned().unwrap_or(0.0)
+}
+
+/// Returns the 3 values starting at `index` in a transform's data.
+fn vector3(data: &[f64], index: usize) -> [f64; 3] {
+    [component(data, index), component(data, index + 1), component(data, index + 2)]
+}
+
+/// Returns the number of indices used for each vertex of a primitive with `inputs`, or `None` if
+/// there aren't any inputs.
+///
+/// Generally, we expect this to be the same as the number of inputs (e.g. if there's an input for
+/// position and an input for normal, then we'd expect there to be 2 indices for each vertex), but
+/// the COLLADA spec allows multiple inputs to share an offset, effectively reducing the number of
+/// indices needed for each vertex. To account for this, we look for the largest offset used by the
+/// inputs, which should tell us consistently how many unique offsets there are.
+fn indices_per_vertex(inputs: &[SharedInput]) -> ::std::result::Result<Option<usize>, ErrorKind> {
+    match inputs.iter().map(|input| input.offset).max() {
+        Some(offset) => offset.checked_add(1).map(Some).ok_or_else(|| ErrorKind::InvalidValue {
+            element: "input",
+            value: offset.to_string(),
+        }),
+        None => Ok(None),
+    }
+}
@@ -1,13 +1,18 @@
 //! Type definitions matching the COLLADA `1.5.0` specification.
 
-use {Result, Error, ErrorKind};
+use {ParseOptions, Result, Error, ErrorKind, Warning};
 use common::*;
-use std::io::Read;
+use std::io::{self, Read, Write};
+use std::path::Path;
 use utils;
 use utils::*;
 use xml::common::Position;
+use xml::namespace::Namespace;
 use xml::reader::EventReader;
 
+/// The XML namespace for COLLADA 1.5 documents.
+pub(crate) static NAMESPACE: &'static str = "http://www.collada.org/2008/03/COLLADASchema";
+
 /// Represents a parsed COLLADA document.
 #[derive(Debug, Clone, PartialEq, ColladaElement)]
 #[name = "COLLADA"]
@@ -18,7 +23,12 @@ pub struct Collada {
     #[attribute]
     pub version: String,
 
-    /// Included for completeness in parsing, not actually used.
+    /// The namespace of the root `<COLLADA>` element.
+    ///
+    /// Filled in from the parsed document, and `None` if the root element isn't in a namespace.
+    /// Used as the default namespace when writing the document. See [`schema_namespace`].
+    ///
+    /// [`schema_namespace`]: #method.schema_namespace
     #[attribute]
     pub xmlns: Option<String>,
 
@@ -84,8 +94,19 @@ impl Collada {
     ///
     /// [crate]: index.html
     pub fn from_str(source: &str) -> Result<Collada> {
-        let reader = EventReader::new_with_config(source.as_bytes(), utils::PARSER_CONFIG.clone());
-        Self::parse(reader)
+        Self::from_str_with_options(source, ParseOptions::default())
+    }
+
+    /// Read a COLLADA document from a string, using the specified options.
+    ///
+    /// See [`ParseOptions`] for the available options. Otherwise this behaves exactly like
+    /// [`from_str`](#method.from_str).
+    ///
+    /// [`ParseOptions`]: ../struct.ParseOptions.html
+    pub fn from_str_with_options(source: &str, options: ParseOptions) -> Result<Collada> {
+        let source = utils::strip_bom_str(source);
+        let mut reader = ColladaReader::new(utils::options_event_reader(source.as_bytes(), &options), options);
+        Self::parse_reader(&mut reader)
     }
 
     /// Attempts to parse the contents of a COLLADA document.
@@ -109,23 +130,106 @@ impl Collada {
     ///
     /// [crate]: index.html
     pub fn read<R: Read>(reader: R) -> Result<Collada> {
-        let reader = EventReader::new_with_config(reader, utils::PARSER_CONFIG.clone());
-        Self::parse(reader)
+        Self::read_with_options(reader, ParseOptions::default())
+    }
+
+    /// Attempts to parse the contents of a COLLADA document, using the specified options.
+    ///
+    /// See [`ParseOptions`] for the available options. Otherwise this behaves exactly like
+    /// [`read`](#method.read).
+    ///
+    /// [`ParseOptions`]: ../struct.ParseOptions.html
+    pub fn read_with_options<R: Read>(reader: R, options: ParseOptions) -> Result<Collada> {
+        Self::read_with_warnings(reader, options).map(|(collada, _)| collada)
+    }
+
+    /// Attempts to parse the contents of a COLLADA document, also returning any warnings.
+    ///
+    /// Warnings are only produced when [`ParseOptions::lenient`] is set. Otherwise this behaves
+    /// exactly like [`read_with_options`](#method.read_with_options).
+    ///
+    /// [`ParseOptions::lenient`]: ../struct.ParseOptions.html#structfield.lenient
+    pub fn read_with_warnings<R: Read>(reader: R, options: ParseOptions) -> Result<(Collada, Vec<Warning>)> {
+        let reader = utils::strip_bom(reader)?;
+        let mut reader = ColladaReader::new(utils::options_event_reader(reader, &options), options);
+        let collada = Self::parse_reader(&mut reader)?;
+        Ok((collada, reader.warnings))
+    }
+
+    /// Read a COLLADA document from a byte slice.
+    ///
+    /// This is a shorthand for [`read`](#method.read), for when the document has already been
+    /// loaded into memory.
+    pub fn from_bytes(bytes: &[u8]) -> Result<Collada> {
+        Self::read(bytes)
+    }
+
+    /// Read a COLLADA document from the file at `path`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # #![allow(unused_variables)]
+    /// use collaborate::v1_5::Collada;
+    ///
+    /// let document = Collada::from_path("resources/v1_5_minimal.dae").unwrap();
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Returns an [`ErrorKind::Io`] error with the position `0:0` if the file can't be opened.
+    /// Otherwise, returns `Err` under the same conditions as [`read`](#method.read).
+    ///
+    /// [`ErrorKind::Io`]: ../enum.ErrorKind.html#variant.Io
+    pub fn from_path<P: AsRef<Path>>(path: P) -> Result<Collada> {
+        Self::read(utils::open_file(path)?)
     }
 
-    pub fn parse<R: Read>(mut reader: EventReader<R>) -> Result<Collada> {
+    /// Returns the rotation that converts the document's coordinates to use `target` as the up axis.
+    ///
+    /// This is a shorthand for calling [`UpAxis::conversion_matrix`] with the up axis declared in
+    /// the document's `asset`, or `Y_UP` if it doesn't declare one. The result is a column-major
+    /// 4x4 matrix.
+    ///
+    /// [`UpAxis::conversion_matrix`]: ../common/enum.UpAxis.html#method.conversion_matrix
+    pub fn up_axis_to(&self, target: UpAxis) -> [f64; 16] {
+        UpAxis::conversion_matrix(self.asset.up_axis.unwrap_or_default(), target)
+    }
+
+    /// Writes the document as XML to `writer`.
+    ///
+    /// The document is written with an XML declaration and declares the COLLADA 1.5 schema as
+    /// its default namespace, unless `xmlns` specifies a different one.
+    ///
+    /// # Errors
+    ///
+    /// Returns `Err` if writing to `writer` fails.
+    pub fn write<W: Write>(&self, writer: W) -> io::Result<()> {
+        let namespace = self.xmlns.clone().unwrap_or_else(|| NAMESPACE.into());
+        let mut writer = ColladaWriter::new(writer, namespace);
+        self.write_element(&mut writer)
+    }
+
+    /// Returns the namespace declared for the root `<COLLADA>` element, if any.
+    ///
+    /// COLLADA 1.5 documents should use `"http://www.collada.org/2008/03/COLLADASchema"`, but
+    /// documents in other namespaces (such as the 1.4 namespace, which some exporters use by
+    /// mistake) are still parsed. Parse with [`ParseOptions::lenient`] set to get a warning when
+    /// the namespace doesn't match.
+    ///
+    /// [`ParseOptions::lenient`]: ../struct.ParseOptions.html#structfield.lenient
+    pub fn schema_namespace(&self) -> Option<&str> {
+        self.xmlns.as_deref()
+    }
+
+    pub fn parse<R: Read>(reader: EventReader<R>) -> Result<Collada> {
+        Self::parse_reader(&mut ColladaReader::new(reader, ParseOptions::default()))
+    }
+
+    fn parse_reader<R: Read>(reader: &mut ColladaReader<R>) -> Result<Collada> {
         // Get the opening `<COLLADA>` tag and find the "version" attribute.
-        let element_start = utils::get_document_start(&mut reader)?;
-        let version = element_start.attributes.iter()
-            .find(|attrib| attrib.name.local_name == "version")
-            .map(|attrib| attrib.value.clone())
-            .ok_or(Error {
-                position: reader.position(),
-                kind: ErrorKind::MissingAttribute {
-                    element: "COLLADA",
-                    attribute: "version",
-                },
-            })?;
+        let element_start = utils::get_document_start(reader)?;
+        let version = ::document_version(reader, &element_start)?;
 
         if version != "1.5.0" {
             return Err(Error {
@@ -136,7 +240,10 @@ impl Collada {
             });
         }
 
-        Collada::parse_element(&mut reader, element_start)
+        let xmlns = ::document_namespace(reader, &element_start, NAMESPACE);
+        let mut collada = Collada::parse_element(reader, element_start)?;
+        collada.xmlns = xmlns;
+        Ok(collada)
     }
 }
 
@@ -189,22 +296,22 @@ pub struct Asset {
     #[child]
     pub title: Option<String>,
 
-    /// Defines the unit of distance for this asset.
+    /// Defines the unit of distance for this asset, or `None` if the `<unit>` element is missing.
     ///
     /// This unit is used by the asset and all of its children, unless overridden by a more
-    /// local `Unit`.
+    /// local `Unit`. The spec defaults the unit to one meter.
     #[child]
-    #[optional_with_default]
-    pub unit: Unit,
+    pub unit: Option<Unit>,
 
-    /// Describes the coordinate system of the asset.
+    /// Describes the coordinate system of the asset, or `None` if the `<up_axis>` element is
+    /// missing.
     ///
-    /// See the documentation for [`UpAxis`] for more details.
+    /// The spec defaults the up axis to `Y_UP`. See the documentation for [`UpAxis`] for more
+    /// details.
     ///
-    /// [`UpAxis`]: ../struct.UpAxis.html
+    /// [`UpAxis`]: ../common/enum.UpAxis.html
     #[child]
-    #[optional_with_default]
-    pub up_axis: UpAxis,
+    pub up_axis: Option<UpAxis>,
 
     /// Provides arbitrary additional data about the asset.
     ///
@@ -215,6 +322,18 @@ pub struct Asset {
     pub extras: Vec<Extra>,
 }
 
+impl Asset {
+    /// Returns the asset's keywords as a list.
+    ///
+    /// The `keywords` field holds a whitespace-separated list of search terms, which is split
+    /// into the individual keywords. Returns an empty list if the asset has no keywords.
+    pub fn keyword_list(&self) -> Vec<&str> {
+        self.keywords.iter()
+            .flat_map(|keywords| keywords.split_whitespace())
+            .collect()
+    }
+}
+
 #[derive(Debug, Clone, PartialEq, ColladaElement)]
 #[name = "coverage"]
 pub struct Coverage {
@@ -284,7 +403,7 @@ pub struct Contributor {
 /// application is free to pick whichever technique is preferred.
 ///
 /// [Technique]: struct.Technique.html
-#[derive(Debug, Clone, Default, PartialEq, ColladaElement)]
+#[derive(Debug, Clone, PartialEq, ColladaElement)]
 #[name = "extra"]
 pub struct Extra {
     /// The identifier of the element, if present. Will be unique within the document.
@@ -309,11 +428,61 @@ pub struct Extra {
     #[child]
     pub asset: Option<Asset>,
 
-    /// The arbitrary additional information, containing unprocessed XML events. There will always
-    /// be at least one item in `techniques`.
+    /// The arbitrary additional information, containing unprocessed XML events.
+    ///
+    /// The specification requires at least one technique, but some exporters write `<extra>`
+    /// elements that only contain an `<asset>`, so `techniques` may be empty. Empty `<extra />`
+    /// elements are accepted as well.
+    #[child]
+    pub techniques: Vec<Technique>,
+
+    /// The namespace mappings in scope for the `<extra>` element, including any prefixes
+    /// declared on the element itself.
+    #[namespace]
+    pub namespace: Namespace,
+}
+
+impl Default for Extra {
+    fn default() -> Extra {
+        Extra {
+            id: None,
+            name: None,
+            type_hint: None,
+            asset: None,
+            techniques: Vec::new(),
+            namespace: Namespace::empty(),
+        }
+    }
+}
+
+/// Describes a force that affects the bodies in a physics simulation, e.g. wind or gravity.
+///
+/// COLLADA doesn't define any common properties for force fields, so the behavior of the force is
+/// described entirely by the profile-specific `techniques`.
+#[derive(Debug, Clone, PartialEq, ColladaElement)]
+#[name = "force_field"]
+pub struct ForceField {
+    /// The identifier of the element, if present. Will be unique within the document.
+    #[attribute]
+    pub id: Option<String>,
+
+    /// The text string name of the element, if present.
+    #[attribute]
+    pub name: Option<String>,
+
+    /// Asset-management information for the force field, if present.
+    #[child]
+    pub asset: Option<Asset>,
+
+    /// Profile-specific descriptions of the force field. There will always be at least one item
+    /// in `techniques`.
     #[child]
     #[required]
     pub techniques: Vec<Technique>,
+
+    /// Arbitrary additional information about the force field.
+    #[child]
+    pub extras: Vec<Extra>,
 }
 
 /// Defines geographic location information for an [`Asset`][Asset].
@@ -339,170 +508,1253 @@ pub struct GeographicLocation {
     pub altitude: Altitude,
 }
 
+/// The maximum acceleration of a motion axis.
 #[derive(Debug, Clone, PartialEq, ColladaElement)]
-pub enum Library {
-    Animations(LibraryAnimations),
-    AnimationClips(LibraryAnimationClips),
-    ArticulatedSystmes(LibraryArticulatedSystems),
-    Cameras(LibraryCameras),
-    Controllers(LibraryControllers),
-    Effects(LibraryEffects),
-    ForceFields(LibraryForceFields),
-    Formulas(LibraryFormulas),
-    Geometries(LibraryGeometries),
-    Images(LibraryImages),
-    Joints(LibraryJoints),
-    KinematicsModels(LibraryKinematicsModels),
-    KinematicsScenes(LibraryKinematicsScenes),
-    Lights(LibraryLights),
-    Materials(LibraryMaterials),
-    Nodes(LibraryNodes),
-    PhysicsMaterials(LibraryPhysicsMaterials),
-    PhysicsModels(LibraryPhysicsModels),
-    PhysicsScenes(LibraryPhysicsScenes),
-    VisualScenes(LibraryVisualScenes),
+#[name = "acceleration"]
+pub struct Acceleration {
+    /// The acceleration, or the parameter that holds it.
+    #[child]
+    pub value: FloatOrParam,
 }
 
+/// Describes a complex kinematic system, such as a robot, built on top of kinematics models.
+///
+/// An articulated system either adds kinematics information to a set of kinematics models, or
+/// adds motion information to another articulated system.
 #[derive(Debug, Clone, PartialEq, ColladaElement)]
-#[name = "library_animations"]
-pub struct LibraryAnimations;
+#[name = "articulated_system"]
+pub struct ArticulatedSystem {
+    /// The identifier of the element, if present. Will be unique within the document.
+    #[attribute]
+    pub id: Option<String>,
 
-#[derive(Debug, Clone, PartialEq, ColladaElement)]
-#[name = "library_animation_clips"]
-pub struct LibraryAnimationClips;
+    /// The text string name of the element, if present.
+    #[attribute]
+    pub name: Option<String>,
 
-#[derive(Debug, Clone, PartialEq, ColladaElement)]
-#[name = "library_articulated_systems"]
-pub struct LibraryArticulatedSystems;
+    /// Asset-management information for the system, if present.
+    #[child]
+    pub asset: Option<Asset>,
 
-#[derive(Debug, Clone, PartialEq, ColladaElement)]
-#[name = "library_cameras"]
-pub struct LibraryCameras;
+    /// The kinematics or motion information described by the system.
+    #[child]
+    pub contents: ArticulatedSystemContents,
 
-#[derive(Debug, Clone, PartialEq, ColladaElement)]
-#[name = "library_controllers"]
-pub struct LibraryControllers;
+    /// Arbitrary additional information about the system.
+    #[child]
+    pub extras: Vec<Extra>,
+}
 
-#[derive(Debug, Clone, PartialEq, ColladaElement)]
-#[name = "library_effects"]
-pub struct LibraryEffects;
+impl ArticulatedSystem {
+    /// Returns the system's kinematics information, if it describes kinematics.
+    pub fn kinematics(&self) -> Option<&Kinematics> {
+        match self.contents {
+            ArticulatedSystemContents::Kinematics(ref kinematics) => Some(kinematics),
+            _ => None,
+        }
+    }
+
+    /// Returns the system's motion information, if it describes motion.
+    pub fn motion(&self) -> Option<&Motion> {
+        match self.contents {
+            ArticulatedSystemContents::Motion(ref motion) => Some(motion),
+            _ => None,
+        }
+    }
+}
 
+/// The information described by an [`ArticulatedSystem`].
+///
+/// [`ArticulatedSystem`]: ./struct.ArticulatedSystem.html
 #[derive(Debug, Clone, PartialEq, ColladaElement)]
-#[name = "library_force_fields"]
-pub struct LibraryForceFields;
+pub enum ArticulatedSystemContents {
+    Kinematics(Kinematics),
+    Motion(Motion),
+}
 
+/// Attaches the end of a kinematic chain to a [`Link`] through a joint.
+///
+/// [`Link`]: ./struct.Link.html
 #[derive(Debug, Clone, PartialEq, ColladaElement)]
-#[name = "library_formulas"]
-pub struct LibraryFormulas;
+pub enum Attachment {
+    End(AttachmentEnd),
+    Full(AttachmentFull),
+    Start(AttachmentStart),
+}
 
+/// Attaches the end of a closed kinematic loop to a joint.
 #[derive(Debug, Clone, PartialEq, ColladaElement)]
-#[name = "library_geometries"]
-pub struct LibraryGeometries;
+#[name = "attachment_end"]
+pub struct AttachmentEnd {
+    /// The SID path of the joint that the loop is attached to.
+    #[attribute]
+    pub joint: String,
+
+    /// The position of the attachment relative to the link.
+    #[child]
+    pub transforms: Vec<::v1_4::RigidTransform>,
+}
 
+/// Connects a child [`Link`] to its parent through a joint.
+///
+/// [`Link`]: ./struct.Link.html
 #[derive(Debug, Clone, PartialEq, ColladaElement)]
-#[name = "library_images"]
-pub struct LibraryImages;
+#[name = "attachment_full"]
+pub struct AttachmentFull {
+    /// The SID path of the joint that connects the links.
+    #[attribute]
+    pub joint: String,
+
+    /// The position of the joint relative to the parent link.
+    #[child]
+    pub transforms: Vec<::v1_4::RigidTransform>,
+
+    /// The child link moved by the joint.
+    #[child]
+    pub link: Link,
+}
 
+/// Attaches the start of a closed kinematic loop to a joint.
 #[derive(Debug, Clone, PartialEq, ColladaElement)]
-#[name = "library_joints"]
-pub struct LibraryJoints;
+#[name = "attachment_start"]
+pub struct AttachmentStart {
+    /// The SID path of the joint that the loop is attached to.
+    #[attribute]
+    pub joint: String,
 
+    /// The position of the attachment relative to the link.
+    #[child]
+    pub transforms: Vec<::v1_4::RigidTransform>,
+}
+
+/// The axis that a joint rotates around or moves along.
 #[derive(Debug, Clone, PartialEq, ColladaElement)]
-#[name = "library_kinematics_models"]
-pub struct LibraryKinematicsModels;
+#[name = "axis"]
+pub struct Axis {
+    /// An identifier for the axis that's unique amongst its sibling elements.
+    #[attribute]
+    pub sid: Option<String>,
+
+    /// The X, Y, and Z components of the axis.
+    #[text]
+    pub data: Vec<f64>,
+}
+
+impl Axis {
+    /// Returns the axis as a vector.
+    ///
+    /// Missing components are treated as `0.0`.
+    pub fn vector(&self) -> [f64; 3] {
+        let component = |index| self.data.get(index).cloned().unwrap_or(0.0);
+        [component(0), component(1), component(2)]
+    }
+}
 
+/// A boolean value for a kinematics parameter.
 #[derive(Debug, Clone, PartialEq, ColladaElement)]
-#[name = "library_kinematics_scenes"]
-pub struct LibraryKinematicsScenes;
+#[name = "bool"]
+pub struct Bool {
+    #[text]
+    pub value: Boolean,
+}
 
+/// A floating point value for a kinematics parameter.
 #[derive(Debug, Clone, PartialEq, ColladaElement)]
-#[name = "library_lights"]
-pub struct LibraryLights;
+#[name = "float"]
+pub struct Float {
+    #[text]
+    pub value: f64,
+}
 
+/// Either a floating point value or a reference to the parameter that holds it.
 #[derive(Debug, Clone, PartialEq, ColladaElement)]
-#[name = "library_materials"]
-pub struct LibraryMaterials;
+pub enum FloatOrParam {
+    Float(Float),
+    Param(KinematicsParamRef),
+}
 
+/// The maximum deceleration of a motion axis.
 #[derive(Debug, Clone, PartialEq, ColladaElement)]
-#[name = "library_nodes"]
-pub struct LibraryNodes;
+#[name = "deceleration"]
+pub struct Deceleration {
+    /// The deceleration, or the parameter that holds it.
+    #[child]
+    pub value: FloatOrParam,
+}
 
+/// Motion information for the end effector of an articulated system.
+///
+/// Effector info isn't parsed yet.
 #[derive(Debug, Clone, PartialEq, ColladaElement)]
-#[name = "library_physics_materials"]
-pub struct LibraryPhysicsMaterials;
+#[name = "effector_info"]
+pub struct EffectorInfo;
 
+/// The frame of the object being manipulated by an articulated system.
 #[derive(Debug, Clone, PartialEq, ColladaElement)]
-#[name = "library_physics_models"]
-pub struct LibraryPhysicsModels;
+#[name = "frame_object"]
+pub struct FrameObject {
+    /// The SID path of the link that the frame is relative to.
+    #[attribute]
+    pub link: String,
+
+    /// The position of the frame relative to the link.
+    #[child]
+    pub transforms: Vec<::v1_4::RigidTransform>,
+}
 
+/// The base frame of an articulated system.
 #[derive(Debug, Clone, PartialEq, ColladaElement)]
-#[name = "library_physics_scenes"]
-pub struct LibraryPhysicsScenes;
+#[name = "frame_origin"]
+pub struct FrameOrigin {
+    /// The SID path of the link that the frame is relative to.
+    #[attribute]
+    pub link: String,
+
+    /// The position of the frame relative to the link.
+    #[child]
+    pub transforms: Vec<::v1_4::RigidTransform>,
+}
 
+/// The tool center point frame of an articulated system, relative to its tip.
 #[derive(Debug, Clone, PartialEq, ColladaElement)]
-#[name = "library_visual_scenes"]
-pub struct LibraryVisualScenes;
+#[name = "frame_tcp"]
+pub struct FrameTcp {
+    /// The SID path of the link that the frame is relative to.
+    #[attribute]
+    pub link: String,
+
+    /// The position of the frame relative to the link.
+    #[child]
+    pub transforms: Vec<::v1_4::RigidTransform>,
+}
 
+/// The frame at the tip of an articulated system's kinematic chain.
 #[derive(Debug, Clone, PartialEq, ColladaElement)]
-#[name = "scene"]
-pub struct Scene;
+#[name = "frame_tip"]
+pub struct FrameTip {
+    /// The SID path of the link that the frame is relative to.
+    #[attribute]
+    pub link: String,
 
-/// Specifies the altitude of a [`GeographicLocation`][GeographicLocation].
+    /// The position of the frame relative to the link.
+    #[child]
+    pub transforms: Vec<::v1_4::RigidTransform>,
+}
+
+/// Instantiates an [`ArticulatedSystem`] within a [`KinematicsScene`] or a [`Motion`].
 ///
-/// [GeographicLocation]: struct.GeographicLocation.html
-#[derive(Debug, Clone, PartialEq)]
-pub enum Altitude {
-    /// The altitude is relative to global sea level.
-    Absolute(f64),
+/// [`ArticulatedSystem`]: ./struct.ArticulatedSystem.html
+/// [`KinematicsScene`]: ./struct.KinematicsScene.html
+/// [`Motion`]: ./struct.Motion.html
+#[derive(Debug, Clone, PartialEq, ColladaElement)]
+#[name = "instance_articulated_system"]
+pub struct InstanceArticulatedSystem {
+    /// An identifier for the instance that's unique amongst its sibling elements.
+    #[attribute]
+    pub sid: Option<String>,
 
-    /// The altitude is relative to ground level at the specified latitude and longitude.
-    RelativeToGround(f64),
-}
+    /// The text string name of the element, if present.
+    #[attribute]
+    pub name: Option<String>,
 
-impl ColladaElement for Altitude {
-    fn name_test(name: &str) -> bool {
-        name == "altitude"
-    }
+    /// The location of the instantiated articulated system.
+    #[attribute]
+    pub url: AnyUri,
 
-    fn parse_element<R>(
-        reader: &mut EventReader<R>,
-        element_start: ElementStart,
-    ) -> Result<Self>
-    where
-        R: Read,
-    {
-        let mut mode = None;
-        for attribute in element_start.attributes {
-            match &*attribute.name.local_name {
-                "mode" => {
-                    mode = Some(attribute.value);
-                }
+    /// Binds the system's parameters to values or to parameters of the instance's parent.
+    #[child]
+    pub binds: Vec<KinematicsBind>,
 
-                attrib_name @ _ => {
-                    return Err(Error {
-                        position: reader.position(),
-                        kind: ErrorKind::UnexpectedAttribute {
-                            element: "altitude",
-                            attribute: attrib_name.into(),
-                            expected: vec!["mode"],
-                        },
-                    });
-                }
-            }
-        }
+    /// Values assigned to the system's parameters for this instance.
+    #[child]
+    pub set_params: Vec<KinematicsSetParam>,
 
-        let mode = match mode {
-            Some(mode) => { mode }
-            None => {
-                return Err(Error {
-                    position: reader.position(),
-                    kind: ErrorKind::MissingAttribute {
-                        element: "altitude",
-                        attribute: "mode",
-                    },
-                });
+    /// Parameters declared for the instance.
+    #[child]
+    pub new_params: Vec<KinematicsNewParam>,
+
+    /// Arbitrary additional information about the instance.
+    #[child]
+    pub extras: Vec<Extra>,
+}
+
+/// A mathematical formula, used to relate the values of a kinematics model's joints.
+#[derive(Debug, Clone, PartialEq, ColladaElement)]
+#[name = "formula"]
+pub struct Formula {
+    /// The identifier of the element, if present. Will be unique within the document.
+    #[attribute]
+    pub id: Option<String>,
+
+    /// The text string name of the element, if present.
+    #[attribute]
+    pub name: Option<String>,
+
+    /// An identifier for the formula that's unique amongst its sibling elements.
+    #[attribute]
+    pub sid: Option<String>,
+
+    /// Parameters declared by the formula, which can be used as variables in its expression.
+    #[child]
+    pub new_params: Vec<KinematicsNewParam>,
+
+    /// The value that the result of the formula is assigned to.
+    #[child]
+    pub target: Target,
+
+    /// The formula's expression, as defined by the common profile.
+    #[child]
+    pub technique_common: FormulaTechniqueCommon,
+
+    /// Profile-specific information about the formula.
+    #[child]
+    pub techniques: Vec<Technique>,
+}
+
+/// Instantiates a formula declared in a `<library_formulas>`.
+///
+/// Formulas aren't parsed yet.
+#[derive(Debug, Clone, PartialEq, ColladaElement)]
+#[name = "instance_formula"]
+pub struct InstanceFormula;
+
+/// Instantiates a [`Joint`] declared elsewhere, usually in a `<library_joints>`.
+///
+/// [`Joint`]: ./struct.Joint.html
+#[derive(Debug, Clone, PartialEq, ColladaElement)]
+#[name = "instance_joint"]
+pub struct InstanceJoint {
+    /// An identifier for the instance that's unique amongst its sibling elements.
+    ///
+    /// Links refer to the joint by this SID.
+    #[attribute]
+    pub sid: Option<String>,
+
+    /// The text string name of the element, if present.
+    #[attribute]
+    pub name: Option<String>,
+
+    /// The location of the instantiated joint.
+    #[attribute]
+    pub url: AnyUri,
+
+    /// Arbitrary additional information about the instance.
+    #[child]
+    pub extras: Vec<Extra>,
+}
+
+/// Instantiates a [`KinematicsModel`] within a [`KinematicsScene`].
+///
+/// [`KinematicsModel`]: ./struct.KinematicsModel.html
+/// [`KinematicsScene`]: ./struct.KinematicsScene.html
+#[derive(Debug, Clone, PartialEq, ColladaElement)]
+#[name = "instance_kinematics_model"]
+pub struct InstanceKinematicsModel {
+    /// An identifier for the instance that's unique amongst its sibling elements.
+    #[attribute]
+    pub sid: Option<String>,
+
+    /// The text string name of the element, if present.
+    #[attribute]
+    pub name: Option<String>,
+
+    /// The location of the instantiated kinematics model.
+    #[attribute]
+    pub url: AnyUri,
+
+    /// Binds the model's parameters to values or to parameters of the scene.
+    #[child]
+    pub binds: Vec<KinematicsBind>,
+
+    /// Parameters declared for the instance.
+    #[child]
+    pub new_params: Vec<KinematicsNewParam>,
+
+    /// Values assigned to the model's parameters for this instance.
+    #[child]
+    pub set_params: Vec<KinematicsSetParam>,
+
+    /// Arbitrary additional information about the instance.
+    #[child]
+    pub extras: Vec<Extra>,
+}
+
+/// An integer value for a kinematics parameter.
+#[derive(Debug, Clone, PartialEq, ColladaElement)]
+#[name = "int"]
+pub struct Int {
+    #[text]
+    pub value: i64,
+}
+
+/// The maximum jerk, or rate of change of acceleration, of a motion axis.
+#[derive(Debug, Clone, PartialEq, ColladaElement)]
+#[name = "jerk"]
+pub struct Jerk {
+    /// The jerk, or the parameter that holds it.
+    #[child]
+    pub value: FloatOrParam,
+}
+
+/// Describes a joint, which constrains how a [`Link`] can move relative to its parent.
+///
+/// A joint is made up of one or more primitive joints, each of which adds a single degree of
+/// freedom.
+///
+/// [`Link`]: ./struct.Link.html
+#[derive(Debug, Clone, PartialEq, ColladaElement)]
+#[name = "joint"]
+pub struct Joint {
+    /// The identifier of the element, if present. Will be unique within the document.
+    #[attribute]
+    pub id: Option<String>,
+
+    /// The text string name of the element, if present.
+    #[attribute]
+    pub name: Option<String>,
+
+    /// An identifier for the joint that's unique amongst its sibling elements.
+    ///
+    /// Links refer to the joint by this SID.
+    #[attribute]
+    pub sid: Option<String>,
+
+    /// The primitive joints that make up the joint. There will always be at least one item in
+    /// `primitives`.
+    #[child]
+    #[required]
+    pub primitives: Vec<PrimitiveJoint>,
+
+    /// Arbitrary additional information about the joint.
+    #[child]
+    pub extras: Vec<Extra>,
+}
+
+/// Adds kinematics information to a set of [`KinematicsModel`] instances.
+///
+/// [`KinematicsModel`]: ./struct.KinematicsModel.html
+#[derive(Debug, Clone, PartialEq, ColladaElement)]
+#[name = "kinematics"]
+pub struct Kinematics {
+    /// The kinematics models that make up the system. There will always be at least one item in
+    /// `instance_kinematics_models`.
+    #[child]
+    #[required]
+    pub instance_kinematics_models: Vec<InstanceKinematicsModel>,
+
+    /// The axes and frames of the system, as defined by the common profile.
+    #[child]
+    pub technique_common: KinematicsTechniqueCommon,
+
+    /// Profile-specific information about the system.
+    #[child]
+    pub techniques: Vec<Technique>,
+
+    /// Arbitrary additional information about the system.
+    #[child]
+    pub extras: Vec<Extra>,
+}
+
+/// Kinematics information for one of the joint axes of an articulated system.
+///
+/// Kinematics axis info isn't parsed yet.
+#[derive(Debug, Clone, PartialEq, ColladaElement)]
+#[name = "axis_info"]
+pub struct KinematicsAxisInfo;
+
+/// Binds a parameter of an [`InstanceKinematicsModel`] to a value.
+///
+/// [`InstanceKinematicsModel`]: ./struct.InstanceKinematicsModel.html
+#[derive(Debug, Clone, PartialEq, ColladaElement)]
+#[name = "bind"]
+pub struct KinematicsBind {
+    /// The parameter being bound.
+    #[attribute]
+    pub symbol: String,
+
+    /// The value bound to the parameter.
+    #[child]
+    pub value: KinematicsBindValue,
+}
+
+/// The value of a [`KinematicsBind`], which may also refer to another parameter.
+///
+/// [`KinematicsBind`]: ./struct.KinematicsBind.html
+#[derive(Debug, Clone, PartialEq, ColladaElement)]
+pub enum KinematicsBindValue {
+    Bool(Bool),
+    Float(Float),
+    Int(Int),
+    Param(KinematicsParamRef),
+    SidRef(SidRef),
+}
+
+/// Either a joint declared within a kinematics model, or an instance of one declared elsewhere.
+#[derive(Debug, Clone, PartialEq, ColladaElement)]
+pub enum KinematicsJoint {
+    Instance(InstanceJoint),
+    Joint(Joint),
+}
+
+/// Either a formula declared within a kinematics model, or an instance of one declared elsewhere.
+#[derive(Debug, Clone, PartialEq, ColladaElement)]
+pub enum KinematicsFormula {
+    Formula(Formula),
+    Instance(InstanceFormula),
+}
+
+/// Describes the links and joints of a kinematic chain, such as a robot arm.
+#[derive(Debug, Clone, PartialEq, ColladaElement)]
+#[name = "kinematics_model"]
+pub struct KinematicsModel {
+    /// The identifier of the element, if present. Will be unique within the document.
+    #[attribute]
+    pub id: Option<String>,
+
+    /// The text string name of the element, if present.
+    #[attribute]
+    pub name: Option<String>,
+
+    /// Asset-management information for the model, if present.
+    #[child]
+    pub asset: Option<Asset>,
+
+    /// The joints and links of the model, as defined by the common profile.
+    #[child]
+    pub technique_common: KinematicsModelTechniqueCommon,
+
+    /// Profile-specific information about the model.
+    #[child]
+    pub techniques: Vec<Technique>,
+
+    /// Arbitrary additional information about the model.
+    #[child]
+    pub extras: Vec<Extra>,
+}
+
+/// The common profile's description of a [`KinematicsModel`].
+///
+/// [`KinematicsModel`]: ./struct.KinematicsModel.html
+#[derive(Debug, Clone, PartialEq, ColladaElement)]
+#[name = "technique_common"]
+pub struct KinematicsModelTechniqueCommon {
+    /// Parameters declared by the model.
+    #[child]
+    pub new_params: Vec<KinematicsNewParam>,
+
+    /// The joints used by the model's links. There will always be at least one item in `joints`.
+    #[child]
+    #[required]
+    pub joints: Vec<KinematicsJoint>,
+
+    /// The root links of the model's kinematic chains. There will always be at least one item in
+    /// `links`.
+    #[child]
+    #[required]
+    pub links: Vec<Link>,
+
+    /// Formulas that relate the values of the model's joints.
+    #[child]
+    pub formulas: Vec<KinematicsFormula>,
+}
+
+impl KinematicsModelTechniqueCommon {
+    /// Returns the joint with the SID `sid`.
+    ///
+    /// Joints declared within the model are matched by their own `sid`, and instantiated joints
+    /// by the `sid` of the `<instance_joint>`. Use the instance's `url` to find the joint that
+    /// it instantiates.
+    pub fn joint(&self, sid: &str) -> Option<&KinematicsJoint> {
+        self.joints.iter().find(|joint| {
+            let joint_sid = match **joint {
+                KinematicsJoint::Instance(ref instance) => instance.sid.as_ref(),
+                KinematicsJoint::Joint(ref joint) => joint.sid.as_ref(),
+            };
+            joint_sid.map(String::as_str) == Some(sid)
+        })
+    }
+}
+
+/// The common profile's description of a [`Kinematics`].
+///
+/// [`Kinematics`]: ./struct.Kinematics.html
+#[derive(Debug, Clone, PartialEq, ColladaElement)]
+#[name = "technique_common"]
+pub struct KinematicsTechniqueCommon {
+    /// Kinematics information for the joint axes of the system.
+    #[child]
+    pub axis_infos: Vec<KinematicsAxisInfo>,
+
+    /// The base frame of the system.
+    #[child]
+    pub frame_origin: FrameOrigin,
+
+    /// The frame at the tip of the system's kinematic chain.
+    #[child]
+    pub frame_tip: FrameTip,
+
+    /// The tool center point frame, if present.
+    #[child]
+    pub frame_tcp: Option<FrameTcp>,
+
+    /// The frame of the object being manipulated, if present.
+    #[child]
+    pub frame_object: Option<FrameObject>,
+}
+
+/// Declares a new parameter for a kinematics model or one of its instances.
+#[derive(Debug, Clone, PartialEq, ColladaElement)]
+#[name = "newparam"]
+pub struct KinematicsNewParam {
+    /// The identifier of the parameter, which is unique amongst its sibling elements.
+    #[attribute]
+    pub sid: String,
+
+    /// The initial value of the parameter.
+    #[child]
+    pub value: KinematicsValue,
+}
+
+/// Refers to a parameter by its SID.
+#[derive(Debug, Clone, PartialEq, ColladaElement)]
+#[name = "param"]
+pub struct KinematicsParamRef {
+    /// The SID path of the parameter.
+    #[attribute]
+    #[name = "ref"]
+    pub reference: String,
+}
+
+/// Ties together instances of kinematics models and articulated systems.
+#[derive(Debug, Clone, PartialEq, ColladaElement)]
+#[name = "kinematics_scene"]
+pub struct KinematicsScene {
+    /// The identifier of the element, if present. Will be unique within the document.
+    #[attribute]
+    pub id: Option<String>,
+
+    /// The text string name of the element, if present.
+    #[attribute]
+    pub name: Option<String>,
+
+    /// Asset-management information for the scene, if present.
+    #[child]
+    pub asset: Option<Asset>,
+
+    /// The kinematics models instantiated by the scene.
+    #[child]
+    pub instance_kinematics_models: Vec<InstanceKinematicsModel>,
+
+    /// The articulated systems instantiated by the scene.
+    #[child]
+    pub instance_articulated_systems: Vec<InstanceArticulatedSystem>,
+
+    /// Arbitrary additional information about the scene.
+    #[child]
+    pub extras: Vec<Extra>,
+}
+
+/// Assigns a value to an existing parameter of a kinematics model instance.
+#[derive(Debug, Clone, PartialEq, ColladaElement)]
+#[name = "setparam"]
+pub struct KinematicsSetParam {
+    /// The SID path of the parameter being assigned.
+    #[attribute]
+    #[name = "ref"]
+    pub reference: String,
+
+    /// The value assigned to the parameter.
+    #[child]
+    pub value: KinematicsValue,
+}
+
+/// The value of a kinematics parameter.
+#[derive(Debug, Clone, PartialEq, ColladaElement)]
+pub enum KinematicsValue {
+    Bool(Bool),
+    Float(Float),
+    Int(Int),
+    SidRef(SidRef),
+}
+
+/// The limits of a primitive joint's movement.
+///
+/// The limits are in degrees for revolute joints, and in distance units for prismatic joints.
+#[derive(Debug, Clone, PartialEq, ColladaElement)]
+#[name = "limits"]
+pub struct Limits {
+    /// The lower limit of the joint.
+    #[child]
+    pub min: Option<Min>,
+
+    /// The upper limit of the joint.
+    #[child]
+    pub max: Option<Max>,
+}
+
+/// A rigid body in a kinematic chain, connected to further links through joints.
+#[derive(Debug, Clone, PartialEq, ColladaElement)]
+#[name = "link"]
+pub struct Link {
+    /// An identifier for the link that's unique amongst its sibling elements.
+    #[attribute]
+    pub sid: Option<String>,
+
+    /// The text string name of the element, if present.
+    #[attribute]
+    pub name: Option<String>,
+
+    /// The position of the link relative to its parent.
+    #[child]
+    pub transforms: Vec<::v1_4::RigidTransform>,
+
+    /// The links and loop ends attached to this link.
+    #[child]
+    pub attachments: Vec<Attachment>,
+}
+
+/// The upper limit of a primitive joint.
+#[derive(Debug, Clone, PartialEq, ColladaElement)]
+#[name = "max"]
+pub struct Max {
+    /// An identifier for the limit that's unique amongst its sibling elements.
+    #[attribute]
+    pub sid: Option<String>,
+
+    /// The value of the limit.
+    #[text]
+    pub value: f64,
+}
+
+/// The lower limit of a primitive joint.
+#[derive(Debug, Clone, PartialEq, ColladaElement)]
+#[name = "min"]
+pub struct Min {
+    /// An identifier for the limit that's unique amongst its sibling elements.
+    #[attribute]
+    pub sid: Option<String>,
+
+    /// The value of the limit.
+    #[text]
+    pub value: f64,
+}
+
+/// Adds motion information to an [`ArticulatedSystem`].
+///
+/// [`ArticulatedSystem`]: ./struct.ArticulatedSystem.html
+#[derive(Debug, Clone, PartialEq, ColladaElement)]
+#[name = "motion"]
+pub struct Motion {
+    /// The articulated system that the motion information applies to.
+    #[child]
+    pub instance_articulated_system: InstanceArticulatedSystem,
+
+    /// The motion of the system's axes, as defined by the common profile.
+    #[child]
+    pub technique_common: MotionTechniqueCommon,
+
+    /// Profile-specific information about the motion.
+    #[child]
+    pub techniques: Vec<Technique>,
+
+    /// Arbitrary additional information about the motion.
+    #[child]
+    pub extras: Vec<Extra>,
+}
+
+/// Motion information for one of the joint axes of an articulated system.
+#[derive(Debug, Clone, PartialEq, ColladaElement)]
+#[name = "axis_info"]
+pub struct MotionAxisInfo {
+    /// An identifier for the axis info that's unique amongst its sibling elements.
+    #[attribute]
+    pub sid: Option<String>,
+
+    /// The text string name of the element, if present.
+    #[attribute]
+    pub name: Option<String>,
+
+    /// The SID path of the kinematics axis info that the motion applies to.
+    #[attribute]
+    pub axis: String,
+
+    /// Binds the axis's parameters to values or to parameters of the system.
+    #[child]
+    pub binds: Vec<KinematicsBind>,
+
+    /// Parameters declared for the axis.
+    #[child]
+    pub new_params: Vec<KinematicsNewParam>,
+
+    /// Values assigned to the axis's parameters.
+    #[child]
+    pub set_params: Vec<KinematicsSetParam>,
+
+    /// The maximum speed of the axis, if specified.
+    #[child]
+    pub speed: Option<Speed>,
+
+    /// The maximum acceleration of the axis, if specified.
+    #[child]
+    pub acceleration: Option<Acceleration>,
+
+    /// The maximum deceleration of the axis, if specified.
+    #[child]
+    pub deceleration: Option<Deceleration>,
+
+    /// The maximum jerk of the axis, if specified.
+    #[child]
+    pub jerk: Option<Jerk>,
+}
+
+/// The common profile's description of a [`Motion`].
+///
+/// [`Motion`]: ./struct.Motion.html
+#[derive(Debug, Clone, PartialEq, ColladaElement)]
+#[name = "technique_common"]
+pub struct MotionTechniqueCommon {
+    /// Motion information for the joint axes of the system.
+    #[child]
+    pub axis_infos: Vec<MotionAxisInfo>,
+
+    /// Motion information for the end effector, if present.
+    #[child]
+    pub effector_info: Option<EffectorInfo>,
+}
+
+/// A single degree of freedom of a [`Joint`].
+///
+/// [`Joint`]: ./struct.Joint.html
+#[derive(Debug, Clone, PartialEq, ColladaElement)]
+pub enum PrimitiveJoint {
+    Prismatic(Prismatic),
+    Revolute(Revolute),
+}
+
+impl PrimitiveJoint {
+    /// Returns the axis the joint rotates around or moves along.
+    pub fn axis(&self) -> &Axis {
+        match *self {
+            PrimitiveJoint::Prismatic(ref prismatic) => &prismatic.axis,
+            PrimitiveJoint::Revolute(ref revolute) => &revolute.axis,
+        }
+    }
+
+    /// Returns the limits of the joint's movement, if it has any.
+    pub fn limits(&self) -> Option<&Limits> {
+        match *self {
+            PrimitiveJoint::Prismatic(ref prismatic) => prismatic.limits.as_ref(),
+            PrimitiveJoint::Revolute(ref revolute) => revolute.limits.as_ref(),
+        }
+    }
+}
+
+/// A primitive joint that moves along an axis.
+#[derive(Debug, Clone, PartialEq, ColladaElement)]
+#[name = "prismatic"]
+pub struct Prismatic {
+    /// An identifier for the joint that's unique amongst its sibling elements.
+    #[attribute]
+    pub sid: Option<String>,
+
+    /// The axis the joint moves along.
+    #[child]
+    pub axis: Axis,
+
+    /// The distances the joint can move along the axis.
+    #[child]
+    pub limits: Option<Limits>,
+}
+
+/// A primitive joint that rotates around an axis.
+#[derive(Debug, Clone, PartialEq, ColladaElement)]
+#[name = "revolute"]
+pub struct Revolute {
+    /// An identifier for the joint that's unique amongst its sibling elements.
+    #[attribute]
+    pub sid: Option<String>,
+
+    /// The axis the joint rotates around.
+    #[child]
+    pub axis: Axis,
+
+    /// The angles the joint can rotate to, in degrees.
+    #[child]
+    pub limits: Option<Limits>,
+}
+
+/// Refers to an element by its SID path.
+#[derive(Debug, Clone, PartialEq, ColladaElement)]
+#[name = "SIDREF"]
+pub struct SidRef {
+    #[text]
+    pub value: String,
+}
+
+/// The maximum speed of a motion axis.
+#[derive(Debug, Clone, PartialEq, ColladaElement)]
+#[name = "speed"]
+pub struct Speed {
+    /// The speed, or the parameter that holds it.
+    #[child]
+    pub value: FloatOrParam,
+}
+
+/// The value that the result of a [`Formula`] is assigned to.
+///
+/// [`Formula`]: ./struct.Formula.html
+#[derive(Debug, Clone, PartialEq, ColladaElement)]
+#[name = "target"]
+pub struct Target {
+    /// The target value, or the parameter that holds it.
+    #[child]
+    pub value: FloatOrParam,
+}
+
+#[derive(Debug, Clone, PartialEq, ColladaElement)]
+pub enum Library {
+    Animations(LibraryAnimations),
+    AnimationClips(LibraryAnimationClips),
+    ArticulatedSystems(LibraryArticulatedSystems),
+    Cameras(LibraryCameras),
+    Controllers(LibraryControllers),
+    Effects(LibraryEffects),
+    ForceFields(LibraryForceFields),
+    Formulas(LibraryFormulas),
+    Geometries(LibraryGeometries),
+    Images(LibraryImages),
+    Joints(LibraryJoints),
+    KinematicsModels(LibraryKinematicsModels),
+    KinematicsScenes(LibraryKinematicsScenes),
+    Lights(LibraryLights),
+    Materials(LibraryMaterials),
+    Nodes(LibraryNodes),
+    PhysicsMaterials(LibraryPhysicsMaterials),
+    PhysicsModels(LibraryPhysicsModels),
+    PhysicsScenes(LibraryPhysicsScenes),
+    VisualScenes(LibraryVisualScenes),
+}
+
+impl Library {
+    pub fn as_library_articulated_systems(&self) -> Option<&LibraryArticulatedSystems> {
+        match *self {
+            Library::ArticulatedSystems(ref library_articulated_systems) => {
+                Some(library_articulated_systems)
+            }
+            _ => None,
+        }
+    }
+
+    pub fn as_library_force_fields(&self) -> Option<&LibraryForceFields> {
+        match *self {
+            Library::ForceFields(ref library_force_fields) => Some(library_force_fields),
+            _ => None,
+        }
+    }
+
+    pub fn as_library_formulas(&self) -> Option<&LibraryFormulas> {
+        match *self {
+            Library::Formulas(ref library_formulas) => Some(library_formulas),
+            _ => None,
+        }
+    }
+
+    pub fn as_library_joints(&self) -> Option<&LibraryJoints> {
+        match *self {
+            Library::Joints(ref library_joints) => Some(library_joints),
+            _ => None,
+        }
+    }
+
+    pub fn as_library_kinematics_models(&self) -> Option<&LibraryKinematicsModels> {
+        match *self {
+            Library::KinematicsModels(ref library_kinematics_models) => {
+                Some(library_kinematics_models)
+            }
+            _ => None,
+        }
+    }
+
+    pub fn as_library_kinematics_scenes(&self) -> Option<&LibraryKinematicsScenes> {
+        match *self {
+            Library::KinematicsScenes(ref library_kinematics_scenes) => {
+                Some(library_kinematics_scenes)
+            }
+            _ => None,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, ColladaElement)]
+#[name = "library_animations"]
+pub struct LibraryAnimations;
+
+#[derive(Debug, Clone, PartialEq, ColladaElement)]
+#[name = "library_animation_clips"]
+pub struct LibraryAnimationClips;
+
+/// Contains the articulated systems in the document.
+#[derive(Debug, Clone, PartialEq, ColladaElement)]
+#[name = "library_articulated_systems"]
+pub struct LibraryArticulatedSystems {
+    /// The identifier of the element, if present. Will be unique within the document.
+    #[attribute]
+    pub id: Option<String>,
+
+    /// The text string name of the element, if present.
+    #[attribute]
+    pub name: Option<String>,
+
+    /// Asset-management information for the library, if present.
+    #[child]
+    pub asset: Option<Asset>,
+
+    /// The articulated systems contained within the library. There will always be at least one
+    /// item in `articulated_systems`.
+    #[child]
+    #[required]
+    pub articulated_systems: Vec<ArticulatedSystem>,
+
+    /// Arbitrary additional information about the library.
+    #[child]
+    pub extras: Vec<Extra>,
+}
+
+#[derive(Debug, Clone, PartialEq, ColladaElement)]
+#[name = "library_cameras"]
+pub struct LibraryCameras;
+
+#[derive(Debug, Clone, PartialEq, ColladaElement)]
+#[name = "library_controllers"]
+pub struct LibraryControllers;
+
+#[derive(Debug, Clone, PartialEq, ColladaElement)]
+#[name = "library_effects"]
+pub struct LibraryEffects;
+
+/// Contains the force fields in the document.
+#[derive(Debug, Clone, PartialEq, ColladaElement)]
+#[name = "library_force_fields"]
+pub struct LibraryForceFields {
+    /// The identifier of the element, if present. Will be unique within the document.
+    #[attribute]
+    pub id: Option<String>,
+
+    /// The text string name of the element, if present.
+    #[attribute]
+    pub name: Option<String>,
+
+    /// Asset-management information for the library, if present.
+    #[child]
+    pub asset: Option<Asset>,
+
+    /// The force fields contained within the library. There will always be at least one item in
+    /// `force_fields`.
+    #[child]
+    #[required]
+    pub force_fields: Vec<ForceField>,
+
+    /// Arbitrary additional information about the library.
+    #[child]
+    pub extras: Vec<Extra>,
+}
+
+/// Contains the formulas in the document.
+#[derive(Debug, Clone, PartialEq, ColladaElement)]
+#[name = "library_formulas"]
+pub struct LibraryFormulas {
+    /// The identifier of the element, if present. Will be unique within the document.
+    #[attribute]
+    pub id: Option<String>,
+
+    /// The text string name of the element, if present.
+    #[attribute]
+    pub name: Option<String>,
+
+    /// Asset-management information for the library, if present.
+    #[child]
+    pub asset: Option<Asset>,
+
+    /// The formulas contained within the library. There will always be at least one item in
+    /// `formulas`.
+    #[child]
+    #[required]
+    pub formulas: Vec<Formula>,
+
+    /// Arbitrary additional information about the library.
+    #[child]
+    pub extras: Vec<Extra>,
+}
+
+#[derive(Debug, Clone, PartialEq, ColladaElement)]
+#[name = "library_geometries"]
+pub struct LibraryGeometries;
+
+#[derive(Debug, Clone, PartialEq, ColladaElement)]
+#[name = "library_images"]
+pub struct LibraryImages;
+
+/// Contains the joints in the document.
+#[derive(Debug, Clone, PartialEq, ColladaElement)]
+#[name = "library_joints"]
+pub struct LibraryJoints {
+    /// The identifier of the element, if present. Will be unique within the document.
+    #[attribute]
+    pub id: Option<String>,
+
+    /// The text string name of the element, if present.
+    #[attribute]
+    pub name: Option<String>,
+
+    /// Asset-management information for the library, if present.
+    #[child]
+    pub asset: Option<Asset>,
+
+    /// The joints contained within the library. There will always be at least one item in
+    /// `joints`.
+    #[child]
+    #[required]
+    pub joints: Vec<Joint>,
+
+    /// Arbitrary additional information about the library.
+    #[child]
+    pub extras: Vec<Extra>,
+}
+
+/// Contains the kinematics models in the document.
+#[derive(Debug, Clone, PartialEq, ColladaElement)]
+#[name = "library_kinematics_models"]
+pub struct LibraryKinematicsModels {
+    /// The identifier of the element, if present. Will be unique within the document.
+    #[attribute]
+    pub id: Option<String>,
+
+    /// The text string name of the element, if present.
+    #[attribute]
+    pub name: Option<String>,
+
+    /// Asset-management information for the library, if present.
+    #[child]
+    pub asset: Option<Asset>,
+
+    /// The kinematics models contained within the library. There will always be at least one item in
+    /// `kinematics_models`.
+    #[child]
+    #[required]
+    pub kinematics_models: Vec<KinematicsModel>,
+
+    /// Arbitrary additional information about the library.
+    #[child]
+    pub extras: Vec<Extra>,
+}
+
+/// Contains the kinematics scenes in the document.
+#[derive(Debug, Clone, PartialEq, ColladaElement)]
+#[name = "library_kinematics_scenes"]
+pub struct LibraryKinematicsScenes {
+    /// The identifier of the element, if present. Will be unique within the document.
+    #[attribute]
+    pub id: Option<String>,
+
+    /// The text string name of the element, if present.
+    #[attribute]
+    pub name: Option<String>,
+
+    /// Asset-management information for the library, if present.
+    #[child]
+    pub asset: Option<Asset>,
+
+    /// The kinematics scenes contained within the library. There will always be at least one item in
+    /// `kinematics_scenes`.
+    #[child]
+    #[required]
+    pub kinematics_scenes: Vec<KinematicsScene>,
+
+    /// Arbitrary additional information about the library.
+    #[child]
+    pub extras: Vec<Extra>,
+}
+
+#[derive(Debug, Clone, PartialEq, ColladaElement)]
+#[name = "library_lights"]
+pub struct LibraryLights;
+
+#[derive(Debug, Clone, PartialEq, ColladaElement)]
+#[name = "library_materials"]
+pub struct LibraryMaterials;
+
+#[derive(Debug, Clone, PartialEq, ColladaElement)]
+#[name = "library_nodes"]
+pub struct LibraryNodes;
+
+#[derive(Debug, Clone, PartialEq, ColladaElement)]
+#[name = "library_physics_materials"]
+pub struct LibraryPhysicsMaterials;
+
+#[derive(Debug, Clone, PartialEq, ColladaElement)]
+#[name = "library_physics_models"]
+pub struct LibraryPhysicsModels;
+
+#[derive(Debug, Clone, PartialEq, ColladaElement)]
+#[name = "library_physics_scenes"]
+pub struct LibraryPhysicsScenes;
+
+#[derive(Debug, Clone, PartialEq, ColladaElement)]
+#[name = "library_visual_scenes"]
+pub struct LibraryVisualScenes;
+
+#[derive(Debug, Clone, PartialEq, ColladaElement)]
+#[name = "scene"]
+pub struct Scene;
+
+/// Specifies the altitude of a [`GeographicLocation`][GeographicLocation].
+///
+/// [GeographicLocation]: struct.GeographicLocation.html
+#[derive(Debug, Clone, PartialEq)]
+pub enum Altitude {
+    /// The altitude is relative to global sea level.
+    Absolute(f64),
+
+    /// The altitude is relative to ground level at the specified latitude and longitude.
+    ///
+    /// This is the default if the `<altitude>` element doesn't specify a mode.
+    RelativeToGround(f64),
+}
+
+impl ColladaElement for Altitude {
+    fn name_test(name: &str) -> bool {
+        name == "altitude"
+    }
+
+    fn parse_element<R>(
+        reader: &mut ColladaReader<R>,
+        element_start: ElementStart,
+    ) -> Result<Self>
+    where
+        R: Read,
+    {
+        let mut mode = None;
+        for attribute in element_start.attributes {
+            match &*attribute.name.local_name {
+                "mode" => {
+                    mode = Some(attribute.value);
+                }
+
+                attrib_name @ _ => {
+                    return Err(Error {
+                        position: reader.position(),
+                        kind: ErrorKind::UnexpectedAttribute {
+                            element: "altitude",
+                            attribute: attrib_name.into(),
+                            expected: vec!["mode"],
+                        },
+                    });
+                }
             }
-        };
+        }
+
+        // The schema defaults `mode` to "relativeToGround" when it isn't specified.
+        let mode = mode.unwrap_or_else(|| "relativeToGround".into());
 
         match &*mode {
             "absolute" => {
@@ -530,4 +1782,229 @@ impl ColladaElement for Altitude {
     fn add_names(names: &mut Vec<&'static str>) {
         names.push("altitude");
     }
+
+    fn write_element<W>(&self, writer: &mut ColladaWriter<W>) -> ::std::io::Result<()>
+    where
+        W: ::std::io::Write,
+    {
+        let (mode, value) = match *self {
+            Altitude::Absolute(value) => ("absolute", value),
+            Altitude::RelativeToGround(value) => ("relativeToGround", value),
+        };
+
+        writer.start_element(::xml::writer::XmlEvent::start_element("altitude").attr("mode", mode))?;
+        writer.characters(&value.to_string())?;
+        writer.end_element()
+    }
+}
+
+/// The common profile's description of a [`Formula`].
+///
+/// The expression is written in [MathML], which isn't parsed by this library.
+///
+/// [`Formula`]: ./struct.Formula.html
+/// [MathML]: https://www.w3.org/Math/
+#[derive(Debug, Clone, PartialEq)]
+pub struct FormulaTechniqueCommon {
+    /// The raw XML events for the contents of the technique, usually a single `<math>` element.
+    /// These events do not contain the `StartElement` and `EndElement` events for the
+    /// `<technique_common>` element itself.
+    pub data: Vec<::xml::reader::XmlEvent>,
+}
+
+impl ColladaElement for FormulaTechniqueCommon {
+    fn name_test(name: &str) -> bool {
+        name == "technique_common"
+    }
+
+    fn parse_element<R>(
+        reader: &mut ColladaReader<R>,
+        element_start: ElementStart,
+    ) -> Result<Self>
+    where
+        R: Read,
+    {
+        utils::verify_attributes(reader, "technique_common", element_start.attributes)?;
+
+        let mut data = Vec::new();
+        let mut depth = 0;
+        loop {
+            let event = reader.next_raw()?;
+            match event {
+                ::xml::reader::XmlEvent::StartElement { .. } => { depth += 1; }
+
+                ::xml::reader::XmlEvent::EndElement { .. } => {
+                    if depth == 0 {
+                        break;
+                    }
+
+                    depth -= 1;
+                }
+
+                _ => {}
+            }
+
+            data.push(event);
+        }
+
+        Ok(FormulaTechniqueCommon { data })
+    }
+
+    fn add_names(names: &mut Vec<&'static str>) {
+        names.push("technique_common");
+    }
+
+    fn write_element<W>(&self, writer: &mut ColladaWriter<W>) -> ::std::io::Result<()>
+    where
+        W: ::std::io::Write,
+    {
+        writer.start_element(::xml::writer::XmlEvent::start_element("technique_common"))?;
+
+        for event in self.data.iter().filter_map(::xml::reader::XmlEvent::as_writer_event) {
+            writer.write(event)?;
+        }
+
+        writer.end_element()
+    }
+}
+
+impl ::v1_4::Collada {
+    /// Converts a `1.4` document into a `1.5` document, discarding anything that can't yet be
+    /// represented in `1.5`.
+    ///
+    /// The two versions share most of their document-level structure, so the document's
+    /// `base_uri`, asset metadata, contributors, and extra data carry over directly. Elements that
+    /// were added in version `1.5.0`, such as [`Asset::coverage`][Asset] and
+    /// [`Contributor::author_email`][Contributor], are left empty.
+    ///
+    /// Most of the contents of a `1.5` document aren't parsed yet, so the following are dropped
+    /// by the conversion:
+    ///
+    /// * The contents of every library other than `<library_force_fields>`. Each library is
+    ///   still converted into an empty library of the same kind, so e.g. a `1.4` document's
+    ///   geometries become a single empty [`LibraryGeometries`].
+    /// * The contents of the `<scene>`, i.e. its instanced physics scenes, visual scene, and
+    ///   extra data. A `1.4` document with a scene still gets an empty [`Scene`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::fs::File;
+    /// use collaborate::v1_4;
+    ///
+    /// let file = File::open("resources/blender_cube.dae").unwrap();
+    /// let document = v1_4::Collada::read(file).unwrap();
+    ///
+    /// let document = document.into_1_5_lossy();
+    /// assert_eq!("1.5.0", document.version);
+    /// ```
+    ///
+    /// [Asset]: ../v1_5/struct.Asset.html#structfield.coverage
+    /// [Contributor]: ../v1_5/struct.Contributor.html#structfield.author_email
+    /// [`LibraryGeometries`]: ../v1_5/struct.LibraryGeometries.html
+    /// [`Scene`]: ../v1_5/struct.Scene.html
+    pub fn into_1_5_lossy(self) -> Collada {
+        Collada {
+            version: "1.5.0".into(),
+            xmlns: self.xmlns.map(|_| "http://www.collada.org/2008/03/COLLADASchema".into()),
+            base_uri: self.base_uri,
+            asset: self.asset.into(),
+            libraries: self.libraries.into_iter().map(library_from_1_4).collect(),
+            scene: self.scene.map(|_| Scene),
+            extras: self.extras.into_iter().map(Into::into).collect(),
+        }
+    }
+}
+
+impl From<::v1_4::Asset> for Asset {
+    fn from(from: ::v1_4::Asset) -> Asset {
+        Asset {
+            contributors: from.contributors.into_iter().map(Into::into).collect(),
+            coverage: None,
+            created: from.created,
+            keywords: from.keywords,
+            modified: from.modified,
+            revision: from.revision,
+            subject: from.subject,
+            title: from.title,
+            unit: from.unit,
+            up_axis: from.up_axis,
+            extras: Vec::new(),
+        }
+    }
+}
+
+impl From<::v1_4::Contributor> for Contributor {
+    fn from(from: ::v1_4::Contributor) -> Contributor {
+        Contributor {
+            author: from.author,
+            author_email: None,
+            author_website: None,
+            authoring_tool: from.authoring_tool,
+            comments: from.comments,
+            copyright: from.copyright,
+            source_data: from.source_data,
+        }
+    }
+}
+
+impl From<::v1_4::Extra> for Extra {
+    fn from(from: ::v1_4::Extra) -> Extra {
+        Extra {
+            id: from.id,
+            name: from.name,
+            type_hint: from.type_hint,
+            asset: from.asset.map(Into::into),
+            techniques: from.techniques,
+            namespace: from.namespace,
+        }
+    }
+}
+
+impl From<::v1_4::ForceField> for ForceField {
+    fn from(from: ::v1_4::ForceField) -> ForceField {
+        ForceField {
+            id: from.id,
+            name: from.name,
+            asset: from.asset.map(Into::into),
+            techniques: from.techniques,
+            extras: from.extras.into_iter().map(Into::into).collect(),
+        }
+    }
+}
+
+impl From<::v1_4::LibraryForceFields> for LibraryForceFields {
+    fn from(from: ::v1_4::LibraryForceFields) -> LibraryForceFields {
+        LibraryForceFields {
+            id: from.id,
+            name: from.name,
+            asset: from.asset.map(Into::into),
+            force_fields: from.force_fields.into_iter().map(Into::into).collect(),
+            extras: from.extras.into_iter().map(Into::into).collect(),
+        }
+    }
+}
+
+/// Converts a `1.4` library into an empty `1.5` library of the same kind, keeping the contents
+/// only for the kinds of library that `1.5` can represent.
+fn library_from_1_4(from: ::v1_4::Library) -> Library {
+    use v1_4::Library as V1_4;
+
+    match from {
+        V1_4::Animations(_) => Library::Animations(LibraryAnimations),
+        V1_4::AnimationClips(_) => Library::AnimationClips(LibraryAnimationClips),
+        V1_4::Cameras(_) => Library::Cameras(LibraryCameras),
+        V1_4::Controllers(_) => Library::Controllers(LibraryControllers),
+        V1_4::Effects(_) => Library::Effects(LibraryEffects),
+        V1_4::ForceFields(library) => Library::ForceFields(library.into()),
+        V1_4::Geometries(_) => Library::Geometries(LibraryGeometries),
+        V1_4::Images(_) => Library::Images(LibraryImages),
+        V1_4::Lights(_) => Library::Lights(LibraryLights),
+        V1_4::Materials(_) => Library::Materials(LibraryMaterials),
+        V1_4::Nodes(_) => Library::Nodes(LibraryNodes),
+        V1_4::PhysicsMaterials(_) => Library::PhysicsMaterials(LibraryPhysicsMaterials),
+        V1_4::PhysicsModels(_) => Library::PhysicsModels(LibraryPhysicsModels),
+        V1_4::PhysicsScenes(_) => Library::PhysicsScenes(LibraryPhysicsScenes),
+        V1_4::VisualScenes(_) => Library::VisualScenes(LibraryVisualScenes),
+    }
 }
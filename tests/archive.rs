@@ -0,0 +1,43 @@
+#![cfg(feature = "zip")]
+
+extern crate collaborate;
+
+use ::collaborate::*;
+use std::fs::File;
+use std::io::{Cursor, Read};
+
+#[test]
+fn read_kmz() {
+    let file = File::open("resources/blender_cube.kmz").unwrap();
+    let (document, files) = archive::read_kmz(file).unwrap();
+
+    // `doc.kml` references `models/cube.dae`, so it's used instead of `models/backup.dae` even
+    // though it appears later in the archive.
+    let expected = v1_4::Collada::from_path("resources/blender_cube.dae").unwrap();
+    assert_eq!(VersionedDocument::V1_4(expected), document);
+
+    let paths: Vec<_> = files.iter().map(|(path, _)| &**path).collect();
+    assert_eq!(vec!["doc.kml", "models/backup.dae", "models/textures/cube.png"], paths);
+    assert_eq!(b"\x89PNG\r\n\x1a\n", &*files[2].1);
+}
+
+#[test]
+fn read_kmz_oversized_entry() {
+    let mut bytes = Vec::new();
+    File::open("resources/blender_cube.kmz").unwrap().read_to_end(&mut bytes).unwrap();
+
+    // Claim that every file is 4GB in the central directory, which is where the sizes are read
+    // from. The contents are read until the end of the compressed data regardless.
+    let mut offset = 0;
+    while let Some(position) = bytes[offset..].windows(4).position(|window| window == b"PK\x01\x02") {
+        let header = offset + position;
+        bytes[header + 24..header + 28].copy_from_slice(&[0xff; 4]);
+        offset = header + 4;
+    }
+
+    let (_, files) = archive::read_kmz(Cursor::new(bytes)).unwrap();
+    assert_eq!(b"\x89PNG\r\n\x1a\n", &files[2].1[..8]);
+
+    // The claimed size shouldn't be reserved up front.
+    assert!(files.iter().all(|(_, contents)| contents.capacity() < 1 << 30));
+}
@@ -0,0 +1,232 @@
+extern crate collaborate;
+extern crate serde_json;
+
+use ::collaborate::export::{gltf, obj};
+use ::collaborate::v1_4::*;
+use std::io;
+
+#[test]
+fn obj_cube() {
+    static TEST_DOCUMENT: &'static [u8] = include_bytes!("../resources/blender_cube.dae");
+
+    let document = Collada::read(TEST_DOCUMENT).unwrap();
+    let library = document.libraries[5].as_library_geometries().unwrap();
+    let mesh = library.geometries[0].geometric_element.as_mesh().unwrap();
+
+    let mut output = Vec::new();
+    obj::write_mesh(mesh, &mut output).unwrap();
+    let output = String::from_utf8(output).unwrap();
+
+    let count = |prefix: &str| output.lines().filter(|line| line.starts_with(prefix)).count();
+    assert_eq!(8, count("v "));
+    assert_eq!(12, count("vn "));
+    assert_eq!(0, count("vt "));
+    assert_eq!(12, count("f "));
+
+    let first_face = output.lines().find(|line| line.starts_with("f ")).unwrap();
+    assert_eq!("f 1//1 3//1 4//1", first_face);
+}
+
+#[test]
+fn obj_positions_only() {
+    static DOCUMENT: &'static str = r##"
+    <?xml version="1.0" encoding="utf-8"?>
+    <COLLADA xmlns="http://www.collada.org/2005/11/COLLADASchema" version="1.4.1">
+        <asset>
+            <created>2017-02-07T20:44:30Z</created>
+            <modified>2017-02-07T20:44:30Z</modified>
+        </asset>
+        <library_geometries>
+            <geometry id="Quad-mesh">
+                <mesh>
+                    <source id="Quad-mesh-positions">
+                        <float_array id="Quad-mesh-positions-array" count="12">0 0 0 1 0 0 1 1 0 0 1 0</float_array>
+                        <technique_common>
+                            <accessor source="#Quad-mesh-positions-array" count="4" stride="3">
+                                <param name="X" type="float"/>
+                                <param name="Y" type="float"/>
+                                <param name="Z" type="float"/>
+                            </accessor>
+                        </technique_common>
+                    </source>
+                    <vertices id="Quad-mesh-vertices">
+                        <input semantic="POSITION" source="#Quad-mesh-positions"/>
+                    </vertices>
+                    <polylist count="1">
+                        <input semantic="VERTEX" source="#Quad-mesh-vertices" offset="0"/>
+                        <vcount>4</vcount>
+                        <p>0 1 2 3</p>
+                    </polylist>
+                </mesh>
+            </geometry>
+        </library_geometries>
+    </COLLADA>
+    "##;
+
+    let document = Collada::from_str(DOCUMENT).unwrap();
+    let library = document.libraries[0].as_library_geometries().unwrap();
+    let mesh = library.geometries[0].geometric_element.as_mesh().unwrap();
+
+    let mut output = Vec::new();
+    obj::write_mesh(mesh, &mut output).unwrap();
+    let output = String::from_utf8(output).unwrap();
+
+    let expected = "v 0 0 0\nv 1 0 0\nv 1 1 0\nv 0 1 0\nf 1 2 3\nf 1 3 4\n";
+    assert_eq!(expected, output);
+    // Indices past the end of the position source are reported rather than written.
+    let document = Collada::from_str(&DOCUMENT.replace("<p>0 1 2 3</p>", "<p>0 1 2 7</p>")).unwrap();
+    let library = document.libraries[0].as_library_geometries().unwrap();
+    let mesh = library.geometries[0].geometric_element.as_mesh().unwrap();
+    let error = obj::write_mesh(mesh, Vec::new()).unwrap_err();
+    assert_eq!(io::ErrorKind::InvalidData, error.kind());
+}
+
+#[test]
+fn export_triangles() {
+    static DOCUMENT: &'static str = r##"
+    <?xml version="1.0" encoding="utf-8"?>
+    <COLLADA xmlns="http://www.collada.org/2005/11/COLLADASchema" version="1.4.1">
+        <asset>
+            <created>2017-02-07T20:44:30Z</created>
+            <modified>2017-02-07T20:44:30Z</modified>
+        </asset>
+        <library_geometries>
+            <geometry id="Quad-mesh">
+                <mesh>
+                    <source id="Quad-mesh-positions">
+                        <float_array id="Quad-mesh-positions-array" count="12">0 0 0 1 0 0 1 1 0 0 1 0</float_array>
+                        <technique_common>
+                            <accessor source="#Quad-mesh-positions-array" count="4" stride="3">
+                                <param name="X" type="float"/>
+                                <param name="Y" type="float"/>
+                                <param name="Z" type="float"/>
+                            </accessor>
+                        </technique_common>
+                    </source>
+                    <vertices id="Quad-mesh-vertices">
+                        <input semantic="POSITION" source="#Quad-mesh-positions"/>
+                    </vertices>
+                    <triangles count="2">
+                        <input semantic="VERTEX" source="#Quad-mesh-vertices" offset="0"/>
+                        <p>0 1 2 0 2 3</p>
+                    </triangles>
+                </mesh>
+            </geometry>
+        </library_geometries>
+    </COLLADA>
+    "##;
+
+    let document = Collada::from_str(DOCUMENT).unwrap();
+    let library = document.libraries[0].as_library_geometries().unwrap();
+    let mesh = library.geometries[0].geometric_element.as_mesh().unwrap();
+
+    let mut output = Vec::new();
+    obj::write_mesh(mesh, &mut output).unwrap();
+    let output = String::from_utf8(output).unwrap();
+    assert_eq!("v 0 0 0\nv 1 0 0\nv 1 1 0\nv 0 1 0\nf 1 2 3\nf 1 3 4\n", output);
+
+    let output = gltf::to_gltf(&document).unwrap();
+    let json: serde_json::Value = serde_json::from_str(&output.json).unwrap();
+    let primitive = &json["meshes"][0]["primitives"][0];
+    assert_eq!(6, json["accessors"][primitive["indices"].as_u64().unwrap() as usize]["count"].as_u64().unwrap());
+    let accessor = &json["accessors"][primitive["attributes"]["POSITION"].as_u64().unwrap() as usize];
+    assert_eq!(4, accessor["count"].as_u64().unwrap());
+
+    // Indices past the end of the position source are reported rather than exported.
+    let document = Collada::from_str(&DOCUMENT.replace("<p>0 1 2 0 2 3</p>", "<p>0 1 2 0 2 9</p>")).unwrap();
+    assert_eq!(
+        gltf::Error::IndexOutOfRange {
+            geometry: Some("Quad-mesh".into()),
+            semantic: "POSITION",
+            index: 9,
+        },
+        gltf::to_gltf(&document).unwrap_err(),
+    );
+}
+
+#[test]
+fn gltf_materials_only() {
+    static DOCUMENT: &'static str = r##"
+    <?xml version="1.0" encoding="utf-8"?>
+    <COLLADA xmlns="http://www.collada.org/2005/11/COLLADASchema" version="1.4.1">
+        <asset>
+            <created>2017-02-07T20:44:30Z</created>
+            <modified>2017-02-07T20:44:30Z</modified>
+        </asset>
+        <library_effects>
+            <effect id="broken-effect">
+                <profile_COMMON>
+                    <technique sid="common">
+                        <phong>
+                            <diffuse>
+                                <color>NaN 0.5 inf 1</color>
+                            </diffuse>
+                            <shininess>
+                                <float>NaN</float>
+                            </shininess>
+                        </phong>
+                    </technique>
+                </profile_COMMON>
+            </effect>
+        </library_effects>
+        <library_materials>
+            <material id="broken">
+                <instance_effect url="#broken-effect"/>
+            </material>
+        </library_materials>
+    </COLLADA>
+    "##;
+
+    // NaN and infinity can't be written to JSON, so the defaults are used instead.
+    let document = Collada::from_str(DOCUMENT).unwrap();
+    let output = gltf::to_gltf(&document).unwrap();
+    let json: serde_json::Value = serde_json::from_str(&output.json).unwrap();
+    let material = &json["materials"][0]["pbrMetallicRoughness"];
+    assert_eq!(serde_json::json!([1, 1, 1, 1]), material["baseColorFactor"]);
+    assert_eq!(1.0, material["roughnessFactor"].as_f64().unwrap());
+
+    // glTF doesn't allow empty arrays, and there's no data to put in a buffer.
+    assert!(output.buffer.is_empty());
+    for name in &["nodes", "meshes", "accessors", "bufferViews", "buffers"] {
+        assert!(json.get(name).is_none(), "{} should be left out", name);
+    }
+    assert_eq!(serde_json::json!([{}]), json["scenes"]);
+}
+
+#[test]
+fn gltf_cube() {
+    static TEST_DOCUMENT: &'static [u8] = include_bytes!("../resources/blender_cube.dae");
+
+    let document = Collada::read(TEST_DOCUMENT).unwrap();
+    let output = gltf::to_gltf(&document).unwrap();
+    let json: serde_json::Value = serde_json::from_str(&output.json).unwrap();
+
+    assert_eq!(1, json["meshes"].as_array().unwrap().len());
+    assert_eq!(output.buffer.len() as u64, json["buffers"][0]["byteLength"].as_u64().unwrap());
+
+    let primitive = &json["meshes"][0]["primitives"][0];
+    assert_eq!(0, primitive["material"].as_u64().unwrap());
+    assert_eq!(36, json["accessors"][primitive["indices"].as_u64().unwrap() as usize]["count"].as_u64().unwrap());
+
+    // Blender gives each triangle its own normal index, so none of the 36 triangle corners can
+    // share a vertex. Those vertices still only cover the cube's 8 unique positions, though.
+    let accessor = &json["accessors"][primitive["attributes"]["POSITION"].as_u64().unwrap() as usize];
+    assert_eq!(36, accessor["count"].as_u64().unwrap());
+    assert!(primitive["attributes"]["NORMAL"].is_u64());
+
+    let view = &json["bufferViews"][accessor["bufferView"].as_u64().unwrap() as usize];
+    let offset = view["byteOffset"].as_u64().unwrap() as usize;
+    let length = view["byteLength"].as_u64().unwrap() as usize;
+    let mut positions = output.buffer[offset..offset + length]
+        .chunks(12)
+        .map(|position| position.to_vec())
+        .collect::<Vec<_>>();
+    positions.sort();
+    positions.dedup();
+    assert_eq!(8, positions.len());
+
+    let material = &json["materials"][0];
+    assert_eq!("Material", material["name"].as_str().unwrap());
+    let base_color = material["pbrMetallicRoughness"]["baseColorFactor"].as_array().unwrap();
+    assert!((base_color[0].as_f64().unwrap() - 0.64).abs() < 1e-6);
+}
@@ -88,3 +88,506 @@ fn float_array_text_contents() {
 
     assert_eq!(EXPECTED, &*array.data, "`<float_array>` contents were not parsed correctly");
 }
+
+#[test]
+fn utf8_bom() {
+    static DOCUMENT: &'static str = "\u{FEFF}<?xml version=\"1.0\" encoding=\"utf-8\"?>
+    <COLLADA xmlns=\"http://www.collada.org/2005/11/COLLADASchema\" version=\"1.4.1\">
+        <asset>
+            <created>2017-02-07T20:44:30Z</created>
+            <modified>2017-02-07T20:44:30Z</modified>
+        </asset>
+    </COLLADA>
+    ";
+
+    let _ = VersionedDocument::from_str(DOCUMENT).unwrap();
+    let _ = VersionedDocument::read(DOCUMENT.as_bytes()).unwrap();
+    let _ = v1_4::Collada::from_str(DOCUMENT).unwrap();
+    let _ = v1_4::Collada::read(DOCUMENT.as_bytes()).unwrap();
+}
+
+#[test]
+fn datetime_utc() {
+    use ::collaborate::chrono::{FixedOffset, TimeZone};
+    use ::collaborate::common::DateTime;
+
+    let expected = DateTime::Utc(FixedOffset::east(0).ymd(2017, 2, 7).and_hms(20, 44, 30));
+    assert_eq!(expected, "2017-02-07T20:44:30Z".parse().unwrap());
+}
+
+#[test]
+fn datetime_fractional_seconds() {
+    use ::collaborate::chrono::{FixedOffset, TimeZone};
+    use ::collaborate::common::DateTime;
+
+    let expected = DateTime::Utc(FixedOffset::east(0).ymd(2017, 2, 7).and_hms_milli(20, 44, 30, 250));
+    assert_eq!(expected, "2017-02-07T20:44:30.250Z".parse().unwrap());
+
+    let expected = DateTime::Utc(FixedOffset::east(0).ymd(2017, 2, 7).and_hms_milli(20, 44, 30, 500));
+    assert_eq!(expected, "2017-02-07T20:44:30.5Z".parse().unwrap());
+}
+
+#[test]
+fn datetime_offset() {
+    use ::collaborate::chrono::{FixedOffset, TimeZone};
+    use ::collaborate::common::DateTime;
+
+    let expected = DateTime::Utc(FixedOffset::east(2 * 3600).ymd(2017, 2, 7).and_hms(20, 44, 30));
+    assert_eq!(expected, "2017-02-07T20:44:30+02:00".parse().unwrap());
+}
+
+#[test]
+fn datetime_naive() {
+    use ::collaborate::chrono::NaiveDate;
+    use ::collaborate::common::DateTime;
+
+    let expected = DateTime::Naive(NaiveDate::from_ymd(2017, 2, 7).and_hms(20, 44, 30));
+    assert_eq!(expected, "2017-02-07T20:44:30".parse().unwrap());
+
+    let expected = DateTime::Naive(NaiveDate::from_ymd(2017, 2, 7).and_hms_milli(20, 44, 30, 250));
+    assert_eq!(expected, "2017-02-07T20:44:30.250".parse().unwrap());
+}
+
+#[test]
+fn up_axis_round_trip() {
+    use ::collaborate::common::UpAxis;
+
+    for &axis in &[UpAxis::X, UpAxis::Y, UpAxis::Z] {
+        assert_eq!(Ok(axis), axis.to_string().parse());
+    }
+    assert_eq!("Z_UP", UpAxis::Z.to_string());
+
+    let expected = ErrorKind::InvalidValue {
+        element: "up_axis",
+        value: "z_up".into(),
+    };
+    assert_eq!(Err(expected), "z_up".parse::<UpAxis>());
+}
+
+#[test]
+fn uri_fragment_round_trip() {
+    use ::collaborate::common::{UriFragment, UriFragmentParseError};
+
+    assert_eq!("#Cube", UriFragment::new("Cube").to_string());
+    assert_eq!(UriFragment::new("Cube"), UriFragment::new("#Cube"));
+
+    let parsed = "#Cube".parse::<UriFragment>().unwrap();
+    assert_eq!("Cube", parsed.id());
+    assert_eq!(UriFragment::new("Cube"), parsed);
+    assert_eq!(Err(UriFragmentParseError), "Cube".parse::<UriFragment>());
+}
+
+#[test]
+fn up_axis_conversion_z_to_y() {
+    use ::collaborate::common::UpAxis;
+
+    let matrix = UpAxis::conversion_matrix(UpAxis::Z, UpAxis::Y);
+    let transform = |vector: [f64; 3]| {
+        let mut result = [0.0; 3];
+        for row in 0..3 {
+            result[row] = (0..3).map(|column| matrix[column * 4 + row] * vector[column]).sum();
+        }
+        result
+    };
+
+    // Up stays up, and the Z-up "in" axis (negative Y) becomes the Y-up "in" axis (positive Z).
+    assert_eq!([0.0, 1.0, 0.0], transform([0.0, 0.0, 1.0]));
+    assert_eq!([0.0, 0.0, 1.0], transform([0.0, -1.0, 0.0]));
+    assert_eq!([1.0, 0.0, 0.0], transform([1.0, 0.0, 0.0]));
+    assert_eq!([0.0, 0.0, 0.0, 1.0], matrix[12..16]);
+}
+
+#[test]
+fn up_axis_conversion_identity() {
+    use ::collaborate::common::UpAxis;
+
+    static IDENTITY: [f64; 16] = [
+        1.0, 0.0, 0.0, 0.0,
+        0.0, 1.0, 0.0, 0.0,
+        0.0, 0.0, 1.0, 0.0,
+        0.0, 0.0, 0.0, 1.0,
+    ];
+
+    for &axis in &[UpAxis::X, UpAxis::Y, UpAxis::Z] {
+        assert_eq!(IDENTITY, UpAxis::conversion_matrix(axis, axis));
+    }
+
+    let document = v1_4::Collada::read(::std::fs::File::open("resources/blender_cube.dae").unwrap()).unwrap();
+    assert_eq!(IDENTITY, document.up_axis_to(document.asset.up_axis.unwrap()));
+}
+
+#[test]
+fn unit_scale() {
+    use ::collaborate::common::Unit;
+
+    let centimeter = Unit { meter: 0.01, name: "centimeter".into() };
+    let foot = Unit { meter: 0.3048, name: "foot".into() };
+
+    assert_eq!(0.01, centimeter.to_meters());
+    assert_eq!(0.01, centimeter.scale_to(&Unit::default()));
+    assert!((centimeter.scale_to(&foot) - 0.01 / 0.3048).abs() < 1e-12);
+    assert!((foot.scale_to(&centimeter) - 30.48).abs() < 1e-12);
+}
+
+#[test]
+fn color_components() {
+    use ::collaborate::common::Color;
+
+    static DOCUMENT: &'static str = r#"
+    <COLLADA xmlns="http://www.collada.org/2005/11/COLLADASchema" version="1.4.1">
+        <asset>
+            <created>2017-02-07T20:44:30Z</created>
+            <modified>2017-02-07T20:44:30Z</modified>
+        </asset>
+        <extra>
+            <technique profile="rgb"><color>1 0 0</color></technique>
+            <technique profile="rgba"><color sid="diffuse">0.5 0.5 0.5 0.25</color></technique>
+            <technique profile="too_short"><color>1 0</color></technique>
+            <technique profile="too_long"><color>1 0 0 1 0</color></technique>
+        </extra>
+    </COLLADA>
+    "#;
+
+    let document = v1_4::Collada::from_str(DOCUMENT).unwrap();
+    let techniques = &document.extras[0].techniques;
+    let color = |index: usize| techniques[index].reparse_child::<Color>("color").unwrap();
+
+    // A color with only RGB components is fully opaque.
+    assert_eq!(Color { sid: None, rgba: [1.0, 0.0, 0.0, 1.0] }, color(0).unwrap());
+    assert_eq!(
+        Color { sid: Some("diffuse".into()), rgba: [0.5, 0.5, 0.5, 0.25] },
+        color(1).unwrap(),
+    );
+
+    for &(index, text) in &[(2, "1 0"), (3, "1 0 0 1 0")] {
+        let expected = ErrorKind::InvalidValue {
+            element: "color",
+            value: text.into(),
+        };
+        assert_eq!(expected, color(index).unwrap_err().kind);
+    }
+}
+
+#[test]
+fn float_values() {
+    use ::collaborate::common::{Float2, Float3, Float4, Float4x4, FloatValue};
+
+    static DOCUMENT: &'static str = r#"
+    <COLLADA xmlns="http://www.collada.org/2005/11/COLLADASchema" version="1.4.1">
+        <asset>
+            <created>2017-02-07T20:44:30Z</created>
+            <modified>2017-02-07T20:44:30Z</modified>
+        </asset>
+        <extra>
+            <technique profile="values">
+                <float sid="shininess">20</float>
+                <float2>1 2</float2>
+                <float3 sid="offset">1 2 3</float3>
+                <float4>1 2 3 4</float4>
+                <float4x4>1 0 0 0 0 1 0 0 0 0 1 0 0 0 0 1</float4x4>
+            </technique>
+            <technique profile="too_long">
+                <float3>1 2 3 4</float3>
+            </technique>
+        </extra>
+    </COLLADA>
+    "#;
+
+    let document = v1_4::Collada::from_str(DOCUMENT).unwrap();
+    let technique = &document.extras[0].techniques[0];
+
+    assert_eq!(
+        FloatValue { sid: Some("shininess".into()), value: 20.0 },
+        technique.reparse_child("float").unwrap().unwrap(),
+    );
+    assert_eq!(
+        Float2 { sid: None, data: [1.0, 2.0] },
+        technique.reparse_child("float2").unwrap().unwrap(),
+    );
+    assert_eq!(
+        Float3 { sid: Some("offset".into()), data: [1.0, 2.0, 3.0] },
+        technique.reparse_child("float3").unwrap().unwrap(),
+    );
+    assert_eq!(
+        Float4 { sid: None, data: [1.0, 2.0, 3.0, 4.0] },
+        technique.reparse_child("float4").unwrap().unwrap(),
+    );
+
+    let matrix: Float4x4 = technique.reparse_child("float4x4").unwrap().unwrap();
+    assert_eq!(
+        [1.0, 0.0, 0.0, 0.0, 0.0, 1.0, 0.0, 0.0, 0.0, 0.0, 1.0, 0.0, 0.0, 0.0, 0.0, 1.0],
+        matrix.data,
+    );
+
+    let technique = &document.extras[0].techniques[1];
+    let error = technique.reparse_child::<Float3>("float3").unwrap().unwrap_err();
+    let expected = ErrorKind::InvalidValue {
+        element: "float3",
+        value: "1 2 3 4".into(),
+    };
+    assert_eq!(expected, error.kind);
+}
+
+/// A reader that returns the first `remaining` bytes of `data` and then fails.
+struct FailingReader<'a> {
+    data: &'a [u8],
+    remaining: usize,
+}
+
+impl<'a> ::std::io::Read for FailingReader<'a> {
+    fn read(&mut self, buf: &mut [u8]) -> ::std::io::Result<usize> {
+        if self.remaining == 0 {
+            return Err(::std::io::Error::new(::std::io::ErrorKind::ConnectionReset, "connection reset"));
+        }
+
+        let count = buf.len().min(self.remaining).min(self.data.len());
+        buf[..count].copy_from_slice(&self.data[..count]);
+        self.data = &self.data[count..];
+        self.remaining -= count;
+        Ok(count)
+    }
+}
+
+/// An in-memory reader that returns at most `chunk_size` bytes from each call to `read`.
+struct ChunkedReader<'a> {
+    data: &'a [u8],
+    chunk_size: usize,
+}
+
+impl<'a> ::std::io::Read for ChunkedReader<'a> {
+    fn read(&mut self, buf: &mut [u8]) -> ::std::io::Result<usize> {
+        let count = buf.len().min(self.chunk_size).min(self.data.len());
+        buf[..count].copy_from_slice(&self.data[..count]);
+        self.data = &self.data[count..];
+        Ok(count)
+    }
+}
+
+#[test]
+fn custom_reader() {
+    let source = ::std::fs::read("resources/blender_cube.dae").unwrap();
+    let expected = v1_4::Collada::from_path("resources/blender_cube.dae").unwrap();
+
+    // Small chunks split the document, and the byte-order mark check, across many reads.
+    for &chunk_size in &[1, 7, 4096] {
+        let reader = ChunkedReader { data: &source, chunk_size };
+        assert_eq!(expected, v1_4::Collada::read(reader).unwrap());
+
+        let reader = ChunkedReader { data: &source, chunk_size };
+        match VersionedDocument::read(reader).unwrap() {
+            VersionedDocument::V1_4(document) => assert_eq!(expected, document),
+            other => panic!("Expected a 1.4 document, got {:?}", other),
+        }
+    }
+}
+
+#[test]
+fn io_error() {
+    static DOCUMENT: &'static str = r#"
+    <?xml version="1.0" encoding="utf-8"?>
+    <COLLADA xmlns="http://www.collada.org/2005/11/COLLADASchema" version="1.4.1">
+        <asset>
+            <created>2017-02-07T20:44:30Z</created>
+            <modified>2017-02-07T20:44:30Z</modified>
+        </asset>
+    </COLLADA>
+    "#;
+
+    // Fail both before and after the byte-order mark check.
+    for &remaining in &[0, 100] {
+        let reader = FailingReader { data: DOCUMENT.as_bytes(), remaining };
+        let error = VersionedDocument::read(reader).unwrap_err();
+        match error.kind {
+            ErrorKind::Io(ref io_error) => {
+                assert_eq!(::std::io::ErrorKind::ConnectionReset, io_error.kind());
+                assert_eq!("connection reset", io_error.to_string());
+            }
+
+            _ => panic!("Expected an IO error, got {:?}", error),
+        }
+        assert!(error.to_string().contains("Failed to read the document: connection reset"));
+    }
+}
+
+#[test]
+fn error_source() {
+    static DOCUMENT: &'static str = r#"
+    <?xml version="1.0" encoding="utf-8"?>
+    <COLLADA xmlns="http://www.collada.org/2005/11/COLLADASchema" version="1.4.1">
+        <asset>
+            <created>2017-02-07T20:44:30Z</created>
+            <modified>2017-02-07T20:44:30Z</modified>
+            <unit meter="one" />
+        </asset>
+    </COLLADA>
+    "#;
+
+    fn parse() -> ::std::result::Result<v1_4::Collada, Box<dyn std::error::Error>> {
+        Ok(v1_4::Collada::from_str(DOCUMENT)?)
+    }
+
+    let error = parse().unwrap_err();
+    let source = error.source().expect("Parse errors should have a source");
+    assert!(source.is::<::std::num::ParseFloatError>());
+    assert_eq!("invalid float literal", source.to_string());
+
+    let error = v1_4::Collada::from_str("<COLLADA").unwrap_err();
+    assert!(std::error::Error::source(&error).unwrap().is::<XmlError>());
+
+    let error = v1_4::Collada::from_str(r#"<COLLADA version="1.4.1"><foo /></COLLADA>"#).unwrap_err();
+    assert!(std::error::Error::source(&error).is_none());
+}
+
+#[test]
+fn detect_version_only() {
+    static DOCUMENT: &'static str = r#"
+    <?xml version="1.0" encoding="utf-8"?>
+    <COLLADA xmlns="http://www.collada.org/2005/11/COLLADASchema" version=" 1.4.1 ">
+        <asset>
+            <created>2017-02-07T20:44:30Z</created>
+            <modified>2017-02-07T20:44:30Z</modified>
+        </asset>
+    </COLLADA>
+    "#;
+
+    assert_eq!("1.4.1", detect_version(DOCUMENT.as_bytes()).unwrap());
+
+    // Nothing past the opening tag is read, so a broken document body isn't an error.
+    static UNFINISHED: &'static str = r#"<COLLADA version="1.5.0"><asset><created>"#;
+    assert_eq!("1.5.0", detect_version(UNFINISHED.as_bytes()).unwrap());
+
+    static NO_VERSION: &'static str = r#"<COLLADA><asset /></COLLADA>"#;
+    let error = detect_version(NO_VERSION.as_bytes()).unwrap_err();
+    match error.kind {
+        ErrorKind::MissingAttribute { element: "COLLADA", attribute: "version" } => {}
+        _ => panic!("Unexpected error: {:?}", error),
+    }
+}
+
+#[test]
+fn namespace_prefixed_root() {
+    static DOCUMENT: &'static str = r#"
+    <?xml version="1.0" encoding="utf-8"?>
+    <c:COLLADA xmlns:c="http://www.collada.org/2005/11/COLLADASchema" version="1.4.1">
+        <c:asset>
+            <c:created>2017-02-07T20:44:30Z</c:created>
+            <c:modified>2017-02-07T20:44:30Z</c:modified>
+            <c:up_axis>Z_UP</c:up_axis>
+        </c:asset>
+    </c:COLLADA>
+    "#;
+
+    assert_eq!("1.4.1", detect_version(DOCUMENT.as_bytes()).unwrap());
+
+    let document = v1_4::Collada::from_str(DOCUMENT).unwrap();
+    assert_eq!("1.4.1", document.version);
+    assert_eq!(Some(common::UpAxis::Z), document.asset.up_axis);
+
+    // The root element is in the COLLADA namespace whether or not it's bound to a prefix.
+    assert_eq!(Some("http://www.collada.org/2005/11/COLLADASchema"), document.schema_namespace());
+
+    let unprefixed = DOCUMENT.replace("c:", "").replace("xmlns:c", "xmlns");
+    assert_eq!(v1_4::Collada::from_str(&unprefixed).unwrap(), document);
+
+    // Some exporters also put the version attribute in the COLLADA namespace.
+    let prefixed_version = DOCUMENT.replace("version=\"1.4.1\"", "c:version=\"1.4.1\"");
+    match VersionedDocument::from_str(&prefixed_version).unwrap() {
+        VersionedDocument::V1_4(document) => assert_eq!("1.4.1", document.version),
+        VersionedDocument::V1_5(_) => panic!("Expected a 1.4 document"),
+    }
+}
+
+#[test]
+fn versioned_document_parse() {
+    let source = ::std::fs::read_to_string("resources/v1_5_minimal.dae").unwrap();
+    let document: VersionedDocument = source.parse().unwrap();
+    assert_eq!("1.5.0", document.version());
+}
+
+#[test]
+fn invalid_number_token() {
+    static DOCUMENT: &'static str = r##"
+    <?xml version="1.0" encoding="utf-8"?>
+    <COLLADA xmlns="http://www.collada.org/2005/11/COLLADASchema" version="1.4.1">
+        <asset>
+            <created>2017-02-07T20:44:30Z</created>
+            <modified>2017-02-07T20:44:30Z</modified>
+        </asset>
+        <library_geometries>
+            <geometry>
+                <mesh>
+                    <source id="positions">
+                        <float_array count="3">0 1.5x 1</float_array>
+                    </source>
+                    <vertices id="vertices">
+                        <input semantic="POSITION" source="#positions" />
+                    </vertices>
+                </mesh>
+            </geometry>
+        </library_geometries>
+    </COLLADA>
+    "##;
+
+    let error = v1_4::Collada::from_str(DOCUMENT).unwrap_err();
+    match error.kind {
+        ErrorKind::InvalidNumber { element, ref token, ref error } => {
+            assert_eq!("float_array", element);
+            assert_eq!("1.5x", token);
+            match **error {
+                ErrorKind::ParseFloatError(_) => {}
+                ref kind => panic!("Expected a float parsing error, got {:?}", kind),
+            }
+        }
+
+        ref kind => panic!("Expected an invalid number error, got {:?}", kind),
+    }
+
+    assert!(error.to_string().contains("<float_array> contained an invalid number \"1.5x\""));
+    assert!(std::error::Error::source(&error).unwrap().is::<::std::num::ParseFloatError>());
+}
+
+#[test]
+fn error_category() {
+    let parse_float = "1.5x".parse::<f64>().unwrap_err();
+    assert_eq!(ErrorCategory::Value, ErrorKind::from(parse_float).category());
+
+    let invalid_value = ErrorKind::InvalidValue { element: "up_axis", value: "W_UP".into() };
+    assert_eq!(ErrorCategory::Value, invalid_value.category());
+
+    let unexpected_element = ErrorKind::UnexpectedElement {
+        parent: "COLLADA",
+        element: "library_everything".into(),
+        expected: vec!["asset"],
+    };
+    assert_eq!(ErrorCategory::Structure, unexpected_element.category());
+
+    let error = VersionedDocument::from_str("<COLLADA version=\"2.0\"></COLLADA>").unwrap_err();
+    assert_eq!(ErrorCategory::Version, error.kind.category());
+
+    let error = VersionedDocument::from_str("<COLLADA version=\"1.4.1\">").unwrap_err();
+    assert_eq!(ErrorCategory::Xml, error.kind.category());
+}
+
+#[test]
+fn non_collada_input() {
+    // Empty and plain text input are rejected by the XML parser rather than panicking.
+    for input in &["", "   \n", "hello world", "\u{0}\u{1}binary", "<?xml version=\"1.0\"?>"] {
+        let error = VersionedDocument::from_str(input).unwrap_err();
+        assert_eq!(ErrorCategory::Xml, error.kind.category(), "{:?}", input);
+        assert!(detect_version(input.as_bytes()).is_err());
+    }
+
+    // Processing instructions may appear before the root element.
+    static DOCUMENT: &'static str = r#"<?xml version="1.0" encoding="utf-8"?>
+    <?xml-stylesheet href="style.css"?>
+    <COLLADA xmlns="http://www.collada.org/2005/11/COLLADASchema" version="1.4.1">
+        <asset>
+            <created>2017-02-07T20:44:30Z</created>
+            <modified>2017-02-07T20:44:30Z</modified>
+        </asset>
+    </COLLADA>
+    "#;
+
+    assert_eq!("1.4.1", detect_version(DOCUMENT.as_bytes()).unwrap());
+    let _ = VersionedDocument::from_str(DOCUMENT).unwrap();
+}
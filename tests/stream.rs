@@ -0,0 +1,85 @@
+extern crate collaborate;
+
+use ::collaborate::stream::{self, Visitor};
+use ::collaborate::v1_4::*;
+use std::fs::File;
+
+#[derive(Default)]
+struct Counter {
+    assets: usize,
+    sources: usize,
+    geometries: Vec<Option<String>>,
+    nodes: usize,
+    libraries: usize,
+}
+
+impl Visitor for Counter {
+    fn visit_asset(&mut self, _: &Asset) {
+        self.assets += 1;
+    }
+
+    fn visit_source(&mut self, _: &Source) {
+        self.sources += 1;
+    }
+
+    fn visit_geometry(&mut self, geometry: &Geometry) {
+        self.geometries.push(geometry.id.clone());
+    }
+
+    fn visit_node(&mut self, _: &Node) {
+        self.nodes += 1;
+    }
+
+    fn visit_library(&mut self, _: &Library) {
+        self.libraries += 1;
+    }
+}
+
+#[test]
+fn visit_blender_cube() {
+    let file = File::open("resources/blender_cube.dae").unwrap();
+    let mut counter = Counter::default();
+    stream::visit(file, &mut counter).unwrap();
+
+    let collada = Collada::read(File::open("resources/blender_cube.dae").unwrap()).unwrap();
+    let geometries: Vec<_> = collada.libraries()
+        .filter_map(Library::as_library_geometries)
+        .flat_map(|library| library.geometries.iter())
+        .collect();
+    let visual_scenes: Vec<_> = collada.libraries()
+        .filter_map(Library::as_library_visual_scenes)
+        .flat_map(|library| library.visual_scenes.iter())
+        .collect();
+
+    assert_eq!(1, counter.assets);
+    assert_eq!(
+        geometries.iter().map(|geometry| geometry.id.clone()).collect::<Vec<_>>(),
+        counter.geometries,
+    );
+    let sources = geometries.iter()
+        .map(|geometry| geometry.geometric_element.as_mesh().unwrap().sources.len())
+        .sum::<usize>();
+    assert_eq!(sources, counter.sources);
+    assert_eq!(
+        visual_scenes.iter().map(|scene| scene.nodes.len()).sum::<usize>(),
+        counter.nodes,
+    );
+    assert_eq!(6, counter.libraries);
+}
+
+#[test]
+fn visit_wrong_version() {
+    static DOCUMENT: &'static str = r#"
+    <?xml version="1.0" encoding="utf-8"?>
+    <COLLADA xmlns="http://www.collada.org/2005/11/COLLADASchema" version="1.5.0">
+        <asset>
+            <created>2017-02-07T20:44:30Z</created>
+            <modified>2017-02-07T20:44:30Z</modified>
+        </asset>
+    </COLLADA>
+    "#;
+
+    let mut counter = Counter::default();
+    assert!(stream::visit(DOCUMENT.as_bytes(), &mut counter).is_err());
+    assert_eq!(0, counter.assets);
+}
@@ -12,6 +12,30 @@ fn blender_cube() {
     let _ = Collada::from_str(&*document).unwrap();
 }
 
+#[test]
+fn blender_cube_from_path() {
+    static TEST_DOCUMENT: &'static [u8] = include_bytes!("../resources/blender_cube.dae");
+
+    let from_path = Collada::from_path("resources/blender_cube.dae").unwrap();
+    let from_bytes = Collada::from_bytes(TEST_DOCUMENT).unwrap();
+    assert_eq!(from_bytes, from_path);
+
+    match VersionedDocument::from_path("resources/blender_cube.dae").unwrap() {
+        VersionedDocument::V1_4(document) => assert_eq!(from_path, document),
+        _ => panic!("blender_cube.dae wasn't parsed as 1.4.1"),
+    }
+}
+
+#[test]
+fn from_path_missing_file() {
+    let error = Collada::from_path("resources/does_not_exist.dae").unwrap_err();
+    assert_eq!(TextPosition { row: 0, column: 0 }, error.position);
+    match error.kind {
+        ErrorKind::Io(ref error) => assert_eq!(::std::io::ErrorKind::NotFound, error.kind()),
+        _ => panic!("Expected an IO error, got {:?}", error),
+    }
+}
+
 #[test]
 fn collada_asset_minimal() {
     static DOCUMENT: &'static str = r#"
@@ -27,7 +51,7 @@ fn collada_asset_minimal() {
 
     let expected = Collada {
         version: "1.4.1".into(),
-        xmlns: None,
+        xmlns: Some("http://www.collada.org/2005/11/COLLADASchema".into()),
         base_uri: None,
         asset: Asset {
             contributors: vec![],
@@ -37,11 +61,8 @@ fn collada_asset_minimal() {
             revision: None,
             subject: None,
             title: None,
-            unit: Unit {
-                meter: 1.0,
-                name: "meter".into(),
-            },
-            up_axis: UpAxis::Y,
+            unit: None,
+            up_axis: None,
         },
         libraries: Vec::new(),
         scene: None,
@@ -52,6 +73,77 @@ fn collada_asset_minimal() {
     assert_eq!(expected, actual);
 }
 
+#[test]
+fn collada_version_whitespace() {
+    static DOCUMENT: &'static str = r#"
+    <?xml version="1.0" encoding="utf-8"?>
+    <COLLADA xmlns="http://www.collada.org/2005/11/COLLADASchema" version=" 1.4.1 ">
+        <asset>
+            <created>2017-02-07T20:44:30Z</created>
+            <modified>2017-02-07T20:44:30Z</modified>
+        </asset>
+    </COLLADA>
+    "#;
+
+    let _ = Collada::from_str(DOCUMENT).unwrap();
+    match VersionedDocument::from_str(DOCUMENT).unwrap() {
+        VersionedDocument::V1_4(_) => {}
+        _ => panic!("Document with padded version wasn't parsed as 1.4.1"),
+    }
+}
+
+#[test]
+fn versioned_document_version() {
+    static DOCUMENT: &'static str = r#"
+    <?xml version="1.0" encoding="utf-8"?>
+    <COLLADA xmlns="http://www.collada.org/2005/11/COLLADASchema" version="1.4.1">
+        <asset>
+            <created>2017-02-07T20:44:30Z</created>
+            <modified>2017-02-07T20:44:30Z</modified>
+        </asset>
+    </COLLADA>
+    "#;
+
+    let document = VersionedDocument::from_str(DOCUMENT).unwrap();
+    assert_eq!("1.4.1", document.version());
+    assert_eq!(Some((1, 4, 1)), document.version_number());
+    assert!(document.is_1_4());
+    assert!(!document.is_1_5());
+
+    // 1.4.0 documents are parsed as 1.4, but still report the version they declared.
+    let document = VersionedDocument::from_str(&DOCUMENT.replace("1.4.1", " 1.4.0 ")).unwrap();
+    assert_eq!("1.4.0", document.version());
+    assert_eq!(Some((1, 4, 0)), document.version_number());
+    assert!(document.is_1_4());
+}
+
+#[test]
+fn collada_missing_version() {
+    static DOCUMENT: &'static str = r#"
+    <?xml version="1.0" encoding="utf-8"?>
+    <COLLADA
+        xmlns="http://www.collada.org/2005/11/COLLADASchema">
+        <!-- The error should point at the opening tag, not at this comment. -->
+        <asset>
+            <created>2017-02-07T20:44:30Z</created>
+            <modified>2017-02-07T20:44:30Z</modified>
+        </asset>
+    </COLLADA>
+    "#;
+
+    let expected = Error {
+        position: TextPosition { row: 2, column: 4 },
+        kind: ErrorKind::MissingAttribute {
+            element: "COLLADA".into(),
+            attribute: "version".into()
+        },
+    };
+
+    assert_eq!(expected, Collada::from_str(DOCUMENT).unwrap_err());
+    assert_eq!(expected, VersionedDocument::from_str(DOCUMENT).unwrap_err());
+    assert_eq!(expected, detect_version(DOCUMENT.as_bytes()).unwrap_err());
+}
+
 #[test]
 fn collada_missing_asset() {
     static DOCUMENT: &'static str = r#"
@@ -101,11 +193,11 @@ fn asset_full() {
         revision: Some("7".into()),
         subject: Some("A thing".into()),
         title: Some("Model of a thing".into()),
-        unit: Unit {
+        unit: Some(Unit {
             meter: 7.0,
             name: "septimeter".into(),
-        },
-        up_axis: UpAxis::Z,
+        }),
+        up_axis: Some(UpAxis::Z),
     };
 
     let collada = Collada::from_str(DOCUMENT).unwrap();
@@ -144,11 +236,11 @@ fn asset_blender() {
         revision: None,
         subject: None,
         title: None,
-        unit: Unit {
+        unit: Some(Unit {
             meter: 1.0,
             name: "meter".into(),
-        },
-        up_axis: UpAxis::Z,
+        }),
+        up_axis: Some(UpAxis::Z),
     };
 
     let collada = Collada::from_str(DOCUMENT).unwrap();
@@ -196,6 +288,90 @@ fn asset_wrong_version() {
     assert_eq!(expected, actual);
 }
 
+#[test]
+fn asset_wrong_version_lenient() {
+    static DOCUMENT: &'static str = r#"
+    <?xml version="1.0" encoding="utf-8"?>
+    <COLLADA xmlns="http://www.collada.org/2005/11/COLLADASchema" version="1.4.1">
+        <asset>
+            <contributor>
+                <author>Someone</author>
+                <author_email>someone@example.com</author_email>
+            </contributor>
+            <coverage>
+                <geographic_location>
+                    <longitude>-105.2830</longitude>
+                    <latitude>40.0170</latitude>
+                    <altitude mode="relativeToGround">0</altitude>
+                </geographic_location>
+            </coverage>
+            <created>2017-02-07T20:44:30Z</created>
+            <modified>2017-02-07T20:44:30Z</modified>
+            <unknown />
+            <up_axis>Z_UP</up_axis>
+        </asset>
+    </COLLADA>
+    "#;
+
+    let options = ParseOptions { lenient: true, ..ParseOptions::default() };
+    let (collada, warnings) = Collada::read_with_warnings(DOCUMENT.as_bytes(), options.clone()).unwrap();
+    assert_eq!(Some("Someone".into()), collada.asset.contributors[0].author);
+    assert_eq!(Some(UpAxis::Z), collada.asset.up_axis);
+
+    let kinds = warnings.iter().map(|warning| warning.kind.clone()).collect::<Vec<_>>();
+    assert_eq!(
+        vec![
+            WarningKind::WrongVersion {
+                element: "author_email".into(),
+                version: "1.5",
+                document_version: "1.4",
+            },
+            WarningKind::WrongVersion {
+                element: "coverage".into(),
+                version: "1.5",
+                document_version: "1.4",
+            },
+            WarningKind::SkippedElement {
+                parent: "asset",
+                element: "unknown".into(),
+            },
+        ],
+        kinds,
+    );
+    assert_eq!("element <coverage> belongs to COLLADA 1.5, not 1.4", warnings[1].kind.to_string());
+
+    let (_, versioned_warnings) = VersionedDocument::read_with_warnings(DOCUMENT.as_bytes(), options).unwrap();
+    assert_eq!(warnings, versioned_warnings);
+}
+
+#[test]
+fn skip_extra_data() {
+    static DOCUMENT: &'static str = r#"
+    <?xml version="1.0" encoding="utf-8"?>
+    <COLLADA xmlns="http://www.collada.org/2005/11/COLLADASchema" version="1.4.1">
+        <asset>
+            <created>2017-02-07T20:44:30Z</created>
+            <modified>2017-02-07T20:44:30Z</modified>
+        </asset>
+        <extra>
+            <technique profile="foo">
+                <bar baz="1">Some text</bar>
+                <technique profile="nested" />
+            </technique>
+        </extra>
+    </COLLADA>
+    "#;
+
+    let collada = Collada::from_str(DOCUMENT).unwrap();
+    assert_eq!(5, collada.extras[0].techniques[0].data.len());
+
+    let options = ParseOptions { skip_extra_data: true, ..ParseOptions::default() };
+    let collada = Collada::from_str_with_options(DOCUMENT, options).unwrap();
+    let technique = &collada.extras[0].techniques[0];
+    assert_eq!("foo", technique.profile);
+    assert!(technique.data.is_empty());
+}
+
 #[test]
 fn contributor_minimal() {
     static DOCUMENT: &'static str = r#"
@@ -244,6 +420,58 @@ fn contributor_full() {
     assert_eq!(vec![expected], collada.asset.contributors);
 }
 
+#[test]
+fn contributor_resolved_source_data() {
+    static DOCUMENT: &'static str = r#"
+    <?xml version="1.0" encoding="utf-8"?>
+    <COLLADA xmlns="http://www.collada.org/2005/11/COLLADASchema" version="1.4.1">
+        <asset>
+            <contributor>
+                <author>David LeGare</author>
+                <authoring_tool>Atom</authoring_tool>
+                <comments>This is a sample COLLADA document.</comments>
+                <copyright>David LeGare, free for public use</copyright>
+                <source_data>C:/models/tank.s3d</source_data>
+            </contributor>
+            <created>2017-02-07T20:44:30Z</created>
+            <modified>2017-02-07T20:44:30Z</modified>
+        </asset>
+    </COLLADA>
+    "#;
+
+    let collada = Collada::from_str(DOCUMENT).unwrap();
+    let contributor = &collada.asset.contributors[0];
+    let uri = |uri: &str| -> AnyUri { uri.parse().unwrap() };
+
+    // The drive letter isn't mistaken for a URL scheme, so the path is already absolute.
+    let source_data = uri("C:/models/tank.s3d");
+    assert_eq!(Some(source_data.clone()), contributor.resolved_source_data(None));
+    assert_eq!(
+        Some(source_data.clone()),
+        contributor.resolved_source_data(Some(&uri("file:///D:/exports/scene.dae"))),
+    );
+    assert_eq!(
+        Some(source_data),
+        contributor.resolved_source_data(Some(&uri("http://example.com/models/scene.dae"))),
+    );
+
+    let relative = Contributor { source_data: Some(uri("../scans/tank.s3d")), ..contributor.clone() };
+    assert_eq!(
+        Some(uri("C:/scans/tank.s3d")),
+        relative.resolved_source_data(Some(&uri("C:/models/scene.dae"))),
+    );
+    assert_eq!(
+        Some(uri("file:///home/user/scans/tank.s3d")),
+        relative.resolved_source_data(Some(&uri("file:///home/user/models/scene.dae"))),
+    );
+    assert_eq!(
+        Some(uri("http://example.com/scans/tank.s3d?v=2")),
+        Contributor { source_data: Some(uri("/scans/tank.s3d?v=2")), ..Contributor::default() }
+            .resolved_source_data(Some(&uri("http://example.com/models/scene.dae"))),
+    );
+    assert_eq!(None, Contributor::default().resolved_source_data(Some(&uri("C:/models/"))));
+}
+
 #[test]
 fn contributor_wrong_order() {
     static DOCUMENT: &'static str = r#"
@@ -414,7 +642,7 @@ fn polylist_iter() {
 
     let source = String::from_utf8(TEST_DOCUMENT.into()).unwrap();
     let document = Collada::from_str(&*source).unwrap();
-    let library = document.libraries[5].as_library_geometries().unwrap();
+    let library = document.libraries_of(Library::as_library_geometries).next().unwrap();
     let mesh = library.geometries[0].geometric_element.as_mesh().unwrap();
     let polylist = mesh.primitives[0].as_polylist().unwrap();
 
@@ -484,3 +712,3236 @@ fn polylist_iter() {
 
     assert!(polygons.next().is_none());
 }
+
+#[test]
+fn source_float_values() {
+    static TEST_DOCUMENT: &'static [u8] = include_bytes!("../resources/blender_cube.dae");
+
+    let source = String::from_utf8(TEST_DOCUMENT.into()).unwrap();
+    let document = Collada::from_str(&*source).unwrap();
+    let library = document.libraries[5].as_library_geometries().unwrap();
+    let mesh = library.geometries[0].geometric_element.as_mesh().unwrap();
+    let positions = mesh.find_source("Cube-mesh-positions").unwrap();
+
+    let mut values = positions.float_values().unwrap();
+    assert_eq!(&[1.0, 1.0, -1.0], values.next().unwrap());
+    assert_eq!(7, values.count());
+
+    let x: Vec<f32> = positions.float_param_values("X").unwrap().cloned().collect();
+    let z: Vec<f32> = positions.float_param_values("Z").unwrap().cloned().collect();
+    assert_eq!(vec![1.0, 1.0, -1.0, -0.9999997, 1.0, 0.9999994, -1.0, -1.0], x);
+    assert_eq!(vec![-1.0, -1.0, -1.0, -1.0, 1.0, 1.0, 1.0, 1.0], z);
+    assert!(positions.float_param_values("W").is_none());
+}
+
+#[test]
+fn accessor_iter() {
+    let document = Collada::from_path("resources/blender_cube.dae").unwrap();
+    let library = document.libraries[5].as_library_geometries().unwrap();
+    let mesh = library.geometries[0].mesh().unwrap();
+    let positions = mesh.find_source("Cube-mesh-positions").unwrap();
+    let accessor = positions.common_accessor().unwrap();
+    let array = positions.array.as_ref().and_then(|array| array.as_float_array()).unwrap();
+
+    let records = accessor.iter(&array.data).collect::<Vec<_>>();
+    assert_eq!(8, records.len());
+    assert!(records.iter().all(|record| record.len() == 3));
+    assert_eq!(&[1.0, 1.0, -1.0], records[0]);
+    assert_eq!(&[-1.0, 1.0, 1.0], records[7]);
+
+    // A truncated array stops iteration rather than panicking.
+    assert_eq!(2, accessor.iter(&array.data[..8]).count());
+}
+
+#[test]
+fn accessor_iter_overflow() {
+    static DOCUMENT: &'static str = r##"
+        <?xml version="1.0" encoding="utf-8"?>
+        <COLLADA xmlns="http://www.collada.org/2005/11/COLLADASchema" version="1.4.1">
+            <asset>
+                <created>2017-02-07T20:44:30Z</created>
+                <modified>2017-02-07T20:44:30Z</modified>
+            </asset>
+            <library_geometries>
+                <geometry>
+                    <mesh>
+                        <source id="huge-offset">
+                            <float_array id="huge-offset-array" count="3">1 2 3</float_array>
+                            <technique_common>
+                                <accessor source="#huge-offset-array" count="1" offset="18446744073709551615" stride="2"/>
+                            </technique_common>
+                        </source>
+                        <vertices id="huge-offset-vertices">
+                            <input semantic="POSITION" source="#huge-offset"/>
+                        </vertices>
+                    </mesh>
+                </geometry>
+            </library_geometries>
+        </COLLADA>
+    "##;
+
+    let document = Collada::from_str(DOCUMENT.trim()).unwrap();
+    let library = document.libraries[0].as_library_geometries().unwrap();
+    let source = library.geometries[0].mesh().unwrap().find_source("huge-offset").unwrap();
+    assert_eq!(0, source.float_values().unwrap().count());
+
+    // A stride large enough to overflow when multiplied by the index is also handled.
+    let mut accessor = source.common_accessor().unwrap().clone();
+    accessor.offset = 0;
+    accessor.stride = usize::MAX / 2 + 1;
+    accessor.count = 3;
+    assert_eq!(0, accessor.iter(&[1.0, 2.0, 3.0]).count());
+}
+
+#[test]
+fn source_validate() {
+    let document = Collada::from_path("resources/blender_cube.dae").unwrap();
+    let library = document.libraries[5].as_library_geometries().unwrap();
+    let mesh = library.geometries[0].mesh().unwrap();
+    for source in &mesh.sources {
+        assert_eq!(Ok(()), source.validate(), "Source {} should be valid", source.id);
+    }
+
+    // The positions hold 8 records of 3 values, so a 9th record reads past the end of the array.
+    let mut positions = mesh.find_source("Cube-mesh-positions").unwrap().clone();
+    positions.technique_common.as_mut().unwrap().accessor.count = 9;
+    let expected = ErrorKind::InvalidValue {
+        element: "accessor",
+        value: "9 records of 3 values from offset 0 need 27 values, but the array has 24".into(),
+    };
+    let error = positions.validate().unwrap_err();
+    assert_eq!(expected, error.kind);
+    assert_eq!(positions.position.get(), Some(error.position));
+
+    // Sizes too large to compute are reported rather than overflowing.
+    positions.technique_common.as_mut().unwrap().accessor.count = usize::MAX;
+    let expected = ErrorKind::InvalidValue {
+        element: "accessor",
+        value: format!("{} records of 3 values from offset 0, which overflows", usize::MAX),
+    };
+    assert_eq!(expected, positions.validate().unwrap_err().kind);
+
+    // Fewer params than the stride is allowed, but more isn't.
+    let accessor = &mut positions.technique_common.as_mut().unwrap().accessor;
+    accessor.stride = 2;
+    accessor.count = 12;
+    let expected = ErrorKind::InvalidValue {
+        element: "accessor",
+        value: "3 params with a stride of 2".into(),
+    };
+    assert_eq!(expected, positions.validate().unwrap_err().kind);
+
+    positions.technique_common.as_mut().unwrap().accessor.params.pop();
+    assert_eq!(Ok(()), positions.validate());
+}
+
+#[test]
+fn accessor_named_components() {
+    let document = Collada::from_path("resources/blender_cube.dae").unwrap();
+    let library = document.libraries[5].as_library_geometries().unwrap();
+    let mesh = library.geometries[0].mesh().unwrap();
+    let positions = mesh.find_source("Cube-mesh-positions").unwrap();
+    let accessor = positions.common_accessor().unwrap();
+    let record = positions.float_values().unwrap().next().unwrap();
+
+    assert_eq!(Some([1.0, 1.0, -1.0]), accessor.vec3(record));
+    assert_eq!(Some(&-1.0), accessor.named_component(record, "Z"));
+    assert_eq!(None, accessor.named_component(record, "W"));
+
+    // Unnamed params are skipped but still take up a slot, and unknown names are ignored.
+    let param = |name: Option<&str>| Param {
+        name: name.map(Into::into),
+        sid: None,
+        data_type: Some("float".into()),
+        semantic: None,
+    };
+    let shuffled = Accessor {
+        params: vec![param(None), param(Some("Z")), param(Some("W")), param(Some("X")), param(Some("Y"))],
+        stride: 5,
+        ..accessor.clone()
+    };
+    let record = [9.0, 3.0, 0.5, 1.0, 2.0];
+    assert_eq!(Some([1.0, 2.0, 3.0]), shuffled.vec3(&record));
+    assert_eq!(Some(&0.5), shuffled.named_component(&record, "W"));
+    assert_eq!(None, shuffled.vec3(&record[..4]));
+}
+
+#[test]
+fn float_array_matches_generic_parse() {
+    static TEST_DOCUMENT: &'static [u8] = include_bytes!("../resources/blender_cube.dae");
+
+    let source = String::from_utf8(TEST_DOCUMENT.into()).unwrap();
+    let document = Collada::from_str(&*source).unwrap();
+    let library = document.libraries[5].as_library_geometries().unwrap();
+
+    // Pull the raw text for each array straight out of the document and parse it the simple way.
+    let mut raw_arrays = source.split("<float_array").skip(1).map(|element| {
+        let start = element.find('>').unwrap() + 1;
+        let end = element.find("</float_array>").unwrap();
+        element[start..end]
+            .split_whitespace()
+            .map(|word| word.parse::<f32>().unwrap())
+            .collect::<Vec<_>>()
+    });
+
+    for geometry in &library.geometries {
+        let mesh = geometry.geometric_element.as_mesh().unwrap();
+        for source in &mesh.sources {
+            let array = source.array.as_ref().unwrap().as_float_array().unwrap();
+            assert_eq!(array.count, array.data.len());
+            assert_eq!(raw_arrays.next().unwrap(), array.data);
+        }
+    }
+    assert!(raw_arrays.next().is_none());
+}
+
+#[test]
+fn float_array_whitespace() {
+    static DOCUMENT: &'static str = "
+    <?xml version=\"1.0\" encoding=\"utf-8\"?>
+    <COLLADA xmlns=\"http://www.collada.org/2005/11/COLLADASchema\" version=\"1.4.1\">
+        <asset>
+            <created>2017-02-07T20:44:30Z</created>
+            <modified>2017-02-07T20:44:30Z</modified>
+        </asset>
+        <library_geometries>
+            <geometry>
+                <mesh>
+                    <source id=\"positions\">
+                        <float_array count=\"1000000\">
+                            1\t-0.9999998\r\n  2.5e3
+                            -4
+                        </float_array>
+                    </source>
+                    <vertices id=\"vertices\">
+                        <input semantic=\"POSITION\" source=\"#positions\" />
+                    </vertices>
+                </mesh>
+            </geometry>
+        </library_geometries>
+    </COLLADA>
+    ";
+
+    let document = Collada::from_str(DOCUMENT).unwrap();
+    let library = document.libraries[0].as_library_geometries().unwrap();
+    let mesh = library.geometries[0].geometric_element.as_mesh().unwrap();
+    let array = mesh.sources[0].array.as_ref().unwrap().as_float_array().unwrap();
+
+    // The declared count is only used as a capacity hint, so a wrong count is harmless.
+    assert_eq!(vec![1.0, -0.9999998, 2500.0, -4.0], array.data);
+    assert!(array.data.capacity() < 1000000);
+}
+
+#[test]
+fn float_array_cdata_and_entities() {
+    static DOCUMENT: &'static str = r##"
+    <?xml version="1.0" encoding="utf-8"?>
+    <COLLADA xmlns="http://www.collada.org/2005/11/COLLADASchema" version="1.4.1">
+        <asset>
+            <created>2017-02-07T20:44:30Z</created>
+            <modified>2017-02-07T20:44:30Z</modified>
+        </asset>
+        <library_geometries>
+            <geometry>
+                <mesh>
+                    <source id="cdata">
+                        <float_array count="3"><![CDATA[1 2 3]]></float_array>
+                    </source>
+                    <source id="mixed">
+                        <float_array count="6">1 2<![CDATA[ 3 4 ]]>5<![CDATA[ ]]>6</float_array>
+                    </source>
+                    <source id="entities">
+                        <float_array count="5">1&#32;2&#x20;3&#9;4&#10;-5e-1</float_array>
+                    </source>
+                    <vertices id="vertices">
+                        <input semantic="POSITION" source="#cdata" />
+                    </vertices>
+                </mesh>
+            </geometry>
+        </library_geometries>
+    </COLLADA>
+    "##;
+
+    let document = Collada::from_str(DOCUMENT).unwrap();
+    let library = document.libraries[0].as_library_geometries().unwrap();
+    let mesh = library.geometries[0].geometric_element.as_mesh().unwrap();
+    let arrays = mesh.sources.iter()
+        .map(|source| source.array.as_ref().unwrap().as_float_array().unwrap().data.clone())
+        .collect::<Vec<_>>();
+
+    // CDATA sections and entity references are merged into the surrounding text, so separators
+    // on either side of a CDATA boundary are kept and adjacent numbers are never merged.
+    assert_eq!(vec![1.0, 2.0, 3.0], arrays[0]);
+    assert_eq!(vec![1.0, 2.0, 3.0, 4.0, 5.0, 6.0], arrays[1]);
+    assert_eq!(vec![1.0, 2.0, 3.0, 4.0, -0.5], arrays[2]);
+}
+
+#[test]
+fn mesh_resolved_vertices() {
+    static POSITIONS: [[f32; 3]; 8] = [
+        [1.0, 1.0, -1.0],
+        [1.0, -1.0, -1.0],
+        [-1.0, -0.9999998, -1.0],
+        [-0.9999997, 1.0, -1.0],
+        [1.0, 0.9999995, 1.0],
+        [0.9999994, -1.000001, 1.0],
+        [-1.0, -0.9999997, 1.0],
+        [-1.0, 1.0, 1.0],
+    ];
+
+    // The position index of each vertex in the cube's `<p>`, which is already triangulated.
+    static POSITION_INDICES: [usize; 36] = [
+        0, 2, 3, 7, 5, 4, 4, 1, 0, 5, 2, 1, 2, 7, 3, 0, 7, 4,
+        0, 1, 2, 7, 6, 5, 4, 5, 1, 5, 6, 2, 2, 6, 7, 0, 3, 7,
+    ];
+
+    let document = Collada::read(::std::fs::File::open("resources/blender_cube.dae").unwrap()).unwrap();
+    let library = document.libraries[5].as_library_geometries().unwrap();
+    let mesh = library.geometries[0].geometric_element.as_mesh().unwrap();
+    let vertices = mesh.resolved_vertices().unwrap();
+
+    assert_eq!(36, vertices.len());
+    for (vertex, &index) in vertices.iter().zip(POSITION_INDICES.iter()) {
+        assert_eq!(POSITIONS[index], vertex.position);
+        assert!(vertex.normal.is_some());
+        assert_eq!(None, vertex.texcoord);
+        assert_eq!(None, vertex.color);
+    }
+
+    // Every vertex of the first triangle shares the first face normal.
+    assert_eq!(Some([0.0, 0.0, -1.0]), vertices[0].normal);
+    assert_eq!(vertices[0].normal, vertices[2].normal);
+}
+
+#[test]
+fn mesh_resolved_vertices_missing_source() {
+    static DOCUMENT: &'static str = r##"
+    <?xml version="1.0" encoding="utf-8"?>
+    <COLLADA xmlns="http://www.collada.org/2005/11/COLLADASchema" version="1.4.1">
+        <asset>
+            <created>2017-02-07T20:44:30Z</created>
+            <modified>2017-02-07T20:44:30Z</modified>
+        </asset>
+        <library_geometries>
+            <geometry>
+                <mesh>
+                    <source id="positions">
+                        <float_array count="3">0 0 1</float_array>
+                        <technique_common>
+                            <accessor source="#positions-array" count="1" stride="3" />
+                        </technique_common>
+                    </source>
+                    <vertices id="vertices">
+                        <input semantic="POSITION" source="#positions" />
+                    </vertices>
+                    <polylist count="1">
+                        <input semantic="VERTEX" source="#vertices" offset="0" />
+                        <input semantic="NORMAL" source="#normals" offset="1" />
+                        <vcount>3</vcount>
+                        <p>0 0 0 0 0 0</p>
+                    </polylist>
+                </mesh>
+            </geometry>
+        </library_geometries>
+    </COLLADA>
+    "##;
+
+    let document = Collada::from_str(DOCUMENT).unwrap();
+    let library = document.libraries[0].as_library_geometries().unwrap();
+    let mesh = library.geometries[0].geometric_element.as_mesh().unwrap();
+    assert_eq!(
+        Err(ResolveError::MissingSource { id: "normals".into() }),
+        mesh.resolved_vertices(),
+    );
+}
+
+#[test]
+fn source_accessor_for_profile() {
+    static DOCUMENT: &'static str = r##"
+    <?xml version="1.0" encoding="utf-8"?>
+    <COLLADA xmlns="http://www.collada.org/2005/11/COLLADASchema" version="1.4.1">
+        <asset>
+            <created>2017-02-07T20:44:30Z</created>
+            <modified>2017-02-07T20:44:30Z</modified>
+        </asset>
+        <library_geometries>
+            <geometry>
+                <mesh>
+                    <source id="weights">
+                        <float_array id="weights-array" count="4">0.25 0.75 0.5 0.5</float_array>
+                        <technique profile="OTHER">
+                            <unrelated>1</unrelated>
+                        </technique>
+                        <technique profile="VENDOR">
+                            <note>Pairs of weights</note>
+                            <accessor source="#weights-array" count="2" stride="2">
+                                <param name="A" type="float" />
+                                <param name="B" type="float" />
+                            </accessor>
+                        </technique>
+                    </source>
+                    <vertices id="vertices">
+                        <input semantic="POSITION" source="#weights" />
+                    </vertices>
+                </mesh>
+            </geometry>
+        </library_geometries>
+    </COLLADA>
+    "##;
+
+    let document = Collada::from_str(DOCUMENT).unwrap();
+    let library = document.libraries[0].as_library_geometries().unwrap();
+    let mesh = library.geometries[0].geometric_element.as_mesh().unwrap();
+    let source = &mesh.sources[0];
+
+    // The only accessor is in a profile-specific technique, so there's no common accessor.
+    assert!(source.common_accessor().is_none());
+    assert!(source.accessor_for_profile("OTHER").is_none());
+    assert!(source.accessor_for_profile("MISSING").is_none());
+
+    let accessor = source.accessor_for_profile("VENDOR").unwrap();
+    assert_eq!(2, accessor.count);
+    assert_eq!(2, accessor.stride);
+    assert_eq!(0, accessor.offset);
+    assert_eq!(Some(1), accessor.param_index("B"));
+
+    let array = source.array.as_ref().and_then(Array::as_float_array).unwrap();
+    assert_eq!(&[0.5, 0.5], accessor.access(&array.data, 1));
+}
+
+#[test]
+fn vertices_missing_position() {
+    static DOCUMENT: &'static str = r##"
+    <?xml version="1.0" encoding="utf-8"?>
+    <COLLADA xmlns="http://www.collada.org/2005/11/COLLADASchema" version="1.4.1">
+        <asset>
+            <created>2017-02-07T20:44:30Z</created>
+            <modified>2017-02-07T20:44:30Z</modified>
+        </asset>
+        <library_geometries>
+            <geometry>
+                <mesh>
+                    <source id="normals">
+                        <float_array count="3">0 0 1</float_array>
+                    </source>
+                    <vertices id="vertices">
+                        <input semantic="NORMAL" source="#normals" />
+                    </vertices>
+                </mesh>
+            </geometry>
+        </library_geometries>
+    </COLLADA>
+    "##;
+
+    let error = Collada::from_str(DOCUMENT).unwrap_err();
+    assert_eq!(
+        ErrorKind::MissingElement {
+            parent: "vertices",
+            expected: vec!["input (POSITION)"],
+        },
+        error.kind,
+    );
+}
+
+#[test]
+fn polylist_triangulate() {
+    static DOCUMENT: &'static str = r##"
+    <?xml version="1.0" encoding="utf-8"?>
+    <COLLADA xmlns="http://www.collada.org/2005/11/COLLADASchema" version="1.4.1">
+        <asset>
+            <created>2017-02-07T20:44:30Z</created>
+            <modified>2017-02-07T20:44:30Z</modified>
+        </asset>
+        <library_geometries>
+            <geometry id="Mixed-mesh">
+                <mesh>
+                    <source id="Mixed-mesh-positions">
+                        <float_array id="Mixed-mesh-positions-array" count="6">0 0 1 0 1 1</float_array>
+                        <technique_common>
+                            <accessor source="#Mixed-mesh-positions-array" count="2" stride="3">
+                                <param name="X" type="float"/>
+                                <param name="Y" type="float"/>
+                                <param name="Z" type="float"/>
+                            </accessor>
+                        </technique_common>
+                    </source>
+                    <vertices id="Mixed-mesh-vertices">
+                        <input semantic="POSITION" source="#Mixed-mesh-positions"/>
+                    </vertices>
+                    <polylist count="4">
+                        <input semantic="VERTEX" source="#Mixed-mesh-vertices" offset="0"/>
+                        <vcount>4 3 2 5</vcount>
+                        <p>0 1 2 3  4 5 6  7 8  9 10 11 12 13</p>
+                    </polylist>
+                </mesh>
+            </geometry>
+        </library_geometries>
+    </COLLADA>
+    "##;
+
+    let document = Collada::from_str(DOCUMENT).unwrap();
+    let library = document.libraries[0].as_library_geometries().unwrap();
+    let mesh = library.geometries[0].geometric_element.as_mesh().unwrap();
+    let polylist = mesh.primitives[0].as_polylist().unwrap();
+
+    let triangles = polylist.triangulate()
+        .map(|triangle| {
+            let mut indices = [0; 3];
+            for (index, vertex) in indices.iter_mut().zip(triangle.iter()) {
+                let attribute = vertex.iter().next().unwrap();
+                assert_eq!(0, attribute.offset);
+                *index = attribute.index;
+            }
+            indices
+        })
+        .collect::<Vec<_>>();
+
+    // The quad becomes 2 triangles, the triangle is unchanged, the 2-vertex polygon is dropped,
+    // and the 5-gon becomes 3 triangles.
+    let expected = vec![
+        [0, 1, 2],
+        [0, 2, 3],
+        [4, 5, 6],
+        [9, 10, 11],
+        [9, 11, 12],
+        [9, 12, 13],
+    ];
+    assert_eq!(expected, triangles);
+}
+
+static POLYLIST_DOCUMENT: &'static str = r##"
+<?xml version="1.0" encoding="utf-8"?>
+<COLLADA xmlns="http://www.collada.org/2005/11/COLLADASchema" version="1.4.1">
+    <asset>
+        <created>2017-02-07T20:44:30Z</created>
+        <modified>2017-02-07T20:44:30Z</modified>
+    </asset>
+    <library_geometries>
+        <geometry id="Quad-mesh">
+            <mesh>
+                <source id="Quad-mesh-positions">
+                    <float_array id="Quad-mesh-positions-array" count="12">0 0 0 1 0 0 1 1 0 0 1 0</float_array>
+                    <technique_common>
+                        <accessor source="#Quad-mesh-positions-array" count="4" stride="3">
+                            <param name="X" type="float"/>
+                            <param name="Y" type="float"/>
+                            <param name="Z" type="float"/>
+                        </accessor>
+                    </technique_common>
+                </source>
+                <vertices id="Quad-mesh-vertices">
+                    <input semantic="POSITION" source="#Quad-mesh-positions"/>
+                </vertices>
+                <polylist count="2">
+                    <input semantic="VERTEX" source="#Quad-mesh-vertices" offset="0"/>
+                    <input semantic="NORMAL" source="#Quad-mesh-positions" offset="1"/>
+                    <vcount>3 3</vcount>
+                    <p>0 0 1 1 2 2  0 0 2 2 3 3</p>
+                </polylist>
+            </mesh>
+        </geometry>
+    </library_geometries>
+</COLLADA>
+"##;
+
+#[test]
+fn polylist_vcount_mismatch() {
+    assert!(Collada::from_str(POLYLIST_DOCUMENT).is_ok());
+
+    let document = POLYLIST_DOCUMENT.replace("<vcount>3 3</vcount>", "<vcount>3 3 3</vcount>");
+    let error = Collada::from_str(&document).unwrap_err();
+    assert_eq!(
+        ErrorKind::InvalidValue {
+            element: "vcount",
+            value: "3 vertex counts for 2 polygons".into(),
+        },
+        error.kind,
+    );
+}
+
+#[test]
+fn polylist_truncated_primitives() {
+    // Each vertex uses two indices, so the two triangles need 12 indices in total.
+    let document = POLYLIST_DOCUMENT.replace("0 0 2 2 3 3</p>", "0 0 2 2 3</p>");
+    let error = Collada::from_str(&document).unwrap_err();
+    assert_eq!(
+        ErrorKind::InvalidValue {
+            element: "p",
+            value: "11 indices where 12 were expected".into(),
+        },
+        error.kind,
+    );
+    assert_eq!(TextPosition { row: 23, column: 16 }, error.position);
+}
+
+#[test]
+fn polylist_try_iter() {
+    let document = Collada::from_str(POLYLIST_DOCUMENT).unwrap();
+    let mesh = document.libraries[0].as_library_geometries().unwrap().geometries[0].mesh().unwrap();
+    let mut polylist = mesh.primitives[0].as_polylist().unwrap().clone();
+    assert_eq!(2, polylist.try_iter().unwrap().count());
+
+    // Polylists can be modified after parsing, so the indices may no longer match `<vcount>`.
+    let mut indices = polylist.primitives.as_ref().unwrap().to_vec();
+    indices.pop();
+    polylist.primitives = Some(indices.into());
+    match polylist.try_iter() {
+        Err(error) => assert_eq!(
+            ErrorKind::InvalidValue {
+                element: "p",
+                value: "11 indices where 12 were expected".into(),
+            },
+            error,
+        ),
+        Ok(_) => panic!("Expected an error for the truncated indices"),
+    }
+
+    // Without any inputs the polylist doesn't describe any polygons.
+    polylist.inputs.clear();
+    assert_eq!(0, polylist.try_iter().unwrap().count());
+    assert_eq!(0, polylist.iter().count());
+}
+
+#[test]
+fn polylist_invalid_index() {
+    let document = POLYLIST_DOCUMENT.replace("<p>0 0 1 1", "<p>0 0 -1 1");
+    let error = Collada::from_str(&document).unwrap_err();
+    match error.kind {
+        ErrorKind::InvalidNumber { element, ref token, ref error } => {
+            assert_eq!("p", element);
+            assert_eq!("-1", token);
+            assert_eq!(ErrorCategory::Value, error.category());
+        }
+
+        ref kind => panic!("Expected an invalid number error, got {:?}", kind),
+    }
+    assert!(error.to_string().contains("<p> contained an invalid number \"-1\""));
+
+    let document = POLYLIST_DOCUMENT.replace("<vcount>3 3</vcount>", "<vcount>3 3.0</vcount>");
+    match Collada::from_str(&document).unwrap_err().kind {
+        ErrorKind::InvalidNumber { element: "vcount", ref token, .. } => assert_eq!("3.0", token),
+        kind => panic!("Expected an invalid number error, got {:?}", kind),
+    }
+}
+
+#[test]
+fn polylist_vertex_count_overflow() {
+    let document = POLYLIST_DOCUMENT.replace(
+        "<vcount>3 3</vcount>",
+        "<vcount>18446744073709551615 1</vcount>",
+    );
+    let error = Collada::from_str(&document).unwrap_err();
+    assert_eq!(
+        ErrorKind::InvalidValue {
+            element: "vcount",
+            value: "2 vertex counts whose sum overflows".into(),
+        },
+        error.kind,
+    );
+
+    let document = POLYLIST_DOCUMENT.replace(
+        "<vcount>3 3</vcount>",
+        "<vcount>9223372036854775807 1</vcount>",
+    );
+    match Collada::from_str(&document).unwrap_err().kind {
+        ErrorKind::InvalidValue { element: "p", .. } => {}
+        kind => panic!("Expected an invalid value error, got {:?}", kind),
+    }
+
+    // Polylists modified after parsing are checked too.
+    let document = Collada::from_str(POLYLIST_DOCUMENT).unwrap();
+    let mesh = document.libraries[0].as_library_geometries().unwrap().geometries[0].mesh().unwrap();
+    let mut polylist = mesh.primitives[0].as_polylist().unwrap().clone();
+    polylist.vcount = Some(vec![usize::MAX, 1].into());
+    assert_eq!(usize::MAX, polylist.total_vertices());
+    assert!(polylist.try_iter().is_err());
+
+    let triangles = Triangles {
+        name: None,
+        count: usize::MAX,
+        material: None,
+        inputs: Vec::new(),
+        primitives: None,
+        extras: Vec::new(),
+    };
+    assert_eq!(usize::MAX, triangles.total_vertices());
+    assert!(triangles.try_iter().is_err());
+    assert_eq!(usize::MAX, Primitive::Triangles(triangles).total_vertices());
+}
+
+#[test]
+fn mesh_bounding_box() {
+    static TEST_DOCUMENT: &'static [u8] = include_bytes!("../resources/blender_cube.dae");
+
+    let source = String::from_utf8(TEST_DOCUMENT.into()).unwrap();
+    let document = Collada::from_str(&*source).unwrap();
+    let library = document.libraries[5].as_library_geometries().unwrap();
+    let mesh = library.geometries[0].geometric_element.as_mesh().unwrap();
+
+    let (min, max) = mesh.bounding_box().unwrap();
+    for axis in 0..3 {
+        assert!((min[axis] + 1.0).abs() < 1e-5, "Wrong minimum bound: {:?}", min);
+        assert!((max[axis] - 1.0).abs() < 1e-5, "Wrong maximum bound: {:?}", max);
+    }
+}
+
+#[test]
+fn mesh_bounding_box_extra_params() {
+    static DOCUMENT: &'static str = r##"
+    <?xml version="1.0" encoding="utf-8"?>
+    <COLLADA xmlns="http://www.collada.org/2005/11/COLLADASchema" version="1.4.1">
+        <asset>
+            <created>2017-02-07T20:44:30Z</created>
+            <modified>2017-02-07T20:44:30Z</modified>
+        </asset>
+        <library_geometries>
+            <geometry id="Strided-mesh">
+                <mesh>
+                    <source id="Strided-mesh-positions">
+                        <float_array id="Strided-mesh-positions-array" count="9">100 0 1 2 -50 3 -1 5 50</float_array>
+                        <technique_common>
+                            <accessor source="#Strided-mesh-positions-array" count="2" offset="1" stride="4">
+                                <param name="X" type="float"/>
+                                <param name="Y" type="float"/>
+                                <param name="Z" type="float"/>
+                                <param name="W" type="float"/>
+                            </accessor>
+                        </technique_common>
+                    </source>
+                    <vertices id="Strided-mesh-vertices">
+                        <input semantic="POSITION" source="#Strided-mesh-positions"/>
+                    </vertices>
+                </mesh>
+            </geometry>
+        </library_geometries>
+    </COLLADA>
+    "##;
+
+    let document = Collada::from_str(DOCUMENT).unwrap();
+    let library = document.libraries[0].as_library_geometries().unwrap();
+    let mesh = library.geometries[0].geometric_element.as_mesh().unwrap();
+
+    // The leading value (skipped by `offset`) and the W components must not contribute.
+    assert_eq!(Some(([0.0, -1.0, 2.0], [3.0, 1.0, 5.0])), mesh.bounding_box());
+}
+
+#[test]
+fn visual_scene_world_transforms() {
+    static DOCUMENT: &'static str = r#"
+    <?xml version="1.0" encoding="utf-8"?>
+    <COLLADA xmlns="http://www.collada.org/2005/11/COLLADASchema" version="1.4.1">
+        <asset>
+            <created>2017-02-07T20:44:30Z</created>
+            <modified>2017-02-07T20:44:30Z</modified>
+        </asset>
+        <library_visual_scenes>
+            <visual_scene id="Scene">
+                <node id="Parent">
+                    <translate sid="location">1 2 3</translate>
+                    <node id="Child" type="JOINT">
+                        <rotate sid="rotationZ">0 0 1 90</rotate>
+                        <translate sid="offset">1 0 0</translate>
+                    </node>
+                </node>
+            </visual_scene>
+        </library_visual_scenes>
+    </COLLADA>
+    "#;
+
+    let document = Collada::from_str(DOCUMENT).unwrap();
+    let library = document.libraries[0].as_library_visual_scenes().unwrap();
+    let scene = &library.visual_scenes[0];
+    let transforms = scene.world_transforms();
+    assert_eq!(2, transforms.len());
+
+    let (ref parent_path, parent) = transforms[0];
+    assert_eq!(&[0], parent_path.indices());
+    assert_eq!(Some("Parent"), scene.node(parent_path).and_then(|node| node.id.as_ref()).map(String::as_str));
+    assert_eq!(
+        [1.0, 0.0, 0.0, 0.0, 0.0, 1.0, 0.0, 0.0, 0.0, 0.0, 1.0, 0.0, 1.0, 2.0, 3.0, 1.0],
+        parent,
+    );
+
+    // The child's transforms apply in document order, so the offset is rotated onto the Y axis
+    // before the parent's translation is applied.
+    let (ref child_path, child) = transforms[1];
+    assert_eq!(&[0, 0], child_path.indices());
+    assert_eq!(NodeType::Joint, scene.node(child_path).unwrap().node_type);
+    let expected = [0.0, 1.0, 0.0, 0.0, -1.0, 0.0, 0.0, 0.0, 0.0, 0.0, 1.0, 0.0, 1.0, 3.0, 3.0, 1.0];
+    for (actual, expected) in child.iter().zip(expected.iter()) {
+        assert!((actual - expected).abs() < 1e-9, "Expected {:?}, got {:?}", expected, child);
+    }
+}
+
+#[test]
+#[cfg(feature = "serde")]
+fn serde_round_trip() {
+    extern crate serde_json;
+
+    static TEST_DOCUMENT: &'static [u8] = include_bytes!("../resources/blender_cube.dae");
+
+    let document = String::from_utf8(TEST_DOCUMENT.into()).unwrap();
+    let collada = Collada::from_str(&*document).unwrap();
+
+    let json = serde_json::to_string(&collada.asset).unwrap();
+    let asset: Asset = serde_json::from_str(&json).unwrap();
+    assert_eq!(collada.asset, asset);
+
+    let json = serde_json::to_string(&collada).unwrap();
+    let round_trip: Collada = serde_json::from_str(&json).unwrap();
+    assert_eq!(collada, round_trip);
+}
+
+#[test]
+#[cfg(feature = "serde")]
+fn serde_technique_round_trip() {
+    extern crate serde_json;
+
+    static DOCUMENT: &'static str = r#"
+    <?xml version="1.0" encoding="utf-8"?>
+    <COLLADA xmlns="http://www.collada.org/2005/11/COLLADASchema" version="1.4.1">
+        <asset>
+            <created>2017-02-07T20:44:30Z</created>
+            <modified>2017-02-07T20:44:30Z</modified>
+        </asset>
+        <extra>
+            <technique profile="foo">
+                <bar baz="1">Some text</bar>
+                <qux />
+            </technique>
+        </extra>
+    </COLLADA>
+    "#;
+
+    let collada = Collada::from_str(DOCUMENT).unwrap();
+    assert_eq!(5, collada.extras[0].techniques[0].data.len());
+
+    let json = serde_json::to_string(&collada).unwrap();
+    let round_trip: Collada = serde_json::from_str(&json).unwrap();
+    assert_eq!(collada, round_trip);
+}
+
+#[test]
+fn blender_technique() {
+    use ::collaborate::v1_4::*;
+
+    // The `<extra>` that Blender writes for the lamp in blender_cube.dae, with some of the
+    // unrecognized values trimmed out.
+    static DOCUMENT: &'static str = r#"
+    <?xml version="1.0" encoding="utf-8"?>
+    <COLLADA xmlns="http://www.collada.org/2005/11/COLLADASchema" version="1.4.1">
+        <asset>
+            <created>2017-02-07T20:44:30Z</created>
+            <modified>2017-02-07T20:44:30Z</modified>
+        </asset>
+        <extra>
+            <technique profile="blender">
+                <adapt_thresh>0.000999987</adapt_thresh>
+                <blue>1</blue>
+                <clipend>30.002</clipend>
+                <clipsta>1.000799</clipsta>
+                <dist sid="blender_dist">29.99998</dist>
+                <energy sid="blender_energy">1</energy>
+                <gamma sid="blender_gamma">1</gamma>
+                <green>1</green>
+                <mode>8192</mode>
+                <red>1</red>
+                <shadow_b sid="blender_shadow_b">0</shadow_b>
+                <shadow_g sid="blender_shadow_g">0</shadow_g>
+                <shadow_r sid="blender_shadow_r">0</shadow_r>
+                <spotblend>0.15</spotblend>
+                <spotsize>75</spotsize>
+                <type>0</type>
+            </technique>
+            <technique profile="FCOLLADA" />
+        </extra>
+    </COLLADA>
+    "#;
+
+    let document = Collada::from_str(DOCUMENT).unwrap();
+    let techniques = &document.extras[0].techniques;
+    assert_eq!(None, techniques[1].as_blender());
+
+    let blender = techniques[0].as_blender().unwrap();
+    assert_eq!(None, blender.shift_x);
+    assert_eq!(Some(1.0), blender.energy);
+    assert_eq!(Some(29.99998), blender.distance);
+    assert_eq!(Some(1.0), blender.gamma);
+    assert_eq!(Some([1.0, 1.0, 1.0]), blender.color);
+    assert_eq!(Some([0.0, 0.0, 0.0]), blender.shadow_color);
+    assert_eq!(Some(75.0), blender.spot_size);
+    assert_eq!(Some(0.15), blender.spot_blend);
+    assert_eq!(Some(1.000799), blender.clip_start);
+    assert_eq!(Some(30.002), blender.clip_end);
+
+    assert_eq!(3, blender.other.len());
+    assert_eq!("0.000999987", blender.other["adapt_thresh"]);
+    assert_eq!("8192", blender.other["mode"]);
+    assert_eq!("0", blender.other["type"]);
+}
+
+#[test]
+fn maya_technique() {
+    use ::collaborate::v1_4::*;
+
+    static DOCUMENT: &'static str = r#"
+    <?xml version="1.0" encoding="utf-8"?>
+    <COLLADA xmlns="http://www.collada.org/2005/11/COLLADASchema" version="1.4.1">
+        <asset>
+            <created>2017-02-07T20:44:30Z</created>
+            <modified>2017-02-07T20:44:30Z</modified>
+        </asset>
+        <extra>
+            <technique profile="MAYA">
+                <double_sided>1</double_sided>
+                <dynamic_attributes>
+                    <filmFit short_name="ff" type="int">1</filmFit>
+                </dynamic_attributes>
+            </technique>
+            <technique profile="OpenCOLLADAMaya">
+                <originalMayaNodeId>pCube1</originalMayaNodeId>
+                <layer>layer1</layer>
+            </technique>
+            <technique profile="blender" />
+        </extra>
+    </COLLADA>
+    "#;
+
+    let document = Collada::from_str(DOCUMENT).unwrap();
+    let techniques = &document.extras[0].techniques;
+    assert_eq!(None, techniques[2].as_maya());
+
+    let maya = techniques[0].as_maya().unwrap();
+    assert_eq!(Some(true), maya.double_sided);
+    assert_eq!(None, maya.original_node_name);
+    assert_eq!(1, maya.other.len());
+    assert!(maya.other.contains_key("dynamic_attributes"));
+
+    let maya = techniques[1].as_maya().unwrap();
+    assert_eq!(None, maya.double_sided);
+    assert_eq!(Some("pCube1"), maya.original_node_name.as_deref());
+    assert_eq!(Some("layer1"), maya.layer.as_deref());
+    assert!(maya.other.is_empty());
+}
+
+#[test]
+fn technique_get() {
+    use ::collaborate::v1_4::*;
+
+    static DOCUMENT: &'static str = r#"
+    <?xml version="1.0" encoding="utf-8"?>
+    <COLLADA xmlns="http://www.collada.org/2005/11/COLLADASchema" version="1.4.1">
+        <asset>
+            <created>2017-02-07T20:44:30Z</created>
+            <modified>2017-02-07T20:44:30Z</modified>
+        </asset>
+        <extra>
+            <technique profile="custom">
+                <shadow>soft</shadow>
+                <tag>first</tag>
+                <settings>
+                    <tag>nested</tag>
+                </settings>
+                <tag>second</tag>
+                <empty />
+            </technique>
+        </extra>
+    </COLLADA>
+    "#;
+
+    let document = Collada::from_str(DOCUMENT).unwrap();
+    let technique = &document.extras[0].techniques[0];
+
+    assert_eq!(Some("soft".into()), technique.get("shadow"));
+    assert_eq!(Some("first".into()), technique.get("tag"));
+    assert_eq!(Some("".into()), technique.get("empty"));
+    assert_eq!(None, technique.get("missing"));
+
+    // Only direct children are matched, so the `<tag>` inside `<settings>` isn't included.
+    assert_eq!(vec!["first".to_owned(), "second".to_owned()], technique.get_all("tag"));
+    assert!(technique.get_all("missing").is_empty());
+}
+
+#[test]
+fn technique_semantic_eq() {
+    static DOCUMENT: &'static str = r#"
+    <?xml version="1.0" encoding="utf-8"?>
+    <COLLADA xmlns="http://www.collada.org/2005/11/COLLADASchema" version="1.4.1">
+        <asset>
+            <created>2017-02-07T20:44:30Z</created>
+            <modified>2017-02-07T20:44:30Z</modified>
+        </asset>
+        <extra>
+            <technique profile="custom"><light type="spot" intensity="2.5" /></technique>
+        </extra>
+        <extra>
+            <technique profile="custom"><light intensity="2.5" type="spot" /></technique>
+        </extra>
+        <extra>
+            <technique profile="custom"><light intensity="3.0" type="spot" /></technique>
+        </extra>
+    </COLLADA>
+    "#;
+
+    let document = Collada::from_str(DOCUMENT).unwrap();
+    let original = &document.extras[0].techniques[0];
+    let reordered = &document.extras[1].techniques[0];
+    let changed = &document.extras[2].techniques[0];
+
+    assert!(original != reordered);
+    assert!(original.semantic_eq(reordered));
+    assert!(reordered.semantic_eq(original));
+    assert!(!reordered.semantic_eq(changed));
+}
+
+#[test]
+fn extra_namespace() {
+    use ::collaborate::v1_4::*;
+
+    static DOCUMENT: &'static str = r#"
+    <?xml version="1.0" encoding="utf-8"?>
+    <COLLADA
+        xmlns="http://www.collada.org/2005/11/COLLADASchema"
+        xmlns:xsi="http://www.w3.org/2001/XMLSchema-instance"
+        version="1.4.1">
+        <asset>
+            <created>2017-02-07T20:44:30Z</created>
+            <modified>2017-02-07T20:44:30Z</modified>
+        </asset>
+        <extra>
+            <technique profile="vendor" xmlns:vendor="http://example.com/vendor">
+                <vendor:shadow>soft</vendor:shadow>
+            </technique>
+        </extra>
+    </COLLADA>
+    "#;
+
+    let document = Collada::from_str(DOCUMENT).unwrap();
+    let extra = &document.extras[0];
+    assert_eq!(Some("http://www.collada.org/2005/11/COLLADASchema"), extra.namespace.get(""));
+    assert_eq!(Some("http://www.w3.org/2001/XMLSchema-instance"), extra.namespace.get("xsi"));
+    assert_eq!(None, extra.namespace.get("vendor"));
+
+    // Prefixes declared on the `<technique>` itself are in scope for it, along with everything
+    // declared on its ancestors.
+    let technique = &extra.techniques[0];
+    assert_eq!(Some("http://www.w3.org/2001/XMLSchema-instance"), technique.namespace.get("xsi"));
+    assert_eq!(Some("http://example.com/vendor"), technique.namespace.get("vendor"));
+    assert_eq!(Some("soft".into()), technique.get("shadow"));
+}
+
+#[test]
+fn extra_asset_only() {
+    static DOCUMENT: &'static str = r#"
+    <?xml version="1.0" encoding="utf-8"?>
+    <COLLADA xmlns="http://www.collada.org/2005/11/COLLADASchema" version="1.4.1">
+        <asset>
+            <created>2017-02-07T20:44:30Z</created>
+            <modified>2017-02-07T20:44:30Z</modified>
+        </asset>
+        <extra type="source_asset">
+            <asset>
+                <created>2016-11-03T08:15:00Z</created>
+                <modified>2016-11-04T10:30:00Z</modified>
+                <title>Original scan</title>
+            </asset>
+        </extra>
+    </COLLADA>
+    "#;
+
+    let document = Collada::from_str(DOCUMENT).unwrap();
+    let extra = &document.extras[0];
+    assert_eq!(Some("source_asset"), extra.type_hint.as_deref());
+    assert_eq!(Some("Original scan"), extra.asset.as_ref().and_then(|asset| asset.title.as_deref()));
+    assert!(extra.techniques.is_empty());
+
+    // The asset may still be followed by techniques.
+    let with_technique = DOCUMENT.replace(
+        "</asset>\n        </extra>",
+        "</asset>\n            <technique profile=\"scanner\" />\n        </extra>",
+    );
+    let document = Collada::from_str(&with_technique).unwrap();
+    assert_eq!("scanner", document.extras[0].techniques[0].profile);
+}
+
+#[test]
+fn builder_round_trip() {
+    let positions = Source {
+        id: "triangle-positions".into(),
+        name: None,
+        asset: None,
+        array: Some(Array::Float(FloatArray {
+            count: 9,
+            id: Some("triangle-positions-array".into()),
+            name: None,
+            digits: 6,
+            magnitude: 38,
+            data: vec![0.0, 0.0, 0.0, 1.0, 0.0, 0.0, 0.0, 1.0, 0.5],
+        })),
+        technique_common: Some(SourceTechniqueCommon {
+            accessor: Accessor {
+                count: 3,
+                offset: 0,
+                source: "#triangle-positions-array".parse().unwrap(),
+                stride: 3,
+                params: ["X", "Y", "Z"].iter()
+                    .map(|&name| Param {
+                        name: Some(name.into()),
+                        sid: None,
+                        data_type: Some("float".into()),
+                        semantic: None,
+                    })
+                    .collect(),
+            },
+        }),
+        techniques: Vec::new(),
+        position: Default::default(),
+    };
+
+    let mesh = Mesh {
+        sources: vec![positions],
+        vertices: Vertices {
+            id: "triangle-vertices".into(),
+            name: None,
+            inputs: vec![UnsharedInput {
+                semantic: "POSITION".into(),
+                source: "#triangle-positions".parse().unwrap(),
+            }],
+            extras: Vec::new(),
+        },
+        primitives: vec![Primitive::Polylist(Polylist {
+            name: None,
+            count: 1,
+            material: None,
+            inputs: vec![SharedInput {
+                offset: 0,
+                semantic: "VERTEX".into(),
+                source: "#triangle-vertices".parse().unwrap(),
+                set: None,
+            }],
+            vcount: Some(vec![3].into()),
+            primitives: Some(vec![0, 1, 2].into()),
+            extras: Vec::new(),
+        })],
+        extras: Vec::new(),
+    };
+
+    let document = Collada::builder()
+        .push_geometry(Geometry {
+            id: Some("triangle".into()),
+            name: Some("Triangle".into()),
+            asset: None,
+            geometric_element: GeometricElement::Mesh(mesh),
+            extra: Vec::new(),
+            position: Default::default(),
+        })
+        .build();
+    assert_eq!("1.4.1", document.version);
+    assert_eq!(document.asset.created, document.asset.modified);
+    assert_eq!(1, document.libraries.len());
+
+    let mut output = Vec::new();
+    document.write(&mut output).unwrap();
+    let reparsed = Collada::read(&*output).unwrap();
+    assert_eq!(document, reparsed);
+
+    let library = reparsed.libraries[0].as_library_geometries().unwrap();
+    let mesh = library.geometries[0].geometric_element.as_mesh().unwrap();
+    assert_eq!(3, mesh.resolved_vertices().unwrap().len());
+}
+
+#[test]
+fn write_round_trip() {
+    let document = Collada::from_path("resources/blender_cube.dae").unwrap();
+
+    let mut output = Vec::new();
+    document.write(&mut output).unwrap();
+    assert_eq!(document, Collada::read(&*output).unwrap());
+}
+
+#[test]
+fn check_unique_ids() {
+    static DOCUMENT: &'static str = r##"
+    <?xml version="1.0" encoding="utf-8"?>
+    <COLLADA xmlns="http://www.collada.org/2005/11/COLLADASchema" version="1.4.1">
+        <asset>
+            <created>2017-02-07T20:44:30Z</created>
+            <modified>2017-02-07T20:44:30Z</modified>
+        </asset>
+        <library_geometries>
+            <geometry id="mesh">
+                <mesh>
+                    <source id="dup">
+                        <float_array id="positions" count="3">0 0 0</float_array>
+                    </source>
+                    <source id="dup">
+                        <float_array id="normals" count="3">0 0 1</float_array>
+                    </source>
+                    <vertices id="vertices">
+                        <input semantic="POSITION" source="#dup"/>
+                    </vertices>
+                </mesh>
+            </geometry>
+        </library_geometries>
+    </COLLADA>
+    "##;
+
+    let document = Collada::from_str(DOCUMENT).unwrap();
+    let duplicates = document.check_unique_ids().unwrap_err();
+    assert_eq!(1, duplicates.len());
+
+    let (ref id, ref positions) = duplicates[0];
+    assert_eq!("dup", id);
+    assert_eq!(2, positions.len());
+    assert!(positions[0].row < positions[1].row);
+
+    // Scanning the source reports positions within it.
+    let duplicates = Collada::check_source_unique_ids(DOCUMENT.as_bytes()).unwrap_err();
+    let positions = vec![TextPosition { row: 10, column: 20 }, TextPosition { row: 13, column: 20 }];
+    assert_eq!(vec![("dup".to_string(), positions)], duplicates);
+
+    // Ids within elements that aren't parsed yet are only found in the source.
+    let stubbed = DOCUMENT.replace(
+        "<library_geometries>",
+        "<library_lights><light id=\"mesh\"/></library_lights><library_geometries>",
+    );
+    assert_eq!(1, Collada::from_str(&stubbed).unwrap().check_unique_ids().unwrap_err().len());
+    assert_eq!(2, Collada::check_source_unique_ids(stubbed.as_bytes()).unwrap_err().len());
+
+    let document = Collada::from_path("resources/blender_cube.dae").unwrap();
+    assert_eq!(Ok(()), document.check_unique_ids());
+}
+
+#[test]
+fn geometry_accessors() {
+    let document = Collada::from_path("resources/blender_cube.dae").unwrap();
+    let library = document.libraries[5].as_library_geometries().unwrap();
+    let geometry = &library.geometries[0];
+
+    assert_eq!(geometry.geometric_element.as_mesh(), geometry.mesh());
+    assert!(geometry.mesh().is_some());
+    assert!(geometry.convex_mesh().is_none());
+    assert!(geometry.spline().is_none());
+}
+
+#[test]
+fn mesh_primitives_for_material() {
+    static DOCUMENT: &'static str = r##"
+    <?xml version="1.0" encoding="utf-8"?>
+    <COLLADA xmlns="http://www.collada.org/2005/11/COLLADASchema" version="1.4.1">
+        <asset>
+            <created>2017-02-07T20:44:30Z</created>
+            <modified>2017-02-07T20:44:30Z</modified>
+        </asset>
+        <library_geometries>
+            <geometry>
+                <mesh>
+                    <source id="positions">
+                        <float_array id="positions-array" count="12">0 0 0 1 0 0 0 1 0 1 1 0</float_array>
+                    </source>
+                    <vertices id="vertices">
+                        <input semantic="POSITION" source="#positions"/>
+                    </vertices>
+                    <polylist material="red" count="1">
+                        <input semantic="VERTEX" source="#vertices" offset="0"/>
+                        <vcount>3</vcount>
+                        <p>0 1 2</p>
+                    </polylist>
+                    <polylist material="blue" count="1">
+                        <input semantic="VERTEX" source="#vertices" offset="0"/>
+                        <vcount>3</vcount>
+                        <p>1 3 2</p>
+                    </polylist>
+                    <polylist count="1">
+                        <input semantic="VERTEX" source="#vertices" offset="0"/>
+                        <vcount>3</vcount>
+                        <p>0 3 2</p>
+                    </polylist>
+                </mesh>
+            </geometry>
+        </library_geometries>
+    </COLLADA>
+    "##;
+
+    let document = Collada::from_str(DOCUMENT).unwrap();
+    let library = document.libraries[0].as_library_geometries().unwrap();
+    let mesh = library.geometries[0].mesh().unwrap();
+
+    assert_eq!(Some("red"), mesh.primitives[0].material());
+    assert_eq!(None, mesh.primitives[2].material());
+
+    let blue = mesh.primitives_for_material("blue").collect::<Vec<_>>();
+    assert_eq!(vec![&mesh.primitives[1]], blue);
+    assert_eq!(0, mesh.primitives_for_material("green").count());
+}
+
+#[test]
+fn primitive_total_vertices() {
+    static DOCUMENT: &'static str = r##"
+    <?xml version="1.0" encoding="utf-8"?>
+    <COLLADA xmlns="http://www.collada.org/2005/11/COLLADASchema" version="1.4.1">
+        <asset>
+            <created>2017-02-07T20:44:30Z</created>
+            <modified>2017-02-07T20:44:30Z</modified>
+        </asset>
+        <library_geometries>
+            <geometry id="quad">
+                <mesh>
+                    <source id="quad-positions">
+                        <float_array id="quad-positions-array" count="12">0 0 0 1 0 0 1 1 0 0 1 0</float_array>
+                    </source>
+                    <vertices id="quad-vertices">
+                        <input semantic="POSITION" source="#quad-positions"/>
+                    </vertices>
+                    <triangles material="surface" count="2">
+                        <input semantic="VERTEX" source="#quad-vertices" offset="0"/>
+                        <p>0 1 2 0 2 3</p>
+                    </triangles>
+                    <polylist count="0">
+                        <input semantic="VERTEX" source="#quad-vertices" offset="0"/>
+                    </polylist>
+                </mesh>
+            </geometry>
+        </library_geometries>
+    </COLLADA>
+    "##;
+
+    // The cube is a polylist of 12 triangles.
+    let cube = Collada::from_path("resources/blender_cube.dae").unwrap();
+    let mesh = cube.geometry_by_id("Cube-mesh").unwrap().mesh().unwrap();
+    assert_eq!(36, mesh.primitives[0].as_polylist().unwrap().total_vertices());
+    assert_eq!(36, mesh.primitives.iter().map(Primitive::total_vertices).sum::<usize>());
+
+    let document = Collada::from_str(DOCUMENT).unwrap();
+    let mesh = document.geometry_by_id("quad").unwrap().mesh().unwrap();
+    let triangles = mesh.primitives[0].as_triangles().unwrap();
+    assert_eq!(6, triangles.total_vertices());
+    assert_eq!(triangles.primitives.as_ref().unwrap().len(), triangles.total_vertices());
+    assert_eq!(6, mesh.primitives[0].total_vertices());
+    assert_eq!(2, mesh.primitives[0].count());
+    assert_eq!(Some("surface"), mesh.primitives[0].material());
+    assert_eq!(1, mesh.primitives[0].inputs().len());
+
+    // A polylist without a `<vcount>` doesn't have any vertices.
+    assert_eq!(0, mesh.primitives[1].total_vertices());
+}
+
+#[test]
+fn primitive_downcasts() {
+    let document = Collada::from_path("resources/blender_cube.dae").unwrap();
+    let library = document.libraries[5].as_library_geometries().unwrap();
+    let polylist = &library.geometries[0].mesh().unwrap().primitives[0];
+
+    assert!(polylist.as_polylist().is_some());
+    assert!(polylist.as_lines().is_none());
+    assert!(polylist.as_linestrips().is_none());
+    assert!(polylist.as_polygons().is_none());
+    assert!(polylist.as_triangles().is_none());
+    assert!(polylist.as_trifans().is_none());
+    assert!(polylist.as_tristrips().is_none());
+    assert_eq!(12, polylist.count());
+    assert_eq!(2, polylist.inputs().len());
+
+    let primitives = [
+        Primitive::Lines(Lines),
+        Primitive::Linestrips(Linestrips),
+        Primitive::Polygons(Polygons),
+        Primitive::Triangles(Triangles {
+            name: None,
+            count: 0,
+            material: None,
+            inputs: Vec::new(),
+            primitives: None,
+            extras: Vec::new(),
+        }),
+        Primitive::Trifans(Trifans),
+        Primitive::Tristrips(Tristrips),
+    ];
+    for (index, primitive) in primitives.iter().enumerate() {
+        let matches = [
+            primitive.as_lines().is_some(),
+            primitive.as_linestrips().is_some(),
+            primitive.as_polygons().is_some(),
+            primitive.as_triangles().is_some(),
+            primitive.as_trifans().is_some(),
+            primitive.as_tristrips().is_some(),
+        ];
+        for (other, &matched) in matches.iter().enumerate() {
+            assert_eq!(index == other, matched, "Unexpected downcast result for {:?}", primitive);
+        }
+
+        assert!(primitive.as_polylist().is_none());
+        assert_eq!(0, primitive.count());
+        assert!(primitive.inputs().is_empty());
+    }
+}
+
+#[test]
+fn effect_params() {
+    static DOCUMENT: &'static str = r##"
+    <?xml version="1.0" encoding="utf-8"?>
+    <COLLADA xmlns="http://www.collada.org/2005/11/COLLADASchema" version="1.4.1">
+        <asset>
+            <created>2017-02-07T20:44:30Z</created>
+            <modified>2017-02-07T20:44:30Z</modified>
+        </asset>
+        <library_effects>
+            <effect id="wood-effect">
+                <profile_COMMON>
+                    <newparam sid="wood-surface">
+                        <surface type="2D">
+                            <init_from>wood-image</init_from>
+                            <format>A8R8G8B8</format>
+                        </surface>
+                    </newparam>
+                    <newparam sid="wood-sampler">
+                        <sampler2D>
+                            <source>wood-surface</source>
+                            <wrap_s>WRAP</wrap_s>
+                            <minfilter>LINEAR_MIPMAP_LINEAR</minfilter>
+                        </sampler2D>
+                    </newparam>
+                    <technique sid="common">
+                        <newparam sid="shininess">
+                            <semantic>SHININESS</semantic>
+                            <float>50</float>
+                        </newparam>
+                        <lambert>
+                            <diffuse>
+                                <texture texture="wood-sampler" texcoord="UVMap"/>
+                            </diffuse>
+                        </lambert>
+                    </technique>
+                </profile_COMMON>
+            </effect>
+        </library_effects>
+        <library_materials>
+            <material id="wood-material">
+                <instance_effect url="#wood-effect">
+                    <setparam ref="shininess">
+                        <float>20</float>
+                    </setparam>
+                </instance_effect>
+            </material>
+        </library_materials>
+    </COLLADA>
+    "##;
+
+    let document = Collada::from_str(DOCUMENT).unwrap();
+    let effects = document.libraries[0].as_library_effects().unwrap();
+    let profile = effects.effects[0].profile_common().unwrap();
+
+    let surface = profile.new_params[0].value.as_surface().unwrap();
+    assert_eq!("2D", surface.surface_type);
+    assert_eq!("wood-image", surface.init_from[0].image);
+    assert_eq!(Some("A8R8G8B8".into()), surface.format);
+
+    let sampler = profile.new_params[1].value.as_sampler_2d().unwrap();
+    assert_eq!("wood-surface", sampler.source);
+    assert_eq!(Some("WRAP".into()), sampler.wrap_s);
+    assert_eq!(None, sampler.wrap_t);
+
+    let shininess = profile.find_param("shininess").unwrap();
+    assert_eq!(Some("SHININESS".into()), shininess.semantic);
+    match shininess.value {
+        ParamValue::Float(ref float) => assert_eq!(50.0, float.value),
+        ref value => panic!("Expected a float, got {:?}", value),
+    }
+
+    let texture = match profile.technique.shader {
+        Shader::Lambert(ref lambert) => match lambert.diffuse.as_ref().unwrap().value {
+            ColorOrTexture::Texture(ref texture) => texture.clone(),
+            ref value => panic!("Expected a texture, got {:?}", value),
+        },
+        ref shader => panic!("Expected a lambert shader, got {:?}", shader),
+    };
+    assert_eq!(Some("wood-image"), profile.image_for_texture(&texture));
+
+    let materials = document.libraries[1].as_library_materials().unwrap();
+    let set_param = &materials.materials[0].instance_effect.set_params[0];
+    assert_eq!("shininess", set_param.reference);
+    match set_param.value {
+        ParamValue::Float(ref float) => assert_eq!(20.0, float.value),
+        ref value => panic!("Expected a float, got {:?}", value),
+    }
+
+    let mut output = Vec::new();
+    document.write(&mut output).unwrap();
+    assert_eq!(document, Collada::read(&*output).unwrap());
+}
+
+#[test]
+fn effect_params_other_types() {
+    static DOCUMENT: &'static str = r##"
+    <?xml version="1.0" encoding="utf-8"?>
+    <COLLADA xmlns="http://www.collada.org/2005/11/COLLADASchema" version="1.4.1">
+        <asset>
+            <created>2017-02-07T20:44:30Z</created>
+            <modified>2017-02-07T20:44:30Z</modified>
+        </asset>
+        <library_effects>
+            <effect id="effect">
+                <profile_COMMON>
+                    <newparam sid="count">
+                        <int>4</int>
+                    </newparam>
+                    <newparam sid="target">
+                        <surface type="2D">
+                            <init_as_null/>
+                            <format>A8R8G8B8</format>
+                        </surface>
+                    </newparam>
+                    <technique sid="common">
+                        <constant/>
+                    </technique>
+                </profile_COMMON>
+            </effect>
+        </library_effects>
+        <library_materials>
+            <material id="material">
+                <instance_effect url="#effect">
+                    <setparam ref="enabled">
+                        <bool>true</bool>
+                    </setparam>
+                </instance_effect>
+            </material>
+        </library_materials>
+    </COLLADA>
+    "##;
+
+    let document = Collada::from_str(DOCUMENT).unwrap();
+    let effects = document.libraries[0].as_library_effects().unwrap();
+    let profile = effects.effects[0].profile_common().unwrap();
+
+    match profile.new_params[0].value {
+        ParamValue::Other(ref other) => assert_eq!("int", other.name),
+        ref value => panic!("Expected an int, got {:?}", value),
+    }
+
+    let surface = profile.new_params[1].value.as_surface().unwrap();
+    assert!(surface.init_as_null.is_some());
+    assert!(surface.init_from.is_empty());
+
+    let materials = document.libraries[1].as_library_materials().unwrap();
+    match materials.materials[0].instance_effect.set_params[0].value {
+        ParamValue::Other(ref other) => assert_eq!("bool", other.name),
+        ref value => panic!("Expected a bool, got {:?}", value),
+    }
+
+    let mut output = Vec::new();
+    document.write(&mut output).unwrap();
+    assert_eq!(document, Collada::read(&*output).unwrap());
+}
+
+#[test]
+fn collada_asset_helpers() {
+    let document = Collada::from_path("resources/blender_cube.dae").unwrap();
+
+    assert_eq!(
+        vec!["Blender 2.78.0 commit date:2016-10-24, commit time:12:20, hash:e8299c8"],
+        document.authoring_tools(),
+    );
+    assert_eq!("2017-02-01T09:29:54".parse::<DateTime>().unwrap(), document.created());
+    assert_eq!(document.asset.modified, document.modified());
+    assert_eq!(None, document.title());
+}
+
+#[test]
+fn resolve_instance_node() {
+    static DOCUMENT: &'static str = r##"
+    <?xml version="1.0" encoding="utf-8"?>
+    <COLLADA xmlns="http://www.collada.org/2005/11/COLLADASchema" version="1.4.1">
+        <asset>
+            <created>2017-02-07T20:44:30Z</created>
+            <modified>2017-02-07T20:44:30Z</modified>
+        </asset>
+        <library_nodes>
+            <node id="wheel" name="Wheel">
+                <node id="hubcap" name="Hubcap" />
+            </node>
+        </library_nodes>
+        <library_visual_scenes>
+            <visual_scene id="scene">
+                <node id="car" name="Car">
+                    <instance_node url="#wheel" name="Front Wheel" />
+                    <instance_node url="#hubcap" />
+                    <instance_node url="#body" />
+                    <instance_node url="#missing" />
+                    <instance_node url="parts.dae#wheel" />
+                </node>
+                <node id="body" name="Body" />
+            </visual_scene>
+        </library_visual_scenes>
+    </COLLADA>
+    "##;
+
+    let document = Collada::from_str(DOCUMENT).unwrap();
+    let library = document.libraries[0].as_library_nodes().unwrap();
+    assert_eq!(1, library.nodes.len());
+
+    let scenes = document.libraries[1].as_library_visual_scenes().unwrap();
+    let instances = &scenes.visual_scenes[0].nodes[0].instance_nodes;
+    assert_eq!(Some("wheel"), instances[0].url.local_id());
+    assert_eq!(Some("Front Wheel".into()), instances[0].name);
+
+    let resolve_name = |instance| document.resolve_node(instance).and_then(|node| node.name.as_deref());
+    assert_eq!(Some("Wheel"), resolve_name(&instances[0]));
+    assert_eq!(Some("Hubcap"), resolve_name(&instances[1]));
+    assert_eq!(Some("Body"), resolve_name(&instances[2]));
+    assert_eq!(None, resolve_name(&instances[3]));
+
+    // Nodes in other documents aren't loaded, so they can't be resolved.
+    assert_eq!("parts.dae#wheel", instances[4].url.as_str());
+    assert_eq!(None, instances[4].url.local_id());
+    assert_eq!(None, resolve_name(&instances[4]));
+}
+
+#[test]
+fn technique_reparse_child() {
+    static DOCUMENT: &'static str = r#"
+    <?xml version="1.0" encoding="utf-8"?>
+    <COLLADA xmlns="http://www.collada.org/2005/11/COLLADASchema" version="1.4.1">
+        <asset>
+            <created>2017-02-07T20:44:30Z</created>
+            <modified>2017-02-07T20:44:30Z</modified>
+        </asset>
+        <extra>
+            <technique profile="VENDOR">
+                <note>Measured in inches</note>
+                <unit meter="0.0254" name="inch" />
+                <scale meter="one" name="broken" />
+            </technique>
+        </extra>
+    </COLLADA>
+    "#;
+
+    let document = Collada::from_str(DOCUMENT).unwrap();
+    let technique = &document.extras[0].techniques[0];
+
+    let unit = technique.reparse_child::<Unit>("unit").unwrap().unwrap();
+    assert_eq!(Unit { meter: 0.0254, name: "inch".into() }, unit);
+
+    assert!(technique.reparse_child::<Unit>("missing").is_none());
+
+    let error = technique.reparse_child::<Unit>("scale").unwrap().unwrap_err();
+    match error.kind {
+        ErrorKind::ParseFloatError(_) => {}
+        ref kind => panic!("Expected a float parsing error, got {:?}", kind),
+    }
+}
+
+#[test]
+fn mesh_to_indexed() {
+    let document = Collada::from_path("resources/blender_cube.dae").unwrap();
+    let library = document.libraries[5].as_library_geometries().unwrap();
+    let mesh = library.geometries[0].mesh().unwrap();
+
+    // Blender exports one normal per triangle, and a few faces have triangles whose normals
+    // differ in the last bits, so those corners don't merge.
+    let indexed = mesh.to_indexed().unwrap();
+    assert_eq!(32, indexed.positions.len());
+    assert_eq!(Some(32), indexed.normals.as_ref().map(Vec::len));
+    assert_eq!(None, indexed.texcoords);
+    assert_eq!(36, indexed.indices.len());
+
+    // Every corner still resolves to the same attributes after indexing.
+    let vertices = mesh.resolved_vertices().unwrap();
+    for (vertex, &index) in vertices.iter().zip(indexed.indices.iter()) {
+        assert_eq!(vertex.position, indexed.positions[index as usize]);
+        assert_eq!(vertex.normal, indexed.normals.as_ref().map(|normals| normals[index as usize]));
+    }
+}
+
+static TRIANGLES_DOCUMENT: &'static str = r##"
+<?xml version="1.0" encoding="utf-8"?>
+<COLLADA xmlns="http://www.collada.org/2005/11/COLLADASchema" version="1.4.1">
+    <asset>
+        <created>2017-02-07T20:44:30Z</created>
+        <modified>2017-02-07T20:44:30Z</modified>
+    </asset>
+    <library_geometries>
+        <geometry id="quad">
+            <mesh>
+                <source id="quad-positions">
+                    <float_array id="quad-positions-array" count="12">0 0 0 1 0 0 1 1 0 0 1 0</float_array>
+                    <technique_common>
+                        <accessor source="#quad-positions-array" count="4" stride="3">
+                            <param name="X" type="float"/>
+                            <param name="Y" type="float"/>
+                            <param name="Z" type="float"/>
+                        </accessor>
+                    </technique_common>
+                </source>
+                <vertices id="quad-vertices">
+                    <input semantic="POSITION" source="#quad-positions"/>
+                </vertices>
+                <triangles count="2">
+                    <input semantic="VERTEX" source="#quad-vertices" offset="0"/>
+                    <p>0 1 2 0 2 3</p>
+                </triangles>
+            </mesh>
+        </geometry>
+    </library_geometries>
+</COLLADA>
+"##;
+
+#[test]
+fn triangles_iter() {
+    let document = Collada::from_str(TRIANGLES_DOCUMENT).unwrap();
+    let mesh = document.geometry_by_id("quad").unwrap().mesh().unwrap();
+    let triangles = mesh.primitives[0].as_triangles().unwrap();
+
+    let corners = triangles.iter()
+        .map(|triangle| {
+            let indices = triangle.iter().map(|vertex| vertex.iter().next().unwrap().index);
+            indices.collect::<Vec<_>>()
+        })
+        .collect::<Vec<_>>();
+    assert_eq!(vec![vec![0, 1, 2], vec![0, 2, 3]], corners);
+    assert_eq!(2, mesh.primitives[0].triangulate().unwrap().count());
+
+    // Triangles are resolved and indexed the same way as polylists.
+    let vertices = mesh.resolved_vertices().unwrap();
+    assert_eq!(6, vertices.len());
+    assert_eq!([1.0, 1.0, 0.0], vertices[2].position);
+    let indexed = mesh.to_indexed().unwrap();
+    assert_eq!(4, indexed.positions.len());
+    assert_eq!(vec![0, 1, 2, 0, 2, 3], indexed.indices);
+    let generated = mesh.with_generated_normals(false).unwrap();
+    assert_eq!(Some(&[0.0, 0.0, 1.0]), generated.normals.as_ref().map(|normals| &normals[0]));
+}
+
+#[test]
+fn triangles_truncated_primitives() {
+    let document = TRIANGLES_DOCUMENT.replace("<p>0 1 2 0 2 3</p>", "<p>0 1 2 0 2</p>");
+    let error = Collada::from_str(&document).unwrap_err();
+    assert_eq!(
+        ErrorKind::InvalidValue {
+            element: "p",
+            value: "5 indices where 6 were expected".into(),
+        },
+        error.kind,
+    );
+
+    // Triangles modified after parsing are checked when iterating.
+    let document = Collada::from_str(TRIANGLES_DOCUMENT).unwrap();
+    let mesh = document.geometry_by_id("quad").unwrap().mesh().unwrap();
+    let mut triangles = mesh.primitives[0].as_triangles().unwrap().clone();
+    triangles.count = 3;
+    assert!(triangles.try_iter().is_err());
+}
+
+#[test]
+fn stub_like_named_descendant() {
+    static DOCUMENT: &'static str = r#"
+    <?xml version="1.0" encoding="utf-8"?>
+    <COLLADA xmlns="http://www.collada.org/2005/11/COLLADASchema" version="1.4.1">
+        <asset>
+            <created>2017-02-07T20:44:30Z</created>
+            <modified>2017-02-07T20:44:30Z</modified>
+        </asset>
+        <library_lights>
+            <light id="light">
+                <extra>
+                    <technique profile="VENDOR">
+                        <library_lights><light /></library_lights>
+                    </technique>
+                </extra>
+            </light>
+            <light id="other" />
+        </library_lights>
+        <library_nodes>
+            <node id="wheel" />
+        </library_nodes>
+    </COLLADA>
+    "#;
+
+    let document = Collada::from_str(DOCUMENT).unwrap();
+    assert_eq!(2, document.libraries.len());
+    let nodes = document.libraries[1].as_library_nodes().unwrap();
+    assert_eq!(Some("wheel"), nodes.nodes[0].id.as_deref());
+}
+
+#[test]
+fn accessor_external_source() {
+    static DOCUMENT: &'static str = r##"
+    <?xml version="1.0" encoding="utf-8"?>
+    <COLLADA xmlns="http://www.collada.org/2005/11/COLLADASchema" version="1.4.1">
+        <asset>
+            <created>2017-02-07T20:44:30Z</created>
+            <modified>2017-02-07T20:44:30Z</modified>
+        </asset>
+        <library_geometries>
+            <geometry>
+                <mesh>
+                    <source id="positions">
+                        <float_array id="array" count="3">0 0 1</float_array>
+                        <technique_common>
+                            <accessor source="file://data.bin#array" count="1" stride="3" />
+                        </technique_common>
+                    </source>
+                    <vertices id="vertices">
+                        <input semantic="POSITION" source="#positions" />
+                    </vertices>
+                    <polylist count="1">
+                        <input semantic="VERTEX" source="#vertices" offset="0" />
+                        <vcount>3</vcount>
+                        <p>0 0 0</p>
+                    </polylist>
+                </mesh>
+            </geometry>
+        </library_geometries>
+    </COLLADA>
+    "##;
+
+    let document = Collada::from_str(DOCUMENT).unwrap();
+    let library = document.libraries[0].as_library_geometries().unwrap();
+    let mesh = library.geometries[0].geometric_element.as_mesh().unwrap();
+    let source = mesh.find_source("positions").unwrap();
+
+    let uri: AnyUri = "file://data.bin#array".parse().unwrap();
+    assert_eq!(Some(AccessorSource::External(uri.clone())), source.accessor_source());
+    assert!(source.float_values().is_none());
+    assert_eq!(
+        Err(ResolveError::ExternalSource { source: "positions".into(), uri }),
+        mesh.resolved_vertices(),
+    );
+}
+
+#[test]
+fn collada_semantic_eq() {
+    static DOCUMENT: &'static str = r#"
+    <?xml version="1.0" encoding="utf-8"?>
+    <COLLADA xmlns="http://www.collada.org/2005/11/COLLADASchema" version="1.4.1">
+        <asset>
+            <created>2017-02-07T20:44:30Z</created>
+            <modified>2017-02-07T20:44:30Z</modified>
+        </asset>
+        <extra>
+            <technique profile="VENDOR">
+                <note>Exported on Tuesday</note>
+            </technique>
+        </extra>
+    </COLLADA>
+    "#;
+
+    static OTHER: &'static str = r#"
+    <?xml version="1.0" encoding="utf-8"?>
+    <COLLADA version="1.4.1">
+        <asset>
+            <created>2017-02-07T20:44:30Z</created>
+            <modified>2017-02-07T20:44:30Z</modified>
+        </asset>
+        <extra>
+            <technique profile="VENDOR">
+                <note>Exported on Wednesday</note>
+            </technique>
+        </extra>
+    </COLLADA>
+    "#;
+
+    let document = Collada::from_str(DOCUMENT).unwrap();
+
+    let mut namespaced = document.clone();
+    namespaced.xmlns = Some("http://example.com/schema".into());
+    assert!(document != namespaced);
+    assert!(document.semantic_eq(&namespaced));
+
+    let other = Collada::from_str(OTHER).unwrap();
+    assert!(document != other);
+    assert!(document.semantic_eq(&other));
+
+    let mut renamed = document.clone();
+    renamed.asset.title = Some("Renamed".into());
+    assert!(!document.semantic_eq(&renamed));
+}
+
+#[test]
+fn asset_keyword_list() {
+    static DOCUMENT: &'static str = r#"
+    <?xml version="1.0" encoding="utf-8"?>
+    <COLLADA xmlns="http://www.collada.org/2005/11/COLLADASchema" version="1.4.1">
+        <asset>
+            <created>2017-02-07T20:44:30Z</created>
+            <keywords> foo bar
+                baz </keywords>
+            <modified>2017-02-07T20:44:30Z</modified>
+        </asset>
+    </COLLADA>
+    "#;
+
+    let mut document = Collada::from_str(DOCUMENT).unwrap();
+    assert_eq!(vec!["foo", "bar", "baz"], document.asset.keyword_list());
+
+    document.asset.keywords = None;
+    assert!(document.asset.keyword_list().is_empty());
+}
+
+#[test]
+fn library_animation_clips() {
+    static DOCUMENT: &'static str = r##"
+    <?xml version="1.0" encoding="utf-8"?>
+    <COLLADA xmlns="http://www.collada.org/2005/11/COLLADASchema" version="1.4.1">
+        <asset>
+            <created>2017-02-07T20:44:30Z</created>
+            <modified>2017-02-07T20:44:30Z</modified>
+        </asset>
+        <library_animation_clips>
+            <animation_clip id="walk" name="Walk" start="0.5" end="2.25">
+                <instance_animation url="#legs" />
+                <instance_animation url="#arms" />
+            </animation_clip>
+            <animation_clip id="idle">
+                <instance_animation url="#breathing" />
+            </animation_clip>
+        </library_animation_clips>
+    </COLLADA>
+    "##;
+
+    let document = Collada::from_str(DOCUMENT).unwrap();
+    let library = document.libraries[0].as_library_animation_clips().unwrap();
+
+    let walk = &library.animation_clips[0];
+    assert_eq!(Some("Walk"), walk.name.as_deref());
+    assert_eq!(0.5, walk.start);
+    assert_eq!(Some(2.25), walk.end);
+    assert_eq!(2, walk.instance_animations.len());
+    assert_eq!(Some("arms"), walk.instance_animations[1].url.local_id());
+
+    // A clip without an explicit end runs until the end of its animations.
+    let idle = &library.animation_clips[1];
+    assert_eq!(0.0, idle.start);
+    assert_eq!(None, idle.end);
+}
+
+#[test]
+fn camera_fov() {
+    static DOCUMENT: &'static str = r#"
+    <?xml version="1.0" encoding="utf-8"?>
+    <COLLADA xmlns="http://www.collada.org/2005/11/COLLADASchema" version="1.4.1">
+        <asset>
+            <created>2017-02-07T20:44:30Z</created>
+            <modified>2017-02-07T20:44:30Z</modified>
+        </asset>
+        <library_cameras>
+            <camera id="xfov-only">
+                <optics>
+                    <technique_common>
+                        <perspective>
+                            <xfov sid="xfov">60</xfov>
+                            <znear>0.1</znear>
+                            <zfar>100</zfar>
+                        </perspective>
+                    </technique_common>
+                </optics>
+            </camera>
+            <camera id="yfov-aspect">
+                <optics>
+                    <technique_common>
+                        <perspective>
+                            <yfov>30</yfov>
+                            <aspect_ratio>1.5</aspect_ratio>
+                            <znear>0.1</znear>
+                            <zfar>100</zfar>
+                        </perspective>
+                    </technique_common>
+                </optics>
+            </camera>
+            <camera id="ortho">
+                <optics>
+                    <technique_common>
+                        <orthographic>
+                            <xmag>2</xmag>
+                            <znear>0.1</znear>
+                            <zfar>100</zfar>
+                        </orthographic>
+                    </technique_common>
+                </optics>
+            </camera>
+        </library_cameras>
+    </COLLADA>
+    "#;
+
+    let document = Collada::from_str(DOCUMENT).unwrap();
+    let library = document.libraries[0].as_library_cameras().unwrap();
+
+    // Without an aspect ratio, the viewport's aspect ratio is used.
+    let xfov_only = &library.cameras[0];
+    assert_eq!(Some(60.0), xfov_only.horizontal_fov(2.0));
+    assert_eq!(Some(30.0), xfov_only.vertical_fov(2.0));
+
+    // The camera's own aspect ratio takes precedence over the viewport's.
+    let yfov_aspect = &library.cameras[1];
+    assert_eq!(Some(45.0), yfov_aspect.horizontal_fov(2.0));
+    assert_eq!(Some(30.0), yfov_aspect.vertical_fov(2.0));
+
+    let ortho = &library.cameras[2];
+    assert!(ortho.perspective().is_none());
+    assert_eq!(None, ortho.horizontal_fov(2.0));
+}
+
+#[test]
+fn camera_fov_missing() {
+    let perspective = Perspective {
+        xfov: None,
+        yfov: None,
+        aspect_ratio: None,
+        znear: Znear { sid: None, value: 0.1 },
+        zfar: Zfar { sid: None, value: 100.0 },
+    };
+    let camera = Camera {
+        id: None,
+        name: None,
+        asset: None,
+        optics: Optics {
+            technique_common: OpticsTechniqueCommon {
+                projection: Projection::Perspective(perspective),
+            },
+            techniques: Vec::new(),
+            extras: Vec::new(),
+        },
+        imager: None,
+        extras: Vec::new(),
+    };
+
+    assert_eq!(None, camera.horizontal_fov(1.0));
+    assert_eq!(None, camera.vertical_fov(1.0));
+}
+
+#[test]
+fn mesh_resolve_source_or_vertices() {
+    let document = Collada::from_path("resources/blender_cube.dae").unwrap();
+    let library = document.libraries[5].as_library_geometries().unwrap();
+    let mesh = library.geometries[0].mesh().unwrap();
+
+    match mesh.resolve_source_or_vertices("Cube-mesh-positions") {
+        Some(MeshSourceRef::Source(source)) => assert_eq!("Cube-mesh-positions", source.id),
+        other => panic!("Expected the positions source, got {:?}", other),
+    }
+
+    match mesh.resolve_source_or_vertices("Cube-mesh-vertices") {
+        Some(MeshSourceRef::Vertices(vertices)) => assert_eq!("Cube-mesh-vertices", vertices.id),
+        other => panic!("Expected the mesh's vertices, got {:?}", other),
+    }
+
+    assert_eq!(None, mesh.resolve_source_or_vertices("missing"));
+}
+
+#[test]
+fn parse_options_keep_comments() {
+    static DOCUMENT: &'static str = r#"
+    <?xml version="1.0" encoding="utf-8"?>
+    <!-- Exported by hand -->
+    <COLLADA xmlns="http://www.collada.org/2005/11/COLLADASchema" version="1.4.1">
+        <asset>
+            <!-- The creation time is made up. -->
+            <created>2017-02-07T20:44:30Z</created>
+            <modified>2017-02-07T20:44:30Z</modified>
+            <title><!-- Before -->Commented<!-- After --></title>
+        </asset>
+        <extra>
+            <technique profile="VENDOR">
+                <!-- Vendor note -->
+                <flag/>
+            </technique>
+        </extra>
+    </COLLADA>
+    "#;
+
+    let is_comment = |event: &XmlEvent| match *event {
+        XmlEvent::Comment(ref comment) => comment.trim() == "Vendor note",
+        _ => false,
+    };
+
+    // Comments are discarded by default.
+    let document = Collada::from_str(DOCUMENT).unwrap();
+    assert!(!document.extras[0].techniques[0].data.iter().any(is_comment));
+
+    // Comments in raw technique data are kept, and comments elsewhere are still skipped.
+    let options = ParseOptions { keep_comments: true, ..ParseOptions::default() };
+    let commented = Collada::from_str_with_options(DOCUMENT, options.clone()).unwrap();
+    assert_eq!(Some("Commented"), commented.title());
+    assert_eq!(document.asset, commented.asset);
+    assert!(commented.extras[0].techniques[0].data.iter().any(is_comment));
+
+    // The kept comments are written back out.
+    let mut output = Vec::new();
+    commented.write(&mut output).unwrap();
+    let reparsed = Collada::read_with_options(&*output, options).unwrap();
+    assert_eq!(commented, reparsed);
+}
+
+#[test]
+fn parse_options_keep_whitespace() {
+    static DOCUMENT: &'static str = "
+    <?xml version=\"1.0\" encoding=\"utf-8\"?>
+    <COLLADA xmlns=\"http://www.collada.org/2005/11/COLLADASchema\" version=\"1.4.1\">
+        <asset>
+            <created>
+                2017-02-07T20:44:30Z
+            </created>
+            <modified>2017-02-07T20:44:30Z</modified>
+            <title>  Padded\t</title>
+        </asset>
+        <extra>
+            <technique profile=\"VENDOR\">
+                <!-- Vendor note -->
+                <flag>  on </flag>
+            </technique>
+        </extra>
+    </COLLADA>
+    ";
+
+    // Whitespace is trimmed and dropped by default.
+    let document = Collada::from_str(DOCUMENT).unwrap();
+    let data = &document.extras[0].techniques[0].data;
+    assert!(!data.iter().any(|event| match *event { XmlEvent::Whitespace(_) => true, _ => false }));
+    assert!(data.contains(&XmlEvent::Characters("on".into())));
+
+    // Whitespace in raw technique data is kept exactly, and values elsewhere are still trimmed.
+    let options = ParseOptions { keep_comments: true, keep_whitespace: true, ..ParseOptions::default() };
+    let spaced = Collada::from_str_with_options(DOCUMENT, options.clone()).unwrap();
+    assert_eq!(document.asset, spaced.asset);
+    assert_eq!(Some("Padded"), spaced.title());
+
+    let data = &spaced.extras[0].techniques[0].data;
+    assert_eq!(XmlEvent::Whitespace("\n                ".into()), data[0]);
+    assert_eq!(XmlEvent::Comment(" Vendor note ".into()), data[1]);
+    assert_eq!(XmlEvent::Whitespace("\n                ".into()), data[2]);
+    assert!(data.contains(&XmlEvent::Characters("  on ".into())));
+    assert_eq!(XmlEvent::Whitespace("\n            ".into()), data[data.len() - 1]);
+}
+
+#[test]
+fn library_physics_materials() {
+    static DOCUMENT: &'static str = r#"
+    <?xml version="1.0" encoding="utf-8"?>
+    <COLLADA xmlns="http://www.collada.org/2005/11/COLLADASchema" version="1.4.1">
+        <asset>
+            <created>2017-02-07T20:44:30Z</created>
+            <modified>2017-02-07T20:44:30Z</modified>
+        </asset>
+        <library_physics_materials>
+            <physics_material id="rubber" name="Rubber">
+                <technique_common>
+                    <dynamic_friction sid="dynamic">0.8</dynamic_friction>
+                    <restitution>0.6</restitution>
+                    <static_friction>1.0</static_friction>
+                </technique_common>
+            </physics_material>
+            <physics_material id="ice">
+                <technique_common>
+                    <dynamic_friction>0.02</dynamic_friction>
+                </technique_common>
+            </physics_material>
+        </library_physics_materials>
+    </COLLADA>
+    "#;
+
+    let document = Collada::from_str(DOCUMENT).unwrap();
+    let library = document.libraries[0].as_library_physics_materials().unwrap();
+
+    let rubber = &library.physics_materials[0].technique_common;
+    assert_eq!(
+        DynamicFriction { sid: Some("dynamic".into()), value: 0.8 },
+        rubber.dynamic_friction,
+    );
+    assert_eq!(0.6, rubber.restitution.value);
+    assert_eq!(1.0, rubber.static_friction.value);
+
+    // Missing properties default to zero.
+    let ice = &library.physics_materials[1].technique_common;
+    assert_eq!(0.02, ice.dynamic_friction.value);
+    assert_eq!(Restitution::default(), ice.restitution);
+    assert_eq!(0.0, ice.static_friction.value);
+}
+
+#[test]
+fn library_physics_models() {
+    static DOCUMENT: &'static str = r##"
+    <?xml version="1.0" encoding="utf-8"?>
+    <COLLADA xmlns="http://www.collada.org/2005/11/COLLADASchema" version="1.4.1">
+        <asset>
+            <created>2017-02-07T20:44:30Z</created>
+            <modified>2017-02-07T20:44:30Z</modified>
+        </asset>
+        <library_physics_models>
+            <physics_model id="crate-model">
+                <rigid_body sid="crate" name="Crate">
+                    <technique_common>
+                        <dynamic>1</dynamic>
+                        <mass>2.5</mass>
+                        <mass_frame>
+                            <translate>0 0.5 0</translate>
+                        </mass_frame>
+                        <instance_physics_material url="#wood" />
+                        <shape>
+                            <hollow>0</hollow>
+                            <box>
+                                <half_extents>0.5 0.5 1</half_extents>
+                            </box>
+                        </shape>
+                        <shape>
+                            <sphere>
+                                <radius>0.25</radius>
+                            </sphere>
+                            <translate>0 1 0</translate>
+                        </shape>
+                    </technique_common>
+                </rigid_body>
+            </physics_model>
+        </library_physics_models>
+    </COLLADA>
+    "##;
+
+    let document = Collada::from_str(DOCUMENT).unwrap();
+    let library = document.libraries[0].as_library_physics_models().unwrap();
+    let rigid_body = &library.physics_models[0].rigid_bodies[0];
+    assert_eq!("crate", rigid_body.sid);
+
+    let technique = &rigid_body.technique_common;
+    assert_eq!(Some(Boolean(true)), technique.dynamic.as_ref().map(|dynamic| dynamic.value));
+    assert_eq!(Some(2.5), technique.mass.as_ref().map(|mass| mass.value));
+    assert_eq!(1, technique.mass_frame.as_ref().unwrap().transforms.len());
+    match technique.physics_material {
+        Some(PhysicsMaterialSource::Instance(ref instance)) => assert_eq!(Some("wood"), instance.url.local_id()),
+        ref other => panic!("Expected an instanced physics material, got {:?}", other),
+    }
+
+    assert_eq!(2, technique.shapes.len());
+    assert_eq!(Some(Boolean(false)), technique.shapes[0].hollow.as_ref().map(|hollow| hollow.value));
+    match technique.shapes[0].geometry {
+        Some(ShapeGeometry::Box(ref shape)) => assert_eq!(vec![0.5, 0.5, 1.0], shape.half_extents.data),
+        ref other => panic!("Expected a box shape, got {:?}", other),
+    }
+    match technique.shapes[1].geometry {
+        Some(ShapeGeometry::Sphere(ref shape)) => assert_eq!(vec![0.25], shape.radius.data),
+        ref other => panic!("Expected a sphere shape, got {:?}", other),
+    }
+    assert_eq!(1, technique.shapes[1].transforms.len());
+}
+
+#[test]
+fn library_physics_scenes() {
+    static DOCUMENT: &'static str = r##"
+    <?xml version="1.0" encoding="utf-8"?>
+    <COLLADA xmlns="http://www.collada.org/2005/11/COLLADASchema" version="1.4.1">
+        <asset>
+            <created>2017-02-07T20:44:30Z</created>
+            <modified>2017-02-07T20:44:30Z</modified>
+        </asset>
+        <library_physics_scenes>
+            <physics_scene id="moon">
+                <instance_physics_model url="#lander" />
+                <technique_common>
+                    <gravity sid="gravity">0 -1.62 0</gravity>
+                    <time_step>0.0166</time_step>
+                </technique_common>
+            </physics_scene>
+        </library_physics_scenes>
+        <scene>
+            <instance_physics_scene url="#moon" />
+            <instance_visual_scene url="#surface" />
+        </scene>
+    </COLLADA>
+    "##;
+
+    let document = Collada::from_str(DOCUMENT).unwrap();
+    let library = document.libraries[0].as_library_physics_scenes().unwrap();
+    let physics_scene = &library.physics_scenes[0];
+    assert_eq!(Some("lander"), physics_scene.instance_physics_models[0].url.local_id());
+
+    let technique = &physics_scene.technique_common;
+    assert_eq!(Some([0.0, -1.62, 0.0]), technique.gravity.as_ref().map(Gravity::vector));
+    assert_eq!(Some(0.0166), technique.time_step.as_ref().map(|time_step| time_step.value));
+
+    let scene = document.scene.as_ref().unwrap();
+    let instance = scene.instance_visual_scene.as_ref().unwrap();
+    assert_eq!(Some("surface"), instance.url.local_id());
+    let resolved = document.resolve_physics_scene(&scene.instance_physics_scenes[0]);
+    assert_eq!(Some(physics_scene), resolved);
+}
+
+#[test]
+fn library_force_fields() {
+    static DOCUMENT: &'static str = r##"
+    <?xml version="1.0" encoding="utf-8"?>
+    <COLLADA xmlns="http://www.collada.org/2005/11/COLLADASchema" version="1.4.1">
+        <asset>
+            <created>2017-02-07T20:44:30Z</created>
+            <modified>2017-02-07T20:44:30Z</modified>
+        </asset>
+        <library_force_fields>
+            <force_field id="wind" name="Wind">
+                <technique profile="PhysX">
+                    <direction>1 0 0</direction>
+                    <strength>4.5</strength>
+                </technique>
+            </force_field>
+        </library_force_fields>
+        <library_physics_scenes>
+            <physics_scene id="storm">
+                <instance_force_field url="#wind" />
+                <technique_common />
+            </physics_scene>
+        </library_physics_scenes>
+    </COLLADA>
+    "##;
+
+    let document = Collada::from_str(DOCUMENT).unwrap();
+    let library = document.libraries[0].as_library_force_fields().unwrap();
+    let force_field = &library.force_fields[0];
+    assert_eq!(Some("wind"), force_field.id.as_deref());
+    assert_eq!(Some("Wind"), force_field.name.as_deref());
+    assert_eq!(1, force_field.techniques.len());
+    assert_eq!("PhysX", force_field.techniques[0].profile);
+
+    let physics_scene = &document.libraries[1].as_library_physics_scenes().unwrap().physics_scenes[0];
+    let resolved = document.resolve_force_field(&physics_scene.instance_force_fields[0]);
+    assert_eq!(Some(force_field), resolved);
+    assert_eq!(None, document.libraries[1].as_library_force_fields());
+}
+
+#[test]
+fn scene_graph_blender_cube() {
+    let document = Collada::from_path("resources/blender_cube.dae").unwrap();
+    let scene_graph = document.scene_graph().unwrap();
+    assert_eq!(3, scene_graph.instances.len());
+
+    let meshes = scene_graph.instances.iter()
+        .filter_map(|instance| match instance.object {
+            SceneObject::Geometry(geometry) => Some((instance, geometry)),
+            _ => None,
+        })
+        .collect::<Vec<_>>();
+    assert_eq!(1, meshes.len());
+
+    let (instance, geometry) = meshes[0];
+    assert_eq!(Some("Cube-mesh"), geometry.id.as_deref());
+    assert_eq!(Some("Cube"), instance.node.id.as_deref());
+    assert_eq!(&[2], instance.path.indices());
+    assert_eq!([
+        1.0, 0.0, 0.0, 0.0,
+        0.0, 1.0, 0.0, 0.0,
+        0.0, 0.0, 1.0, 0.0,
+        0.0, 0.0, 0.0, 1.0,
+    ], instance.transform);
+    assert_eq!(1, scene_graph.instances_at(&instance.path).count());
+
+    let bind_material = instance.node.instance_geometries[0].bind_material.as_ref().unwrap();
+    let instance_material = &bind_material.technique_common.instance_materials[0];
+    assert_eq!("Material-material", instance_material.symbol);
+    assert_eq!("Material-material", instance_material.target.id());
+
+    match scene_graph.instances[0].object {
+        SceneObject::Camera(camera) => assert_eq!(Some("Camera-camera"), camera.id.as_deref()),
+        ref object => panic!("Expected the camera, found {:?}", object),
+    }
+}
+
+#[test]
+fn scene_graph_external_instances() {
+    static DOCUMENT: &'static str = r##"
+    <?xml version="1.0" encoding="utf-8"?>
+    <COLLADA xmlns="http://www.collada.org/2005/11/COLLADASchema" version="1.4.1">
+        <asset>
+            <created>2017-02-07T20:44:30Z</created>
+            <modified>2017-02-07T20:44:30Z</modified>
+        </asset>
+        <library_cameras>
+            <camera id="camera">
+                <optics>
+                    <technique_common>
+                        <perspective>
+                            <yfov>45</yfov>
+                            <znear>0.1</znear>
+                            <zfar>100</zfar>
+                        </perspective>
+                    </technique_common>
+                </optics>
+            </camera>
+        </library_cameras>
+        <library_visual_scenes>
+            <visual_scene id="scene">
+                <node id="car">
+                    <instance_camera url="#camera" />
+                    <instance_camera url="cameras.dae#chase" />
+                    <instance_geometry url="parts.dae#body" />
+                    <instance_node url="parts.dae#wheel" />
+                </node>
+            </visual_scene>
+        </library_visual_scenes>
+        <scene>
+            <instance_visual_scene url="#scene" />
+        </scene>
+    </COLLADA>
+    "##;
+
+    let document = Collada::from_str(DOCUMENT).unwrap();
+    let scene_graph = document.scene_graph().unwrap();
+    assert_eq!(1, scene_graph.instances.len());
+    match scene_graph.instances[0].object {
+        SceneObject::Camera(camera) => assert_eq!(Some("camera"), camera.id.as_deref()),
+        ref object => panic!("Expected the camera, found {:?}", object),
+    }
+
+    let external = scene_graph.external.iter().map(|url| url.as_str()).collect::<Vec<_>>();
+    assert_eq!(vec!["parts.dae#body", "cameras.dae#chase", "parts.dae#wheel"], external);
+
+    // A visual scene in another document can't be walked at all.
+    let document = Collada::from_str(&DOCUMENT.replace("url=\"#scene\"", "url=\"scenes.dae#scene\""))
+        .unwrap();
+    let scene_graph = document.scene_graph().unwrap();
+    assert!(scene_graph.instances.is_empty());
+    assert_eq!("scenes.dae#scene", scene_graph.external[0].as_str());
+}
+
+#[test]
+fn visual_scene_evaluate_scenes() {
+    static DOCUMENT: &'static str = r##"
+    <?xml version="1.0" encoding="utf-8"?>
+    <COLLADA xmlns="http://www.collada.org/2005/11/COLLADASchema" version="1.4.1">
+        <asset>
+            <created>2017-02-07T20:44:30Z</created>
+            <modified>2017-02-07T20:44:30Z</modified>
+        </asset>
+        <library_visual_scenes>
+            <visual_scene id="Scene">
+                <node id="Camera" />
+                <node id="Backdrop" layer="background" />
+                <evaluate_scene name="beauty">
+                    <render camera_node="#Camera">
+                        <layer>background</layer>
+                        <layer>foreground</layer>
+                    </render>
+                    <render camera_node="#Camera">
+                        <instance_effect url="#outline-effect" />
+                    </render>
+                </evaluate_scene>
+                <evaluate_scene name="preview">
+                    <render camera_node="#Camera" />
+                </evaluate_scene>
+            </visual_scene>
+        </library_visual_scenes>
+    </COLLADA>
+    "##;
+
+    let document = Collada::from_str(DOCUMENT).unwrap();
+    let library = document.libraries[0].as_library_visual_scenes().unwrap();
+    let evaluate_scenes = &library.visual_scenes[0].evaluate_scenes;
+    assert_eq!(2, evaluate_scenes.len());
+    assert_eq!(Some("beauty"), evaluate_scenes[0].name.as_deref());
+    assert_eq!(Some("preview"), evaluate_scenes[1].name.as_deref());
+
+    let renders = &evaluate_scenes[0].renders;
+    assert_eq!(2, renders.len());
+    assert_eq!("Camera", renders[0].camera_node.id());
+    assert_eq!(vec!["background", "foreground"], renders[0].layers);
+    assert_eq!(None, renders[0].instance_effect);
+    let effect = renders[1].instance_effect.as_ref().unwrap();
+    assert_eq!(Some("outline-effect"), effect.url.local_id());
+    assert!(renders[1].layers.is_empty());
+
+    let mut output = Vec::new();
+    document.write(&mut output).unwrap();
+    assert_eq!(document, Collada::read(&*output).unwrap());
+}
+
+#[test]
+fn strip_extras() {
+    static DOCUMENT: &'static str = r##"
+    <?xml version="1.0" encoding="utf-8"?>
+    <COLLADA xmlns="http://www.collada.org/2005/11/COLLADASchema" version="1.4.1">
+        <asset>
+            <created>2017-02-07T20:44:30Z</created>
+            <modified>2017-02-07T20:44:30Z</modified>
+        </asset>
+        <library_force_fields>
+            <force_field id="wind">
+                <technique profile="PhysX">
+                    <strength>4.5</strength>
+                </technique>
+                <extra>
+                    <technique profile="PhysX">
+                        <gusts>true</gusts>
+                    </technique>
+                </extra>
+            </force_field>
+            <extra type="library">
+                <technique profile="custom" />
+            </extra>
+        </library_force_fields>
+        <library_visual_scenes>
+            <visual_scene id="scene">
+                <node id="root">
+                    <node id="child">
+                        <extra>
+                            <technique profile="blender">
+                                <layer>1</layer>
+                            </technique>
+                        </extra>
+                    </node>
+                </node>
+            </visual_scene>
+        </library_visual_scenes>
+        <extra>
+            <technique profile="blender" />
+        </extra>
+    </COLLADA>
+    "##;
+
+    let mut document = Collada::from_str(DOCUMENT).unwrap();
+    document.strip_extras();
+    assert!(document.extras.is_empty());
+
+    let library = document.libraries[0].as_library_force_fields().unwrap();
+    assert!(library.extras.is_empty());
+    assert!(library.force_fields[0].extras.is_empty());
+
+    // Techniques required by the schema are kept.
+    assert_eq!("PhysX", library.force_fields[0].techniques[0].profile);
+
+    let visual_scene = &document.libraries[1].as_library_visual_scenes().unwrap().visual_scenes[0];
+    assert!(visual_scene.nodes[0].children[0].extras.is_empty());
+
+    let mut output = Vec::new();
+    document.write(&mut output).unwrap();
+    assert_eq!(document, Collada::read(&*output).unwrap());
+}
+
+#[test]
+fn transform_decompose() {
+    static DOCUMENT: &'static str = r#"
+    <?xml version="1.0" encoding="utf-8"?>
+    <COLLADA xmlns="http://www.collada.org/2005/11/COLLADASchema" version="1.4.1">
+        <asset>
+            <created>2017-02-07T20:44:30Z</created>
+            <modified>2017-02-07T20:44:30Z</modified>
+        </asset>
+        <library_visual_scenes>
+            <visual_scene id="Scene">
+                <node id="Rotated">
+                    <matrix sid="transform">0 -2 0 1 2 0 0 2 0 0 2 3 0 0 0 1</matrix>
+                </node>
+                <node id="Mirrored">
+                    <matrix sid="transform">-1 0 0 0 0 1 0 0 0 0 1 0 0 0 0 1</matrix>
+                    <translate sid="location">4 5 6</translate>
+                </node>
+            </visual_scene>
+        </library_visual_scenes>
+    </COLLADA>
+    "#;
+
+    fn assert_close(expected: &[f64], actual: &[f64]) {
+        for (actual_value, expected_value) in actual.iter().zip(expected.iter()) {
+            assert!((actual_value - expected_value).abs() < 1e-9, "Expected {:?}, got {:?}", expected, actual);
+        }
+    }
+
+    let document = Collada::from_str(DOCUMENT).unwrap();
+    let scene = &document.libraries[0].as_library_visual_scenes().unwrap().visual_scenes[0];
+
+    // A uniform scale of 2, then a rotation of 90 degrees around Z, then a translation.
+    let (translation, rotation, scale) = scene.nodes[0].transforms[0].decompose().unwrap();
+    assert_close(&[1.0, 2.0, 3.0], &translation);
+    let half_sqrt_2 = 0.5f64.sqrt();
+    assert_close(&[0.0, 0.0, half_sqrt_2, half_sqrt_2], &rotation);
+    assert_close(&[2.0, 2.0, 2.0], &scale);
+
+    // Mirroring is reported as a negative scale rather than being folded into the rotation.
+    let (translation, rotation, scale) = scene.nodes[1].transforms[0].decompose().unwrap();
+    assert_close(&[0.0, 0.0, 0.0], &translation);
+    assert_close(&[0.0, 0.0, 0.0, 1.0], &rotation);
+    assert_close(&[-1.0, 1.0, 1.0], &scale);
+
+    assert_eq!(
+        Some(([4.0, 5.0, 6.0], [0.0, 0.0, 0.0, 1.0], [1.0, 1.0, 1.0])),
+        scene.nodes[1].transforms[1].decompose(),
+    );
+}
+
+#[test]
+fn library_filter() {
+    static TEST_DOCUMENT: &'static [u8] = include_bytes!("../resources/blender_cube.dae");
+
+    let options = ParseOptions {
+        libraries: LibraryFilter::Only(vec![LibraryKind::Geometries]),
+        ..ParseOptions::default()
+    };
+    let document = Collada::read_with_options(TEST_DOCUMENT, options).unwrap();
+    assert_eq!(1, document.libraries.len());
+
+    let library = document.libraries[0].as_library_geometries().unwrap();
+    assert_eq!(Some("Cube-mesh"), library.geometries[0].id.as_deref());
+
+    // Everything outside of the libraries is still parsed.
+    assert!(document.scene.is_some());
+
+    let full = Collada::read(TEST_DOCUMENT).unwrap();
+    assert_eq!(full.libraries[5], document.libraries[0]);
+    assert!(full.libraries.len() > document.libraries.len());
+}
+
+#[test]
+fn display_uris() {
+    static DOCUMENT: &'static str = r##"
+    <?xml version="1.0" encoding="utf-8"?>
+    <COLLADA xmlns="http://www.collada.org/2005/11/COLLADASchema" version="1.4.1">
+        <asset>
+            <contributor>
+                <source_data>file:///home/user/models/tank.blend</source_data>
+            </contributor>
+            <created>2017-02-07T20:44:30Z</created>
+            <modified>2017-02-07T20:44:30Z</modified>
+        </asset>
+        <library_visual_scenes>
+            <visual_scene id="scene">
+                <node id="tank">
+                    <instance_geometry url="#tank-mesh" />
+                </node>
+            </visual_scene>
+        </library_visual_scenes>
+    </COLLADA>
+    "##;
+
+    let document = Collada::from_str(DOCUMENT).unwrap();
+
+    let source_data = document.asset.contributors[0].source_data.as_ref().unwrap();
+    assert_eq!("file:///home/user/models/tank.blend", source_data.to_string());
+    assert_eq!(source_data.as_str(), AsRef::<str>::as_ref(source_data));
+
+    let library = document.libraries[0].as_library_visual_scenes().unwrap();
+    let instance = &library.visual_scenes[0].nodes[0].instance_geometries[0];
+    assert_eq!("#tank-mesh", format!("{}", instance.url));
+    assert_eq!(Some("tank-mesh"), instance.url.local_id());
+}
+
+#[test]
+fn asset_unit_defaults() {
+    static DOCUMENT: &'static str = r#"
+    <?xml version="1.0" encoding="utf-8"?>
+    <COLLADA xmlns="http://www.collada.org/2005/11/COLLADASchema" version="1.4.1">
+        <asset>
+            <created>2017-02-07T20:44:30Z</created>
+            <modified>2017-02-07T20:44:30Z</modified>
+            <unit/>
+        </asset>
+        <library_visual_scenes>
+            <visual_scene id="scene">
+                <node id="root">
+                    <asset>
+                        <created>2017-02-07T20:44:30Z</created>
+                        <modified>2017-02-07T20:44:30Z</modified>
+                        <unit meter="0.01" />
+                    </asset>
+                </node>
+            </visual_scene>
+        </library_visual_scenes>
+    </COLLADA>
+    "#;
+
+    let document = Collada::from_str(DOCUMENT).unwrap();
+    assert_eq!(Some(Unit::default()), document.asset.unit);
+
+    // Each attribute falls back to its default independently.
+    let library = document.libraries[0].as_library_visual_scenes().unwrap();
+    let asset = library.visual_scenes[0].nodes[0].asset.as_ref().unwrap();
+    assert_eq!(Some(Unit { meter: 0.01, name: "meter".into() }), asset.unit);
+}
+
+#[test]
+fn asset_chain_overrides() {
+    static DOCUMENT: &'static str = r##"
+    <?xml version="1.0" encoding="utf-8"?>
+    <COLLADA xmlns="http://www.collada.org/2005/11/COLLADASchema" version="1.4.1">
+        <asset>
+            <created>2017-02-07T20:44:30Z</created>
+            <modified>2017-02-07T20:44:30Z</modified>
+            <unit name="centimeter" meter="0.01"/>
+            <up_axis>Z_UP</up_axis>
+        </asset>
+        <library_geometries>
+            <geometry id="meters">
+                <asset>
+                    <created>2017-02-07T20:44:30Z</created>
+                    <modified>2017-02-07T20:44:30Z</modified>
+                    <unit meter="1"/>
+                    <up_axis>Y_UP</up_axis>
+                </asset>
+                <mesh>
+                    <source id="meters-positions">
+                        <float_array id="meters-positions-array" count="3">0 0 0</float_array>
+                    </source>
+                    <vertices id="meters-vertices">
+                        <input semantic="POSITION" source="#meters-positions"/>
+                    </vertices>
+                </mesh>
+            </geometry>
+            <geometry id="inherited">
+                <mesh>
+                    <source id="inherited-positions">
+                        <float_array id="inherited-positions-array" count="3">0 0 0</float_array>
+                    </source>
+                    <vertices id="inherited-vertices">
+                        <input semantic="POSITION" source="#inherited-positions"/>
+                    </vertices>
+                </mesh>
+            </geometry>
+        </library_geometries>
+        <library_visual_scenes>
+            <visual_scene id="scene">
+                <node id="parent">
+                    <asset>
+                        <created>2017-02-07T20:44:30Z</created>
+                        <modified>2017-02-07T20:44:30Z</modified>
+                        <unit name="inch" meter="0.0254"/>
+                    </asset>
+                    <node id="child">
+                        <asset>
+                            <created>2017-02-07T20:44:30Z</created>
+                            <modified>2017-02-07T20:44:30Z</modified>
+                            <up_axis>X_UP</up_axis>
+                        </asset>
+                    </node>
+                </node>
+            </visual_scene>
+        </library_visual_scenes>
+    </COLLADA>
+    "##;
+
+    let document = Collada::from_str(DOCUMENT).unwrap();
+
+    // The geometry's own asset overrides the document's values, even though it declares the
+    // spec's defaults.
+    assert_eq!(2, document.asset_chain("meters").unwrap().len());
+    assert_eq!(Some(Unit::default()), document.effective_unit("meters"));
+    assert_eq!(Some(UpAxis::Y), document.effective_up_axis("meters"));
+
+    // Without an asset of its own, the geometry uses the document's values.
+    assert_eq!(1, document.asset_chain("inherited").unwrap().len());
+    assert_eq!(document.asset.unit, document.effective_unit("inherited"));
+    assert_eq!(Some(0.01), document.effective_unit("inherited").map(|unit| unit.meter));
+    assert_eq!(Some(UpAxis::Z), document.effective_up_axis("inherited"));
+
+    // Nested nodes inherit from every ancestor, with the innermost declaration winning.
+    assert_eq!(3, document.asset_chain("child").unwrap().len());
+    assert_eq!(Some(0.0254), document.effective_unit("child").map(|unit| unit.meter));
+    assert_eq!(Some(UpAxis::X), document.effective_up_axis("child"));
+    assert_eq!(Some(UpAxis::Z), document.effective_up_axis("parent"));
+
+    // A document that doesn't declare anything uses the spec's defaults.
+    let document = Collada::from_str(&DOCUMENT
+        .replace("<unit name=\"centimeter\" meter=\"0.01\"/>", "")
+        .replace("<up_axis>Z_UP</up_axis>", ""))
+        .unwrap();
+    assert_eq!(Some(Unit::default()), document.effective_unit("inherited"));
+    assert_eq!(Some(UpAxis::Y), document.effective_up_axis("parent"));
+
+    assert_eq!(1, document.asset_chain("scene").unwrap().len());
+    assert_eq!(None, document.asset_chain("missing"));
+    assert_eq!(None, document.effective_unit("missing"));
+}
+
+#[test]
+fn lookup_by_id() {
+    let document = Collada::from_path("resources/blender_cube.dae").unwrap();
+
+    let geometry = document.geometry_by_id("Cube-mesh").unwrap();
+    assert_eq!(Some("Cube"), geometry.name.as_deref());
+    assert_eq!(None, document.geometry_by_id("Cube"));
+
+    let material = document.material_by_id("Material-material").unwrap();
+    assert_eq!(Some("Material-effect"), material.instance_effect.url.local_id());
+    assert_eq!("Material-effect", document.effect_by_id("Material-effect").unwrap().id);
+
+    assert_eq!(Some("Lamp"), document.node_by_id("Lamp").and_then(|node| node.name.as_deref()));
+    assert_eq!(None, document.image_by_id("Material-effect"));
+}
+
+#[test]
+fn library_images() {
+    static DOCUMENT: &'static str = r#"
+    <?xml version="1.0" encoding="utf-8"?>
+    <COLLADA xmlns="http://www.collada.org/2005/11/COLLADASchema" version="1.4.1">
+        <asset>
+            <created>2017-02-07T20:44:30Z</created>
+            <modified>2017-02-07T20:44:30Z</modified>
+        </asset>
+        <library_images>
+            <image id="diffuse" name="Diffuse" width="256" height="256">
+                <init_from>textures/diffuse.png</init_from>
+            </image>
+            <image id="pixel" format="RGBA">
+                <data>FF0000FF</data>
+            </image>
+        </library_images>
+    </COLLADA>
+    "#;
+
+    let document = Collada::from_str(DOCUMENT).unwrap();
+    let library = document.libraries[0].as_library_images().unwrap();
+    assert_eq!(2, library.images.len());
+
+    let diffuse = document.image_by_id("diffuse").unwrap();
+    assert_eq!((Some(256), Some(256), 1), (diffuse.width, diffuse.height, diffuse.depth));
+    match diffuse.source {
+        ImageSource::InitFrom(ref init_from) => assert_eq!("textures/diffuse.png", init_from.uri.as_str()),
+        ref source => panic!("Expected `init_from`, got {:?}", source),
+    }
+
+    let pixel = document.image_by_id("pixel").unwrap();
+    assert_eq!(Some("RGBA"), pixel.format.as_deref());
+    assert_eq!(ImageSource::Data(ImageData { hex: "FF0000FF".into() }), pixel.source);
+}
+
+#[test]
+fn instance_material_bindings() {
+    static DOCUMENT: &'static str = r##"
+    <?xml version="1.0" encoding="utf-8"?>
+    <COLLADA xmlns="http://www.collada.org/2005/11/COLLADASchema" version="1.4.1">
+        <asset>
+            <created>2017-02-07T20:44:30Z</created>
+            <modified>2017-02-07T20:44:30Z</modified>
+        </asset>
+        <library_visual_scenes>
+            <visual_scene id="scene">
+                <node id="crate">
+                    <instance_geometry url="#crate-mesh">
+                        <bind_material>
+                            <technique_common>
+                                <instance_material symbol="wood" target="#wood-material">
+                                    <bind semantic="LIGHT_POS" target="lamp/translate" />
+                                    <bind_vertex_input semantic="TEX0" input_semantic="TEXCOORD" input_set="0" />
+                                    <bind_vertex_input semantic="LIGHTMAP" input_semantic="TEXCOORD" input_set="1" />
+                                </instance_material>
+                            </technique_common>
+                        </bind_material>
+                    </instance_geometry>
+                </node>
+            </visual_scene>
+        </library_visual_scenes>
+    </COLLADA>
+    "##;
+
+    let document = Collada::from_str(DOCUMENT).unwrap();
+    let library = document.libraries[0].as_library_visual_scenes().unwrap();
+    let instance = &library.visual_scenes[0].nodes[0].instance_geometries[0];
+    let bind_material = instance.bind_material.as_ref().unwrap();
+    let instance_material = &bind_material.technique_common.instance_materials[0];
+    assert_eq!("wood", instance_material.symbol);
+    assert_eq!("wood-material", instance_material.target.id());
+
+    assert_eq!(Bind { semantic: "LIGHT_POS".into(), target: "lamp/translate".into() }, instance_material.binds[0]);
+
+    let expected = BindVertexInput {
+        semantic: "TEX0".into(),
+        input_semantic: "TEXCOORD".into(),
+        input_set: Some(0),
+    };
+    assert_eq!(Some(&expected), instance_material.bind_vertex_input("TEX0"));
+
+    // Each texture slot can be bound to a different set of texture coordinates.
+    let lightmap = instance_material.bind_vertex_input("LIGHTMAP").unwrap();
+    assert_eq!(Some(1), lightmap.input_set);
+    assert_eq!(None, instance_material.bind_vertex_input("TEX1"));
+}
+
+#[test]
+fn libraries_of() {
+    let document = Collada::from_path("resources/blender_cube.dae").unwrap();
+
+    let geometries = document.libraries_of(Library::as_library_geometries).collect::<Vec<_>>();
+    assert_eq!(1, geometries.len());
+    assert!(::std::ptr::eq(document.libraries[5].as_library_geometries().unwrap(), geometries[0]));
+
+    let scene = document.libraries_of(Library::as_library_visual_scenes).next().unwrap();
+    assert_eq!(Some("Scene"), scene.visual_scenes[0].id.as_deref());
+    assert_eq!(0, document.libraries_of(Library::as_library_force_fields).count());
+}
+
+#[test]
+fn asset_now() {
+    use ::collaborate::chrono::UTC;
+
+    let before = UTC::now().timestamp();
+    let asset = Asset::now();
+    let after = UTC::now().timestamp();
+
+    assert_eq!(asset.created, asset.modified);
+    match asset.created {
+        DateTime::Utc(created) => {
+            assert!(before <= created.timestamp() && created.timestamp() <= after, "{:?} isn't the current time", created);
+        }
+        DateTime::Naive(created) => panic!("Expected a UTC timestamp, got {:?}", created),
+    }
+    assert!(asset.contributors.is_empty());
+    assert_eq!(None, asset.unit);
+
+    let asset = asset.with_tool("collaborate");
+    assert_eq!(1, asset.contributors.len());
+    assert_eq!(Some("collaborate"), asset.contributors[0].authoring_tool.as_deref());
+    assert_eq!(None, asset.contributors[0].author);
+}
+
+#[test]
+fn collada_merge() {
+    static DOCUMENT: &'static str = r##"
+    <?xml version="1.0" encoding="utf-8"?>
+    <COLLADA xmlns="http://www.collada.org/2005/11/COLLADASchema" version="1.4.1">
+        <asset>
+            <created>2017-02-07T20:44:30Z</created>
+            <modified>2017-02-07T20:44:30Z</modified>
+        </asset>
+        <library_geometries>
+            <geometry id="first">
+                <mesh>
+                    <source id="first-positions">
+                        <float_array id="first-positions-array" count="9">0 0 0 1 0 0 0 1 0</float_array>
+                        <technique_common>
+                            <accessor source="#first-positions-array" count="3" stride="3">
+                                <param name="X" type="float"/>
+                                <param name="Y" type="float"/>
+                                <param name="Z" type="float"/>
+                            </accessor>
+                        </technique_common>
+                    </source>
+                    <vertices id="first-vertices">
+                        <input semantic="POSITION" source="#first-positions"/>
+                    </vertices>
+                    <triangles count="1">
+                        <input semantic="VERTEX" source="#first-vertices" offset="0"/>
+                        <p>0 1 2</p>
+                    </triangles>
+                </mesh>
+            </geometry>
+        </library_geometries>
+    </COLLADA>
+    "##;
+
+    let mut document = Collada::from_str(DOCUMENT).unwrap();
+    let other = Collada::from_str(&DOCUMENT.replace("first", "second")).unwrap();
+    document.merge(other).unwrap();
+    assert_eq!(2, document.libraries.len());
+    assert!(document.geometry_by_id("first").is_some());
+    assert!(document.geometry_by_id("second").is_some());
+
+    // Conflicting ids are reported without modifying the document.
+    let other = Collada::from_str(DOCUMENT).unwrap();
+    let conflicts = document.merge(other.clone()).unwrap_err();
+    assert_eq!(
+        vec!["first", "first-positions", "first-positions-array", "first-vertices"],
+        conflicts,
+    );
+    assert_eq!(2, document.libraries.len());
+
+    document.merge_with_prefix(other, "copy-").unwrap();
+    assert_eq!(3, document.libraries.len());
+    assert_eq!(Ok(()), document.check_unique_ids());
+
+    let mesh = document.geometry_by_id("copy-first").unwrap().mesh().unwrap();
+    assert!(mesh.find_source("copy-first-positions").is_some());
+    assert_eq!("copy-first-positions", mesh.vertices.inputs[0].source.id());
+    assert_eq!(Some(([0.0, 0.0, 0.0], [1.0, 1.0, 0.0])), mesh.bounding_box());
+
+    let other = Collada::from_str(DOCUMENT).unwrap();
+    match document.merge_with_prefix(other, "copy-") {
+        Err(MergeError::Conflicts(ids)) => assert_eq!("copy-first", ids[0]),
+        result => panic!("Expected conflicting ids, got {:?}", result),
+    }
+}
+
+#[test]
+fn collada_merge_deep() {
+    // Nesting this deep takes more stack than the default test thread has in a debug build.
+    let thread = std::thread::Builder::new().stack_size(256 * 1024 * 1024).spawn(|| {
+        let source = format!(
+            r#"<?xml version="1.0" encoding="utf-8"?>
+            <COLLADA xmlns="http://www.collada.org/2005/11/COLLADASchema" version="1.4.1">
+                <asset>
+                    <created>2017-02-07T20:44:30Z</created>
+                    <modified>2017-02-07T20:44:30Z</modified>
+                </asset>
+                <library_visual_scenes>
+                    <visual_scene id="scene">{}{}</visual_scene>
+                </library_visual_scenes>
+            </COLLADA>"#,
+            "<node>".repeat(1000),
+            "</node>".repeat(1000),
+        );
+        let options = ParseOptions { max_depth: 2000, ..ParseOptions::default() };
+        let other = Collada::from_str_with_options(&source, options).unwrap();
+
+        // A document deeper than the default limit is still merged rather than rejected.
+        let mut document = Collada::from_str(&source.replace("<node>", "").replace("</node>", ""))
+            .unwrap();
+        document.merge_with_prefix(other, "copy-").unwrap();
+        let library = document.libraries[1].as_library_visual_scenes().unwrap();
+        assert_eq!(Some("copy-scene".into()), library.visual_scenes[0].id);
+    });
+    thread.unwrap().join().unwrap();
+}
+
+#[test]
+fn expected_children_by_name() {
+    assert_eq!(
+        Some(&["author", "authoring_tool", "comments", "copyright", "source_data"][..]),
+        expected_children("contributor"),
+    );
+
+    // Groups of elements are listed in place, matching the parse error for an invalid child.
+    let mesh_children = expected_children("mesh").unwrap();
+    assert_eq!(
+        [
+            "source", "vertices", "lines", "linestrips", "polygons", "polylist", "triangles",
+            "trifans", "tristrips", "extra",
+        ],
+        mesh_children,
+    );
+
+    assert_eq!(Some(&[][..]), expected_children("float_array"));
+    assert_eq!(None, expected_children("input"));
+
+    // `<technique>` is used both in profiles and in `<extra>`, which have different children.
+    assert_eq!(None, expected_children("technique"));
+    assert_eq!(None, expected_children("spline"));
+    assert_eq!(None, expected_children("not_an_element"));
+}
+
+#[test]
+fn polylist_input_by_set() {
+    static DOCUMENT: &'static str = r##"
+    <?xml version="1.0" encoding="utf-8"?>
+    <COLLADA xmlns="http://www.collada.org/2005/11/COLLADASchema" version="1.4.1">
+        <asset>
+            <created>2017-02-07T20:44:30Z</created>
+            <modified>2017-02-07T20:44:30Z</modified>
+        </asset>
+        <library_geometries>
+            <geometry id="lightmapped">
+                <mesh>
+                    <source id="positions">
+                        <float_array id="positions-array" count="9">0 0 0 1 0 0 0 1 0</float_array>
+                    </source>
+                    <source id="uv0">
+                        <float_array id="uv0-array" count="6">0 0 1 0 0 1</float_array>
+                    </source>
+                    <source id="uv1">
+                        <float_array id="uv1-array" count="6">0 0 0.5 0 0 0.5</float_array>
+                    </source>
+                    <vertices id="vertices">
+                        <input semantic="POSITION" source="#positions"/>
+                    </vertices>
+                    <polylist count="1">
+                        <input semantic="VERTEX" source="#vertices" offset="0"/>
+                        <input semantic="TEXCOORD" source="#uv0" offset="1" set="0"/>
+                        <input semantic="TEXCOORD" source="#uv1" offset="2" set="1"/>
+                        <vcount>3</vcount>
+                        <p>0 0 0 1 1 1 2 2 2</p>
+                    </polylist>
+                </mesh>
+            </geometry>
+        </library_geometries>
+    </COLLADA>
+    "##;
+
+    let document = Collada::from_str(DOCUMENT).unwrap();
+    let mesh = document.geometry_by_id("lightmapped").unwrap().mesh().unwrap();
+    let polylist = mesh.primitives[0].as_polylist().unwrap();
+
+    assert_eq!("uv0", polylist.input("TEXCOORD", Some(0)).unwrap().source.id());
+    assert_eq!("uv1", polylist.input("TEXCOORD", Some(1)).unwrap().source.id());
+    assert_eq!(None, polylist.input("TEXCOORD", Some(2)));
+
+    // Without a set, the first input with the semantic is used.
+    assert_eq!("uv0", polylist.input("TEXCOORD", None).unwrap().source.id());
+    assert_eq!("vertices", polylist.input("VERTEX", None).unwrap().source.id());
+    assert_eq!(None, polylist.input("VERTEX", Some(0)));
+    assert_eq!(None, polylist.input("NORMAL", None));
+}
+
+#[test]
+fn mesh_semantics() {
+    let document = Collada::from_path("resources/blender_cube.dae").unwrap();
+    let mesh = document.geometry_by_id("Cube-mesh").unwrap().mesh().unwrap();
+
+    let semantics = mesh.semantics();
+    assert_eq!(2, semantics.len());
+    assert!(semantics.contains("POSITION"));
+    assert!(semantics.contains("NORMAL"));
+    assert!(!semantics.contains("VERTEX"));
+
+    assert!(mesh.has_normals());
+    assert!(!mesh.has_texcoords());
+    assert!(!mesh.has_colors());
+}
+
+#[test]
+fn mesh_with_generated_normals() {
+    // A unit cube without normals, with an extra degenerate triangle at the end.
+    static DOCUMENT: &'static str = r##"
+    <?xml version="1.0" encoding="utf-8"?>
+    <COLLADA xmlns="http://www.collada.org/2005/11/COLLADASchema" version="1.4.1">
+        <asset>
+            <created>2017-02-07T20:44:30Z</created>
+            <modified>2017-02-07T20:44:30Z</modified>
+        </asset>
+        <library_geometries>
+            <geometry id="cube">
+                <mesh>
+                    <source id="cube-positions">
+                        <float_array id="cube-positions-array" count="24">
+                            0 0 0  1 0 0  1 1 0  0 1 0
+                            0 0 1  1 0 1  1 1 1  0 1 1
+                        </float_array>
+                        <technique_common>
+                            <accessor source="#cube-positions-array" count="8" stride="3">
+                                <param name="X" type="float"/>
+                                <param name="Y" type="float"/>
+                                <param name="Z" type="float"/>
+                            </accessor>
+                        </technique_common>
+                    </source>
+                    <vertices id="cube-vertices">
+                        <input semantic="POSITION" source="#cube-positions"/>
+                    </vertices>
+                    <polylist count="7">
+                        <input semantic="VERTEX" source="#cube-vertices" offset="0"/>
+                        <vcount>4 4 4 4 4 4 3</vcount>
+                        <p>0 3 2 1  4 5 6 7  0 1 5 4  3 7 6 2  0 4 7 3  1 2 6 5  0 1 0</p>
+                    </polylist>
+                </mesh>
+            </geometry>
+        </library_geometries>
+    </COLLADA>
+    "##;
+
+    let document = Collada::from_str(DOCUMENT).unwrap();
+    let mesh = document.geometry_by_id("cube").unwrap().mesh().unwrap();
+    assert!(!mesh.has_normals());
+    assert_eq!(None, mesh.to_indexed().unwrap().normals);
+
+    // Each face gets its own copy of its corners, and the degenerate triangle is dropped.
+    let flat = mesh.with_generated_normals(false).unwrap();
+    assert_eq!(36, flat.indices.len());
+    assert_eq!(24, flat.positions.len());
+
+    let normals = flat.normals.unwrap();
+    let mut distinct = normals.clone();
+    distinct.sort_by(|left, right| left.partial_cmp(right).unwrap());
+    distinct.dedup();
+    assert_eq!(
+        vec![
+            [-1.0, 0.0, 0.0], [0.0, -1.0, 0.0], [0.0, 0.0, -1.0],
+            [0.0, 0.0, 1.0], [0.0, 1.0, 0.0], [1.0, 0.0, 0.0],
+        ],
+        distinct,
+    );
+
+    // Smooth normals are shared by every corner at the same position and point away from the
+    // center of the cube.
+    let smooth = mesh.with_generated_normals(true).unwrap();
+    assert_eq!(36, smooth.indices.len());
+    assert_eq!(8, smooth.positions.len());
+    for (position, normal) in smooth.positions.iter().zip(smooth.normals.unwrap()) {
+        let length = normal.iter().map(|component| component * component).sum::<f32>().sqrt();
+        assert!((length - 1.0).abs() < 1e-6, "{:?} isn't normalized", normal);
+
+        let outward = (0..3).map(|axis| (position[axis] - 0.5) * normal[axis]).sum::<f32>();
+        assert!(outward > 0.0, "{:?} at {:?} points inward", normal, position);
+    }
+}
+
+#[test]
+fn geometry_position() {
+    static DOCUMENT: &'static str = r##"<?xml version="1.0" encoding="utf-8"?>
+<COLLADA xmlns="http://www.collada.org/2005/11/COLLADASchema" version="1.4.1">
+    <asset>
+        <created>2017-02-07T20:44:30Z</created>
+        <modified>2017-02-07T20:44:30Z</modified>
+    </asset>
+    <library_geometries>
+        <geometry id="first">
+            <mesh>
+                <source id="first-positions">
+                    <float_array id="first-positions-array" count="3">0 0 0</float_array>
+                </source>
+                <vertices id="first-vertices">
+                    <input semantic="POSITION" source="#first-positions"/>
+                </vertices>
+            </mesh>
+        </geometry>
+          <geometry id="second">
+            <mesh>
+                <source id="second-positions">
+                    <float_array id="second-positions-array" count="3">0 0 0</float_array>
+                </source>
+                <vertices id="second-vertices">
+                    <input semantic="POSITION" source="#second-positions"/>
+                </vertices>
+            </mesh>
+        </geometry>
+    </library_geometries>
+</COLLADA>
+"##;
+
+    let document = Collada::from_str(DOCUMENT).unwrap();
+
+    // Rows and columns are zero-based and point at the opening tag.
+    let first = document.geometry_by_id("first").unwrap().position.get().unwrap();
+    assert_eq!((7, 8), (first.row, first.column));
+
+    let second = document.geometry_by_id("second").unwrap().position.get().unwrap();
+    assert_eq!((17, 10), (second.row, second.column));
+
+    // Positions don't take part in comparisons, so the same geometry built in code is equal.
+    let mut built = document.geometry_by_id("first").unwrap().clone();
+    built.position = Default::default();
+    assert_eq!(None, built.position.get());
+    assert_eq!(document.geometry_by_id("first").unwrap(), &built);
+}
+
+#[test]
+fn max_depth() {
+    fn nested_nodes(count: usize) -> String {
+        format!(
+            r#"<?xml version="1.0" encoding="utf-8"?>
+            <COLLADA xmlns="http://www.collada.org/2005/11/COLLADASchema" version="1.4.1">
+                <asset>
+                    <created>2017-02-07T20:44:30Z</created>
+                    <modified>2017-02-07T20:44:30Z</modified>
+                </asset>
+                <library_visual_scenes>
+                    <visual_scene id="scene">{}{}</visual_scene>
+                </library_visual_scenes>
+            </COLLADA>"#,
+            "<node>".repeat(count),
+            "</node>".repeat(count),
+        )
+    }
+
+    // Nodes are nested within `<library_visual_scenes>` and `<visual_scene>`, so the first node is
+    // at depth 3.
+    let document = Collada::from_str(&nested_nodes(100)).unwrap();
+    let library = document.libraries[0].as_library_visual_scenes().unwrap();
+    let mut node = &library.visual_scenes[0].nodes[0];
+    let mut depth = 1;
+    while let Some(child) = node.children.first() {
+        node = child;
+        depth += 1;
+    }
+    assert_eq!(100, depth);
+
+    // A pathologically deep document fails cleanly instead of overflowing the stack, even on a
+    // thread with the default stack size.
+    let thread = ::std::thread::spawn(move || {
+        let error = Collada::from_str(&nested_nodes(100_000)).unwrap_err();
+        assert_eq!(ErrorKind::MaxDepthExceeded { depth: 129 }, error.kind);
+        assert_eq!(ErrorCategory::Structure, error.kind.category());
+    });
+    thread.join().unwrap();
+
+    let options = ParseOptions { max_depth: 4, ..ParseOptions::default() };
+    assert!(Collada::read_with_options(nested_nodes(2).as_bytes(), options.clone()).is_ok());
+    let error = Collada::read_with_options(nested_nodes(3).as_bytes(), options).unwrap_err();
+    assert_eq!(ErrorKind::MaxDepthExceeded { depth: 5 }, error.kind);
+}
@@ -3,6 +3,17 @@ extern crate collaborate;
 use ::collaborate::*;
 use ::collaborate::common::*;
 use ::collaborate::v1_5::*;
+use ::collaborate::xml::namespace::Namespace;
+use ::collaborate::xml::reader::XmlEvent;
+
+/// The namespace mappings in scope for elements within the test documents.
+fn collada_namespace() -> Namespace {
+    let mut namespace = Namespace::empty();
+    namespace.put("xml", "http://www.w3.org/XML/1998/namespace");
+    namespace.put("xmlns", "http://www.w3.org/2000/xmlns/");
+    namespace.put("", "http://www.collada.org/2005/11/COLLADASchema");
+    namespace
+}
 
 #[test]
 fn collada_asset_minimal() {
@@ -19,7 +30,7 @@ fn collada_asset_minimal() {
 
     let expected = Collada {
         version: "1.5.0".into(),
-        xmlns: None,
+        xmlns: Some("http://www.collada.org/2005/11/COLLADASchema".into()),
         base_uri: None,
         asset: Asset {
             contributors: vec![],
@@ -30,11 +41,8 @@ fn collada_asset_minimal() {
             revision: None,
             subject: None,
             title: None,
-            unit: Unit {
-                meter: 1.0,
-                name: "meter".into(),
-            },
-            up_axis: UpAxis::Y,
+            unit: None,
+            up_axis: None,
             extras: vec![],
         },
         libraries: Vec::new(),
@@ -46,6 +54,64 @@ fn collada_asset_minimal() {
     assert_eq!(expected, actual);
 }
 
+#[test]
+fn schema_namespace() {
+    static DOCUMENT: &'static str = r#"
+    <?xml version="1.0" encoding="utf-8"?>
+    <COLLADA xmlns="http://www.collada.org/2008/03/COLLADASchema" version="1.5.0">
+        <asset>
+            <created>2017-02-07T20:44:30Z</created>
+            <modified>2017-02-07T20:44:30Z</modified>
+        </asset>
+    </COLLADA>
+    "#;
+
+    let options = ParseOptions { lenient: true, ..ParseOptions::default() };
+    let (document, warnings) = Collada::read_with_warnings(DOCUMENT.as_bytes(), options.clone()).unwrap();
+    assert_eq!(Some("http://www.collada.org/2008/03/COLLADASchema"), document.schema_namespace());
+    assert!(warnings.is_empty());
+
+    // The namespace is written back out as-is.
+    let mut output = Vec::new();
+    document.write(&mut output).unwrap();
+    assert_eq!(document, Collada::read(&*output).unwrap());
+
+    // Documents in the wrong namespace (or none at all) are still parsed, but get a warning in
+    // lenient mode.
+    let mismatched = DOCUMENT.replace("2008/03", "2005/11");
+    let (document, warnings) = Collada::read_with_warnings(mismatched.as_bytes(), options.clone()).unwrap();
+    assert_eq!(Some("http://www.collada.org/2005/11/COLLADASchema"), document.schema_namespace());
+    assert_eq!(
+        vec![WarningKind::UnexpectedNamespace {
+            namespace: Some("http://www.collada.org/2005/11/COLLADASchema".into()),
+            expected: "http://www.collada.org/2008/03/COLLADASchema",
+        }],
+        warnings.iter().map(|warning| warning.kind.clone()).collect::<Vec<_>>(),
+    );
+    assert_eq!(
+        "<COLLADA> has the namespace \"http://www.collada.org/2005/11/COLLADASchema\", expected \"http://www.collada.org/2008/03/COLLADASchema\"",
+        warnings[0].kind.to_string(),
+    );
+
+    let (_, versioned_warnings) = VersionedDocument::read_with_warnings(mismatched.as_bytes(), options.clone()).unwrap();
+    assert_eq!(warnings, versioned_warnings);
+
+    let unqualified = DOCUMENT.replace(r#"xmlns="http://www.collada.org/2008/03/COLLADASchema" "#, "");
+    let (document, warnings) = Collada::read_with_warnings(unqualified.as_bytes(), options).unwrap();
+    assert_eq!(None, document.schema_namespace());
+    assert_eq!(
+        WarningKind::UnexpectedNamespace {
+            namespace: None,
+            expected: "http://www.collada.org/2008/03/COLLADASchema",
+        },
+        warnings[0].kind,
+    );
+
+    // Without lenient mode there are no warnings at all.
+    let (_, warnings) = Collada::read_with_warnings(mismatched.as_bytes(), ParseOptions::default()).unwrap();
+    assert!(warnings.is_empty());
+}
+
 #[test]
 fn collada_missing_version() {
     static DOCUMENT: &'static str = r#"
@@ -160,18 +226,45 @@ fn asset_full() {
         revision: Some("7".into()),
         subject: Some("A thing".into()),
         title: Some("Model of a thing".into()),
-        unit: Unit {
+        unit: Some(Unit {
             meter: 7.0,
             name: "septimeter".into(),
-        },
-        up_axis: UpAxis::Z,
-        extras: vec![Extra::default(), Extra::default()],
+        }),
+        up_axis: Some(UpAxis::Z),
+        extras: vec![
+            Extra { namespace: collada_namespace(), ..Extra::default() },
+            Extra { namespace: collada_namespace(), ..Extra::default() },
+        ],
     };
 
     let collada = Collada::from_str(DOCUMENT).unwrap();
     assert_eq!(expected, collada.asset);
 }
 
+#[test]
+fn altitude_default_mode() {
+    static DOCUMENT: &'static str = r#"
+    <?xml version="1.0" encoding="utf-8"?>
+    <COLLADA xmlns="http://www.collada.org/2005/11/COLLADASchema" version="1.5.0">
+        <asset>
+            <coverage>
+                <geographic_location>
+                    <longitude>-105.2830</longitude>
+                    <latitude>40.0170</latitude>
+                    <altitude>0</altitude>
+                </geographic_location>
+            </coverage>
+            <created>2017-02-07T20:44:30Z</created>
+            <modified>2017-02-07T20:44:30Z</modified>
+        </asset>
+    </COLLADA>
+    "#;
+
+    let collada = Collada::from_str(DOCUMENT).unwrap();
+    let location = collada.asset.coverage.unwrap().geographic_location.unwrap();
+    assert_eq!(Altitude::RelativeToGround(0.0), location.altitude);
+}
+
 #[test]
 fn contributor_minimal() {
     static DOCUMENT: &'static str = r#"
@@ -365,7 +458,8 @@ fn extra_minimal() {
         name: None,
         type_hint: None,
         asset: None,
-        techniques: vec![Technique { profile: "cool".into(), xmlns: None, data: Vec::default() }],
+        techniques: vec![Technique { profile: "cool".into(), xmlns: None, namespace: collada_namespace(), data: Vec::default() }],
+        namespace: collada_namespace(),
     };
 
     let actual = Collada::from_str(DOCUMENT).unwrap();
@@ -406,17 +500,366 @@ fn extra_full() {
             revision: None,
             subject: None,
             title: None,
-            unit: Unit::default(),
-            up_axis: UpAxis::default(),
+            unit: None,
+            up_axis: None,
             extras: Vec::default(),
         }),
         techniques: vec![
-            Technique { profile: "foo".into(), xmlns: None, data: Vec::default() },
-            Technique { profile: "bar".into(), xmlns: None, data: Vec::default() },
-            Technique { profile: "baz".into(), xmlns: None, data: Vec::default() },
+            Technique { profile: "foo".into(), xmlns: None, namespace: collada_namespace(), data: Vec::default() },
+            Technique { profile: "bar".into(), xmlns: None, namespace: collada_namespace(), data: Vec::default() },
+            Technique { profile: "baz".into(), xmlns: None, namespace: collada_namespace(), data: Vec::default() },
         ],
+        namespace: collada_namespace(),
     };
 
     let actual = Collada::from_str(DOCUMENT).unwrap();
     assert_eq!(expected, actual.asset.extras[0]);
 }
+
+#[test]
+fn convert_from_1_4() {
+    static DOCUMENT: &'static str = r#"
+    <?xml version="1.0" encoding="utf-8"?>
+    <COLLADA xmlns="http://www.collada.org/2005/11/COLLADASchema" version="1.4.1">
+        <asset>
+            <contributor>
+                <author>David LeGare</author>
+            </contributor>
+            <created>2017-02-07T20:44:30Z</created>
+            <modified>2017-02-08T13:30:10+02:00</modified>
+            <up_axis>Z_UP</up_axis>
+        </asset>
+        <library_geometries />
+    </COLLADA>
+    "#;
+
+    let document = v1_4::Collada::from_str(DOCUMENT).unwrap();
+    let converted = document.clone().into_1_5_lossy();
+
+    assert_eq!("1.5.0", converted.version);
+    assert_eq!(document.asset.created, converted.asset.created);
+    assert_eq!(document.asset.modified, converted.asset.modified);
+    assert_eq!(Some(UpAxis::Z), converted.asset.up_axis);
+
+    // A missing unit stays missing rather than being filled in with the default.
+    assert_eq!(None, converted.asset.unit);
+    assert_eq!(None, converted.asset.coverage);
+
+    let contributor = &converted.asset.contributors[0];
+    assert_eq!(Some("David LeGare".into()), contributor.author);
+    assert_eq!(None, contributor.author_email);
+
+    // The library's kind is kept, but its contents are dropped.
+    assert_eq!(vec![Library::Geometries(LibraryGeometries)], converted.libraries);
+}
+
+#[test]
+fn library_kinematics_models() {
+    static DOCUMENT: &'static str = r##"
+    <?xml version="1.0" encoding="utf-8"?>
+    <COLLADA xmlns="http://www.collada.org/2008/03/COLLADASchema" version="1.5.0">
+        <asset>
+            <created>2017-02-07T20:44:30Z</created>
+            <modified>2017-02-07T20:44:30Z</modified>
+        </asset>
+        <library_kinematics_models>
+            <kinematics_model id="arm" name="Arm">
+                <technique_common>
+                    <newparam sid="shoulder_enabled">
+                        <bool>true</bool>
+                    </newparam>
+                    <joint id="hinge" sid="hinge">
+                        <revolute sid="axis0">
+                            <axis>0 0 1</axis>
+                            <limits>
+                                <min>-90</min>
+                                <max>90.5</max>
+                            </limits>
+                        </revolute>
+                    </joint>
+                    <instance_joint sid="elbow" url="#elbow_joint" />
+                    <link sid="base">
+                        <attachment_full joint="arm/hinge">
+                            <translate>0 0 1</translate>
+                            <rotate>0 0 1 45</rotate>
+                            <link sid="upper_arm" />
+                        </attachment_full>
+                    </link>
+                </technique_common>
+            </kinematics_model>
+        </library_kinematics_models>
+        <library_kinematics_scenes>
+            <kinematics_scene id="scene">
+                <instance_kinematics_model sid="arm_instance" url="#arm">
+                    <bind symbol="arm_hinge">
+                        <param ref="hinge_value" />
+                    </bind>
+                    <newparam sid="hinge_value">
+                        <float>12.5</float>
+                    </newparam>
+                    <setparam ref="arm/shoulder_enabled">
+                        <bool>0</bool>
+                    </setparam>
+                </instance_kinematics_model>
+            </kinematics_scene>
+        </library_kinematics_scenes>
+    </COLLADA>
+    "##;
+
+    let collada = Collada::from_str(DOCUMENT).unwrap();
+
+    let models = collada.libraries[0].as_library_kinematics_models().unwrap();
+    let model = &models.kinematics_models[0];
+    assert_eq!(Some("arm".into()), model.id);
+
+    let technique = &model.technique_common;
+    assert_eq!(KinematicsValue::Bool(Bool { value: Boolean(true) }), technique.new_params[0].value);
+    assert_eq!(2, technique.joints.len());
+
+    let joint = match *technique.joint("hinge").unwrap() {
+        KinematicsJoint::Joint(ref joint) => joint,
+        _ => panic!("Expected a joint declared within the model"),
+    };
+    assert_eq!(1, joint.primitives.len());
+    match joint.primitives[0] {
+        PrimitiveJoint::Revolute(ref revolute) => assert_eq!(Some("axis0".into()), revolute.sid),
+        _ => panic!("Expected a revolute joint"),
+    }
+    assert_eq!([0.0, 0.0, 1.0], joint.primitives[0].axis().vector());
+    let limits = joint.primitives[0].limits().unwrap();
+    assert_eq!(-90.0, limits.min.as_ref().unwrap().value);
+    assert_eq!(90.5, limits.max.as_ref().unwrap().value);
+
+    match *technique.joint("elbow").unwrap() {
+        KinematicsJoint::Instance(ref instance) => assert_eq!(Some("elbow_joint"), instance.url.local_id()),
+        _ => panic!("Expected an instanced joint"),
+    }
+    assert_eq!(None, technique.joint("wrist"));
+
+    let base = &technique.links[0];
+    assert_eq!(Some("base".into()), base.sid);
+    match base.attachments[0] {
+        Attachment::Full(ref attachment) => {
+            assert_eq!("arm/hinge", attachment.joint);
+            assert_eq!(2, attachment.transforms.len());
+            assert_eq!(Some("upper_arm".into()), attachment.link.sid);
+        }
+        _ => panic!("Expected a full attachment"),
+    }
+
+    let scenes = collada.libraries[1].as_library_kinematics_scenes().unwrap();
+    let instance = &scenes.kinematics_scenes[0].instance_kinematics_models[0];
+    assert_eq!(Some("arm"), instance.url.local_id());
+    assert_eq!("arm_hinge", instance.binds[0].symbol);
+    assert_eq!(
+        KinematicsBindValue::Param(KinematicsParamRef { reference: "hinge_value".into() }),
+        instance.binds[0].value,
+    );
+    assert_eq!(KinematicsValue::Float(Float { value: 12.5 }), instance.new_params[0].value);
+    assert_eq!("arm/shoulder_enabled", instance.set_params[0].reference);
+    assert_eq!(KinematicsValue::Bool(Bool { value: Boolean(false) }), instance.set_params[0].value);
+}
+
+#[test]
+fn library_joints() {
+    static DOCUMENT: &'static str = r#"
+    <?xml version="1.0" encoding="utf-8"?>
+    <COLLADA xmlns="http://www.collada.org/2008/03/COLLADASchema" version="1.5.0">
+        <asset>
+            <created>2017-02-07T20:44:30Z</created>
+            <modified>2017-02-07T20:44:30Z</modified>
+        </asset>
+        <library_joints>
+            <joint id="elbow_joint" name="Elbow">
+                <revolute sid="axis0">
+                    <axis>1 0 0</axis>
+                    <limits>
+                        <min sid="min_angle">-45</min>
+                        <max sid="max_angle">135</max>
+                    </limits>
+                </revolute>
+            </joint>
+        </library_joints>
+    </COLLADA>
+    "#;
+
+    let collada = Collada::from_str(DOCUMENT).unwrap();
+
+    let library = collada.libraries[0].as_library_joints().unwrap();
+    assert_eq!(1, library.joints.len());
+
+    let joint = &library.joints[0];
+    assert_eq!(Some("elbow_joint".into()), joint.id);
+    assert_eq!(Some("Elbow".into()), joint.name);
+    assert_eq!(1, joint.primitives.len());
+
+    let revolute = match joint.primitives[0] {
+        PrimitiveJoint::Revolute(ref revolute) => revolute,
+        _ => panic!("Expected a revolute joint"),
+    };
+    assert_eq!(Some("axis0".into()), revolute.sid);
+    assert_eq!([1.0, 0.0, 0.0], revolute.axis.vector());
+
+    let limits = revolute.limits.as_ref().unwrap();
+    assert_eq!(
+        Some(Min { sid: Some("min_angle".into()), value: -45.0 }),
+        limits.min,
+    );
+    assert_eq!(
+        Some(Max { sid: Some("max_angle".into()), value: 135.0 }),
+        limits.max,
+    );
+}
+
+#[test]
+fn library_articulated_systems() {
+    static DOCUMENT: &'static str = r##"
+    <?xml version="1.0" encoding="utf-8"?>
+    <COLLADA xmlns="http://www.collada.org/2008/03/COLLADASchema" version="1.5.0">
+        <asset>
+            <created>2017-02-07T20:44:30Z</created>
+            <modified>2017-02-07T20:44:30Z</modified>
+        </asset>
+        <library_articulated_systems>
+            <articulated_system id="arm_kinematics">
+                <kinematics>
+                    <instance_kinematics_model sid="arm_instance" url="#arm" />
+                    <technique_common>
+                        <axis_info sid="hinge_info" axis="arm/hinge/axis0">
+                            <active>
+                                <bool>true</bool>
+                            </active>
+                        </axis_info>
+                        <frame_origin link="arm/base" />
+                        <frame_tip link="arm/upper_arm">
+                            <translate>0 0 2</translate>
+                        </frame_tip>
+                    </technique_common>
+                </kinematics>
+            </articulated_system>
+            <articulated_system id="arm_motion">
+                <motion>
+                    <instance_articulated_system url="#arm_kinematics" />
+                    <technique_common>
+                        <axis_info axis="arm_kinematics/hinge_info">
+                            <speed>
+                                <float>30</float>
+                            </speed>
+                            <acceleration>
+                                <param ref="max_acceleration" />
+                            </acceleration>
+                        </axis_info>
+                    </technique_common>
+                </motion>
+            </articulated_system>
+        </library_articulated_systems>
+    </COLLADA>
+    "##;
+
+    let collada = Collada::from_str(DOCUMENT).unwrap();
+
+    let library = collada.libraries[0].as_library_articulated_systems().unwrap();
+    assert_eq!(2, library.articulated_systems.len());
+
+    let kinematics_system = &library.articulated_systems[0];
+    assert_eq!(Some("arm_kinematics".into()), kinematics_system.id);
+    assert_eq!(None, kinematics_system.motion());
+
+    let kinematics = kinematics_system.kinematics().unwrap();
+    let instance = &kinematics.instance_kinematics_models[0];
+    assert_eq!(Some("arm_instance".into()), instance.sid);
+    assert_eq!(Some("arm"), instance.url.local_id());
+
+    let technique = &kinematics.technique_common;
+    assert_eq!(1, technique.axis_infos.len());
+    assert_eq!("arm/base", technique.frame_origin.link);
+    assert_eq!("arm/upper_arm", technique.frame_tip.link);
+    assert_eq!(1, technique.frame_tip.transforms.len());
+    assert_eq!(None, technique.frame_tcp);
+
+    let motion_system = &library.articulated_systems[1];
+    assert_eq!(None, motion_system.kinematics());
+
+    let motion = motion_system.motion().unwrap();
+    assert_eq!(Some("arm_kinematics"), motion.instance_articulated_system.url.local_id());
+
+    let axis_info = &motion.technique_common.axis_infos[0];
+    assert_eq!("arm_kinematics/hinge_info", axis_info.axis);
+    assert_eq!(
+        Some(Speed { value: FloatOrParam::Float(Float { value: 30.0 }) }),
+        axis_info.speed,
+    );
+    assert_eq!(
+        Some(Acceleration {
+            value: FloatOrParam::Param(KinematicsParamRef { reference: "max_acceleration".into() }),
+        }),
+        axis_info.acceleration,
+    );
+    assert_eq!(None, axis_info.jerk);
+}
+
+#[test]
+fn library_formulas() {
+    static DOCUMENT: &'static str = r#"
+    <?xml version="1.0" encoding="utf-8"?>
+    <COLLADA xmlns="http://www.collada.org/2008/03/COLLADASchema" version="1.5.0">
+        <asset>
+            <created>2017-02-07T20:44:30Z</created>
+            <modified>2017-02-07T20:44:30Z</modified>
+        </asset>
+        <library_formulas>
+            <formula id="coupled_joint" sid="coupled">
+                <newparam sid="ratio">
+                    <float>0.5</float>
+                </newparam>
+                <newparam sid="offset">
+                    <float>10</float>
+                </newparam>
+                <target>
+                    <param ref="arm/elbow" />
+                </target>
+                <technique_common>
+                    <math xmlns="http://www.w3.org/1998/Math/MathML">
+                        <apply>
+                            <times />
+                            <ci>ratio</ci>
+                            <ci>offset</ci>
+                        </apply>
+                    </math>
+                </technique_common>
+            </formula>
+        </library_formulas>
+    </COLLADA>
+    "#;
+
+    let collada = Collada::from_str(DOCUMENT).unwrap();
+
+    let library = collada.libraries[0].as_library_formulas().unwrap();
+    let formula = &library.formulas[0];
+    assert_eq!(Some("coupled_joint".into()), formula.id);
+    assert_eq!(Some("coupled".into()), formula.sid);
+
+    let params = formula.new_params.iter().map(|param| &*param.sid).collect::<Vec<_>>();
+    assert_eq!(vec!["ratio", "offset"], params);
+    assert_eq!(KinematicsValue::Float(Float { value: 0.5 }), formula.new_params[0].value);
+    assert_eq!(
+        FloatOrParam::Param(KinematicsParamRef { reference: "arm/elbow".into() }),
+        formula.target.value,
+    );
+
+    let elements = formula.technique_common.data.iter()
+        .filter_map(|event| match *event {
+            XmlEvent::StartElement { ref name, .. } => Some(&*name.local_name),
+            _ => None,
+        })
+        .collect::<Vec<_>>();
+    assert_eq!(vec!["math", "apply", "times", "ci", "ci"], elements);
+
+    let variables = formula.technique_common.data.iter()
+        .filter_map(|event| match *event {
+            XmlEvent::Characters(ref text) => Some(&**text),
+            _ => None,
+        })
+        .collect::<Vec<_>>();
+    assert_eq!(vec!["ratio", "offset"], variables);
+}